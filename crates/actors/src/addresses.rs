@@ -3,8 +3,8 @@ use actix::Addr;
 use crate::{
     block_discovery::BlockDiscoveryActor, block_index_service::BlockIndexService,
     block_producer::BlockProducerActor, mempool_service::MempoolService,
-    mining::PartitionMiningActor, packing::PackingActor, reth_service::RethServiceActor,
-    EpochServiceActor,
+    mining::PartitionMiningActor, mining_pool::MiningPoolService, packing::PackingActor,
+    reth_service::RethServiceActor, EpochServiceActor,
 };
 
 /// Serves as a kind of app state that can be passed into actix web to allow
@@ -19,4 +19,5 @@ pub struct ActorAddresses {
     pub block_index: Addr<BlockIndexService>,
     pub epoch_service: Addr<EpochServiceActor>,
     pub reth: Addr<RethServiceActor>,
+    pub mining_pool: Option<Addr<MiningPoolService>>,
 }
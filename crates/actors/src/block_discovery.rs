@@ -22,7 +22,7 @@ use irys_types::{
     IrysBlockHeader, IrysTransactionHeader,
 };
 use reth_db::Database;
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 use tracing::{debug, error, info, Instrument, Span};
 
 /// `BlockDiscoveryActor` listens for discovered blocks & validates them.
@@ -186,12 +186,46 @@ impl Handler<BlockDiscoveredMessage> for BlockDiscoveryActor {
                 }
             };
 
-            // Pre-Validate the ingress-proof by verifying the signature
-            for (i, tx_header) in publish_txs.iter().enumerate() {
-                if let Err(e) = publish_proofs.0[i].pre_validate(&tx_header.data_root) {
-                    return Box::pin(async move {
-                        Err(eyre::eyre!("Invalid ingress proof signature: {}", e))
-                    });
+            // Each promoted tx needs `number_of_ingress_proofs` proofs, laid out in tx order
+            let proofs_per_tx = self.config.consensus.number_of_ingress_proofs as usize;
+            if publish_proofs.0.len() != publish_txs.len() * proofs_per_tx {
+                let (proof_count, tx_count) = (publish_proofs.0.len(), publish_txs.len());
+                return Box::pin(async move {
+                    Err(eyre::eyre!(
+                        "Expected {} ingress proofs for {} publish txs, got {}",
+                        tx_count * proofs_per_tx,
+                        tx_count,
+                        proof_count
+                    ))
+                });
+            }
+
+            // Pre-Validate the ingress-proofs by verifying the signatures, and make sure
+            // each tx's proofs come from distinct signers
+            for (tx_header, tx_proofs) in publish_txs
+                .iter()
+                .zip(publish_proofs.0.chunks(proofs_per_tx))
+            {
+                let mut signers = HashSet::new();
+                for proof in tx_proofs {
+                    match proof.pre_validate(&tx_header.data_root) {
+                        Ok(signer) if signers.insert(signer) => {}
+                        Ok(signer) => {
+                            let tx_id = tx_header.id;
+                            return Box::pin(async move {
+                                Err(eyre::eyre!(
+                                    "Duplicate ingress proof signer {} for tx {}",
+                                    signer,
+                                    tx_id
+                                ))
+                            });
+                        }
+                        Err(e) => {
+                            return Box::pin(async move {
+                                Err(eyre::eyre!("Invalid ingress proof signature: {}", e))
+                            });
+                        }
+                    }
                 }
             }
         }
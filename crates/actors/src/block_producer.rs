@@ -5,7 +5,7 @@ use base58::ToBase58;
 use eyre::eyre;
 use irys_database::{
     block_header_by_hash, cached_data_root_by_data_root, db::IrysDatabaseExt as _,
-    insert_commitment_tx, tables::IngressProofs, tx_header_by_txid, SystemLedger,
+    insert_commitment_tx, tables::IngressProofsBySigner, tx_header_by_txid, SystemLedger,
 };
 use irys_price_oracle::IrysPriceOracle;
 use irys_reth_node_bridge::{
@@ -188,7 +188,7 @@ impl Handler<SolutionFoundMessage> for BlockProducerActor {
                     eyre!("Failed to create DB transaction: {}", e)
                 )?;
 
-                let mut read_cursor = read_tx.new_cursor::<IngressProofs>().map_err(|e|
+                let mut read_cursor = read_tx.new_cursor::<IngressProofsBySigner>().map_err(|e|
                     eyre!("Failed to create DB read cursor: {}", e)
                 )?;
 
@@ -196,9 +196,18 @@ impl Handler<SolutionFoundMessage> for BlockProducerActor {
                     eyre!("Failed to create DB read cursor walker: {}", e)
                 )?;
 
-                let ingress_proofs = walker.collect::<Result<HashMap<_, _>, _>>().map_err(|e|
-                    eyre!("Failed to collect ingress proofs from database: {}", e)
-                )?;
+                // Group the proofs by data_root, the table holds one proof per signer
+                let mut ingress_proofs: HashMap<H256, Vec<TxIngressProof>> = HashMap::new();
+                for entry in walker {
+                    let (data_root, cached_proof) = entry.map_err(|e|
+                        eyre!("Failed to collect ingress proofs from database: {}", e)
+                    )?;
+                    ingress_proofs.entry(data_root).or_default().push(TxIngressProof {
+                        proof: cached_proof.proof.proof,
+                        signature: cached_proof.proof.signature,
+                    });
+                }
+                let proofs_per_tx = config.consensus.number_of_ingress_proofs as usize;
 
 
                 let mut publish_txids: Vec<H256> = Vec::new();
@@ -227,18 +236,19 @@ impl Handler<SolutionFoundMessage> for BlockProducerActor {
 
                     // If there's no ingress proof included in the tx header, it means the tx still needs to be promoted
                     if tx_header.ingress_proofs.is_none() {
-                        // Get the proof
+                        // Get `number_of_ingress_proofs` proofs from distinct signers
                         match ingress_proofs.get(&tx_header.data_root) {
-                            Some(proof) => {
+                            Some(tx_proofs) if tx_proofs.len() >= proofs_per_tx => {
                                 let mut tx_header = tx_header.clone();
-                                let proof = TxIngressProof {
-                                    proof: proof.proof,
-                                    signature: proof.signature,
-                                };
-                                proofs.push(proof.clone());
-                                tx_header.ingress_proofs = Some(proof);
+                                let tx_proofs = &tx_proofs[..proofs_per_tx];
+                                proofs.extend_from_slice(tx_proofs);
+                                tx_header.ingress_proofs = tx_proofs.first().cloned();
                                 publish_txs.push(tx_header);
                             },
+                            Some(tx_proofs) => {
+                                debug!("Not enough ingress proofs for data_root: {} ({}/{})", tx_header.data_root, tx_proofs.len(), proofs_per_tx);
+                                continue;
+                            },
                             None => {
                                 error!("No ingress proof found for data_root: {}", tx_header.data_root);
                                 continue;
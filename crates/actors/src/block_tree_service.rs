@@ -1,7 +1,7 @@
 use std::{
-    collections::{BTreeMap, HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
     sync::{Arc, RwLock, RwLockReadGuard},
-    time::SystemTime,
+    time::{SystemTime, UNIX_EPOCH},
 };
 
 use crate::{
@@ -9,6 +9,7 @@ use crate::{
     block_index_service::{BlockIndexReadGuard, BlockIndexService},
     block_producer::BlockConfirmedMessage,
     chunk_migration_service::ChunkMigrationService,
+    commitment_cache::CommitmentCacheMessage,
     ema_service::EmaServiceMessage,
     mempool_service::MempoolService,
     reth_service::{BlockHashType, ForkChoiceUpdateMessage, RethServiceActor},
@@ -20,14 +21,15 @@ use actix::prelude::*;
 use base58::ToBase58 as _;
 use eyre::ensure;
 use irys_database::{
-    block_header_by_hash, db::IrysDatabaseExt as _, tx_header_by_txid, BlockIndex,
+    block_header_by_hash, db::IrysDatabaseExt as _, tx_header_by_txid, BlockIndex, SystemLedger,
 };
 use irys_types::{
-    Address, BlockHash, ConsensusConfig, DataLedger, DatabaseProvider, IrysBlockHeader,
-    IrysTransactionHeader, IrysTransactionId, H256, U256,
+    Address, BlockHash, ConsensusConfig, DataLedger, DatabaseProvider, ForkChoiceConfig, H256List,
+    IrysBlockHeader, IrysTransactionHeader, IrysTransactionId, H256, U256,
 };
 use reth_db::{transaction::DbTx, Database as _};
-use tracing::{debug, error, info};
+use serde::Serialize;
+use tracing::{debug, error, info, warn};
 
 //==============================================================================
 // BlockTreeReadGuard
@@ -86,6 +88,8 @@ pub struct BlockTreeService {
     pub block_index_guard: Option<BlockIndexReadGuard>,
     /// Global storage config
     pub consensus_config: ConsensusConfig,
+    /// Limits of the forks the node switches to
+    pub fork_choice_config: ForkChoiceConfig,
     /// Channels for communicating with the services
     pub service_senders: ServiceSenders,
 }
@@ -117,6 +121,7 @@ impl BlockTreeService {
         miner_address: &Address,
         block_index_guard: BlockIndexReadGuard,
         consensus_config: ConsensusConfig,
+        fork_choice_config: ForkChoiceConfig,
         service_senders: ServiceSenders,
     ) -> Self {
         let cache = BlockTreeCache::initialize_from_list(block_index, db.clone());
@@ -127,6 +132,7 @@ impl BlockTreeService {
             miner_address: *miner_address,
             block_index_guard: Some(block_index_guard),
             consensus_config,
+            fork_choice_config,
             service_senders,
         }
     }
@@ -205,6 +211,43 @@ impl BlockTreeService {
             .expect("EMA service has unexpectedly become unreachable");
     }
 
+    /// Rolls back the commitments of the orphaned branch that the new branch doesn't
+    /// include, and lets the mempool return the orphaned transactions to the pool
+    fn notify_services_of_reorg(&self, reorg: ReorgMessage) {
+        let new_commitment_txids: HashSet<&H256> = reorg
+            .new_blocks
+            .iter()
+            .flat_map(|block| commitment_tx_ids(block))
+            .collect();
+        let orphaned_commitment_txids: Vec<H256> = reorg
+            .orphaned_blocks
+            .iter()
+            .flat_map(|block| commitment_tx_ids(block))
+            .filter(|txid| !new_commitment_txids.contains(txid))
+            .copied()
+            .collect();
+
+        if !orphaned_commitment_txids.is_empty() {
+            let (tx, rx) = tokio::sync::oneshot::channel();
+            if let Err(e) = self.service_senders.commitment_cache.send(
+                CommitmentCacheMessage::RollbackCommitments {
+                    commitment_txs: H256List(orphaned_commitment_txids),
+                    response: tx,
+                },
+            ) {
+                error!("Unable to roll back orphaned commitments: {}", e);
+            } else {
+                tokio::spawn(async move {
+                    if let Ok(Err(e)) = rx.await {
+                        error!("Failed to roll back orphaned commitments: {}", e);
+                    }
+                });
+            }
+        }
+
+        MempoolService::from_registry().do_send(reorg);
+    }
+
     /// Checks if a block that is `chunk_migration_depth` blocks behind `arc_block`
     /// should be finalized. If eligible, sends finalization message unless block
     /// is already in `block_index`. Panics if the `block_tree` and `block_index` are
@@ -295,7 +338,8 @@ pub struct ValidationResultMessage {
 /// - For peer blocks: Added normally via `add_block`
 ///
 /// After adding the block, it's scheduled for full validation and the previous
-/// block is marked for storage finalization.
+/// block is marked for storage finalization. Blocks of forks that would reorganize
+/// migrated blocks or more than `max_reorg_depth` blocks are rejected.
 impl Handler<BlockPreValidatedMessage> for BlockTreeService {
     type Result = ResponseFuture<eyre::Result<()>>;
 
@@ -303,6 +347,14 @@ impl Handler<BlockPreValidatedMessage> for BlockTreeService {
         let miner_address = self.miner_address;
         let ema_service = self.service_senders.ema.clone();
         let cache = self.cache.clone().expect("cache to be initialised");
+        let max_reorg_depth = self.fork_choice_config.max_reorg_depth;
+        // Height of the latest block migrated to the block index
+        let migrated_height = self.block_index_guard.as_ref().and_then(|block_index| {
+            let block_index = block_index.read();
+            block_index
+                .get_latest_item()
+                .map(|_| block_index.latest_height())
+        });
 
         return Box::pin(async move {
             let block = msg.0;
@@ -311,6 +363,8 @@ impl Handler<BlockPreValidatedMessage> for BlockTreeService {
             let _finalized_block_hash = block.previous_block_hash;
             let mut cache = cache.write().expect("cache lock poisoined");
 
+            cache.check_fork_limits(&block, max_reorg_depth, migrated_height)?;
+
             // Handle block addition differently based on origin
             let add_result = if block.miner_address == miner_address {
                 // For locally mined blocks: Add as `BlockState::Unknown `to allow chain
@@ -388,6 +442,9 @@ impl Handler<ValidationResultMessage> for BlockTreeService {
                 let arc_block = Arc::new(block_entry.block.clone());
                 let all_tx = block_entry.all_tx.clone();
 
+                // Collect the branches before the tip moves
+                let reorg = cache.get_reorg_branches(&block_hash);
+
                 // Now do mutable operations
                 let mark_tip = cache.mark_tip(&block_hash);
                 let _ = RethServiceActor::from_registry().try_send(ForkChoiceUpdateMessage {
@@ -396,6 +453,9 @@ impl Handler<ValidationResultMessage> for BlockTreeService {
                     finalized_hash: None,
                 });
                 if mark_tip.is_ok() {
+                    if let Some(reorg) = reorg {
+                        self.notify_services_of_reorg(reorg);
+                    }
                     self.notify_services_of_block_confirmation(block_hash, &arc_block, all_tx);
                 }
 
@@ -406,6 +466,27 @@ impl Handler<ValidationResultMessage> for BlockTreeService {
     }
 }
 
+/// Sent to the mempool when the canonical chain switches to a fork, so the
+/// transactions of the orphaned blocks can be returned to the pool
+#[derive(Message, Debug, Clone)]
+#[rtype(result = "()")]
+pub struct ReorgMessage {
+    /// Blocks removed from the canonical chain, oldest first
+    pub orphaned_blocks: Vec<Arc<IrysBlockHeader>>,
+    /// Blocks of the fork that replaced them, oldest first
+    pub new_blocks: Vec<Arc<IrysBlockHeader>>,
+}
+
+/// Returns the ids of the transactions in the commitment ledger of a block
+pub(crate) fn commitment_tx_ids(block: &IrysBlockHeader) -> &[H256] {
+    block
+        .system_ledgers
+        .iter()
+        .find(|ledger| ledger.ledger_id == SystemLedger::Commitment)
+        .map(|ledger| ledger.tx_ids.0.as_slice())
+        .unwrap_or_default()
+}
+
 /// Fetches full transaction headers from a ledger in a block.
 /// Returns None if any headers are missing or on DB errors.
 fn get_ledger_tx_headers<T: DbTx>(
@@ -439,6 +520,9 @@ fn get_ledger_tx_headers<T: DbTx>(
 /// Number of blocks to retain in cache from chain head
 const BLOCK_CACHE_DEPTH: u64 = 50;
 
+/// Number of fork events kept in the fork history
+const FORK_HISTORY_LEN: usize = 100;
+
 type ChainCacheEntry = (
     BlockHash,
     u64,
@@ -465,6 +549,37 @@ pub struct BlockTreeCache {
 
     // Cache of longest chain: (block/tx pairs, count of non-onchain blocks)
     longest_chain_cache: (Vec<ChainCacheEntry>, usize),
+
+    // Most recent forks, reorgs and rejected forks, oldest first
+    fork_history: VecDeque<ForkEvent>,
+}
+
+/// A fork observed by the block tree
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForkEvent {
+    pub kind: ForkEventKind,
+    /// The block that forked off, became the tip or was rejected
+    pub block_hash: BlockHash,
+    pub height: u64,
+    /// The canonical block the fork branches off from
+    pub fork_point_hash: BlockHash,
+    pub fork_point_height: u64,
+    /// Number of canonical blocks the fork replaces, or would replace
+    pub depth: u64,
+    /// Unix timestamp in milliseconds
+    pub timestamp: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ForkEventKind {
+    /// A block was added next to an existing child of its parent
+    Fork,
+    /// The tip moved to a block that doesn't descend from the previous tip
+    Reorg,
+    /// A block was rejected because its fork exceeds the reorg limits
+    Rejected,
 }
 
 #[derive(Debug)]
@@ -551,6 +666,7 @@ impl BlockTreeCache {
             max_cumulative_difficulty: (cumulative_diff, block_hash),
             height_index,
             longest_chain_cache,
+            fork_history: VecDeque::new(),
         }
     }
 
@@ -667,6 +783,9 @@ impl BlockTreeCache {
             .get_mut(&prev_hash)
             .ok_or_else(|| eyre::eyre!("Previous block not found"))?;
 
+        // A sibling already descends from the parent
+        let is_fork = !prev_entry.children.is_empty() && !prev_entry.children.contains(&hash);
+
         // Update indices
         prev_entry.children.insert(hash);
         self.solutions
@@ -693,6 +812,18 @@ impl BlockTreeCache {
             },
         );
 
+        if is_fork {
+            if let Some(event) = self.fork_event(ForkEventKind::Fork, block) {
+                debug!(
+                    "fork at height {}: block {} branches off {}",
+                    event.fork_point_height,
+                    hash.0.to_base58(),
+                    event.fork_point_hash.0.to_base58()
+                );
+                self.record_fork_event(event);
+            }
+        }
+
         self.update_longest_chain_cache();
         Ok(())
     }
@@ -970,10 +1101,25 @@ impl BlockTreeCache {
 
         let block = block_entry.block.clone();
         let old_tip = self.tip;
+        let reorg = self
+            .fork_event(ForkEventKind::Reorg, &block)
+            .filter(|event| event.fork_point_hash != old_tip && *block_hash != old_tip);
 
         // Recursively mark previous blocks
         self.mark_on_chain(&block)?;
 
+        if let Some(event) = reorg {
+            warn!(
+                "reorg of depth {}: tip {} replaced by {} at height {}, fork point height {}",
+                event.depth,
+                old_tip.0.to_base58(),
+                block_hash.0.to_base58(),
+                event.height,
+                event.fork_point_height
+            );
+            self.record_fork_event(event);
+        }
+
         // Mark the tip block as on_chain
         if let Some(entry) = self.blocks.get_mut(block_hash) {
             entry.chain_state = ChainState::Onchain;
@@ -1232,6 +1378,122 @@ impl BlockTreeCache {
     pub fn is_known_solution_hash(&self, solution_hash: &H256) -> bool {
         self.solutions.contains_key(solution_hash)
     }
+
+    /// Finds the canonical block the chain of `block` branches off from, walking back
+    /// through its ancestors until reaching an on-chain block
+    #[must_use]
+    pub fn get_fork_point(&self, block: &IrysBlockHeader) -> Option<&IrysBlockHeader> {
+        let mut prev_hash = block.previous_block_hash;
+        while let Some(prev_entry) = self.blocks.get(&prev_hash) {
+            if prev_entry.chain_state == ChainState::Onchain {
+                return Some(&prev_entry.block);
+            }
+            prev_hash = prev_entry.block.previous_block_hash;
+        }
+        None
+    }
+
+    /// Rejects the block if its chain would reorganize blocks migrated to the block index,
+    /// i.e. at or below `migrated_height`, or more than `max_reorg_depth` canonical blocks.
+    /// Rejections are recorded in the fork history
+    pub fn check_fork_limits(
+        &mut self,
+        block: &IrysBlockHeader,
+        max_reorg_depth: u64,
+        migrated_height: Option<u64>,
+    ) -> eyre::Result<()> {
+        // Blocks whose parent is unknown are rejected when added
+        let Some(event) = self.fork_event(ForkEventKind::Rejected, block) else {
+            return Ok(());
+        };
+        let reorgs_migrated_blocks = event.depth > 0
+            && migrated_height
+                .is_some_and(|migrated_height| event.fork_point_height < migrated_height);
+        if !reorgs_migrated_blocks && event.depth <= max_reorg_depth {
+            return Ok(());
+        }
+
+        error!(
+            "rejecting block {} at height {}: its fork would reorganize {} blocks from height {} (max reorg depth {}, migrated height {:?})",
+            block.block_hash.0.to_base58(),
+            block.height,
+            event.depth,
+            event.fork_point_height + 1,
+            max_reorg_depth,
+            migrated_height
+        );
+        let depth = event.depth;
+        let fork_point_height = event.fork_point_height;
+        self.record_fork_event(event);
+        Err(eyre::eyre!(
+            "fork of depth {} from height {} exceeds the reorg limits",
+            depth,
+            fork_point_height
+        ))
+    }
+
+    /// Returns the orphaned and new branches if marking `block_hash` as the tip would
+    /// switch the canonical chain to a fork. Must be called before `mark_tip`
+    #[must_use]
+    pub fn get_reorg_branches(&self, block_hash: &BlockHash) -> Option<ReorgMessage> {
+        let entry = self.blocks.get(block_hash)?;
+        if entry.chain_state == ChainState::Onchain {
+            return None;
+        }
+        let fork_point_hash = self.get_fork_point(&entry.block)?.block_hash;
+        if fork_point_hash == self.tip {
+            return None;
+        }
+
+        // Walks back from `hash` to the fork point
+        let collect_branch = |mut hash: BlockHash| {
+            let mut branch = Vec::new();
+            while hash != fork_point_hash {
+                let Some(entry) = self.blocks.get(&hash) else {
+                    break;
+                };
+                branch.push(Arc::new(entry.block.clone()));
+                hash = entry.block.previous_block_hash;
+            }
+            branch.reverse();
+            branch
+        };
+
+        Some(ReorgMessage {
+            orphaned_blocks: collect_branch(self.tip),
+            new_blocks: collect_branch(*block_hash),
+        })
+    }
+
+    /// Returns the recent forks, reorgs and rejected forks, oldest first
+    #[must_use]
+    pub fn get_fork_history(&self) -> Vec<ForkEvent> {
+        self.fork_history.iter().cloned().collect()
+    }
+
+    fn fork_event(&self, kind: ForkEventKind, block: &IrysBlockHeader) -> Option<ForkEvent> {
+        let fork_point = self.get_fork_point(block)?;
+        let tip_height = self.blocks.get(&self.tip)?.block.height;
+        Some(ForkEvent {
+            kind,
+            block_hash: block.block_hash,
+            height: block.height,
+            fork_point_hash: fork_point.block_hash,
+            fork_point_height: fork_point.height,
+            depth: tip_height.saturating_sub(fork_point.height),
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_millis() as u64)
+                .unwrap_or_default(),
+        })
+    }
+
+    fn record_fork_event(&mut self, event: ForkEvent) {
+        if self.fork_history.len() == FORK_HISTORY_LEN {
+            self.fork_history.pop_front();
+        }
+        self.fork_history.push_back(event);
+    }
 }
 
 pub async fn get_optimistic_chain(tree: BlockTreeReadGuard) -> eyre::Result<Vec<(H256, u64)>> {
@@ -2026,6 +2288,87 @@ mod tests {
         );
     }
 
+    #[actix::test]
+    async fn test_fork_limits_and_history() {
+        let all_tx = Arc::new(vec![]);
+        let genesis = random_block(U256::zero());
+        let mut cache = BlockTreeCache::new(&genesis);
+
+        // Canonical chain of 5 blocks on top of genesis
+        let mut chain = vec![genesis.clone()];
+        for i in 1..=5 {
+            let block = extend_chain(random_block(U256::from(i)), &chain[i - 1]);
+            cache
+                .add_validated_block(block.clone(), BlockState::ValidBlock, all_tx.clone())
+                .unwrap();
+            cache.mark_tip(&block.block_hash).unwrap();
+            chain.push(block);
+        }
+        assert!(cache.get_fork_history().is_empty());
+
+        // A heavier fork branching off block 2, replacing blocks 3 to 5
+        let fork_block = extend_chain(random_block(U256::from(10)), &chain[2]);
+        assert_eq!(
+            cache
+                .get_fork_point(&fork_block)
+                .map(|block| block.block_hash),
+            Some(chain[2].block_hash)
+        );
+
+        // Deeper than the max reorg depth
+        assert!(cache.check_fork_limits(&fork_block, 2, None).is_err());
+        // Reorganizes migrated blocks
+        assert!(cache.check_fork_limits(&fork_block, 10, Some(3)).is_err());
+        // Within the limits
+        assert_matches!(cache.check_fork_limits(&fork_block, 3, Some(2)), Ok(_));
+        // Extending the tip is always allowed
+        let next_block = extend_chain(random_block(U256::from(6)), &chain[5]);
+        assert_matches!(cache.check_fork_limits(&next_block, 0, Some(5)), Ok(_));
+
+        let history = cache.get_fork_history();
+        assert_eq!(history.len(), 2);
+        assert!(history
+            .iter()
+            .all(|event| event.kind == ForkEventKind::Rejected
+                && event.depth == 3
+                && event.fork_point_hash == chain[2].block_hash));
+
+        // Switching to the fork is recorded as a fork and a reorg
+        cache
+            .add_validated_block(fork_block.clone(), BlockState::ValidBlock, all_tx.clone())
+            .unwrap();
+        let reorg = cache.get_reorg_branches(&fork_block.block_hash).unwrap();
+        assert_eq!(
+            reorg
+                .orphaned_blocks
+                .iter()
+                .map(|block| block.block_hash)
+                .collect::<Vec<_>>(),
+            chain[3..]
+                .iter()
+                .map(|block| block.block_hash)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(reorg.new_blocks.len(), 1);
+        assert_eq!(reorg.new_blocks[0].block_hash, fork_block.block_hash);
+        assert!(cache.mark_tip(&fork_block.block_hash).unwrap());
+
+        // Extending the new tip isn't a reorg
+        let fork_child = extend_chain(random_block(U256::from(11)), &fork_block);
+        cache
+            .add_validated_block(fork_child.clone(), BlockState::ValidBlock, all_tx.clone())
+            .unwrap();
+        assert!(cache.get_reorg_branches(&fork_child.block_hash).is_none());
+
+        let history = cache.get_fork_history();
+        assert_eq!(history.len(), 4);
+        assert_eq!(history[2].kind, ForkEventKind::Fork);
+        assert_eq!(history[2].block_hash, fork_block.block_hash);
+        assert_eq!(history[3].kind, ForkEventKind::Reorg);
+        assert_eq!(history[3].depth, 3);
+        assert_eq!(history[3].fork_point_height, 2);
+    }
+
     fn random_block(cumulative_diff: U256) -> IrysBlockHeader {
         let mut block = IrysBlockHeader::new_mock_header();
         block.block_hash = BlockHash::random();
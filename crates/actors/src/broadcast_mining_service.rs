@@ -1,4 +1,5 @@
 use crate::mining::PartitionMiningActor;
+use crate::mining_pool::MiningPoolService;
 use actix::prelude::*;
 use irys_types::{block_production::Seed, H256List, IrysBlockHeader};
 use std::sync::Arc;
@@ -16,6 +17,11 @@ pub struct Subscribe(pub Addr<PartitionMiningActor>);
 #[rtype(result = "()")]
 pub struct Unsubscribe(pub Addr<PartitionMiningActor>);
 
+/// Subscribes the `MiningPoolService` so remote workers receive the broadcast messages
+#[derive(Message, Debug)]
+#[rtype(result = "()")]
+pub struct SubscribeMiningPool(pub Addr<MiningPoolService>);
+
 /// Send the most recent mining step to all the `PartitionMiningActors`
 #[derive(Message, Debug, Clone)]
 #[rtype(result = "()")]
@@ -35,10 +41,19 @@ pub struct BroadcastDifficultyUpdate(pub Arc<IrysBlockHeader>);
 #[rtype(result = "()")]
 pub struct BroadcastPartitionsExpiration(pub H256List);
 
+/// Let all the `PartitionMiningActors` know a solution was found for a VDF step
+/// so they stop mining it and drop any competing solution
+#[derive(Message, Debug, Clone)]
+#[rtype(result = "()")]
+pub struct BroadcastSolutionFound {
+    pub vdf_step: u64,
+}
+
 /// Broadcaster actor
 #[derive(Debug, Default)]
 pub struct BroadcastMiningService {
     pub subscribers: Vec<Addr<PartitionMiningActor>>,
+    pub pool_subscribers: Vec<Addr<MiningPoolService>>,
     pub span: Option<Span>,
 }
 // Actor Definition
@@ -48,9 +63,22 @@ impl BroadcastMiningService {
     pub fn new(span: Option<Span>) -> Self {
         Self {
             subscribers: Vec::new(),
+            pool_subscribers: Vec::new(),
             span: Some(span.unwrap_or(Span::current())),
         }
     }
+
+    /// Forwards a broadcast message to the subscribed mining pools
+    fn broadcast_to_pools<M>(&mut self, msg: M)
+    where
+        M: Message<Result = ()> + Clone + Send + 'static,
+        MiningPoolService: Handler<M>,
+    {
+        self.pool_subscribers.retain(|addr| addr.connected());
+        for pool in &self.pool_subscribers {
+            pool.do_send(msg.clone());
+        }
+    }
 }
 
 impl Actor for BroadcastMiningService {
@@ -82,6 +110,15 @@ impl Handler<Subscribe> for BroadcastMiningService {
     }
 }
 
+impl Handler<SubscribeMiningPool> for BroadcastMiningService {
+    type Result = ();
+
+    fn handle(&mut self, msg: SubscribeMiningPool, _: &mut Context<Self>) {
+        debug!("MiningPoolService subscribed");
+        self.pool_subscribers.push(msg.0);
+    }
+}
+
 // Handle unsubscribe
 impl Handler<Unsubscribe> for BroadcastMiningService {
     type Result = ();
@@ -107,6 +144,7 @@ impl Handler<BroadcastMiningSeed> for BroadcastMiningService {
         for subscriber in &self.subscribers {
             subscriber.do_send(msg.clone());
         }
+        self.broadcast_to_pools(msg);
     }
 }
 
@@ -118,6 +156,7 @@ impl Handler<BroadcastDifficultyUpdate> for BroadcastMiningService {
         for subscriber in &self.subscribers {
             subscriber.do_send(msg.clone());
         }
+        self.broadcast_to_pools(msg);
     }
 }
 
@@ -130,5 +169,19 @@ impl Handler<BroadcastPartitionsExpiration> for BroadcastMiningService {
         for subscriber in &self.subscribers {
             subscriber.do_send(msg.clone());
         }
+        self.broadcast_to_pools(msg);
+    }
+}
+
+impl Handler<BroadcastSolutionFound> for BroadcastMiningService {
+    type Result = ();
+
+    fn handle(&mut self, msg: BroadcastSolutionFound, _: &mut Context<Self>) {
+        self.subscribers.retain(|addr| addr.connected());
+        debug!(vdf_step = msg.vdf_step, "Broadcasting solution found");
+        for subscriber in &self.subscribers {
+            subscriber.do_send(msg.clone());
+        }
+        self.broadcast_to_pools(msg);
     }
 }
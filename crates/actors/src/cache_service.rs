@@ -6,7 +6,8 @@ use irys_database::{
     db_cache::DataRootLRUEntry,
     delete_cached_chunks_by_data_root, get_cache_size,
     tables::{
-        CachedChunks, DataRootLRU, IngressProofs, ProgrammableDataCache, ProgrammableDataLRU,
+        CachedChunks, DataRootLRU, IngressProofsBySigner, ProgrammableDataCache,
+        ProgrammableDataLRU,
     },
 };
 use irys_types::{Config, DatabaseProvider, GIGABYTE};
@@ -88,7 +89,7 @@ impl ChunkCacheService {
             Ok((
                 get_cache_size::<CachedChunks, _>(tx, self.config.consensus.chunk_size)?,
                 get_cache_size::<ProgrammableDataCache, _>(tx, self.config.consensus.chunk_size)?,
-                tx.entries::<IngressProofs>()?,
+                tx.entries::<IngressProofsBySigner>()?,
             ))
         })?;
         info!(
@@ -119,7 +120,7 @@ impl ChunkCacheService {
                     "expiring ingress proof",
                 );
                 write_tx.delete::<DataRootLRU>(data_root, None)?;
-                write_tx.delete::<IngressProofs>(data_root, None)?;
+                write_tx.delete::<IngressProofsBySigner>(data_root, None)?;
                 // delete the cached chunks
                 chunks_pruned = chunks_pruned
                     .saturating_add(delete_cached_chunks_by_data_root(&write_tx, data_root)?);
@@ -1,22 +1,26 @@
 use actix::prelude::*;
 use eyre::eyre;
 use irys_database::{
-    cached_chunk_by_chunk_offset,
+    cached_chunk_by_chunk_offset, commitment_tx_by_txid,
     db::IrysDatabaseExt as _,
     db_cache::{CachedChunk, CachedChunkIndexMetadata},
-    BlockIndex,
+    db_index::BundleItemLocation,
+    delete_pending_bundle, index_commitment_tx, index_tx_header, insert_bundle_item,
+    insert_pending_bundle, pending_bundles, tx_header_by_txid, BlockIndex, SystemLedger,
 };
 use irys_storage::{
-    get_overlapped_storage_modules, ie, ii, InclusiveInterval, StorageModule,
+    get_overlapped_storage_modules, ie, ii, ChunkProvider, InclusiveInterval, StorageModule,
     StorageModulesReadGuard,
 };
 use irys_types::{
-    app_state::DatabaseProvider, Base64, Config, DataLedger, DataRoot, DataTransactionLedger,
-    IrysBlockHeader, IrysTransactionHeader, LedgerChunkOffset, LedgerChunkRange, Proof,
-    TxChunkOffset, UnpackedChunk, H256,
+    app_state::DatabaseProvider,
+    bundle::{Bundle, DataItem, BUNDLE_FORMAT_V1},
+    Base64, Config, DataLedger, DataRoot, DataTransactionLedger, IrysBlockHeader,
+    IrysTransactionHeader, LedgerChunkOffset, LedgerChunkRange, Proof, TxChunkOffset,
+    UnpackedChunk, H256,
 };
 use std::sync::{Arc, RwLock};
-use tracing::error;
+use tracing::{debug, error, warn};
 
 use crate::services::Stop;
 use crate::{
@@ -101,6 +105,11 @@ impl Handler<BlockFinalizedMessage> for ChunkMigrationService {
         let storage_modules = Arc::new(self.storage_modules_guard.clone());
         let db = Arc::new(self.db.clone().unwrap());
         let service_senders = self.service_senders.clone().unwrap();
+        let chunk_provider = ChunkProvider::new(
+            self.config.clone(),
+            self.storage_modules_guard.clone(),
+            (*db).clone(),
+        );
 
         // Extract transactions for each ledger
         let submit_tx_count = block.data_ledgers[DataLedger::Submit].tx_ids.len();
@@ -133,6 +142,30 @@ impl Handler<BlockFinalizedMessage> for ChunkMigrationService {
             )
             .map_err(|_| eyre!("Unexpected error processing publish ledger transactions"))?;
 
+            // Now the txs are confirmed, index them for the list/query endpoints
+            db.update_eyre(|tx| {
+                for tx_header in &all_txs {
+                    index_tx_header(tx, tx_header)?;
+                }
+                let commitment_ids = block
+                    .system_ledgers
+                    .iter()
+                    .filter(|ledger| ledger.ledger_id == SystemLedger::Commitment)
+                    .flat_map(|ledger| ledger.tx_ids.iter());
+                for tx_id in commitment_ids {
+                    match commitment_tx_by_txid(tx, tx_id)? {
+                        Some(commitment_tx) => index_commitment_tx(tx, &commitment_tx)?,
+                        None => warn!("Migrated commitment tx {} not found in the db", tx_id),
+                    }
+                }
+                Ok(())
+            })?;
+
+            // Index the data items of any promoted bundles, reading them can take a while
+            tokio::task::spawn_blocking(move || {
+                index_bundle_items(&publish_txs, &chunk_provider, &db)
+            });
+
             // forward the finalization message to the cache service for cleanup
             let _ = service_senders
                 .chunk_cache
@@ -203,6 +236,174 @@ fn process_ledger_transactions(
     Ok(())
 }
 
+/// Largest bundle header read when indexing a bundle, enough for ~400k items
+const BUNDLE_HEADER_LIMIT: u64 = 16 * 1024 * 1024;
+/// Largest bundle item read when indexing a bundle, items are read one at a time
+const BUNDLE_ITEM_LIMIT: u64 = 64 * 1024 * 1024;
+/// Number of failed attempts at indexing a bundle after which it isn't retried anymore
+const BUNDLE_INDEX_ATTEMPTS: u64 = 10;
+
+/// Why the items of a bundle couldn't be indexed
+#[derive(Debug)]
+enum BundleIndexError {
+    /// Some of the bundle data isn't stored in the local storage modules (yet),
+    /// indexing is retried later
+    NotStored,
+    /// The bundle is malformed or exceeds the indexing limits, retrying won't help
+    Invalid(eyre::Report),
+    /// Reading the bundle or writing its index failed, indexing is retried later
+    Failed(eyre::Report),
+}
+
+/// Parses the data of promoted bundle transactions and records the location of
+/// each of their data items so they can be looked up by item id, then retries
+/// the bundles that failed to be indexed on earlier migrations.
+/// Bundles that aren't stored locally or fail to be read or indexed are recorded
+/// in [`PendingBundles`] and retried up to [`BUNDLE_INDEX_ATTEMPTS`] times.
+/// Bundles that fail to decode are logged and skipped. This runs on a blocking
+/// thread, off the migration of the block.
+///
+/// [`PendingBundles`]: irys_database::tables::PendingBundles
+fn index_bundle_items(
+    txs: &[IrysTransactionHeader],
+    chunk_provider: &ChunkProvider,
+    db: &DatabaseProvider,
+) {
+    let pending = db
+        .view_eyre(|db_tx| {
+            pending_bundles(db_tx)?
+                .into_iter()
+                .filter_map(|(tx_id, attempts)| {
+                    tx_header_by_txid(db_tx, &tx_id)
+                        .map(|header| header.map(|header| (header, attempts)))
+                        .transpose()
+                })
+                .collect::<eyre::Result<Vec<_>>>()
+        })
+        .unwrap_or_else(|e| {
+            error!("Failed to read the bundles pending indexing: {}", e);
+            Vec::new()
+        });
+
+    let bundles = txs
+        .iter()
+        .filter(|tx| tx.bundle_format == Some(BUNDLE_FORMAT_V1))
+        .map(|tx| (tx.clone(), 0))
+        .chain(pending);
+    for (tx, attempts) in bundles {
+        let failed_attempts = match index_bundle(&tx, chunk_provider, db) {
+            Ok(()) => None,
+            Err(BundleIndexError::NotStored) if attempts + 1 < BUNDLE_INDEX_ATTEMPTS => {
+                debug!(
+                    "Bundle tx {} data is not stored locally, will retry indexing",
+                    tx.id
+                );
+                Some(attempts + 1)
+            }
+            Err(BundleIndexError::NotStored) => {
+                debug!(
+                    "Bundle tx {} data is not stored locally after {} attempts, skipping indexing",
+                    tx.id,
+                    attempts + 1
+                );
+                None
+            }
+            Err(BundleIndexError::Invalid(e)) => {
+                warn!("Invalid bundle in tx {}: {}", tx.id, e);
+                None
+            }
+            Err(BundleIndexError::Failed(e)) if attempts + 1 < BUNDLE_INDEX_ATTEMPTS => {
+                warn!(
+                    "Failed to index items of bundle tx {}, will retry: {}",
+                    tx.id, e
+                );
+                Some(attempts + 1)
+            }
+            Err(BundleIndexError::Failed(e)) => {
+                error!(
+                    "Failed to index items of bundle tx {} after {} attempts: {}",
+                    tx.id,
+                    attempts + 1,
+                    e
+                );
+                None
+            }
+        };
+
+        let res = db.update_eyre(|db_tx| match failed_attempts {
+            Some(attempts) => insert_pending_bundle(db_tx, tx.id, attempts),
+            None => delete_pending_bundle(db_tx, &tx.id),
+        });
+        if let Err(e) = res {
+            error!(
+                "Failed to record the indexing state of bundle tx {}: {}",
+                tx.id, e
+            );
+        }
+    }
+}
+
+/// Indexes the items of one bundle, reading its header and then its items one at a
+/// time so only one of them is held in memory, and each chunk is unpacked once.
+fn index_bundle(
+    tx: &IrysTransactionHeader,
+    chunk_provider: &ChunkProvider,
+    db: &DatabaseProvider,
+) -> Result<(), BundleIndexError> {
+    let mut reader = chunk_provider.tx_data_reader(DataLedger::Publish, tx.data_root, tx.data_size);
+    let mut read = |len: u64| {
+        reader
+            .read(len)
+            .map_err(BundleIndexError::Failed)?
+            .ok_or(BundleIndexError::NotStored)
+    };
+
+    let mut header = read(tx.data_size.min(4))?;
+    let header_size = Bundle::header_size(&header).map_err(BundleIndexError::Invalid)?;
+    if header_size > BUNDLE_HEADER_LIMIT {
+        return Err(BundleIndexError::Invalid(eyre!(
+            "bundle header of {} bytes exceeds the limit of {} bytes",
+            header_size,
+            BUNDLE_HEADER_LIMIT
+        )));
+    }
+    // decoding the header checks its size against the bundle size
+    header.extend(read(tx.data_size.min(header_size) - header.len() as u64)?);
+    let index = Bundle::decode_header(&header, tx.data_size).map_err(BundleIndexError::Invalid)?;
+    drop(header);
+
+    // Only index the items once the whole bundle is known to be valid, the items
+    // follow each other right after the header
+    for entry in &index {
+        if entry.size > BUNDLE_ITEM_LIMIT {
+            return Err(BundleIndexError::Invalid(eyre!(
+                "bundle item {} of {} bytes exceeds the limit of {} bytes",
+                entry.id,
+                entry.size,
+                BUNDLE_ITEM_LIMIT
+            )));
+        }
+        let bytes = read(entry.size)?;
+        DataItem::decode_entry(&bytes, entry).map_err(BundleIndexError::Invalid)?;
+    }
+
+    db.update_eyre(|db_tx| {
+        for entry in &index {
+            insert_bundle_item(
+                db_tx,
+                entry.id,
+                BundleItemLocation {
+                    bundle_tx_id: tx.id,
+                    offset: entry.offset,
+                    size: entry.size,
+                },
+            )?;
+        }
+        Ok(())
+    })
+    .map_err(BundleIndexError::Failed)
+}
+
 fn process_transaction_chunks(
     num_chunks_in_tx: u32,
     data_root: DataRoot,
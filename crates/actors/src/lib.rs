@@ -12,6 +12,7 @@ pub mod ema_service;
 pub mod epoch_service;
 pub mod mempool_service;
 pub mod mining;
+pub mod mining_pool;
 pub mod packing;
 pub mod reth_service;
 pub mod services;
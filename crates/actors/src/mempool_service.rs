@@ -1,5 +1,5 @@
 use crate::block_producer::BlockConfirmedMessage;
-use crate::block_tree_service::BlockTreeReadGuard;
+use crate::block_tree_service::{commitment_tx_ids, BlockTreeReadGuard, ReorgMessage};
 use crate::services::ServiceSenders;
 use crate::{CommitmentCacheMessage, CommitmentCacheStatus, CommitmentStateReadGuard};
 use actix::{
@@ -15,17 +15,21 @@ use irys_database::db_cache::data_size_to_chunk_count;
 use irys_database::db_cache::DataRootLRUEntry;
 use irys_database::submodule::get_data_size_by_data_root;
 use irys_database::tables::DataRootLRU;
-use irys_database::tables::{CachedChunks, CachedChunksIndex, IngressProofs};
-use irys_database::{insert_tx_header, tx_header_by_txid, SystemLedger};
+use irys_database::tables::{CachedChunks, CachedChunksIndex};
+use irys_database::{
+    commitment_tx_by_txid, ingress_proof_by_data_root_and_address, insert_tx_header,
+    store_ingress_proof, tx_header_by_txid, SystemLedger,
+};
 use irys_primitives::CommitmentType;
 use irys_storage::StorageModulesReadGuard;
 use irys_types::irys::IrysSigner;
 use irys_types::{
     app_state::DatabaseProvider, chunk::UnpackedChunk, hash_sha256, validate_path, GossipData,
-    IrysTransactionHeader, H256,
+    IrysTransactionHeader, Tag, H256,
 };
 use irys_types::{
-    Address, CommitmentTransaction, Config, DataLedger, DataRoot, IrysBlockHeader,
+    ingress::{verify_ingress_proof, IngressProof},
+    Address, ChunkBytes, CommitmentTransaction, Config, DataLedger, DataRoot, IrysBlockHeader,
     IrysTransactionCommon, IrysTransactionId, TxChunkOffset, U256,
 };
 use lru::LruCache;
@@ -35,7 +39,7 @@ use reth_db::transaction::DbTx as _;
 use reth_db::transaction::DbTxMut as _;
 use reth_db::Database as _;
 use std::collections::HashSet;
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::future::Future;
 use std::num::NonZeroUsize;
 use tracing::{debug, error, info, warn};
@@ -51,6 +55,7 @@ pub trait MempoolFacade: Clone + Send + Sync + 'static {
         tx_header: CommitmentTransaction,
     ) -> Result<(), TxIngressError>;
     async fn handle_chunk(&self, chunk: UnpackedChunk) -> Result<(), ChunkIngressError>;
+    async fn handle_ingress_proof(&self, proof: IngressProof) -> Result<(), IngressProofError>;
     async fn is_known_tx(&self, tx_id: H256) -> Result<bool, TxIngressError>;
 }
 
@@ -83,6 +88,15 @@ impl From<MailboxError> for ChunkIngressError {
     }
 }
 
+impl From<MailboxError> for IngressProofError {
+    fn from(value: MailboxError) -> Self {
+        IngressProofError::Other(format!(
+            "Failed to send a message to MempoolService: {:?}",
+            value
+        ))
+    }
+}
+
 #[async_trait]
 impl MempoolFacade for MempoolServiceFacadeImpl {
     async fn handle_data_transaction(
@@ -105,6 +119,10 @@ impl MempoolFacade for MempoolServiceFacadeImpl {
         self.service.send(ChunkIngressMessage(chunk)).await?
     }
 
+    async fn handle_ingress_proof(&self, proof: IngressProof) -> Result<(), IngressProofError> {
+        self.service.send(IngressProofIngressMessage(proof)).await?
+    }
+
     async fn is_known_tx(&self, tx_id: H256) -> Result<bool, TxIngressError> {
         self.service.send(TxExistenceQuery(tx_id)).await?
     }
@@ -132,6 +150,8 @@ pub struct MempoolService {
     /// LRU caches for out of order gossip data
     pending_chunks: LruCache<DataRoot, LruCache<TxChunkOffset, UnpackedChunk>>,
     pending_pledges: LruCache<Address, LruCache<IrysTransactionId, CommitmentTransaction>>,
+    /// Ingress proofs from peers, waiting for all the chunks of their data_root to be verified
+    pending_ingress_proofs: LruCache<DataRoot, LruCache<Address, IngressProof>>,
 
     /// Reference to all the services we can send messages to
     service_senders: ServiceSenders,
@@ -169,6 +189,7 @@ impl MempoolService {
         let mempool_config = &config.consensus.mempool;
         let max_pending_chunk_items = mempool_config.max_pending_chunk_items;
         let max_pending_pledge_items = mempool_config.max_pending_pledge_items;
+        let max_pending_ingress_proof_items = mempool_config.max_pending_ingress_proof_items;
         Self {
             irys_db,
             reth_db,
@@ -184,6 +205,9 @@ impl MempoolService {
             recent_valid_tx: HashSet::new(),
             pending_chunks: LruCache::new(NonZeroUsize::new(max_pending_chunk_items).unwrap()),
             pending_pledges: LruCache::new(NonZeroUsize::new(max_pending_pledge_items).unwrap()),
+            pending_ingress_proofs: LruCache::new(
+                NonZeroUsize::new(max_pending_ingress_proof_items).unwrap(),
+            ),
         }
     }
     // Helper to get the canonical chain and latest height
@@ -257,6 +281,28 @@ impl MempoolService {
         }
     }
 
+    // Helper to check a data tx's tags are within the configured limits
+    fn validate_tags(&mut self, tx: &IrysTransactionHeader) -> Result<(), TxIngressError> {
+        let mempool_config = &self.config.consensus.mempool;
+        let tag_count = tx.tags.as_ref().map_or(0, Vec::len);
+        let tags_size = tx.tags_size();
+        let oversized_field = tx.tags.iter().flatten().any(|tag| {
+            tag.name.len() > Tag::MAX_FIELD_SIZE || tag.value.len() > Tag::MAX_FIELD_SIZE
+        });
+        if tag_count > mempool_config.max_tags_per_tx
+            || tags_size > mempool_config.max_tags_size
+            || oversized_field
+        {
+            self.invalid_tx.push(tx.id);
+            debug!(
+                "tx {} has {} tags totalling {} bytes, exceeding the limits",
+                tx.id, tag_count, tags_size
+            );
+            return Err(TxIngressError::InvalidTags);
+        }
+        Ok(())
+    }
+
     // Helper to execute async operation in a synchronous handler
     // TODO: This is actually bad, we spawn a thread to perform the async
     // operation from a sync context, to fix the mempool service needs to be
@@ -402,6 +448,8 @@ pub enum TxIngressError {
     Skipped,
     /// Invalid anchor value (unknown or too old)
     InvalidAnchor,
+    /// The transaction's tags exceed the mempool's count or size limits
+    InvalidTags,
     /// Some database error occurred
     DatabaseError,
     /// The service is uninitialized
@@ -434,6 +482,19 @@ impl ChunkIngressMessage {
     }
 }
 
+/// Lists the chunks of a data root the node has received, so clients can resume uploads
+#[derive(Message, Debug)]
+#[rtype(result = "Result<ReceivedChunks, ChunkIngressError>")]
+pub struct GetReceivedChunksMessage(pub DataRoot);
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReceivedChunks {
+    /// Size of the data, known once a tx with the data root is received
+    pub data_size: Option<u64>,
+    /// Offsets of the cached chunks and of those waiting for their tx, in ascending order
+    pub offsets: Vec<TxChunkOffset>,
+}
+
 /// Reasons why Transaction Ingress might fail
 #[derive(Debug, Clone)]
 pub enum ChunkIngressError {
@@ -466,6 +527,35 @@ impl ChunkIngressError {
     }
 }
 
+/// Message for when an ingress proof is received from a peer
+#[derive(Message, Debug)]
+#[rtype(result = "Result<(),IngressProofError>")]
+pub struct IngressProofIngressMessage(pub IngressProof);
+
+/// Reasons why Ingress Proof ingress might fail
+#[derive(Debug, Clone)]
+pub enum IngressProofError {
+    /// The signature of the proof could not be recovered
+    InvalidSignature,
+    /// The proof doesn't match the chunks of its data_root
+    InvalidProof,
+    /// Some database error occurred when reading or writing the proof
+    DatabaseError,
+    // Catch-all variant for other errors.
+    Other(String),
+}
+
+impl IngressProofError {
+    /// Returns an other error with the given message.
+    pub fn other(err: impl Into<String>) -> Self {
+        Self::Other(err.into())
+    }
+    /// Allows converting an error that implements Display into an Other error
+    pub fn other_display(err: impl Display) -> Self {
+        Self::Other(err.to_string())
+    }
+}
+
 impl Handler<TxIngressMessage> for MempoolService {
     type Result = Result<(), TxIngressError>;
 
@@ -536,8 +626,9 @@ impl Handler<TxIngressMessage> for MempoolService {
             return Err(TxIngressError::Unfunded);
         }
 
-        // Validate the transaction signature
+        // Validate the transaction signature and tags
         self.validate_signature(tx)?;
+        self.validate_tags(tx)?;
         self.valid_tx.insert(tx.id, tx.clone());
         self.recent_valid_tx.insert(tx.id);
 
@@ -740,7 +831,7 @@ impl Handler<CommitmentTxIngressMessage> for MempoolService {
 impl Handler<ChunkIngressMessage> for MempoolService {
     type Result = Result<(), ChunkIngressError>;
 
-    fn handle(&mut self, chunk_msg: ChunkIngressMessage, _ctx: &mut Context<Self>) -> Self::Result {
+    fn handle(&mut self, chunk_msg: ChunkIngressMessage, ctx: &mut Context<Self>) -> Self::Result {
         // TODO: maintain a shared read transaction so we have read isolation
         let chunk: UnpackedChunk = chunk_msg.0;
 
@@ -798,6 +889,10 @@ impl Handler<ChunkIngressMessage> for MempoolService {
             }
         };
 
+        // Release the storage modules read lock, we may need `&mut self` to process pending ingress proofs
+        drop(candidate_sms);
+        drop(binding);
+
         // Validate that the data_size for this chunk matches the data_size
         // recorded in the transaction header.
         if data_size != chunk.data_size {
@@ -888,8 +983,8 @@ impl Handler<ChunkIngressMessage> for MempoolService {
         // if we have, update it's expiry height
 
         //  TODO: hook into whatever manages ingress proofs
-        if read_tx
-            .get::<IngressProofs>(root_hash)
+        let signer = self.config.irys_signer();
+        if ingress_proof_by_data_root_and_address(&read_tx, root_hash, signer.address())
             .map_err(|_| ChunkIngressError::DatabaseError)?
             .is_some()
         {
@@ -934,12 +1029,13 @@ impl Handler<ChunkIngressMessage> for MempoolService {
                 .ok_or(ChunkIngressError::ServiceUninitialized)?;
 
             let db = self.irys_db.clone();
-            let signer = self.config.irys_signer();
             let latest_height = *latest_height;
+            let gossip_sender = self.service_senders.gossip_broadcast.clone();
             self.task_exec.clone().spawn_blocking(async move {
-                generate_ingress_proof(db.clone(), root_hash, data_size, chunk_size, signer)
-                    // TODO: handle results instead of unwrapping
-                    .unwrap();
+                let proof =
+                    generate_ingress_proof(db.clone(), root_hash, data_size, chunk_size, signer)
+                        // TODO: handle results instead of unwrapping
+                        .unwrap();
                 db.update(|wtx| {
                     wtx.put::<DataRootLRU>(
                         root_hash,
@@ -951,7 +1047,19 @@ impl Handler<ChunkIngressMessage> for MempoolService {
                 })
                 .unwrap()
                 .unwrap();
+
+                // Share our proof so other producers can promote the data
+                if let Err(error) = gossip_sender.send(GossipData::IngressProof(proof)) {
+                    tracing::error!("Failed to send gossip data: {:?}", error);
+                }
             });
+
+            // Now that all the chunks are available, verify any proofs peers sent us early
+            if let Some(proofs) = self.pending_ingress_proofs.pop(&root_hash) {
+                for (_, proof) in proofs {
+                    ctx.address().do_send(IngressProofIngressMessage(proof));
+                }
+            }
         }
 
         let gossip_sender = self.service_senders.gossip_broadcast.clone();
@@ -964,6 +1072,138 @@ impl Handler<ChunkIngressMessage> for MempoolService {
         Ok(())
     }
 }
+impl Handler<GetReceivedChunksMessage> for MempoolService {
+    type Result = Result<ReceivedChunks, ChunkIngressError>;
+
+    fn handle(&mut self, msg: GetReceivedChunksMessage, _ctx: &mut Context<Self>) -> Self::Result {
+        let data_root = msg.0;
+        let read_tx = self
+            .irys_db
+            .tx()
+            .map_err(|_| ChunkIngressError::DatabaseError)?;
+
+        let data_size = irys_database::cached_data_root_by_data_root(&read_tx, data_root)
+            .map_err(|_| ChunkIngressError::DatabaseError)?
+            .map(|cached_data_root| cached_data_root.data_size);
+        let mut offsets: BTreeSet<TxChunkOffset> =
+            irys_database::cached_chunk_offsets_by_data_root(&read_tx, data_root)
+                .map_err(|_| ChunkIngressError::DatabaseError)?
+                .into_iter()
+                .collect();
+        if let Some(pending) = self.pending_chunks.peek(&data_root) {
+            offsets.extend(pending.iter().map(|(offset, _)| *offset));
+        }
+
+        Ok(ReceivedChunks {
+            data_size,
+            offsets: offsets.into_iter().collect(),
+        })
+    }
+}
+
+impl Handler<IngressProofIngressMessage> for MempoolService {
+    type Result = Result<(), IngressProofError>;
+
+    fn handle(
+        &mut self,
+        proof_msg: IngressProofIngressMessage,
+        _ctx: &mut Context<Self>,
+    ) -> Self::Result {
+        let proof = proof_msg.0;
+        let data_root = proof.data_root;
+
+        let signer = proof
+            .recover_signer()
+            .map_err(|_| IngressProofError::InvalidSignature)?;
+
+        debug!(?data_root, ?signer, "Processing ingress proof");
+
+        let read_tx = self
+            .irys_db
+            .tx()
+            .map_err(|_| IngressProofError::DatabaseError)?;
+
+        if ingress_proof_by_data_root_and_address(&read_tx, data_root, signer)
+            .map_err(|_| IngressProofError::DatabaseError)?
+            .is_some()
+        {
+            debug!(?data_root, ?signer, "Ingress proof is already known");
+            return Ok(());
+        }
+
+        // The proof can only be verified against the full set of chunks for the data_root,
+        // if we don't have them yet park the proof until the last chunk arrives.
+        let data_size = irys_database::cached_data_root_by_data_root(&read_tx, data_root)
+            .map_err(|_| IngressProofError::DatabaseError)?
+            .map(|cdr| cdr.data_size);
+        let chunk_count = read_tx
+            .cursor_dup_read::<CachedChunksIndex>()
+            .and_then(|mut cursor| cursor.dup_count(data_root))
+            .map_err(|_| IngressProofError::DatabaseError)?;
+        let has_all_chunks = match (data_size, chunk_count) {
+            (Some(data_size), Some(chunk_count)) => {
+                data_size_to_chunk_count(data_size, self.config.consensus.chunk_size)
+                    .map_err(IngressProofError::other_display)?
+                    == chunk_count
+            }
+            _ => false,
+        };
+        if !has_all_chunks {
+            debug!(
+                ?data_root,
+                ?signer,
+                "Parking ingress proof until all chunks are available"
+            );
+            if let Some(proofs) = self.pending_ingress_proofs.get_mut(&data_root) {
+                proofs.put(signer, proof);
+            } else {
+                let max_proofs = self.config.consensus.number_of_ingress_proofs.max(1) as usize;
+                let mut new_lru_cache = LruCache::new(NonZeroUsize::new(max_proofs).unwrap());
+                new_lru_cache.put(signer, proof);
+                self.pending_ingress_proofs.put(data_root, new_lru_cache);
+            }
+            return Ok(());
+        }
+
+        if !validate_ingress_proof(&self.irys_db, &proof)
+            .map_err(|_| IngressProofError::DatabaseError)?
+        {
+            warn!(?data_root, ?signer, "Invalid ingress proof");
+            return Err(IngressProofError::InvalidProof);
+        }
+
+        let latest_height = self
+            .get_latest_block_height()
+            .map_err(|_| IngressProofError::other("unable to get latest block height"))?;
+        self.irys_db
+            .update_eyre(|tx| {
+                store_ingress_proof(tx, signer, &proof)?;
+                if tx.get::<DataRootLRU>(data_root)?.is_none() {
+                    tx.put::<DataRootLRU>(
+                        data_root,
+                        DataRootLRUEntry {
+                            last_height: latest_height,
+                            ingress_proof: true,
+                        },
+                    )?;
+                }
+                Ok(())
+            })
+            .map_err(|_| IngressProofError::DatabaseError)?;
+
+        info!(?data_root, ?signer, "Stored ingress proof");
+
+        let gossip_sender = self.service_senders.gossip_broadcast.clone();
+        let gossip_data = GossipData::IngressProof(proof);
+
+        if let Err(error) = gossip_sender.send(gossip_data) {
+            tracing::error!("Failed to send gossip data: {:?}", error);
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(MessageResponse, Debug)]
 pub struct MempoolTxs {
     pub commitment_tx: Vec<CommitmentTransaction>,
@@ -1111,10 +1351,9 @@ impl Handler<BlockConfirmedMessage> for MempoolService {
 
             let published_txids = &block.data_ledgers[DataLedger::Publish].tx_ids.0;
 
-            // Loop though the promoted transactions and remove their ingress proofs
-            // from the mempool. In the future on a multi node network we may keep
-            // ingress proofs around longer to account for re-orgs, but for now
-            // we just remove them.
+            // Loop though the promoted transactions and mark them as promoted. Their
+            // ingress proofs stay in the db until the data root expires from the
+            // DataRootLRU, a reorg of this block restores them from the block otherwise
             if !published_txids.is_empty() {
                 let mut_tx = self
                     .irys_db
@@ -1143,13 +1382,14 @@ impl Handler<BlockConfirmedMessage> for MempoolService {
                         }
                     };
 
-                    // TODO: In a single node world there is only one ingress proof
-                    // per promoted tx, but in the future there will be multiple proofs.
+                    // Each promoted tx has `number_of_ingress_proofs` proofs, the first one
+                    // is kept in the tx header to mark it as promoted
                     let proofs = block.data_ledgers[DataLedger::Publish]
                         .proofs
                         .as_ref()
                         .unwrap();
-                    let proof = proofs.0[i].clone();
+                    let proofs_per_tx = self.config.consensus.number_of_ingress_proofs as usize;
+                    let proof = proofs.0[i * proofs_per_tx].clone();
                     tx_header.ingress_proofs = Some(proof);
 
                     // Update the header record in the database to include the ingress
@@ -1184,6 +1424,197 @@ impl Handler<BlockConfirmedMessage> for MempoolService {
     }
 }
 
+impl Handler<ReorgMessage> for MempoolService {
+    type Result = ();
+
+    fn handle(&mut self, msg: ReorgMessage, ctx: &mut Context<Self>) -> Self::Result {
+        let orphaned_block_hashes: HashSet<H256> = msg
+            .orphaned_blocks
+            .iter()
+            .map(|block| block.block_hash)
+            .collect();
+        let new_branch_txids: HashSet<H256> = msg
+            .new_blocks
+            .iter()
+            .flat_map(|block| {
+                block.data_ledgers[DataLedger::Submit]
+                    .tx_ids
+                    .iter()
+                    .chain(commitment_tx_ids(block))
+                    .copied()
+            })
+            .collect();
+        let new_branch_promoted_txids: HashSet<H256> = msg
+            .new_blocks
+            .iter()
+            .flat_map(|block| {
+                block.data_ledgers[DataLedger::Publish]
+                    .tx_ids
+                    .iter()
+                    .copied()
+            })
+            .collect();
+
+        // Drop the pending txs the new branch already includes
+        for txid in &new_branch_txids {
+            self.valid_tx.remove(txid);
+            self.recent_valid_tx.remove(txid);
+            self.remove_commitment_tx(txid);
+        }
+
+        // Drop the pending txs anchored to an orphaned block
+        let orphan_anchored_txids: Vec<H256> = self
+            .valid_tx
+            .values()
+            .map(|tx| (tx.id, tx.anchor))
+            .chain(
+                self.valid_commitment_tx
+                    .values()
+                    .flatten()
+                    .map(|tx| (tx.id, tx.anchor)),
+            )
+            .filter(|(_, anchor)| orphaned_block_hashes.contains(anchor))
+            .map(|(txid, _)| txid)
+            .collect();
+        for txid in &orphan_anchored_txids {
+            debug!("dropping tx {} anchored to an orphaned block", txid);
+            self.valid_tx.remove(txid);
+            self.recent_valid_tx.remove(txid);
+            self.remove_commitment_tx(txid);
+        }
+
+        let Ok(read_tx) = self.irys_db.tx() else {
+            error!("Failed to create a database read tx to re-inject orphaned txs");
+            return;
+        };
+
+        let mut data_txs = Vec::new();
+        let mut commitment_txs = Vec::new();
+        let mut unpromoted_txs = Vec::new();
+        for block in &msg.orphaned_blocks {
+            for txid in block.data_ledgers[DataLedger::Submit].tx_ids.iter() {
+                if new_branch_txids.contains(txid) {
+                    continue;
+                }
+                match tx_header_by_txid(&read_tx, txid) {
+                    Ok(Some(header)) => data_txs.push(header),
+                    Ok(None) => error!("No transaction header found for orphaned txid: {}", txid),
+                    Err(e) => error!("Error fetching transaction header for txid {}: {}", txid, e),
+                }
+            }
+            for txid in commitment_tx_ids(block) {
+                if new_branch_txids.contains(txid) {
+                    continue;
+                }
+                match commitment_tx_by_txid(&read_tx, txid) {
+                    Ok(Some(commitment_tx)) => commitment_txs.push(commitment_tx),
+                    Ok(None) => error!("No commitment tx found for orphaned txid: {}", txid),
+                    Err(e) => error!("Error fetching commitment tx for txid {}: {}", txid, e),
+                }
+            }
+            let publish_ledger = &block.data_ledgers[DataLedger::Publish];
+            let proofs_per_tx = self.config.consensus.number_of_ingress_proofs as usize;
+            for (i, txid) in publish_ledger.tx_ids.iter().enumerate() {
+                if new_branch_promoted_txids.contains(txid) {
+                    continue;
+                }
+                if let Ok(Some(header)) = tx_header_by_txid(&read_tx, txid) {
+                    let proofs = publish_ledger
+                        .proofs
+                        .as_ref()
+                        .and_then(|proofs| proofs.0.get(i * proofs_per_tx..(i + 1) * proofs_per_tx))
+                        .unwrap_or_default()
+                        .to_vec();
+                    unpromoted_txs.push((header, proofs));
+                }
+            }
+        }
+        drop(read_tx);
+
+        // Promotions in the orphaned branch are undone, so the txs can be promoted again. Their
+        // proofs are restored from the orphaned blocks, as this node may never have stored them
+        // or already pruned them
+        if !unpromoted_txs.is_empty() {
+            let latest_height = self.get_latest_block_height().unwrap_or_default();
+            if let Err(e) = self.irys_db.update_eyre(|db_tx| {
+                for (mut tx_header, proofs) in unpromoted_txs {
+                    let data_root = tx_header.data_root;
+                    for tx_proof in proofs {
+                        let signer = match tx_proof.pre_validate(&data_root) {
+                            Ok(signer) => signer,
+                            Err(e) => {
+                                warn!(?data_root, "Invalid ingress proof in orphaned block: {}", e);
+                                continue;
+                            }
+                        };
+                        let proof = IngressProof {
+                            signature: tx_proof.signature,
+                            data_root,
+                            proof: tx_proof.proof,
+                        };
+                        store_ingress_proof(db_tx, signer, &proof)?;
+                    }
+                    if db_tx.get::<DataRootLRU>(data_root)?.is_none() {
+                        db_tx.put::<DataRootLRU>(
+                            data_root,
+                            DataRootLRUEntry {
+                                last_height: latest_height,
+                                ingress_proof: true,
+                            },
+                        )?;
+                    }
+                    tx_header.ingress_proofs = None;
+                    insert_tx_header(db_tx, &tx_header)?;
+                }
+                Ok(())
+            }) {
+                error!("Failed to undo promotions of orphaned txs: {}", e);
+            }
+        }
+
+        // Re-inject the orphaned txs, re-checking their anchors against the new canonical chain
+        let mut reinjected = 0;
+        for tx in data_txs {
+            let txid = tx.id;
+            if orphaned_block_hashes.contains(&tx.anchor) {
+                debug!(
+                    "dropping orphaned tx {} anchored to an orphaned block",
+                    txid
+                );
+                continue;
+            }
+            match self.handle(TxIngressMessage(tx), ctx) {
+                Ok(()) => reinjected += 1,
+                Err(e) => warn!("Unable to re-inject orphaned tx {}: {:?}", txid, e),
+            }
+        }
+        for commitment_tx in commitment_txs {
+            let txid = commitment_tx.id;
+            if orphaned_block_hashes.contains(&commitment_tx.anchor) {
+                debug!(
+                    "dropping orphaned commitment tx {} anchored to an orphaned block",
+                    txid
+                );
+                continue;
+            }
+            match self.handle(CommitmentTxIngressMessage(commitment_tx), ctx) {
+                Ok(()) => reinjected += 1,
+                Err(e) => warn!(
+                    "Unable to re-inject orphaned commitment tx {}: {:?}",
+                    txid, e
+                ),
+            }
+        }
+
+        info!(
+            "Reorg of {} blocks: re-injected {} orphaned txs, dropped {} txs anchored to orphaned blocks",
+            msg.orphaned_blocks.len(),
+            reinjected,
+            orphan_anchored_txids.len()
+        );
+    }
+}
+
 /// Message to check whether a transaction exists in the mempool or on disk
 #[derive(Message, Debug)]
 #[rtype(result = "Result<bool, TxIngressError>")]
@@ -1269,7 +1700,7 @@ pub fn generate_ingress_proof(
     size: u64,
     chunk_size: u64,
     signer: IrysSigner,
-) -> eyre::Result<()> {
+) -> eyre::Result<IngressProof> {
     // load the chunks from the DB
     // TODO: for now we assume the chunks all all in the DB chunk cache
     // in future, we'll need access to whatever unified storage provider API we have to get chunks
@@ -1326,6 +1757,7 @@ pub fn generate_ingress_proof(
     });
 
     // generate the ingress proof hash
+    let address = signer.address();
     let proof = irys_types::ingress::generate_ingress_proof(signer, data_root, iter)?;
     info!(
         "generated ingress proof {} for data root {}",
@@ -1336,7 +1768,32 @@ pub fn generate_ingress_proof(
 
     ro_tx.commit()?;
 
-    db.update(|rw_tx| rw_tx.put::<IngressProofs>(data_root, proof))??;
+    db.update_eyre(|rw_tx| store_ingress_proof(rw_tx, address, &proof))?;
+
+    Ok(proof)
+}
+
+/// Verifies an ingress proof against the chunks of its `data_root` in the chunk cache
+pub fn validate_ingress_proof(db: &DatabaseProvider, proof: &IngressProof) -> eyre::Result<bool> {
+    let ro_tx = db.tx()?;
+    let mut dup_cursor = ro_tx.cursor_dup_read::<CachedChunksIndex>()?;
+    let dup_walker = dup_cursor.walk_dup(Some(proof.data_root), None)?;
+
+    let chunks = dup_walker
+        .into_iter()
+        .map(|entry| -> eyre::Result<ChunkBytes> {
+            let (_, index_entry) = entry?;
+            let chunk_path_hash = index_entry.meta.chunk_path_hash;
+            let chunk = ro_tx
+                .get::<CachedChunks>(chunk_path_hash)?
+                .ok_or(eyre!("unable to get chunk {chunk_path_hash} from DB"))?;
+            Ok(chunk
+                .chunk
+                .ok_or(eyre!(
+                    "Missing required chunk ({chunk_path_hash}) body from DB"
+                ))?
+                .0)
+        });
 
-    Ok(())
+    verify_ingress_proof(proof.clone(), chunks)
 }
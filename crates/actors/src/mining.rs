@@ -1,9 +1,10 @@
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use crate::block_producer::SolutionFoundMessage;
 use crate::broadcast_mining_service::{
     BroadcastDifficultyUpdate, BroadcastMiningSeed, BroadcastMiningService,
-    BroadcastPartitionsExpiration, Subscribe, Unsubscribe,
+    BroadcastPartitionsExpiration, BroadcastSolutionFound, Subscribe, Unsubscribe,
 };
 use crate::packing::PackingRequest;
 use crate::vdf_service::VdfStepsReadGuard;
@@ -19,6 +20,7 @@ use irys_types::{
     PartitionChunkOffset, PartitionChunkRange,
 };
 use openssl::sha;
+use std::sync::atomic::Ordering;
 use tracing::{debug, error, info, warn, Span};
 
 #[derive(Debug, Clone)]
@@ -32,9 +34,35 @@ pub struct PartitionMiningActor {
     ranges: Ranges,
     steps_guard: VdfStepsReadGuard,
     atomic_global_step_number: AtomicVdfStepNumber,
+    /// Highest VDF step a solution was found for, shared by all the partition miners
+    solved_step_number: AtomicVdfStepNumber,
+    stats: PartitionMiningStats,
     span: Span,
 }
 
+/// Work done by a `PartitionMiningActor` since it started, used to benchmark mining hardware
+#[derive(Debug, Clone, Default, MessageResponse)]
+pub struct PartitionMiningStats {
+    /// Number of recall ranges read from the storage module
+    pub recall_ranges_read: u64,
+    pub chunks_read: u64,
+    /// Number of chunks hashed against a seed
+    pub hashes: u64,
+    /// Total time spent reading recall ranges
+    pub read_time: Duration,
+    /// Slowest recall range read
+    pub max_read_time: Duration,
+    /// Total time spent mining seeds, recall range reads included
+    pub mining_time: Duration,
+}
+
+impl PartitionMiningStats {
+    /// Time spent hashing chunks, that is mining time not spent reading recall ranges
+    pub fn hash_time(&self) -> Duration {
+        self.mining_time.saturating_sub(self.read_time)
+    }
+}
+
 /// Allows this actor to live in the the local service registry
 impl Supervised for PartitionMiningActor {}
 
@@ -47,6 +75,7 @@ impl PartitionMiningActor {
         start_mining: bool,
         steps_guard: VdfStepsReadGuard,
         atomic_global_step_number: AtomicVdfStepNumber,
+        solved_step_number: AtomicVdfStepNumber,
         initial_difficulty: U256,
         span: Option<Span>,
     ) -> Self {
@@ -65,6 +94,8 @@ impl PartitionMiningActor {
             difficulty: initial_difficulty,
             steps_guard,
             atomic_global_step_number,
+            solved_step_number,
+            stats: PartitionMiningStats::default(),
             span: span.unwrap_or(Span::current()),
         }
     }
@@ -115,6 +146,11 @@ impl PartitionMiningActor {
             .wrap_err("recall range larger than u64")
     }
 
+    /// Whether a solution was already found for `vdf_step` by any partition
+    fn is_step_solved(&self, vdf_step: u64) -> bool {
+        self.solved_step_number.load(Ordering::Relaxed) >= vdf_step
+    }
+
     fn mine_partition_with_seed(
         &mut self,
         mining_seed: H256,
@@ -148,8 +184,22 @@ impl PartitionMiningActor {
             start_chunk_offset + self.config.consensus.num_chunks_in_recall_range as u32
         );
 
+        if self.is_step_solved(vdf_step) {
+            debug!(
+                "Step {} already solved, skipping recall range read",
+                vdf_step
+            );
+            return Ok(None);
+        }
+
         // haven't tested this, but it looks correct
+        let read_started = Instant::now();
         let chunks = self.storage_module.read_chunks(read_range)?;
+        let read_time = read_started.elapsed();
+        self.stats.recall_ranges_read += 1;
+        self.stats.chunks_read += chunks.len() as u64;
+        self.stats.read_time += read_time;
+        self.stats.max_read_time = self.stats.max_read_time.max(read_time);
         // debug!(
         //     "Got chunks {} from read range {:?}",
         //     &chunks.len(),
@@ -166,6 +216,15 @@ impl PartitionMiningActor {
         }
 
         for (index, (_chunk_offset, (chunk_bytes, chunk_type))) in chunks.iter().enumerate() {
+            // Stop hashing as soon as another partition found a solution for this step
+            if self.is_step_solved(vdf_step) {
+                debug!(
+                    "Step {} solved by another partition, stopping partition {}",
+                    vdf_step, self.storage_module.id
+                );
+                return Ok(None);
+            }
+
             // TODO: check if difficulty higher now. Will look in DB for latest difficulty info and update difficulty
             let partition_chunk_offset =
                 PartitionChunkOffset::from(start_chunk_offset + index as u32);
@@ -191,9 +250,23 @@ impl PartitionMiningActor {
             hasher.update(&partition_chunk_offset.to_le_bytes());
             hasher.update(mining_seed.as_bytes());
             let solution_hash = hasher.finish();
+            self.stats.hashes += 1;
             let test_solution = hash_to_number(&solution_hash);
 
             if test_solution >= self.difficulty {
+                // Claim the step, only the first partition to find a solution submits it
+                if self
+                    .solved_step_number
+                    .fetch_max(vdf_step, Ordering::Relaxed)
+                    >= vdf_step
+                {
+                    debug!(
+                        "Step {} already solved, dropping solution from partition {}",
+                        vdf_step, self.storage_module.id
+                    );
+                    return Ok(None);
+                }
+
                 info!(
                     "Solution Found - partition_id: {}, ledger_offset: {}/{}, range_offset: {}/{} difficulty {}",
                     self.storage_module.id,
@@ -218,9 +291,10 @@ impl PartitionMiningActor {
                     solution_hash: H256::from(solution_hash),
                 };
 
-                // TODO: Let all partitions know to stop mining
+                // Let all partitions know to stop mining this step
+                BroadcastMiningService::from_registry()
+                    .do_send(BroadcastSolutionFound { vdf_step });
 
-                // Once solution is sent stop mining and let all other partitions know
                 return Ok(Some(solution));
             }
         }
@@ -278,6 +352,14 @@ impl Handler<BroadcastMiningSeed> for PartitionMiningActor {
             current_step
         );
 
+        if self.is_step_solved(msg.global_step) {
+            debug!(
+                "Step {} already solved, skipping seed {:?}",
+                msg.global_step, seed
+            );
+            return;
+        }
+
         let lag = current_step - msg.global_step;
 
         if lag >= 3 {
@@ -294,7 +376,12 @@ impl Handler<BroadcastMiningSeed> for PartitionMiningActor {
             self.difficulty
         );
 
-        match self.mine_partition_with_seed(seed.into_inner(), msg.global_step, msg.checkpoints) {
+        let mining_started = Instant::now();
+        let result =
+            self.mine_partition_with_seed(seed.into_inner(), msg.global_step, msg.checkpoints);
+        self.stats.mining_time += mining_started.elapsed();
+
+        match result {
             Ok(Some(s)) => match self.block_producer_actor.try_send(SolutionFoundMessage(s)) {
                 Ok(_) => {
                     // debug!("Solution sent!");
@@ -329,6 +416,15 @@ impl Handler<BroadcastDifficultyUpdate> for PartitionMiningActor {
     }
 }
 
+impl Handler<BroadcastSolutionFound> for PartitionMiningActor {
+    type Result = ();
+
+    fn handle(&mut self, msg: BroadcastSolutionFound, _: &mut Context<Self>) {
+        self.solved_step_number
+            .fetch_max(msg.vdf_step, Ordering::Relaxed);
+    }
+}
+
 impl Handler<BroadcastPartitionsExpiration> for PartitionMiningActor {
     type Result = ();
 
@@ -387,6 +483,23 @@ impl Handler<MiningControl> for PartitionMiningActor {
     }
 }
 
+#[derive(Message, Debug)]
+#[rtype(result = "PartitionMiningStats")]
+/// Message type for reading the mining stats of a partition
+pub struct GetPartitionMiningStatsMessage;
+
+impl Handler<GetPartitionMiningStatsMessage> for PartitionMiningActor {
+    type Result = PartitionMiningStats;
+
+    fn handle(
+        &mut self,
+        _msg: GetPartitionMiningStatsMessage,
+        _ctx: &mut Context<Self>,
+    ) -> Self::Result {
+        self.stats.clone()
+    }
+}
+
 pub fn hash_to_number(hash: &[u8]) -> U256 {
     U256::from_little_endian(hash)
 }
@@ -551,6 +664,7 @@ mod tests {
             .expect("to receive VdfStepsReadGuard from GetVdfStateMessage message");
 
         let atomic_global_step_number = Arc::new(AtomicU64::new(1));
+        let solved_step_number = Arc::new(AtomicU64::new(0));
 
         let partition_mining_actor = PartitionMiningActor::new(
             &config,
@@ -560,15 +674,16 @@ mod tests {
             true,
             vdf_steps_guard.clone(),
             atomic_global_step_number,
+            solved_step_number.clone(),
             U256::zero(),
             None,
         );
 
         let seed: Seed = Seed(H256::random());
-        partition_mining_actor
-            .start()
+        let partition_mining_addr = partition_mining_actor.start();
+        partition_mining_addr
             .send(BroadcastMiningSeed {
-                seed,
+                seed: seed.clone(),
                 checkpoints: H256List(vec![]),
                 global_step: 1,
             })
@@ -619,6 +734,31 @@ mod tests {
             solution.data_path,
             "Not expected partition"
         );
+
+        let stats = partition_mining_addr
+            .send(GetPartitionMiningStatsMessage)
+            .await
+            .unwrap();
+        assert_eq!(stats.recall_ranges_read, 1);
+        assert!(stats.chunks_read > 0 && stats.hashes > 0);
+        assert!(stats.hashes <= stats.chunks_read);
+
+        // the step is claimed, mining it again must not produce a competing solution
+        assert_eq!(solved_step_number.load(Ordering::Relaxed), 1);
+        arc_rwlock.write().unwrap().take();
+        partition_mining_addr
+            .send(BroadcastMiningSeed {
+                seed,
+                checkpoints: H256List(vec![]),
+                global_step: 1,
+            })
+            .await
+            .unwrap();
+        sleep(Duration::from_millis(200)).await;
+        assert!(
+            arc_rwlock.read().unwrap().is_none(),
+            "Duplicate solution submitted for a solved step"
+        );
     }
 
     #[actix_rt::test]
@@ -710,6 +850,7 @@ mod tests {
             false,
             vdf_steps_guard.clone(),
             atomic_global_step_number,
+            Arc::new(AtomicU64::new(0)),
             U256::zero(),
             None,
         );
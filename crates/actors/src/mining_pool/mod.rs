@@ -0,0 +1,69 @@
+pub mod server;
+pub mod worker;
+
+pub use server::*;
+pub use worker::*;
+
+use eyre::ensure;
+use serde::{de::DeserializeOwned, Serialize};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt as _, AsyncWrite, AsyncWriteExt as _};
+
+/// Maximum size of a single message, solutions are the largest as they carry a chunk and its proofs
+const MAX_MESSAGE_SIZE: usize = 2 * 1024 * 1024;
+
+/// Writes a single newline delimited JSON message to the socket
+async fn write_message<W, T>(writer: &mut W, msg: &T) -> eyre::Result<()>
+where
+    W: AsyncWrite + Unpin,
+    T: Serialize,
+{
+    let mut line = serde_json::to_vec(msg)?;
+    line.push(b'\n');
+    writer.write_all(&line).await?;
+    Ok(())
+}
+
+/// Reads newline delimited JSON messages from the socket. Lines longer than [`MAX_MESSAGE_SIZE`]
+/// are refused, so the other end can't exhaust the memory by never sending a newline
+struct MessageReader<R> {
+    reader: R,
+    /// The part of the current line read so far
+    line: Vec<u8>,
+}
+
+impl<R: AsyncBufRead + Unpin> MessageReader<R> {
+    fn new(reader: R) -> Self {
+        Self {
+            reader,
+            line: Vec::new(),
+        }
+    }
+
+    /// Reads the next message, `None` once the socket is closed.
+    /// Cancel safe, a partially read line is kept for the next call
+    async fn read<T: DeserializeOwned>(&mut self) -> eyre::Result<Option<T>> {
+        loop {
+            let available = self.reader.fill_buf().await?;
+            if available.is_empty() {
+                ensure!(
+                    self.line.is_empty(),
+                    "connection closed in the middle of a message"
+                );
+                return Ok(None);
+            }
+            let newline = available.iter().position(|byte| *byte == b'\n');
+            let len = newline.map_or(available.len(), |pos| pos + 1);
+            ensure!(
+                self.line.len() + len <= MAX_MESSAGE_SIZE,
+                "message exceeds {} bytes",
+                MAX_MESSAGE_SIZE
+            );
+            self.line.extend_from_slice(&available[..len]);
+            self.reader.consume(len);
+            if newline.is_some() {
+                let line = std::mem::take(&mut self.line);
+                return Ok(Some(serde_json::from_slice(&line)?));
+            }
+        }
+    }
+}
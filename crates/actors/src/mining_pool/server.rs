@@ -0,0 +1,682 @@
+use super::{write_message, MessageReader};
+use crate::{
+    block_index_service::BlockIndexReadGuard,
+    block_producer::SolutionFoundMessage,
+    block_validation::{get_recall_range, poa_is_valid},
+    broadcast_mining_service::{
+        BroadcastDifficultyUpdate, BroadcastMiningSeed, BroadcastMiningService,
+        BroadcastPartitionsExpiration, BroadcastSolutionFound, SubscribeMiningPool,
+    },
+    epoch_service::PartitionAssignmentsReadGuard,
+    mining::hash_to_number,
+    vdf_service::VdfStepsReadGuard,
+};
+use actix::prelude::*;
+use eyre::{ensure, eyre, OptionExt as _};
+use irys_storage::ii;
+use irys_types::{
+    block_production::SolutionContext,
+    mining_pool::{pool_auth_prehash, PoolServerMessage, PoolSolution, PoolWorkerMessage},
+    Address, AtomicVdfStepNumber, Config, H256List, MiningPoolConfig, PoaData, H256, U256,
+};
+use openssl::sha;
+use std::{
+    collections::BTreeMap,
+    net::SocketAddr,
+    sync::{atomic::Ordering, Arc},
+    time::Duration,
+};
+use tokio::{
+    io::BufReader,
+    net::{TcpListener, TcpStream},
+    sync::{broadcast, mpsc},
+    task::JoinHandle,
+};
+use tracing::{debug, info, warn, Instrument as _, Span};
+
+/// How long a worker has to answer the authentication challenge
+const AUTH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Number of recent VDF steps solutions are accepted for
+const MAX_SOLUTION_STEP_LAG: u64 = 3;
+
+/// Capacity of the channel fanning broadcast messages out to the worker connections
+const WORKER_CHANNEL_CAPACITY: usize = 1024;
+
+/// Streams VDF seeds and difficulty updates to authenticated remote workers and
+/// validates the solutions they submit before handing them to the block producer
+#[derive(Debug)]
+pub struct MiningPoolService {
+    config: Config,
+    pool_config: MiningPoolConfig,
+    listener: Option<TcpListener>,
+    accept_handle: Option<JoinHandle<()>>,
+    block_producer: Recipient<SolutionFoundMessage>,
+    block_index_guard: BlockIndexReadGuard,
+    partition_assignments_guard: PartitionAssignmentsReadGuard,
+    steps_guard: VdfStepsReadGuard,
+    difficulty: U256,
+    /// Checkpoints of the most recent VDF steps, used to complete submitted solutions
+    recent_checkpoints: BTreeMap<u64, H256List>,
+    /// Highest VDF step a solution was found for, by the node or any worker
+    solved_step_number: AtomicVdfStepNumber,
+    events: broadcast::Sender<PoolServerMessage>,
+    span: Span,
+}
+
+impl MiningPoolService {
+    /// Binds the mining pool socket and starts the service
+    pub async fn spawn_service(
+        config: &Config,
+        pool_config: MiningPoolConfig,
+        block_producer: Recipient<SolutionFoundMessage>,
+        block_index_guard: BlockIndexReadGuard,
+        partition_assignments_guard: PartitionAssignmentsReadGuard,
+        steps_guard: VdfStepsReadGuard,
+        initial_difficulty: U256,
+    ) -> eyre::Result<(Addr<Self>, SocketAddr)> {
+        let listener =
+            TcpListener::bind((pool_config.bind_ip.as_str(), pool_config.bind_port)).await?;
+        let local_addr = listener.local_addr()?;
+        info!(?local_addr, "Mining pool listening for workers");
+
+        let (events, _) = broadcast::channel(WORKER_CHANNEL_CAPACITY);
+        let service = Self {
+            config: config.clone(),
+            pool_config,
+            listener: Some(listener),
+            accept_handle: None,
+            block_producer,
+            block_index_guard,
+            partition_assignments_guard,
+            steps_guard,
+            difficulty: initial_difficulty,
+            recent_checkpoints: BTreeMap::new(),
+            solved_step_number: Arc::default(),
+            events,
+            span: Span::current(),
+        };
+        Ok((service.start(), local_addr))
+    }
+
+    /// Sends a message to every connected worker
+    fn broadcast(&self, msg: PoolServerMessage) {
+        // an error only means that no worker is connected
+        let _ = self.events.send(msg);
+    }
+}
+
+impl Actor for MiningPoolService {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Context<Self>) {
+        BroadcastMiningService::from_registry().do_send(SubscribeMiningPool(ctx.address()));
+
+        let listener = self
+            .listener
+            .take()
+            .expect("mining pool service to be started with a bound listener");
+        let pool = ctx.address();
+        let chain_id = self.config.consensus.chain_id;
+        let allowed_workers = self.pool_config.allowed_workers.clone();
+        let events = self.events.clone();
+        self.accept_handle = Some(tokio::spawn(
+            async move {
+                loop {
+                    let (stream, peer) = match listener.accept().await {
+                        Ok(accepted) => accepted,
+                        Err(err) => {
+                            warn!(?err, "Failed to accept mining pool connection");
+                            continue;
+                        }
+                    };
+                    let connection = WorkerConnection {
+                        pool: pool.clone(),
+                        chain_id,
+                        allowed_workers: allowed_workers.clone(),
+                        events: events.clone(),
+                    };
+                    tokio::spawn(
+                        async move {
+                            if let Err(err) = connection.run(stream).await {
+                                debug!(?err, "Mining pool connection closed");
+                            }
+                        }
+                        .instrument(tracing::info_span!("mining_pool_worker", %peer)),
+                    );
+                }
+            }
+            .instrument(self.span.clone()),
+        ));
+    }
+
+    fn stopped(&mut self, _ctx: &mut Context<Self>) {
+        if let Some(handle) = self.accept_handle.take() {
+            handle.abort();
+        }
+    }
+}
+
+impl Handler<BroadcastMiningSeed> for MiningPoolService {
+    type Result = ();
+
+    fn handle(&mut self, msg: BroadcastMiningSeed, _: &mut Context<Self>) {
+        self.recent_checkpoints
+            .insert(msg.global_step, msg.checkpoints.clone());
+        let oldest_step = msg.global_step.saturating_sub(MAX_SOLUTION_STEP_LAG);
+        self.recent_checkpoints = self.recent_checkpoints.split_off(&oldest_step);
+
+        self.broadcast(PoolServerMessage::Seed {
+            seed: msg.seed,
+            checkpoints: msg.checkpoints,
+            global_step: msg.global_step,
+        });
+    }
+}
+
+impl Handler<BroadcastDifficultyUpdate> for MiningPoolService {
+    type Result = ();
+
+    fn handle(&mut self, msg: BroadcastDifficultyUpdate, _: &mut Context<Self>) {
+        self.difficulty = msg.0.diff;
+        self.broadcast(PoolServerMessage::Difficulty {
+            block_height: msg.0.height,
+            difficulty: msg.0.diff,
+        });
+    }
+}
+
+impl Handler<BroadcastSolutionFound> for MiningPoolService {
+    type Result = ();
+
+    fn handle(&mut self, msg: BroadcastSolutionFound, _: &mut Context<Self>) {
+        self.solved_step_number
+            .fetch_max(msg.vdf_step, Ordering::Relaxed);
+        self.broadcast(PoolServerMessage::StepSolved {
+            vdf_step: msg.vdf_step,
+        });
+    }
+}
+
+impl Handler<BroadcastPartitionsExpiration> for MiningPoolService {
+    type Result = ();
+
+    fn handle(&mut self, msg: BroadcastPartitionsExpiration, _: &mut Context<Self>) {
+        self.broadcast(PoolServerMessage::PartitionsExpired {
+            partition_hashes: msg.0,
+        });
+    }
+}
+
+/// Builds the message that bootstraps a newly authenticated worker
+#[derive(Message, Debug)]
+#[rtype(result = "eyre::Result<PoolServerMessage>")]
+pub struct GetWorkerBootstrapMessage;
+
+impl Handler<GetWorkerBootstrapMessage> for MiningPoolService {
+    type Result = eyre::Result<PoolServerMessage>;
+
+    fn handle(&mut self, _msg: GetWorkerBootstrapMessage, _: &mut Context<Self>) -> Self::Result {
+        let vdf_state = self.steps_guard.read();
+        let global_step = vdf_state.global_step;
+        // the worker needs the steps since the last reset to reconstruct its recall ranges
+        let reset_step =
+            irys_efficient_sampling::reset_step_number(global_step, &self.config.consensus);
+        let seeds = if global_step == 0 {
+            H256List::new()
+        } else {
+            vdf_state.get_steps(ii(reset_step, global_step))?
+        };
+        Ok(PoolServerMessage::Authenticated {
+            global_step,
+            seeds,
+            difficulty: self.difficulty,
+        })
+    }
+}
+
+/// A solution submitted by an authenticated worker
+#[derive(Message, Debug)]
+#[rtype(result = "eyre::Result<()>")]
+pub struct PoolSolutionMessage {
+    pub worker: Address,
+    pub solution: PoolSolution,
+}
+
+impl Handler<PoolSolutionMessage> for MiningPoolService {
+    type Result = ResponseFuture<eyre::Result<()>>;
+
+    fn handle(&mut self, msg: PoolSolutionMessage, _: &mut Context<Self>) -> Self::Result {
+        let PoolSolutionMessage { worker, solution } = msg;
+        let checkpoints = self.recent_checkpoints.get(&solution.vdf_step).cloned();
+        let difficulty = self.difficulty;
+        let config = self.config.clone();
+        let block_producer = self.block_producer.clone();
+        let block_index_guard = self.block_index_guard.clone();
+        let partition_assignments_guard = self.partition_assignments_guard.clone();
+        let steps_guard = self.steps_guard.clone();
+        let solved_step_number = self.solved_step_number.clone();
+
+        Box::pin(
+            async move {
+                let vdf_step = solution.vdf_step;
+                ensure!(
+                    solved_step_number.load(Ordering::Relaxed) < vdf_step,
+                    "VDF step {} is already solved",
+                    vdf_step
+                );
+                let checkpoints = checkpoints
+                    .ok_or_else(|| eyre!("VDF step {} is unknown or stale", vdf_step))?;
+                let solution = validate_pool_solution(
+                    solution,
+                    checkpoints,
+                    difficulty,
+                    &config,
+                    &block_index_guard,
+                    &partition_assignments_guard,
+                    &steps_guard,
+                )
+                .await?;
+
+                // only the first valid solution for a step produces a block, the claim
+                // is released if the block producer doesn't accept the solution
+                let previous_step = solved_step_number.fetch_max(vdf_step, Ordering::Relaxed);
+                ensure!(
+                    previous_step < vdf_step,
+                    "VDF step {} is already solved",
+                    vdf_step
+                );
+                let produced = async {
+                    block_producer
+                        .send(SolutionFoundMessage(solution))
+                        .await??
+                        .ok_or_eyre("block producer discarded the solution")
+                }
+                .await;
+                if let Err(error) = produced {
+                    let _ = solved_step_number.compare_exchange(
+                        vdf_step,
+                        previous_step,
+                        Ordering::Relaxed,
+                        Ordering::Relaxed,
+                    );
+                    return Err(error);
+                }
+
+                BroadcastMiningService::from_registry()
+                    .do_send(BroadcastSolutionFound { vdf_step });
+                info!(
+                    ?worker,
+                    vdf_step, "Accepted solution from mining pool worker"
+                );
+                Ok(())
+            }
+            .instrument(self.span.clone()),
+        )
+    }
+}
+
+/// Validates a worker's solution against the node's VDF state, difficulty and
+/// partition assignments, returning the [`SolutionContext`] to produce a block with
+pub async fn validate_pool_solution(
+    solution: PoolSolution,
+    checkpoints: H256List,
+    difficulty: U256,
+    config: &Config,
+    block_index_guard: &BlockIndexReadGuard,
+    partition_assignments_guard: &PartitionAssignmentsReadGuard,
+    steps_guard: &VdfStepsReadGuard,
+) -> eyre::Result<SolutionContext> {
+    let consensus = &config.consensus;
+    let miner_address = config.node_config.miner_address();
+
+    let assignment = partition_assignments_guard
+        .read()
+        .get_assignment(solution.partition_hash)
+        .ok_or_eyre("partition is not assigned")?;
+    ensure!(
+        assignment.miner_address == miner_address,
+        "partition is assigned to another miner"
+    );
+
+    let step_seed = steps_guard
+        .read()
+        .get_steps(ii(solution.vdf_step, solution.vdf_step))?;
+    ensure!(
+        step_seed.0.first() == Some(&solution.seed.0),
+        "seed does not match VDF step {}",
+        solution.vdf_step
+    );
+
+    let mut hasher = sha::Sha256::new();
+    hasher.update(&solution.chunk.0);
+    hasher.update(&solution.chunk_offset.to_le_bytes());
+    hasher.update(solution.seed.0.as_bytes());
+    let solution_hash = H256(hasher.finish());
+    ensure!(
+        solution_hash == solution.solution_hash,
+        "solution hash does not match the chunk"
+    );
+    ensure!(
+        hash_to_number(&solution_hash.0) >= difficulty,
+        "solution hash is below the current difficulty"
+    );
+
+    let recall_range_index = get_recall_range(
+        solution.vdf_step,
+        consensus,
+        steps_guard,
+        &solution.partition_hash,
+    )
+    .await?;
+    let num_chunks_in_recall_range = consensus.num_chunks_in_recall_range;
+    ensure!(
+        solution.chunk_offset as u64 / num_chunks_in_recall_range == recall_range_index as u64
+            && solution.chunk_offset as u64 % num_chunks_in_recall_range
+                == solution.recall_chunk_index as u64,
+        "chunk is outside of the step's recall range"
+    );
+
+    let poa = PoaData {
+        recall_chunk_index: solution.recall_chunk_index,
+        partition_chunk_offset: solution.chunk_offset,
+        partition_hash: solution.partition_hash,
+        chunk: Some(solution.chunk.clone()),
+        ledger_id: assignment.ledger_id,
+        tx_path: solution.tx_path.clone(),
+        data_path: solution.data_path.clone(),
+    };
+    poa_is_valid(
+        &poa,
+        block_index_guard,
+        partition_assignments_guard,
+        consensus,
+        &miner_address,
+    )?;
+
+    Ok(solution.into_solution_context(miner_address, checkpoints))
+}
+
+/// A socket connection from a remote worker
+struct WorkerConnection {
+    pool: Addr<MiningPoolService>,
+    chain_id: u64,
+    allowed_workers: Vec<Address>,
+    events: broadcast::Sender<PoolServerMessage>,
+}
+
+impl WorkerConnection {
+    async fn run(self, stream: TcpStream) -> eyre::Result<()> {
+        let (reader, mut writer) = stream.into_split();
+        let mut messages = MessageReader::new(BufReader::new(reader));
+
+        // challenge the worker to prove it holds an allowed mining key
+        let nonce = H256::random();
+        write_message(&mut writer, &PoolServerMessage::Challenge { nonce }).await?;
+        let auth = tokio::time::timeout(AUTH_TIMEOUT, messages.read())
+            .await
+            .map_err(|_| eyre!("worker did not authenticate in time"))??;
+        let worker = match auth {
+            Some(PoolWorkerMessage::Auth { signer, signature }) => {
+                ensure!(
+                    self.allowed_workers.contains(&signer),
+                    "worker {} is not allowed",
+                    signer
+                );
+                ensure!(
+                    signature.validate_signature(pool_auth_prehash(self.chain_id, &nonce), signer),
+                    "invalid authentication signature from {}",
+                    signer
+                );
+                signer
+            }
+            Some(_) => return Err(eyre!("expected an authentication message")),
+            None => return Ok(()),
+        };
+        info!(?worker, "Mining pool worker authenticated");
+
+        // subscribe before bootstrapping so no step is missed in between
+        let mut events = self.events.subscribe();
+        let bootstrap = self.pool.send(GetWorkerBootstrapMessage).await??;
+        write_message(&mut writer, &bootstrap).await?;
+
+        let (results_tx, mut results_rx) = mpsc::unbounded_channel();
+        loop {
+            tokio::select! {
+                event = events.recv() => match event {
+                    Ok(msg) => write_message(&mut writer, &msg).await?,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!(?worker, skipped, "Mining pool worker lagging behind");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return Ok(()),
+                },
+                result = results_rx.recv() => {
+                    if let Some(msg) = result {
+                        write_message(&mut writer, &msg).await?;
+                    }
+                }
+                msg = messages.read::<PoolWorkerMessage>() => match msg? {
+                    Some(PoolWorkerMessage::Solution(solution)) => {
+                        // validation and block production may take a while, keep streaming in the meantime
+                        let pool = self.pool.clone();
+                        let results_tx = results_tx.clone();
+                        tokio::spawn(async move {
+                            let vdf_step = solution.vdf_step;
+                            let result = pool
+                                .send(PoolSolutionMessage { worker, solution })
+                                .await
+                                .map_err(eyre::Report::from)
+                                .and_then(|res| res);
+                            if let Err(err) = &result {
+                                warn!(?worker, vdf_step, ?err, "Rejected mining pool solution");
+                            }
+                            let _ = results_tx.send(PoolServerMessage::SolutionResult {
+                                vdf_step,
+                                accepted: result.is_ok(),
+                                reason: result.err().map(|err| err.to_string()),
+                            });
+                        });
+                    }
+                    Some(PoolWorkerMessage::Auth { .. }) => {
+                        warn!(?worker, "Ignoring repeated authentication");
+                    }
+                    None => return Ok(()),
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        epoch_service::PartitionAssignments, mining_pool::MAX_MESSAGE_SIZE, vdf_service::VdfState,
+    };
+    use irys_database::BlockIndex;
+    use irys_testing_utils::setup_tracing_and_temp_dir;
+    use irys_types::{
+        block_production::Seed, irys::IrysSigner, IrysBlockHeader, IrysSignature, NodeConfig,
+        Signature,
+    };
+    use reth::payload::EthBuiltPayload;
+    use std::{collections::VecDeque, sync::RwLock};
+    use tokio::io::AsyncWriteExt as _;
+    use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+
+    struct BlockProducerStub;
+
+    impl Actor for BlockProducerStub {
+        type Context = Context<Self>;
+    }
+
+    impl Handler<SolutionFoundMessage> for BlockProducerStub {
+        type Result = eyre::Result<Option<(Arc<IrysBlockHeader>, EthBuiltPayload)>>;
+
+        fn handle(&mut self, _msg: SolutionFoundMessage, _: &mut Context<Self>) -> Self::Result {
+            Ok(None)
+        }
+    }
+
+    async fn start_pool(
+        config: &Config,
+        allowed_workers: Vec<Address>,
+    ) -> eyre::Result<(Addr<MiningPoolService>, SocketAddr)> {
+        let pool_config = MiningPoolConfig {
+            bind_ip: "127.0.0.1".to_owned(),
+            bind_port: 0,
+            allowed_workers,
+        };
+        let block_index = BlockIndex::new(&config.node_config).await?;
+        let steps_guard = VdfStepsReadGuard::new(Arc::new(RwLock::new(VdfState {
+            global_step: 0,
+            capacity: 10,
+            seeds: VecDeque::new(),
+            mining_state_sender: None,
+        })));
+        MiningPoolService::spawn_service(
+            config,
+            pool_config,
+            BlockProducerStub.start().recipient(),
+            BlockIndexReadGuard::new(Arc::new(RwLock::new(block_index))),
+            PartitionAssignmentsReadGuard::new(Arc::new(RwLock::new(PartitionAssignments::new()))),
+            steps_guard,
+            U256::from(1_u64),
+        )
+        .await
+    }
+
+    /// Connects to the pool and answers its challenge as `signer`, with a signature made by
+    /// `signing_key`. Returns the connection and the pool's reply, `None` if it hung up
+    async fn authenticate(
+        config: &Config,
+        pool_addr: SocketAddr,
+        signer: &IrysSigner,
+        signing_key: &IrysSigner,
+    ) -> eyre::Result<(
+        MessageReader<BufReader<OwnedReadHalf>>,
+        OwnedWriteHalf,
+        Option<PoolServerMessage>,
+    )> {
+        let (reader, mut writer) = TcpStream::connect(pool_addr).await?.into_split();
+        let mut messages = MessageReader::new(BufReader::new(reader));
+        let Some(PoolServerMessage::Challenge { nonce }) = messages.read().await? else {
+            eyre::bail!("expected an authentication challenge");
+        };
+        let signature: Signature = signing_key
+            .signer
+            .sign_prehash_recoverable(&pool_auth_prehash(config.consensus.chain_id, &nonce))?
+            .into();
+        let auth = PoolWorkerMessage::Auth {
+            signer: signer.address(),
+            signature: IrysSignature::new(signature),
+        };
+        write_message(&mut writer, &auth).await?;
+        let reply = messages.read().await.ok().flatten();
+        Ok((messages, writer, reply))
+    }
+
+    #[actix_rt::test]
+    async fn rejects_unauthorized_workers() -> eyre::Result<()> {
+        let temp_dir = setup_tracing_and_temp_dir(Some("rejects_unauthorized_workers"), false);
+        let mut node_config = NodeConfig::testnet();
+        node_config.base_directory = temp_dir.path().to_path_buf();
+        let config = Config::new(node_config);
+        let worker = IrysSigner::random_signer(&config.consensus);
+        let other = IrysSigner::random_signer(&config.consensus);
+        let (_pool, pool_addr) = start_pool(&config, vec![worker.address()]).await?;
+
+        // a worker that isn't allowed
+        let (_, _, reply) = authenticate(&config, pool_addr, &other, &other).await?;
+        assert_eq!(reply, None);
+
+        // an allowed worker's address, signed by another key
+        let (_, _, reply) = authenticate(&config, pool_addr, &worker, &other).await?;
+        assert_eq!(reply, None);
+
+        // an endless line before authenticating
+        let (reader, mut writer) = TcpStream::connect(pool_addr).await?.into_split();
+        let mut messages = MessageReader::new(BufReader::new(reader));
+        assert!(matches!(
+            messages.read::<PoolServerMessage>().await?,
+            Some(PoolServerMessage::Challenge { .. })
+        ));
+        // the pool may hang up before the whole line is written
+        let _ = writer.write_all(&vec![b'a'; MAX_MESSAGE_SIZE + 1]).await;
+        assert!(!matches!(
+            messages.read::<PoolServerMessage>().await,
+            Ok(Some(_))
+        ));
+
+        let (_, _, reply) = authenticate(&config, pool_addr, &worker, &worker).await?;
+        assert!(matches!(
+            reply,
+            Some(PoolServerMessage::Authenticated { global_step: 0, .. })
+        ));
+        Ok(())
+    }
+
+    #[actix_rt::test]
+    async fn streams_seeds_and_answers_solutions() -> eyre::Result<()> {
+        let temp_dir =
+            setup_tracing_and_temp_dir(Some("streams_seeds_and_answers_solutions"), false);
+        let mut node_config = NodeConfig::testnet();
+        node_config.base_directory = temp_dir.path().to_path_buf();
+        let config = Config::new(node_config);
+        let worker = IrysSigner::random_signer(&config.consensus);
+        let (pool, pool_addr) = start_pool(&config, vec![worker.address()]).await?;
+
+        let (mut messages, mut writer, reply) =
+            authenticate(&config, pool_addr, &worker, &worker).await?;
+        assert_eq!(
+            reply,
+            Some(PoolServerMessage::Authenticated {
+                global_step: 0,
+                seeds: H256List::new(),
+                difficulty: U256::from(1_u64),
+            })
+        );
+
+        // new VDF steps are streamed to the worker
+        let seed = Seed(H256::random());
+        let checkpoints = H256List(vec![H256::random()]);
+        pool.send(BroadcastMiningSeed {
+            seed: seed.clone(),
+            checkpoints: checkpoints.clone(),
+            global_step: 1,
+        })
+        .await?;
+        assert_eq!(
+            messages.read::<PoolServerMessage>().await?,
+            Some(PoolServerMessage::Seed {
+                seed: seed.clone(),
+                checkpoints,
+                global_step: 1,
+            })
+        );
+
+        // solutions for steps the pool doesn't know are rejected
+        let solution = PoolSolution {
+            partition_hash: H256::random(),
+            chunk_offset: 0,
+            recall_chunk_index: 0,
+            tx_path: None,
+            data_path: None,
+            chunk: vec![0; 32].into(),
+            vdf_step: 5,
+            seed,
+            solution_hash: H256::random(),
+        };
+        write_message(&mut writer, &PoolWorkerMessage::Solution(solution)).await?;
+        match messages.read::<PoolServerMessage>().await? {
+            Some(PoolServerMessage::SolutionResult {
+                vdf_step, accepted, ..
+            }) => {
+                assert_eq!(vdf_step, 5);
+                assert!(!accepted);
+            }
+            msg => eyre::bail!("expected a solution result, got {:?}", msg),
+        }
+        Ok(())
+    }
+}
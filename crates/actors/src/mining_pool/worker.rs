@@ -0,0 +1,188 @@
+use super::{write_message, MessageReader};
+use crate::{
+    block_producer::SolutionFoundMessage,
+    broadcast_mining_service::{
+        BroadcastDifficultyUpdate, BroadcastMiningSeed, BroadcastMiningService,
+        BroadcastPartitionsExpiration, BroadcastSolutionFound,
+    },
+    vdf_service::AtomicVdfState,
+};
+use actix::prelude::*;
+use eyre::{eyre, OptionExt as _};
+use irys_types::{
+    block_production::Seed,
+    mining_pool::{pool_auth_prehash, PoolServerMessage, PoolWorkerMessage},
+    AtomicVdfStepNumber, Config, IrysBlockHeader, IrysSignature, Signature, U256,
+};
+use reth::payload::EthBuiltPayload;
+use std::{
+    net::SocketAddr,
+    sync::{atomic::Ordering, Arc},
+};
+use tokio::{
+    io::BufReader,
+    net::{
+        tcp::{OwnedReadHalf, OwnedWriteHalf},
+        TcpStream,
+    },
+    sync::mpsc,
+    task::JoinHandle,
+};
+use tracing::{info, warn, Instrument as _, Span};
+
+/// Mines for a remote node: relays the node's VDF seeds and difficulty updates
+/// to the local `PartitionMiningActor`s and submits the solutions they find.
+///
+/// The worker takes the place of the block producer, local partition miners
+/// send their `SolutionFoundMessage`s to it.
+#[derive(Debug)]
+pub struct MiningPoolWorker {
+    outbound: mpsc::UnboundedSender<PoolWorkerMessage>,
+}
+
+impl MiningPoolWorker {
+    /// Connects and authenticates to the node's mining pool, returning the pool's
+    /// current difficulty and the task relaying messages until the connection
+    /// closes. Seeds received from the node are recorded in `vdf_state` and
+    /// `global_step` for the local partition miners
+    pub async fn connect(
+        config: &Config,
+        pool_address: SocketAddr,
+        vdf_state: AtomicVdfState,
+        global_step: AtomicVdfStepNumber,
+    ) -> eyre::Result<(Addr<Self>, U256, JoinHandle<eyre::Result<()>>)> {
+        let stream = TcpStream::connect(pool_address).await?;
+        let (reader, mut writer) = stream.into_split();
+        let mut messages = MessageReader::new(BufReader::new(reader));
+
+        let nonce = match messages.read().await? {
+            Some(PoolServerMessage::Challenge { nonce }) => nonce,
+            _ => return Err(eyre!("expected an authentication challenge from the pool")),
+        };
+        let signing_key = &config.node_config.mining_key;
+        let signature: Signature = signing_key
+            .sign_prehash_recoverable(&pool_auth_prehash(config.consensus.chain_id, &nonce))?
+            .into();
+        let auth = PoolWorkerMessage::Auth {
+            signer: config.node_config.miner_address(),
+            signature: IrysSignature::new(signature),
+        };
+        write_message(&mut writer, &auth).await?;
+
+        let (step, seeds, difficulty) = match messages.read().await? {
+            Some(PoolServerMessage::Authenticated {
+                global_step,
+                seeds,
+                difficulty,
+            }) => (global_step, seeds, difficulty),
+            _ => return Err(eyre!("mining pool rejected the worker")),
+        };
+        info!(%pool_address, step, "Authenticated to mining pool");
+
+        {
+            let mut state = vdf_state.write().unwrap();
+            state.global_step = step;
+            let skip = seeds.0.len().saturating_sub(state.capacity);
+            state.seeds = seeds.0.into_iter().skip(skip).map(Seed).collect();
+        }
+        global_step.store(step, Ordering::Relaxed);
+
+        let (outbound, outbound_rx) = mpsc::unbounded_channel();
+        let worker = Self { outbound }.start();
+        let relay = tokio::spawn(
+            relay_messages(messages, writer, outbound_rx, vdf_state, global_step)
+                .instrument(Span::current()),
+        );
+        Ok((worker, difficulty, relay))
+    }
+}
+
+impl Actor for MiningPoolWorker {
+    type Context = Context<Self>;
+}
+
+impl Handler<SolutionFoundMessage> for MiningPoolWorker {
+    type Result = eyre::Result<Option<(Arc<IrysBlockHeader>, EthBuiltPayload)>>;
+
+    fn handle(&mut self, msg: SolutionFoundMessage, _: &mut Context<Self>) -> Self::Result {
+        let vdf_step = msg.0.vdf_step;
+        self.outbound
+            .send(PoolWorkerMessage::Solution(msg.0.into()))
+            .map_err(|_| eyre!("mining pool connection closed"))?;
+        info!(vdf_step, "Submitted solution to mining pool");
+        // the block is produced by the node
+        Ok(None)
+    }
+}
+
+/// Relays the node's messages to the local miners and the local solutions to the node
+async fn relay_messages(
+    mut messages: MessageReader<BufReader<OwnedReadHalf>>,
+    mut writer: OwnedWriteHalf,
+    mut outbound: mpsc::UnboundedReceiver<PoolWorkerMessage>,
+    vdf_state: AtomicVdfState,
+    global_step: AtomicVdfStepNumber,
+) -> eyre::Result<()> {
+    let broadcaster = BroadcastMiningService::from_registry();
+    loop {
+        tokio::select! {
+            msg = outbound.recv() => {
+                let msg = msg.ok_or_eyre("mining pool worker stopped")?;
+                write_message(&mut writer, &msg).await?;
+            }
+            msg = messages.read::<PoolServerMessage>() => match msg? {
+                Some(PoolServerMessage::Seed { seed, checkpoints, global_step: step }) => {
+                    {
+                        let mut state = vdf_state.write().unwrap();
+                        if step <= state.global_step {
+                            continue;
+                        }
+                        if step > state.global_step + 1 {
+                            // missed steps can't be recovered, restart the stored steps from here
+                            warn!(step, last_step = state.global_step, "Missed VDF steps from mining pool");
+                            state.seeds.clear();
+                            state.global_step = step - 1;
+                        }
+                        state.increment_step(seed.clone());
+                    }
+                    global_step.store(step, Ordering::Relaxed);
+                    broadcaster.do_send(BroadcastMiningSeed {
+                        seed,
+                        checkpoints,
+                        global_step: step,
+                    });
+                }
+                Some(PoolServerMessage::Difficulty { block_height, difficulty }) => {
+                    update_difficulty(block_height, difficulty);
+                }
+                Some(PoolServerMessage::StepSolved { vdf_step }) => {
+                    broadcaster.do_send(BroadcastSolutionFound { vdf_step });
+                }
+                Some(PoolServerMessage::PartitionsExpired { partition_hashes }) => {
+                    broadcaster.do_send(BroadcastPartitionsExpiration(partition_hashes));
+                }
+                Some(PoolServerMessage::SolutionResult { vdf_step, accepted, reason }) => {
+                    if accepted {
+                        info!(vdf_step, "Mining pool accepted solution");
+                    } else {
+                        warn!(vdf_step, ?reason, "Mining pool rejected solution");
+                    }
+                }
+                Some(msg @ (PoolServerMessage::Challenge { .. } | PoolServerMessage::Authenticated { .. })) => {
+                    warn!(?msg, "Unexpected message from mining pool");
+                }
+                None => return Err(eyre!("mining pool connection closed")),
+            },
+        }
+    }
+}
+
+/// Forwards a difficulty update to the local partition miners, which only read the `diff` of the header
+fn update_difficulty(block_height: u64, difficulty: U256) {
+    let header = IrysBlockHeader {
+        height: block_height,
+        diff: difficulty,
+        ..IrysBlockHeader::default()
+    };
+    BroadcastMiningService::from_registry().do_send(BroadcastDifficultyUpdate(Arc::new(header)));
+}
@@ -1,10 +1,13 @@
 use std::{
     collections::{HashMap, VecDeque},
-    sync::{Arc, RwLock},
-    time::Duration,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, RwLock,
+    },
+    time::{Duration, Instant},
 };
 
-use actix::{Actor, Addr, Context, Handler, Message, MessageResponse};
+use actix::{Actor, Addr, AsyncContext as _, Context, Handler, Message, MessageResponse};
 
 use eyre::eyre;
 use irys_packing::{capacity_single::compute_entropy_chunk, PackingType, PACKING_TYPE};
@@ -41,6 +44,54 @@ pub struct PackingActor {
     semaphore: PackingSemaphore,
     /// packing process configuration
     config: PackingConfig,
+    /// number of chunks packed since the actor was created -- sm_id => count
+    packed_chunks: HashMap<usize, Arc<AtomicU64>>,
+    /// recent packing rate of each storage module, sampled from `packed_chunks`
+    throughput: HashMap<usize, PackingThroughput>,
+}
+
+/// How often the packing rate is sampled
+const THROUGHPUT_SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+/// Weight of the latest sample in the packing rate, makes roughly the last 30 samples count
+const THROUGHPUT_SMOOTHING: f64 = 2.0 / 31.0;
+
+/// Exponentially weighted moving average of the packing rate, so it follows the current
+/// throughput rather than the average over the lifetime of the actor
+#[derive(Debug, Clone)]
+struct PackingThroughput {
+    last_packed_chunks: u64,
+    last_sample: Instant,
+    /// `None` until the first sample
+    chunks_per_second: Option<f64>,
+}
+
+impl PackingThroughput {
+    fn new(now: Instant) -> Self {
+        Self {
+            last_packed_chunks: 0,
+            last_sample: now,
+            chunks_per_second: None,
+        }
+    }
+
+    /// Updates the average with the chunks packed since the previous sample
+    fn sample(&mut self, packed_chunks: u64, now: Instant) {
+        let elapsed = now.duration_since(self.last_sample).as_secs_f64();
+        if elapsed <= 0.0 {
+            return;
+        }
+        let rate = packed_chunks.saturating_sub(self.last_packed_chunks) as f64 / elapsed;
+        self.chunks_per_second = Some(match self.chunks_per_second {
+            Some(average) => average + THROUGHPUT_SMOOTHING * (rate - average),
+            None => rate,
+        });
+        self.last_packed_chunks = packed_chunks;
+        self.last_sample = now;
+    }
+
+    fn chunks_per_second(&self) -> f64 {
+        self.chunks_per_second.unwrap_or(0.0)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -86,10 +137,19 @@ impl PackingActor {
             pending_jobs,
             semaphore,
             config,
+            packed_chunks: storage_module_ids
+                .iter()
+                .map(|s| (*s, Arc::new(AtomicU64::new(0))))
+                .collect(),
+            throughput: storage_module_ids
+                .iter()
+                .map(|s| (*s, PackingThroughput::new(Instant::now())))
+                .collect(),
         }
     }
 
-    async fn process_jobs(self, _storage_module_id: usize, pending_jobs: AtomicPackingJobQueue) {
+    async fn process_jobs(self, storage_module_id: usize, pending_jobs: AtomicPackingJobQueue) {
+        let packed_chunks = self.packed_chunks[&storage_module_id].clone();
         loop {
             // block as the compiler can't reason about explicit read guard drops with Send bounds apparently
             let front = {
@@ -157,6 +217,7 @@ impl PackingActor {
                         let config = storage_module.config.clone();
                         self.task_executor.spawn_critical_blocking("packing worker", {
                             let storage_module = storage_module.clone();
+                            let packed_chunks = packed_chunks.clone();
                             let semaphore = semaphore.clone();
                             let permit = semaphore.acquire_owned().await.unwrap();
                             async move {
@@ -175,6 +236,7 @@ impl PackingActor {
 
                                 // write the chunk
                                 storage_module.write_chunk(PartitionChunkOffset::from(i), out, ChunkType::Entropy);
+                                packed_chunks.fetch_add(1, Ordering::Relaxed);
                                 drop(permit); // drop after chunk write so the SM can apply backpressure to packing through the internal pending_writes lock write_chunk acquires
                             }
                         });
@@ -213,6 +275,7 @@ impl PackingActor {
                         let permit = semaphore.acquire_owned().await.unwrap();
                         self.task_executor.spawn_blocking({
                             let storage_module = storage_module.clone();
+                            let packed_chunks = packed_chunks.clone();
                             async move {
                                 let mut out: Vec<u8> = Vec::with_capacity(
                                     (num_chunks * chunk_size as u32).try_into().unwrap(),
@@ -238,6 +301,7 @@ impl PackingActor {
                                             .to_vec(),
                                         ChunkType::Entropy,
                                     );
+                                    packed_chunks.fetch_add(1, Ordering::Relaxed);
                                     if i % short_writes_before_sync == 0 {
                                         debug!("triggering sync");
                                         yield_now().await; // so the shutdown can stop us
@@ -297,6 +361,12 @@ impl Actor for PackingActor {
 
     fn started(&mut self, ctx: &mut Self::Context) {
         ctx.set_mailbox_capacity(5_000);
+        ctx.run_interval(THROUGHPUT_SAMPLE_INTERVAL, |act, _ctx| {
+            let now = Instant::now();
+            for (sm_id, throughput) in act.throughput.iter_mut() {
+                throughput.sample(act.packed_chunks[sm_id].load(Ordering::Relaxed), now);
+            }
+        });
     }
 }
 
@@ -338,6 +408,47 @@ impl Handler<GetInternals> for PackingActor {
     }
 }
 
+#[derive(Debug, Message, Clone)]
+#[rtype("PackingStatus")]
+pub struct GetPackingStatus;
+
+/// Snapshot of the packing queues and the observed packing throughput
+#[derive(Debug, MessageResponse, Clone, Default)]
+pub struct PackingStatus {
+    /// Queued (request count, chunk count) keyed by storage module id
+    pub queued: HashMap<usize, (usize, u64)>,
+    /// Recent number of chunks packed per second as a moving average, keyed by storage module id
+    pub chunks_per_second: HashMap<usize, f64>,
+}
+
+impl Handler<GetPackingStatus> for PackingActor {
+    type Result = PackingStatus;
+
+    fn handle(&mut self, _msg: GetPackingStatus, _ctx: &mut Self::Context) -> Self::Result {
+        let queued = self
+            .pending_jobs
+            .iter()
+            .map(|(sm_id, jobs)| {
+                let jobs = jobs.read().unwrap();
+                let chunks = jobs
+                    .iter()
+                    .map(|job| u64::from(*job.chunk_range.0.end() - *job.chunk_range.0.start()) + 1)
+                    .sum();
+                (*sm_id, (jobs.len(), chunks))
+            })
+            .collect();
+
+        PackingStatus {
+            queued,
+            chunks_per_second: self
+                .throughput
+                .iter()
+                .map(|(sm_id, throughput)| (*sm_id, throughput.chunks_per_second()))
+                .collect(),
+        }
+    }
+}
+
 /// waits for any pending & active packing tasks to complete
 pub async fn wait_for_packing(
     packing_addr: Addr<PackingActor>,
@@ -387,8 +498,31 @@ mod tests {
 
     use crate::packing::{
         cast_vec_u8_to_vec_u8_array, wait_for_packing, PackingActor, PackingConfig, PackingRequest,
+        PackingThroughput,
     };
 
+    #[test]
+    fn packing_throughput_follows_the_recent_rate() {
+        let start = std::time::Instant::now();
+        let mut throughput = PackingThroughput::new(start);
+        assert_eq!(throughput.chunks_per_second(), 0.0);
+
+        // 100 chunks/s for a minute
+        let mut packed = 0;
+        for second in 1..=60 {
+            packed += 100;
+            throughput.sample(packed, start + Duration::from_secs(second));
+        }
+        assert!((throughput.chunks_per_second() - 100.0).abs() < 1e-6);
+
+        // then 10 chunks/s for another minute, a lifetime average would still be at 55
+        for second in 61..=120 {
+            packed += 10;
+            throughput.sample(packed, start + Duration::from_secs(second));
+        }
+        assert!((throughput.chunks_per_second() - 10.0).abs() < 5.0);
+    }
+
     #[actix::test]
     async fn test_packing_actor() -> eyre::Result<()> {
         // setup
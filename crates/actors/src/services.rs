@@ -5,7 +5,7 @@ use crate::{
 };
 use actix::Message;
 use core::ops::Deref;
-use irys_types::GossipData;
+use irys_types::{GossipData, SyncedBlock};
 use std::sync::Arc;
 use tokio::sync::mpsc::{
     channel, unbounded_channel, Receiver, Sender, UnboundedReceiver, UnboundedSender,
@@ -42,6 +42,7 @@ pub struct ServiceReceivers {
     pub vdf_seed: Receiver<BroadcastMiningSeed>,
     pub storage_modules: UnboundedReceiver<StorageModuleServiceMessage>,
     pub gossip_broadcast: UnboundedReceiver<GossipData>,
+    pub synced_blocks: UnboundedReceiver<SyncedBlock>,
 }
 
 #[derive(Debug)]
@@ -54,6 +55,7 @@ pub struct ServiceSendersInner {
     pub vdf_seed: Sender<BroadcastMiningSeed>,
     pub storage_modules: UnboundedSender<StorageModuleServiceMessage>,
     pub gossip_broadcast: UnboundedSender<GossipData>,
+    pub synced_blocks: UnboundedSender<SyncedBlock>,
 }
 
 impl ServiceSendersInner {
@@ -71,6 +73,8 @@ impl ServiceSendersInner {
         let (sm_sender, sm_receiver) = unbounded_channel::<StorageModuleServiceMessage>();
         let (gossip_broadcast_sender, gossip_broadcast_receiver) =
             unbounded_channel::<GossipData>();
+        // blocks downloaded by the chain sync task, processed by the gossip service
+        let (synced_blocks_sender, synced_blocks_receiver) = unbounded_channel::<SyncedBlock>();
 
         let senders = Self {
             chunk_cache: chunk_cache_sender,
@@ -81,6 +85,7 @@ impl ServiceSendersInner {
             vdf_seed: vdf_seed_sender,
             storage_modules: sm_sender,
             gossip_broadcast: gossip_broadcast_sender,
+            synced_blocks: synced_blocks_sender,
         };
         let receivers = ServiceReceivers {
             chunk_cache: chunk_cache_receiver,
@@ -91,6 +96,7 @@ impl ServiceSendersInner {
             vdf_seed: vdf_seed_receiver,
             storage_modules: sm_receiver,
             gossip_broadcast: gossip_broadcast_receiver,
+            synced_blocks: synced_blocks_receiver,
         };
         (senders, receivers)
     }
@@ -1,5 +1,9 @@
+use eyre::OptionExt as _;
 use futures::future::Either;
-use irys_database::{block_header_by_hash, BlockIndex};
+use irys_database::{
+    block_header_by_hash, db::IrysDatabaseExt as _, delete_vdf_step, insert_vdf_step,
+    latest_vdf_steps, tables::VdfSteps, BlockIndex,
+};
 use irys_efficient_sampling::num_recall_ranges_in_partition;
 use irys_storage::irys_consensus_data_db::open_or_create_irys_consensus_data_db;
 use irys_types::{
@@ -10,7 +14,7 @@ use irys_vdf::{apply_reset_seed, step_number_to_salt_number, vdf_sha, warn_misma
 use nodit::{interval::ii, InclusiveInterval, Interval};
 use rayon::prelude::*;
 use reth::tasks::{shutdown::GracefulShutdown, TaskExecutor};
-use reth_db::Database;
+use reth_db::{transaction::DbTxMut as _, Database};
 use sha2::{Digest, Sha256};
 use std::{
     collections::VecDeque,
@@ -151,6 +155,7 @@ pub enum VdfServiceMessage {
 #[derive(Debug)]
 struct Inner {
     vdf_state: AtomicVdfState,
+    irys_db: DatabaseProvider,
 }
 
 #[derive(Debug)]
@@ -178,7 +183,7 @@ impl VdfService {
     ) -> JoinHandle<()> {
         let vdf_state = create_state(
             block_index_read_guard.clone(),
-            irys_db,
+            irys_db.clone(),
             vdf_mining_state_sender,
             config,
         );
@@ -188,6 +193,7 @@ impl VdfService {
                 msg_rx: rx,
                 inner: Inner {
                     vdf_state: Arc::new(RwLock::new(vdf_state)),
+                    irys_db,
                 },
             };
             vdf_service
@@ -236,7 +242,22 @@ impl Inner {
     async fn handle_message(&mut self, msg: VdfServiceMessage) -> eyre::Result<()> {
         match msg {
             VdfServiceMessage::VdfSeed(seed) => {
-                self.vdf_state.write().unwrap().increment_step(seed);
+                let output = seed.0;
+                let (global_step, capacity) = {
+                    let mut state = self.vdf_state.write().unwrap();
+                    state.increment_step(seed);
+                    (state.global_step, state.capacity as u64)
+                };
+                // persist the step so it survives a restart, keeping as many steps as the state
+                if let Err(e) = self.irys_db.update_eyre(|tx| {
+                    insert_vdf_step(tx, global_step, output)?;
+                    if global_step > capacity {
+                        delete_vdf_step(tx, global_step - capacity)?;
+                    }
+                    Ok(())
+                }) {
+                    tracing::error!("failed to persist VDF step {}: {:?}", global_step, e);
+                }
             }
             VdfServiceMessage::GetVdfStateMessage { response } => {
                 let guard = VdfStepsReadGuard::new(self.vdf_state.clone());
@@ -275,7 +296,8 @@ impl Inner {
     }
 }
 
-/// create VDF state using the latest block in db
+/// create VDF state from the steps persisted by the service, falling back to
+/// the latest block in db if they don't continue the latest block's steps
 fn create_state(
     block_index: BlockIndexReadGuard,
     db: DatabaseProvider,
@@ -284,6 +306,136 @@ fn create_state(
 ) -> VdfState {
     let capacity = calc_capacity(config);
 
+    match restore_persisted_steps(&block_index, &db, capacity) {
+        Ok(Some((global_step, seeds))) => {
+            info!(
+                "Restoring {} persisted vdf steps up to step number {}",
+                seeds.len(),
+                global_step
+            );
+            return VdfState {
+                global_step,
+                seeds,
+                capacity,
+                mining_state_sender: Some(vdf_mining_state_sender),
+            };
+        }
+        Ok(None) => {}
+        Err(e) => warn!("Discarding persisted vdf steps: {:?}", e),
+    }
+
+    let state = create_state_from_blocks(block_index, db.clone(), vdf_mining_state_sender, config);
+    // start over the persisted steps from the latest blocks' ones
+    if let Err(e) = persist_steps(&db, &state) {
+        tracing::error!("failed to persist vdf steps: {:?}", e);
+    }
+    state
+}
+
+/// Loads the most recent persisted VDF steps, if they are contiguous and include the
+/// steps of the latest block
+fn restore_persisted_steps(
+    block_index: &BlockIndexReadGuard,
+    db: &DatabaseProvider,
+    capacity: usize,
+) -> eyre::Result<Option<(u64, VecDeque<Seed>)>> {
+    let tx = db.tx()?;
+    let persisted = latest_vdf_steps(&tx, capacity)?;
+    let Some(&(global_step, _)) = persisted.last() else {
+        return Ok(None);
+    };
+
+    let block_hash = block_index
+        .read()
+        .get_latest_item()
+        .map(|item| item.block_hash)
+        .ok_or_eyre("no block to check the persisted vdf steps against")?;
+    let block = block_header_by_hash(&tx, &block_hash, false)?
+        .ok_or_eyre("latest block header not found")?;
+    check_steps_continuity(&persisted, &block.vdf_limiter_info)?;
+
+    Ok(Some((
+        global_step,
+        persisted
+            .into_iter()
+            .map(|(_, output)| Seed(output))
+            .collect(),
+    )))
+}
+
+/// Checks the persisted `(global_step, output)` steps are contiguous and continue the
+/// steps of the block's `VDFLimiterInfo`
+fn check_steps_continuity(
+    persisted: &[(u64, H256)],
+    vdf_info: &VDFLimiterInfo,
+) -> eyre::Result<()> {
+    let (Some(&(first_step, _)), Some(&(last_step, _))) = (persisted.first(), persisted.last())
+    else {
+        return Err(eyre::eyre!("no persisted vdf steps"));
+    };
+    eyre::ensure!(
+        last_step - first_step + 1 == persisted.len() as u64,
+        "persisted vdf steps {}..={} are not contiguous",
+        first_step,
+        last_step
+    );
+
+    let tip_step = vdf_info.global_step_number;
+    if tip_step == 0 {
+        eyre::ensure!(
+            first_step == 1,
+            "persisted vdf steps don't start from the genesis block"
+        );
+        return Ok(());
+    }
+    eyre::ensure!(
+        first_step <= tip_step && tip_step <= last_step,
+        "persisted vdf steps {}..={} don't include the latest block step {}",
+        first_step,
+        last_step,
+        tip_step
+    );
+
+    let block_first_step = tip_step + 1 - vdf_info.steps.len() as u64;
+    for (step, output) in (block_first_step..).zip(vdf_info.steps.iter()) {
+        if step < first_step {
+            continue;
+        }
+        eyre::ensure!(
+            persisted[(step - first_step) as usize].1 == *output,
+            "persisted vdf step {} doesn't match the latest block",
+            step
+        );
+    }
+    eyre::ensure!(
+        persisted[(tip_step - first_step) as usize].1 == vdf_info.output,
+        "persisted vdf step {} doesn't match the latest block output",
+        tip_step
+    );
+    Ok(())
+}
+
+/// Replaces the persisted VDF steps with the steps of the state
+fn persist_steps(db: &DatabaseProvider, state: &VdfState) -> eyre::Result<()> {
+    let first_step = state.global_step + 1 - state.seeds.len() as u64;
+    db.update_eyre(|tx| {
+        tx.clear::<VdfSteps>()?;
+        for (step, seed) in (first_step..).zip(state.seeds.iter()) {
+            insert_vdf_step(tx, step, seed.0)?;
+        }
+        Ok(())
+    })
+}
+
+/// create VDF state using the latest block in db
+fn create_state_from_blocks(
+    block_index: BlockIndexReadGuard,
+    db: DatabaseProvider,
+    vdf_mining_state_sender: Sender<bool>,
+    config: &Config,
+) -> VdfState {
+    let capacity = calc_capacity(config);
+
     if let Some(block_hash) = block_index
         .read()
         .get_latest_item()
@@ -335,7 +487,7 @@ fn create_state(
 /// return the larger of max_allowed_vdf_fork_steps or num_recall_ranges_in_partition()
 /// num_recall_ranges_in_partition() ensures the capacity of VecDeqeue is large enough for the partition.
 /// max_allowed_vdf_fork_steps of 60k allows for forks. VDF capacity limits the depth at which a fork can happen. If the fork happens out of the VDF range, the node cannot validate it.
-fn calc_capacity(config: &Config) -> usize {
+pub fn calc_capacity(config: &Config) -> usize {
     let capacity_from_config: u64 = num_recall_ranges_in_partition(&config.consensus);
 
     let max_allowed_vdf_fork_steps = config.consensus.vdf.max_allowed_vdf_fork_steps;
@@ -611,4 +763,42 @@ mod tests {
             get_all
         );
     }
+
+    #[test]
+    /// Tests persisted steps are only restored when they continue the latest block steps
+    fn test_check_steps_continuity() {
+        let persisted: Vec<(u64, H256)> = (5..=10).map(|i| (i, H256([i as u8; 32]))).collect();
+        let vdf_info = VDFLimiterInfo {
+            output: H256([8; 32]),
+            global_step_number: 8,
+            steps: H256List(vec![H256([6; 32]), H256([7; 32]), H256([8; 32])]),
+            ..VDFLimiterInfo::default()
+        };
+        assert!(check_steps_continuity(&persisted, &vdf_info).is_ok());
+
+        // steps of another fork
+        let mut forked = persisted.clone();
+        forked[2].1 = H256([0xff; 32]);
+        assert!(check_steps_continuity(&forked, &vdf_info).is_err());
+
+        // gap in the persisted steps
+        let mut gapped = persisted.clone();
+        gapped.remove(1);
+        assert!(check_steps_continuity(&gapped, &vdf_info).is_err());
+
+        // block ahead of the persisted steps
+        let ahead = VDFLimiterInfo {
+            output: H256([12; 32]),
+            global_step_number: 12,
+            ..VDFLimiterInfo::default()
+        };
+        assert!(check_steps_continuity(&persisted, &ahead).is_err());
+
+        // genesis block, steps must start from the first one
+        let genesis = VDFLimiterInfo::default();
+        assert!(check_steps_continuity(&persisted, &genesis).is_err());
+        assert!(check_steps_continuity(&persisted[..0], &genesis).is_err());
+        let from_genesis: Vec<(u64, H256)> = (1..=3).map(|i| (i, H256([i as u8; 32]))).collect();
+        assert!(check_steps_continuity(&from_genesis, &genesis).is_ok());
+    }
 }
@@ -396,6 +396,7 @@ async fn partition_expiration_and_repacking_test() {
     let mut part_actors = Vec::new();
 
     let atomic_global_step_number = Arc::new(AtomicU64::new(0));
+    let solved_step_number = Arc::new(AtomicU64::new(0));
 
     for sm in &storage_modules {
         let partition_mining_actor = PartitionMiningActor::new(
@@ -406,6 +407,7 @@ async fn partition_expiration_and_repacking_test() {
             true, // do not start mining automatically
             vdf_steps_guard.clone(),
             atomic_global_step_number.clone(),
+            solved_step_number.clone(),
             U256::zero(),
             None,
         );
@@ -8,6 +8,8 @@ use reqwest::{Client, StatusCode};
 use serde::{de::DeserializeOwned, Serialize};
 use std::net::SocketAddr;
 
+pub mod sdk;
+
 enum Method {
     GET,
     POST,
@@ -0,0 +1,465 @@
+//! User facing client for storing data on an Irys node and reading it back.
+//!
+//! [`IrysClient`] wraps the steps every integration goes through: pricing the data,
+//! signing and posting the transaction header, uploading the chunks, waiting for the
+//! transaction to be promoted and downloading the data, verified against its data root.
+//! Files too large to hold in memory are merklized with a
+//! [`StreamingMerkleBuilder`](irys_types::StreamingMerkleBuilder) and uploaded with
+//! [`IrysClient::upload_file_chunks`].
+use base58::ToBase58 as _;
+use eyre::{ensure, eyre, Result};
+use futures::{future, stream, Future, StreamExt as _};
+use irys_primitives::CommitmentType;
+use irys_types::{
+    generate_data_root, generate_leaves, irys::IrysSigner, Base64, CombinedBlockHeader,
+    CommitmentTransaction, DataLedger, IrysTransaction, IrysTransactionHeader,
+    IrysTransactionResponse, StreamingMerkleTree, TxChunkOffset, UnpackedChunk, H256, U256,
+};
+use reqwest::{Client, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeSet, io::SeekFrom, path::Path, time::Duration};
+use tokio::{
+    fs::File,
+    io::{AsyncReadExt as _, AsyncSeekExt as _},
+    time::{sleep, Instant},
+};
+
+/// Tuning of the uploads and polling done by [`IrysClient`]
+#[derive(Debug, Clone)]
+pub struct ClientOptions {
+    /// Number of chunks uploaded in parallel
+    pub concurrency: usize,
+    /// Number of times a failed chunk upload is retried
+    pub max_retries: u32,
+    /// Delay before retrying a failed chunk upload
+    pub retry_delay: Duration,
+    /// Delay between checks of whether a transaction is promoted
+    pub poll_interval: Duration,
+}
+
+impl Default for ClientOptions {
+    fn default() -> Self {
+        Self {
+            concurrency: 8,
+            max_retries: 5,
+            retry_delay: Duration::from_secs(1),
+            poll_interval: Duration::from_secs(2),
+        }
+    }
+}
+
+/// The chunks of a transaction already accepted by the node. Pass the same progress
+/// to [`IrysClient::upload_chunks`] again to resume an interrupted upload
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadProgress {
+    pub tx_id: H256,
+    pub uploaded_chunks: BTreeSet<u32>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PriceResponse {
+    cost_in_irys: U256,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UploadStatusResponse {
+    received_chunks: Vec<u32>,
+}
+
+/// Client for uploading data to, and retrieving it from, an Irys node
+#[derive(Debug, Clone)]
+pub struct IrysClient {
+    client: Client,
+    /// Base url of the node's API, e.g. `http://127.0.0.1:8080`
+    node_url: String,
+    signer: IrysSigner,
+    options: ClientOptions,
+}
+
+impl IrysClient {
+    pub fn new(node_url: impl Into<String>, signer: IrysSigner) -> Self {
+        Self::with_options(node_url, signer, ClientOptions::default())
+    }
+
+    pub fn with_options(
+        node_url: impl Into<String>,
+        signer: IrysSigner,
+        options: ClientOptions,
+    ) -> Self {
+        Self {
+            client: Client::default(),
+            node_url: node_url.into().trim_end_matches('/').to_owned(),
+            signer,
+            options,
+        }
+    }
+
+    /// Chunk size the client splits data into, which must match the node's
+    pub fn chunk_size(&self) -> u64 {
+        self.signer.chunk_size
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/v1{}", self.node_url, path)
+    }
+
+    /// Returns the price of permanently storing `bytes` bytes
+    pub async fn get_price(&self, bytes: u64) -> Result<U256> {
+        let path = format!("/price/{}/{}", u32::from(DataLedger::Publish), bytes);
+        let response = self.client.get(self.url(&path)).send().await?;
+        let price: PriceResponse = checked(response).await?.json().await?;
+        Ok(price.cost_in_irys)
+    }
+
+    /// Returns the hash of the node's latest block, used to anchor new transactions
+    pub async fn get_anchor(&self) -> Result<H256> {
+        let response = self.client.get(self.url("/block/latest")).send().await?;
+        let block: CombinedBlockHeader = checked(response).await?.json().await?;
+        Ok(block.irys.block_hash)
+    }
+
+    /// Creates a transaction for `data`, anchored to the latest block and paying the
+    /// current storage price, and signs it
+    pub async fn create_transaction(&self, data: Vec<u8>) -> Result<IrysTransaction> {
+        self.create_ledger_transaction(data, DataLedger::Publish)
+            .await
+    }
+
+    /// Creates a signed transaction for `data`, destined to `ledger`
+    pub async fn create_ledger_transaction(
+        &self,
+        data: Vec<u8>,
+        ledger: DataLedger,
+    ) -> Result<IrysTransaction> {
+        let tx = self.signer.create_transaction(data, None)?;
+        self.price_and_sign(tx, ledger).await
+    }
+
+    /// Creates a signed transaction for the data of a [`StreamingMerkleTree`], destined to
+    /// `ledger`. Upload its chunks with [`Self::upload_file_chunks`]
+    pub async fn create_transaction_from_tree(
+        &self,
+        tree: &StreamingMerkleTree,
+        ledger: DataLedger,
+    ) -> Result<IrysTransaction> {
+        let tx = self.signer.create_transaction_from_tree(tree, None)?;
+        self.price_and_sign(tx, ledger).await
+    }
+
+    /// Anchors the transaction to the latest block, pays for publish storage at the current
+    /// price and signs it. The node doesn't price the submit ledger yet, so submit
+    /// transactions only pay the term fee
+    async fn price_and_sign(
+        &self,
+        mut tx: IrysTransaction,
+        ledger: DataLedger,
+    ) -> Result<IrysTransaction> {
+        tx.header.perm_fee = match ledger {
+            DataLedger::Publish => {
+                let price = self.get_price(tx.header.data_size).await?;
+                ensure!(
+                    price <= U256::from(u64::MAX),
+                    "storage price {} overflows u64",
+                    price
+                );
+                Some(price.as_u64())
+            }
+            DataLedger::Submit => None,
+        };
+        tx.header.ledger_id = u32::from(ledger);
+        tx.header.chain_id = self.signer.chain_id;
+        tx.header.anchor = self.get_anchor().await?;
+        self.signer.sign_transaction(tx)
+    }
+
+    /// Posts a signed transaction header to the node
+    pub async fn post_transaction(&self, tx: &IrysTransaction) -> Result<()> {
+        let response = self
+            .client
+            .post(self.url("/tx"))
+            .json(&tx.header)
+            .send()
+            .await?;
+        checked(response).await?;
+        Ok(())
+    }
+
+    /// Returns the offsets of the chunks of `data_root` the node already holds
+    pub async fn get_received_chunks(&self, data_root: H256) -> Result<BTreeSet<u32>> {
+        let path = format!("/chunk/upload_status/{}", data_root.0.to_base58());
+        let response = self.client.get(self.url(&path)).send().await?;
+        let status: UploadStatusResponse = checked(response).await?.json().await?;
+        Ok(status.received_chunks.into_iter().collect())
+    }
+
+    /// Uploads the chunks of `tx` that neither `progress` nor the node list yet, `concurrency`
+    /// at a time. Failed uploads are retried; chunks that still fail are left out of `progress`
+    pub async fn upload_chunks(
+        &self,
+        tx: &IrysTransaction,
+        progress: &mut UploadProgress,
+    ) -> Result<()> {
+        let data = tx
+            .data
+            .as_ref()
+            .ok_or_else(|| eyre!("transaction {} has no data", tx.header.id))?;
+
+        self.upload_missing_chunks(&tx.header, tx.chunks.len(), progress, |offset| {
+            let chunk_node = &tx.chunks[offset as usize];
+            future::ready(Ok(UnpackedChunk {
+                data_root: tx.header.data_root,
+                data_size: tx.header.data_size,
+                data_path: Base64(tx.proofs[offset as usize].proof.clone()),
+                bytes: Base64(
+                    data.0[chunk_node.min_byte_range..chunk_node.max_byte_range].to_vec(),
+                ),
+                tx_offset: TxChunkOffset::from(offset),
+            }))
+        })
+        .await
+    }
+
+    /// Like [`Self::upload_chunks`] for a transaction created with
+    /// [`Self::create_transaction_from_tree`], reading each chunk from the file at `path` as
+    /// it is sent rather than holding the file in memory
+    pub async fn upload_file_chunks(
+        &self,
+        tx: &IrysTransaction,
+        tree: &StreamingMerkleTree,
+        path: &Path,
+        progress: &mut UploadProgress,
+    ) -> Result<()> {
+        ensure!(
+            tree.data_root() == tx.header.data_root,
+            "tree doesn't match the data_root of tx {}",
+            tx.header.id
+        );
+
+        self.upload_missing_chunks(
+            &tx.header,
+            tree.chunk_count(),
+            progress,
+            |offset| async move {
+                let range = tree
+                    .chunk_range(offset as usize)
+                    .ok_or_else(|| eyre!("chunk {} is out of range", offset))?;
+                let mut file = File::open(path).await?;
+                file.seek(SeekFrom::Start(range.start as u64)).await?;
+                let mut bytes = vec![0; range.len()];
+                file.read_exact(&mut bytes).await?;
+
+                Ok(UnpackedChunk {
+                    data_root: tx.header.data_root,
+                    data_size: tx.header.data_size,
+                    data_path: Base64(tree.proof(offset as usize)?.proof),
+                    bytes: Base64(bytes),
+                    tx_offset: TxChunkOffset::from(offset),
+                })
+            },
+        )
+        .await
+    }
+
+    async fn upload_missing_chunks<F, Fut>(
+        &self,
+        header: &IrysTransactionHeader,
+        chunk_count: usize,
+        progress: &mut UploadProgress,
+        load_chunk: F,
+    ) -> Result<()>
+    where
+        F: Fn(u32) -> Fut,
+        Fut: Future<Output = Result<UnpackedChunk>>,
+    {
+        if progress.tx_id != header.id {
+            *progress = UploadProgress {
+                tx_id: header.id,
+                ..Default::default()
+            };
+        }
+        // the node may hold chunks sent by an earlier, interrupted, session
+        progress
+            .uploaded_chunks
+            .extend(self.get_received_chunks(header.data_root).await?);
+
+        let pending = (0..chunk_count as u32)
+            .filter(|offset| !progress.uploaded_chunks.contains(offset))
+            .collect::<Vec<_>>();
+
+        let load_chunk = &load_chunk;
+        let results = stream::iter(pending)
+            .map(|offset| async move {
+                let result = match load_chunk(offset).await {
+                    Ok(chunk) => self.post_chunk_with_retries(&chunk).await,
+                    Err(e) => Err(e),
+                };
+                (offset, result)
+            })
+            .buffer_unordered(self.options.concurrency.max(1))
+            .collect::<Vec<_>>()
+            .await;
+
+        let mut failed = Vec::new();
+        for (offset, result) in results {
+            match result {
+                Ok(()) => {
+                    progress.uploaded_chunks.insert(offset);
+                }
+                Err(e) => failed.push((offset, e)),
+            }
+        }
+        if let Some((offset, e)) = failed.first() {
+            return Err(eyre!(
+                "{} chunks of tx {} failed to upload, first was chunk {}: {}",
+                failed.len(),
+                header.id,
+                offset,
+                e
+            ));
+        }
+        Ok(())
+    }
+
+    async fn post_chunk_with_retries(&self, chunk: &UnpackedChunk) -> Result<()> {
+        let mut attempt = 0;
+        loop {
+            let result = match self
+                .client
+                .post(self.url("/chunk"))
+                .json(chunk)
+                .send()
+                .await
+            {
+                // the node rejected the chunk itself, retrying won't help
+                Ok(response) if response.status() == StatusCode::BAD_REQUEST => {
+                    return checked(response).await.map(|_| ());
+                }
+                Ok(response) => checked(response).await.map(|_| ()),
+                Err(e) => Err(e.into()),
+            };
+            if result.is_ok() || attempt >= self.options.max_retries {
+                return result;
+            }
+            attempt += 1;
+            sleep(self.options.retry_delay).await;
+        }
+    }
+
+    /// Prices, signs and posts a transaction for `data`, then uploads its chunks
+    pub async fn upload(&self, data: Vec<u8>) -> Result<IrysTransaction> {
+        let tx = self.create_transaction(data).await?;
+        self.post_transaction(&tx).await?;
+        self.upload_chunks(&tx, &mut UploadProgress::default())
+            .await?;
+        Ok(tx)
+    }
+
+    /// Returns whether the transaction's data is promoted to the publish ledger
+    pub async fn is_promoted(&self, tx_id: H256) -> Result<bool> {
+        let path = format!("/tx/{}/is_promoted", tx_id.0.to_base58());
+        let response = self.client.get(self.url(&path)).send().await?;
+        Ok(checked(response).await?.json().await?)
+    }
+
+    /// Polls the node until the transaction is promoted, or `timeout` elapses
+    pub async fn wait_for_promotion(&self, tx_id: H256, timeout: Duration) -> Result<()> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            // the header may not be stored by the node yet
+            if self.is_promoted(tx_id).await.unwrap_or(false) {
+                return Ok(());
+            }
+            ensure!(
+                Instant::now() < deadline,
+                "tx {} wasn't promoted within {:?}",
+                tx_id,
+                timeout
+            );
+            sleep(self.options.poll_interval).await;
+        }
+    }
+
+    /// Fetches the header of a storage or commitment transaction
+    pub async fn get_transaction(&self, tx_id: H256) -> Result<IrysTransactionResponse> {
+        let path = format!("/tx/{}", tx_id.0.to_base58());
+        let response = self.client.get(self.url(&path)).send().await?;
+        Ok(checked(response).await?.json().await?)
+    }
+
+    /// Downloads the data of a transaction, checking it hashes to the transaction's data root
+    pub async fn download(&self, tx_id: H256) -> Result<Vec<u8>> {
+        let header = match self.get_transaction(tx_id).await? {
+            IrysTransactionResponse::Storage(header) => header,
+            IrysTransactionResponse::Commitment(_) => {
+                return Err(eyre!("tx {} is a commitment and has no data", tx_id))
+            }
+        };
+
+        let path = format!("/tx/{}/data", tx_id.0.to_base58());
+        let response = self.client.get(self.url(&path)).send().await?;
+        let data = checked(response).await?.bytes().await?.to_vec();
+        ensure!(
+            data.len() as u64 == header.data_size,
+            "downloaded {} bytes of tx {}, expected {}",
+            data.len(),
+            tx_id,
+            header.data_size
+        );
+
+        let leaves = generate_leaves(
+            std::iter::once(Ok(data.clone())),
+            self.signer.chunk_size as usize,
+        )?;
+        let data_root = H256(generate_data_root(leaves)?.id);
+        ensure!(
+            data_root == header.data_root,
+            "downloaded data of tx {} doesn't match its data_root",
+            tx_id
+        );
+        Ok(data)
+    }
+
+    /// Signs a commitment of `commitment_type` paying `fee`, anchored to the latest block, and
+    /// posts it. The fee is taken from the signer's balance, the mempool includes the highest
+    /// paying commitments first
+    pub async fn post_commitment(
+        &self,
+        commitment_type: CommitmentType,
+        fee: u64,
+    ) -> Result<CommitmentTransaction> {
+        let commitment = CommitmentTransaction {
+            commitment_type,
+            anchor: self.get_anchor().await?,
+            chain_id: self.signer.chain_id,
+            fee,
+            ..Default::default()
+        };
+        let commitment = self.signer.sign_commitment(commitment)?;
+
+        let response = self
+            .client
+            .post(self.url("/commitment_tx"))
+            .json(&commitment)
+            .send()
+            .await?;
+        checked(response).await?;
+        Ok(commitment)
+    }
+}
+
+/// Turns unsuccessful responses into errors carrying the response body
+async fn checked(response: reqwest::Response) -> Result<reqwest::Response> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let error_text = response.text().await.unwrap_or_default();
+    Err(eyre!(
+        "API request failed with status: {} - {}",
+        status,
+        error_text
+    ))
+}
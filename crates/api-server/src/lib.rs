@@ -1,4 +1,5 @@
 pub mod error;
+pub mod openapi;
 pub mod routes;
 
 use actix::Addr;
@@ -7,24 +8,34 @@ use actix_web::dev::Server;
 use actix_web::{
     dev::HttpServiceFactory,
     error::InternalError,
-    web::{self, JsonConfig},
-    App, HttpResponse, HttpServer,
+    http::Method,
+    web::{self, JsonConfig, PayloadConfig},
+    App, FromRequest, Handler, HttpResponse, HttpServer, Resource, Responder,
 };
 use irys_actors::ema_service::EmaServiceMessage;
 use irys_actors::{
     block_index_service::BlockIndexReadGuard, block_tree_service::BlockTreeReadGuard,
-    mempool_service::MempoolService,
+    mempool_service::MempoolService, packing::PackingActor, CommitmentStateReadGuard,
 };
 use irys_p2p::PeerListServiceFacade;
-use irys_p2p::SyncState;
+use irys_p2p::{BlockPoolReadGuard, SyncState};
 use irys_reth_node_bridge::node::RethNodeProvider;
 use irys_storage::ChunkProvider;
 use irys_types::{app_state::DatabaseProvider, Config, PeerAddress};
+use openapi::{
+    address_param, array, ingress_responses, integer, json_content, json_or_binary_content,
+    ledger_id_param, not_found_response, ok_binary, ok_json, ok_json_or_binary, operation,
+    paging_params, path_param, query_param, required_query_param, schema_ref, tx_id_param,
+    with_body,
+};
 use routes::commitment;
 use routes::{
-    block, block_index, get_chunk, index, network_config, peer_list, post_chunk, post_version,
-    price, proxy::proxy, tx,
+    block, block_index, block_pool, bundle, forks, get_chunk, index, network_config, peer_list,
+    post_chunk, post_version, price,
+    proxy::{proxy, ExecutionRpcProxy},
+    query, storage_modules, tx,
 };
+use serde_json::{json, Value};
 use std::net::TcpListener;
 use std::{net::SocketAddr, sync::Arc};
 use tokio::sync::mpsc::UnboundedSender;
@@ -44,6 +55,9 @@ pub struct ApiState {
     pub block_tree: BlockTreeReadGuard,
     pub block_index: BlockIndexReadGuard,
     pub sync_state: SyncState,
+    pub block_pool: BlockPoolReadGuard,
+    pub packing: Addr<PackingActor>,
+    pub commitment_state: CommitmentStateReadGuard,
 }
 
 impl ApiState {
@@ -55,56 +69,429 @@ impl ApiState {
     }
 }
 
-pub fn routes() -> impl HttpServiceFactory {
-    web::scope("v1")
-        .route("/block/{block_tag}", web::get().to(block::get_block))
-        .route(
+/// A v1 route: the path and method it's served at, its handler and its OpenAPI operation.
+/// [`routes`] serves the routes of [`api_routes`], and [`openapi::openapi`] documents them
+pub(crate) struct ApiRoute {
+    pub path: &'static str,
+    /// Method the route is documented with
+    pub method: Method,
+    pub operation: Value,
+    resource: Resource,
+}
+
+impl ApiRoute {
+    fn new(method: Method, path: &'static str, operation: Value) -> Self {
+        Self {
+            path,
+            method,
+            operation,
+            resource: web::resource(path),
+        }
+    }
+
+    fn get(path: &'static str, operation: Value) -> Self {
+        Self::new(Method::GET, path, operation)
+    }
+
+    fn post(path: &'static str, operation: Value) -> Self {
+        Self::new(Method::POST, path, operation)
+    }
+
+    /// Handles requests with the route's method with `handler`
+    fn to<F, Args>(mut self, handler: F) -> Self
+    where
+        F: Handler<Args>,
+        Args: FromRequest + 'static,
+        F::Output: Responder + 'static,
+    {
+        self.resource = self
+            .resource
+            .route(web::method(self.method.clone()).to(handler));
+        self
+    }
+
+    /// Handles requests with any method with `handler`, which answers the undocumented ones
+    fn to_any<F, Args>(mut self, handler: F) -> Self
+    where
+        F: Handler<Args>,
+        Args: FromRequest + 'static,
+        F::Output: Responder + 'static,
+    {
+        self.resource = self.resource.route(web::route().to(handler));
+        self
+    }
+
+    /// Adds data to the route, like the payload limits of its extractors
+    fn app_data<U: 'static>(mut self, data: U) -> Self {
+        self.resource = self.resource.app_data(data);
+        self
+    }
+}
+
+/// The v1 routes, one per path
+pub(crate) fn api_routes() -> Vec<ApiRoute> {
+    vec![
+        ApiRoute::get(
+            "/block/{block_tag}",
+            operation(
+                "Block header and execution header by hash, height, or `latest`",
+                vec![path_param(
+                    "block_tag",
+                    json!({ "type": "string" }),
+                    "base58 block hash, height or `latest`",
+                )],
+                ok_json_or_binary(schema_ref("CombinedBlockHeader")),
+            ),
+        )
+        .to(block::get_block),
+        ApiRoute::get(
             "/block_index",
-            web::get().to(block_index::block_index_route),
+            operation(
+                "Block index entries starting at a height",
+                vec![
+                    query_param("height", integer(), "first height"),
+                    query_param("limit", integer(), "number of entries"),
+                ],
+                ok_json(array(schema_ref("BlockIndexItem"))),
+            ),
+        )
+        .to(block_index::block_index_route),
+        ApiRoute::get(
+            "/block_pool",
+            operation(
+                "Blocks waiting in the sync block pool",
+                vec![],
+                ok_json(json!({})),
+            ),
+        )
+        .to(block_pool::block_pool_route),
+        ApiRoute::get(
+            "/blocks",
+            operation(
+                "Canonical blocks starting at a height",
+                vec![
+                    query_param("from", integer(), "height of the first block"),
+                    query_param("limit", integer(), "maximum number of blocks"),
+                    query_param(
+                        "includeTxs",
+                        json!({ "type": "boolean" }),
+                        "include the data transaction headers",
+                    ),
+                ],
+                ok_json(array(schema_ref("BlockWithTxs"))),
+            ),
+        )
+        .to(query::get_blocks),
+        ApiRoute::get(
+            "/bundle/item/{item_id}",
+            operation(
+                "Header of a bundled data item",
+                vec![path_param("item_id", schema_ref("Base58Hash"), "data item id")],
+                ok_json(json!({ "type": "object" })),
+            ),
+        )
+        .to(bundle::get_bundle_item),
+        ApiRoute::get(
+            "/bundle/item/{item_id}/data",
+            operation(
+                "Data of a bundled data item",
+                vec![path_param("item_id", schema_ref("Base58Hash"), "data item id")],
+                ok_binary(),
+            ),
         )
-        .route(
+        .to(bundle::get_bundle_item_data),
+        ApiRoute::post(
             "/commitment_tx",
-            web::post().to(commitment::post_commitment_tx),
+            with_body(
+                operation(
+                    "Submit a signed commitment transaction to the mempool",
+                    vec![],
+                    ingress_responses(),
+                ),
+                json_content(schema_ref("CommitmentTransaction")),
+            ),
         )
-        .route("/chunk", web::post().to(post_chunk::post_chunk))
-        .route(
+        .to(commitment::post_commitment_tx),
+        ApiRoute::get(
+            "/commitments/signer/{address}",
+            operation(
+                "Commitments signed by an address with their status",
+                [vec![address_param()], paging_params()].concat(),
+                ok_json(schema_ref("CommitmentWithStatusPage")),
+            ),
+        )
+        .to(query::get_commitments_by_signer),
+        ApiRoute::post(
+            "/chunk",
+            with_body(
+                operation(
+                    "Submit a chunk of transaction data",
+                    vec![],
+                    json!({
+                        "200": { "description": "accepted" },
+                        "400": { "description": "invalid chunk" },
+                    }),
+                ),
+                json_or_binary_content(schema_ref("UnpackedChunk")),
+            ),
+        )
+        .app_data(PayloadConfig::new(post_chunk::CHUNK_PAYLOAD_LIMIT))
+        .to(post_chunk::post_chunk),
+        ApiRoute::get(
             "/chunk/data_root/{ledger_id}/{data_root}/{offset}",
-            web::get().to(get_chunk::get_chunk_by_data_root_offset),
+            operation(
+                "Chunk by data root and offset within the data, falling back to peers unless `local` is set",
+                vec![
+                    ledger_id_param(),
+                    path_param("data_root", schema_ref("Base58Hash"), "data root"),
+                    path_param("offset", integer(), "chunk offset within the data"),
+                    query_param(
+                        "local",
+                        json!({ "type": "boolean" }),
+                        "don't ask peers for the chunk",
+                    ),
+                ],
+                ok_json_or_binary(schema_ref("ChunkFormat")),
+            ),
         )
-        .route(
+        .to(get_chunk::get_chunk_by_data_root_offset),
+        ApiRoute::get(
             "/chunk/ledger/{ledger_id}/{ledger_offset}",
-            web::get().to(get_chunk::get_chunk_by_ledger_offset),
+            operation(
+                "Packed chunk by ledger offset",
+                vec![
+                    ledger_id_param(),
+                    path_param("ledger_offset", integer(), "chunk offset within the ledger"),
+                ],
+                ok_json_or_binary(schema_ref("ChunkFormat")),
+            ),
+        )
+        .to(get_chunk::get_chunk_by_ledger_offset),
+        ApiRoute::get(
+            "/chunk/upload_status/{data_root}",
+            operation(
+                "Offsets of the chunks of a data root the node holds, to resume uploads",
+                vec![path_param("data_root", schema_ref("Base58Hash"), "data root")],
+                ok_json(schema_ref("UploadStatus")),
+            ),
+        )
+        .to(post_chunk::get_upload_status),
+        ApiRoute::post(
+            "/chunks",
+            with_body(
+                operation(
+                    "Submit a batch of chunks",
+                    vec![],
+                    ok_json(schema_ref("BatchChunkResponse")),
+                ),
+                json_content(array(schema_ref("UnpackedChunk"))),
+            ),
+        )
+        .app_data(JsonConfig::default().limit(post_chunk::BATCH_PAYLOAD_LIMIT))
+        .to(post_chunk::post_chunks),
+        ApiRoute::post(
+            "/execution-rpc",
+            operation(
+                "Proxy to the allowlisted methods of the execution layer JSON-RPC",
+                vec![],
+                json!({
+                    "200": { "description": "JSON-RPC response, or batch of responses" },
+                    "204": { "description": "The request only held notifications" },
+                    "400": { "description": "Malformed request or batch too large" },
+                    "413": { "description": "Request body too large" },
+                    "429": { "description": "Rate limit of the client IP exceeded" },
+                }),
+            ),
         )
-        .route("/execution-rpc", web::to(proxy))
-        .route("/info", web::get().to(index::info_route))
-        .route(
+        .to_any(proxy),
+        ApiRoute::get(
+            "/forks",
+            operation(
+                "Recent reorgs and the current fork tips",
+                vec![],
+                ok_json(json!({ "type": "object" })),
+            ),
+        )
+        .to(forks::get_fork_history),
+        ApiRoute::get(
+            "/info",
+            operation("Node status", vec![], ok_json(schema_ref("NodeInfo"))),
+        )
+        .to(index::info_route),
+        ApiRoute::get(
             "/network/config",
-            web::get().to(network_config::get_network_config),
+            operation(
+                "Network wide storage parameters",
+                vec![],
+                ok_json(schema_ref("PublicStorageConfig")),
+            ),
+        )
+        .to(network_config::get_network_config),
+        ApiRoute::get(
+            "/openapi.json",
+            operation(
+                "This OpenAPI document describing the v1 routes",
+                vec![],
+                ok_json(openapi::document_schema()),
+            ),
+        )
+        .to(openapi::get_openapi),
+        ApiRoute::get(
+            "/peer_list",
+            operation(
+                "Known peers",
+                vec![],
+                ok_json(array(json!({ "type": "object" }))),
+            ),
+        )
+        .to(peer_list::peer_list_route),
+        ApiRoute::get(
+            "/price/{ledger}/{size}",
+            operation(
+                "Price of storing a number of bytes",
+                vec![
+                    path_param("ledger", integer(), "0 for Publish, Submit isn't priced yet"),
+                    path_param("size", integer(), "bytes to store, rounded up to whole chunks"),
+                ],
+                ok_json(schema_ref("PriceInfo")),
+            ),
         )
-        .route("/peer_list", web::get().to(peer_list::peer_list_route))
-        .route("/price/{ledger}/{size}", web::get().to(price::get_price))
-        .route("/tx", web::post().to(tx::post_tx))
-        .route("/tx/{tx_id}", web::get().to(tx::get_transaction_api))
-        .route(
+        .to(price::get_price),
+        ApiRoute::get(
+            "/storage_modules",
+            operation(
+                "Status of the node's storage modules",
+                vec![],
+                ok_json(array(json!({ "type": "object" }))),
+            ),
+        )
+        .to(storage_modules::storage_modules_route),
+        ApiRoute::post(
+            "/tx",
+            with_body(
+                operation(
+                    "Submit a signed data transaction header to the mempool",
+                    vec![],
+                    ingress_responses(),
+                ),
+                json_content(schema_ref("IrysTransactionHeader")),
+            ),
+        )
+        .to(tx::post_tx),
+        ApiRoute::get(
+            "/tx/{tx_id}",
+            operation(
+                "Data or commitment transaction by id",
+                vec![tx_id_param()],
+                json!({
+                    "200": {
+                        "description": "OK",
+                        "content": json_content(schema_ref("IrysTransactionResponse")),
+                    },
+                    "404": not_found_response(),
+                }),
+            ),
+        )
+        .to(tx::get_transaction_api),
+        ApiRoute::get(
+            "/tx/{tx_id}/data",
+            operation(
+                "Unpacked data of a transaction, supports single byte ranges",
+                vec![tx_id_param()],
+                json!({
+                    "200": { "description": "OK", "content": { "application/octet-stream": {} } },
+                    "206": {
+                        "description": "requested range",
+                        "content": { "application/octet-stream": {} },
+                    },
+                    "416": { "description": "range not satisfiable" },
+                }),
+            ),
+        )
+        .to(tx::get_tx_data),
+        ApiRoute::get(
             "/tx/{tx_id}/is_promoted",
-            web::get().to(tx::get_tx_is_promoted),
+            operation(
+                "Whether the transaction is promoted to the Publish ledger",
+                vec![tx_id_param()],
+                ok_json(json!({ "type": "boolean" })),
+            ),
         )
-        .route(
+        .to(tx::get_tx_is_promoted),
+        ApiRoute::get(
             "/tx/{tx_id}/local/data_start_offset",
-            web::get().to(tx::get_tx_local_start_offset),
+            operation(
+                "Ledger offset of the transaction's first chunk in this node's storage",
+                vec![tx_id_param()],
+                ok_json(schema_ref("TxOffset")),
+            ),
+        )
+        .to(tx::get_tx_local_start_offset),
+        ApiRoute::get(
+            "/txs/ledger/{ledger_id}",
+            operation(
+                "Data transactions of a ledger with their promotion status",
+                [vec![ledger_id_param()], paging_params()].concat(),
+                ok_json(schema_ref("TxWithPromotionStatusPage")),
+            ),
+        )
+        .to(query::get_txs_by_ledger),
+        ApiRoute::get(
+            "/txs/signer/{address}",
+            operation(
+                "Data transactions signed by an address",
+                [vec![address_param()], paging_params()].concat(),
+                ok_json(schema_ref("TxHeaderPage")),
+            ),
         )
-        .route("/version", web::post().to(post_version::post_version))
+        .to(query::get_txs_by_signer),
+        ApiRoute::get(
+            "/txs/tag",
+            operation(
+                "Data transactions with a tag",
+                [
+                    vec![
+                        required_query_param("name", json!({ "type": "string" })),
+                        required_query_param("value", json!({ "type": "string" })),
+                    ],
+                    paging_params(),
+                ]
+                .concat(),
+                ok_json(schema_ref("TxHeaderPage")),
+            ),
+        )
+        .to(tx::get_txs_by_tag),
+        ApiRoute::post(
+            "/version",
+            operation("Peer handshake", vec![], ok_json(json!({ "type": "object" }))),
+        )
+        .to(post_version::post_version),
+    ]
+}
+
+pub fn routes() -> impl HttpServiceFactory {
+    api_routes()
+        .into_iter()
+        .fold(web::scope("v1"), |scope, route| {
+            scope.service(route.resource)
+        })
 }
 
 pub async fn run_server(app_state: ApiState, listener: TcpListener) -> Server {
     let port = listener.local_addr().expect("listener to work").port();
     info!(?port, "Starting API server");
+    // shared by the workers, so rate limits and cached results apply across all connections
+    let execution_rpc_proxy = web::Data::new(ExecutionRpcProxy::new(
+        app_state.config.node_config.execution_rpc_proxy.clone(),
+        app_state.reth_http_url.clone(),
+    ));
 
     HttpServer::new(move || {
         let awc_client = awc::Client::new();
         App::new()
             .app_data(web::Data::new(app_state.clone()))
             .app_data(web::Data::new(awc_client))
+            .app_data(execution_rpc_proxy.clone())
             .app_data(
                 JsonConfig::default()
                     .limit(1024 * 1024) // Set JSON payload limit to 1MB
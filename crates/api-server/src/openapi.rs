@@ -0,0 +1,888 @@
+//! OpenAPI description of the v1 HTTP API, served at `/v1/openapi.json`.
+//!
+//! The component schemas spell out the JSON shapes produced by the serde impls of the API
+//! types: camelCase field names, base58 hashes, addresses and signatures, base64url bytes and
+//! the u64/u128/U256 values serialized as decimal strings. Objects don't allow additional
+//! properties, so the tests validating serialized types and live responses against these
+//! schemas fail as soon as a type's JSON representation drifts from its description.
+//!
+//! The paths are built from [`crate::api_routes`], the table the server registers its routes
+//! from, so every served route carries its operation.
+use crate::api_routes;
+use actix_web::web::Json;
+use serde_json::{json, Map, Value};
+
+const OPENAPI_VERSION: &str = "3.1.0";
+
+const BASE58: &str = "^[1-9A-HJ-NP-Za-km-z]+$";
+const BASE64_URL: &str = "^[A-Za-z0-9_-]*$";
+const DECIMAL: &str = "^[0-9]+$";
+
+/// Builds the OpenAPI document of the v1 routes
+pub fn openapi() -> Value {
+    json!({
+        "openapi": OPENAPI_VERSION,
+        "info": {
+            "title": "Irys node API",
+            "version": "1",
+        },
+        "servers": [{ "url": "/v1" }],
+        "paths": paths(),
+        "components": { "schemas": schemas() },
+    })
+}
+
+pub async fn get_openapi() -> Json<Value> {
+    Json(openapi())
+}
+
+/// A self contained JSON schema validating instances of the named component schema
+pub fn component_schema(name: &str) -> Value {
+    json!({
+        "$ref": format!("#/components/schemas/{}", name),
+        "components": { "schemas": schemas() },
+    })
+}
+
+pub(crate) fn schema_ref(name: &str) -> Value {
+    json!({ "$ref": format!("#/components/schemas/{}", name) })
+}
+
+fn nullable(schema: Value) -> Value {
+    json!({ "oneOf": [schema, { "type": "null" }] })
+}
+
+pub(crate) fn array(items: Value) -> Value {
+    json!({ "type": "array", "items": items })
+}
+
+pub(crate) fn integer() -> Value {
+    json!({ "type": "integer", "minimum": 0 })
+}
+
+/// An object with the `required` properties always present and `optional` ones that may be
+/// left out, rejecting any other property
+fn object(required: &[(&str, Value)], optional: &[(&str, Value)]) -> Value {
+    let properties = required
+        .iter()
+        .chain(optional)
+        .map(|(name, schema)| ((*name).to_owned(), schema.clone()))
+        .collect::<Map<_, _>>();
+    let required = required.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    })
+}
+
+/// Adds the `type` discriminator of an internally tagged enum variant to an object schema
+fn tagged(tag: &str, mut schema: Value) -> Value {
+    schema["properties"]["type"] = json!({ "const": tag });
+    schema["required"]
+        .as_array_mut()
+        .expect("object schemas list their required properties")
+        .push(json!("type"));
+    schema
+}
+
+fn schemas() -> Value {
+    let schemas: Vec<(&str, Value)> = vec![
+        // formats shared by the API types
+        (
+            "Base58Hash",
+            json!({
+                "type": "string",
+                "pattern": BASE58,
+                "description": "32 byte hash or id, base58 encoded",
+            }),
+        ),
+        (
+            "Base58Address",
+            json!({
+                "type": "string",
+                "pattern": BASE58,
+                "description": "20 byte address, base58 encoded",
+            }),
+        ),
+        (
+            "Base58Signature",
+            json!({
+                "type": "string",
+                "pattern": BASE58,
+                "description": "65 byte secp256k1 signature, base58 encoded",
+            }),
+        ),
+        (
+            "HexAddress",
+            json!({
+                "type": "string",
+                "pattern": "^0x[0-9a-fA-F]{40}$",
+                "description": "20 byte EVM address, 0x prefixed hex",
+            }),
+        ),
+        (
+            "HexHash",
+            json!({
+                "type": "string",
+                "pattern": "^0x[0-9a-fA-F]{64}$",
+                "description": "32 byte EVM hash, 0x prefixed hex",
+            }),
+        ),
+        (
+            "Base64Url",
+            json!({
+                "type": "string",
+                "pattern": BASE64_URL,
+                "description": "bytes, base64url encoded without padding",
+            }),
+        ),
+        (
+            "StringU64",
+            json!({
+                "type": "string",
+                "pattern": DECIMAL,
+                "description": "u64 as a decimal string, as it may exceed the safe integer range \
+                    of JS numbers. Requests may also use a number",
+            }),
+        ),
+        (
+            "StringU128",
+            json!({
+                "type": "string",
+                "pattern": DECIMAL,
+                "description": "u128 as a decimal string",
+            }),
+        ),
+        (
+            "U256",
+            json!({
+                "type": "string",
+                "pattern": DECIMAL,
+                "description": "256 bit unsigned integer as a decimal string",
+            }),
+        ),
+        // transactions
+        (
+            "Tag",
+            object(
+                &[
+                    ("name", json!({ "type": "string" })),
+                    ("value", json!({ "type": "string" })),
+                ],
+                &[],
+            ),
+        ),
+        (
+            "TxIngressProof",
+            object(
+                &[
+                    ("proof", schema_ref("Base58Hash")),
+                    ("signature", schema_ref("Base58Signature")),
+                ],
+                &[],
+            ),
+        ),
+        ("IrysTransactionHeader", tx_header()),
+        (
+            "CommitmentType",
+            json!({
+                "type": "string",
+                "enum": ["Stake", "Pledge", "Unpledge", "Unstake"],
+            }),
+        ),
+        (
+            "CommitmentStatus",
+            json!({
+                "type": "string",
+                "enum": ["Pending", "Active", "Inactive", "Slashed"],
+            }),
+        ),
+        ("CommitmentTransaction", commitment_tx()),
+        (
+            "IrysTransactionResponse",
+            json!({
+                "oneOf": [tagged("commitment", commitment_tx()), tagged("storage", tx_header())],
+            }),
+        ),
+        (
+            "TxOffset",
+            object(&[("dataStartOffset", schema_ref("StringU64"))], &[]),
+        ),
+        (
+            "TxWithPromotionStatus",
+            object(
+                &[
+                    ("tx", schema_ref("IrysTransactionHeader")),
+                    ("isPromoted", json!({ "type": "boolean" })),
+                ],
+                &[],
+            ),
+        ),
+        (
+            "CommitmentWithStatus",
+            object(
+                &[
+                    ("commitment", schema_ref("CommitmentTransaction")),
+                    ("status", nullable(schema_ref("CommitmentStatus"))),
+                ],
+                &[],
+            ),
+        ),
+        ("TxHeaderPage", page(schema_ref("IrysTransactionHeader"))),
+        (
+            "TxWithPromotionStatusPage",
+            page(schema_ref("TxWithPromotionStatus")),
+        ),
+        (
+            "CommitmentWithStatusPage",
+            page(schema_ref("CommitmentWithStatus")),
+        ),
+        // chunks
+        ("UnpackedChunk", unpacked_chunk()),
+        ("PackedChunk", packed_chunk()),
+        (
+            "ChunkFormat",
+            json!({
+                "oneOf": [tagged("unpacked", unpacked_chunk()), tagged("packed", packed_chunk())],
+            }),
+        ),
+        (
+            "UploadStatus",
+            object(
+                &[
+                    ("dataRoot", schema_ref("Base58Hash")),
+                    ("dataSize", nullable(schema_ref("StringU64"))),
+                    ("receivedChunks", array(integer())),
+                ],
+                &[],
+            ),
+        ),
+        (
+            "RejectedChunk",
+            object(
+                &[
+                    ("txOffset", integer()),
+                    ("error", json!({ "type": "string" })),
+                ],
+                &[],
+            ),
+        ),
+        (
+            "BatchChunkResponse",
+            object(
+                &[
+                    ("accepted", array(integer())),
+                    ("rejected", array(schema_ref("RejectedChunk"))),
+                ],
+                &[],
+            ),
+        ),
+        // blocks
+        ("Amount", object(&[("amount", schema_ref("U256"))], &[])),
+        (
+            "PoaData",
+            object(
+                &[
+                    ("recallChunkIndex", integer()),
+                    ("partitionChunkOffset", integer()),
+                    ("partitionHash", schema_ref("Base58Hash")),
+                    ("chunk", nullable(schema_ref("Base64Url"))),
+                    ("ledgerId", nullable(integer())),
+                    ("txPath", nullable(schema_ref("Base64Url"))),
+                    ("dataPath", nullable(schema_ref("Base64Url"))),
+                ],
+                &[],
+            ),
+        ),
+        (
+            "DataTransactionLedger",
+            object(
+                &[
+                    ("ledgerId", integer()),
+                    ("txRoot", schema_ref("Base58Hash")),
+                    ("txIds", array(schema_ref("Base58Hash"))),
+                    ("maxChunkOffset", schema_ref("StringU64")),
+                ],
+                &[
+                    ("expires", integer()),
+                    ("proofs", array(schema_ref("TxIngressProof"))),
+                ],
+            ),
+        ),
+        (
+            "SystemTransactionLedger",
+            object(
+                &[
+                    ("ledgerId", integer()),
+                    ("txIds", array(schema_ref("Base58Hash"))),
+                ],
+                &[],
+            ),
+        ),
+        (
+            "VDFLimiterInfo",
+            object(
+                &[
+                    ("output", schema_ref("Base58Hash")),
+                    ("globalStepNumber", integer()),
+                    ("seed", schema_ref("Base58Hash")),
+                    ("nextSeed", schema_ref("Base58Hash")),
+                    ("prevOutput", schema_ref("Base58Hash")),
+                    ("lastStepCheckpoints", array(schema_ref("Base58Hash"))),
+                    ("steps", array(schema_ref("Base58Hash"))),
+                    ("vdfDifficulty", nullable(schema_ref("StringU64"))),
+                    ("nextVdfDifficulty", nullable(schema_ref("StringU64"))),
+                ],
+                &[],
+            ),
+        ),
+        ("IrysBlockHeader", object(&block_header_properties(), &[])),
+        ("CombinedBlockHeader", {
+            let mut properties = block_header_properties();
+            // the execution payload header as served by reth, not described here
+            properties.push(("execution", json!({ "type": "object" })));
+            object(&properties, &[])
+        }),
+        (
+            "BlockWithTxs",
+            object(
+                &[("block", schema_ref("IrysBlockHeader"))],
+                &[("txs", array(schema_ref("IrysTransactionHeader")))],
+            ),
+        ),
+        (
+            "LedgerIndexItem",
+            object(
+                &[
+                    ("max_chunk_offset", integer()),
+                    ("tx_root", schema_ref("Base58Hash")),
+                ],
+                &[],
+            ),
+        ),
+        (
+            "BlockIndexItem",
+            object(
+                &[
+                    ("block_hash", schema_ref("Base58Hash")),
+                    ("num_ledgers", integer()),
+                    ("ledgers", array(schema_ref("LedgerIndexItem"))),
+                ],
+                &[],
+            ),
+        ),
+        // node
+        (
+            "NodeInfo",
+            object(
+                &[
+                    ("version", json!({ "type": "string" })),
+                    ("peerCount", integer()),
+                    ("chainId", integer()),
+                    ("height", integer()),
+                    ("blockHash", schema_ref("Base58Hash")),
+                    ("blockIndexHeight", integer()),
+                    ("blocks", integer()),
+                    ("isSyncing", json!({ "type": "boolean" })),
+                    ("currentSyncHeight", integer()),
+                ],
+                &[],
+            ),
+        ),
+        (
+            "PublicStorageConfig",
+            object(
+                &[
+                    ("chunkSize", schema_ref("StringU64")),
+                    ("chainId", schema_ref("StringU64")),
+                    ("numChunksInPartition", schema_ref("StringU64")),
+                    ("numChunksInRecallRange", schema_ref("StringU64")),
+                    ("numPartitionsPerSlot", schema_ref("StringU64")),
+                    ("entropyPackingIterations", integer()),
+                ],
+                &[],
+            ),
+        ),
+        (
+            "PriceInfo",
+            object(
+                &[
+                    ("costInIrys", schema_ref("U256")),
+                    ("ledger", integer()),
+                    ("bytes", integer()),
+                ],
+                &[],
+            ),
+        ),
+        (
+            "ApiError",
+            json!({
+                "oneOf": [
+                    object(&[("ErrNoId", object(&[
+                        ("id", json!({ "type": "string" })),
+                        ("err", json!({ "type": "string" })),
+                    ], &[]))], &[]),
+                    object(&[("Internal", object(&[("err", json!({ "type": "string" }))], &[]))], &[]),
+                ],
+            }),
+        ),
+    ];
+    Value::Object(
+        schemas
+            .into_iter()
+            .map(|(name, value)| (name.to_owned(), value))
+            .collect(),
+    )
+}
+
+fn tx_header() -> Value {
+    object(
+        &[
+            ("id", schema_ref("Base58Hash")),
+            ("version", integer()),
+            ("anchor", schema_ref("Base58Hash")),
+            ("signer", schema_ref("Base58Address")),
+            ("dataRoot", schema_ref("Base58Hash")),
+            ("dataSize", schema_ref("StringU64")),
+            ("termFee", schema_ref("StringU64")),
+            ("ledgerId", integer()),
+            ("chainId", schema_ref("StringU64")),
+            ("signature", schema_ref("Base58Signature")),
+            ("bundleFormat", nullable(schema_ref("StringU64"))),
+            ("permFee", nullable(schema_ref("StringU64"))),
+            ("tags", nullable(array(schema_ref("Tag")))),
+            ("ingressProofs", nullable(schema_ref("TxIngressProof"))),
+        ],
+        &[],
+    )
+}
+
+fn commitment_tx() -> Value {
+    object(
+        &[
+            ("id", schema_ref("Base58Hash")),
+            ("anchor", schema_ref("Base58Hash")),
+            ("signer", schema_ref("Base58Address")),
+            ("commitmentType", schema_ref("CommitmentType")),
+            ("version", integer()),
+            ("chainId", schema_ref("StringU64")),
+            ("fee", schema_ref("StringU64")),
+            ("signature", schema_ref("Base58Signature")),
+        ],
+        &[],
+    )
+}
+
+fn unpacked_chunk() -> Value {
+    object(
+        &[
+            ("dataRoot", schema_ref("Base58Hash")),
+            ("dataSize", schema_ref("StringU64")),
+            ("dataPath", schema_ref("Base64Url")),
+            ("bytes", schema_ref("Base64Url")),
+            ("txOffset", integer()),
+        ],
+        &[],
+    )
+}
+
+fn packed_chunk() -> Value {
+    object(
+        &[
+            ("dataRoot", schema_ref("Base58Hash")),
+            ("dataSize", integer()),
+            ("dataPath", schema_ref("Base64Url")),
+            ("bytes", schema_ref("Base64Url")),
+            ("packingAddress", schema_ref("Base58Address")),
+            ("partitionOffset", integer()),
+            ("txOffset", integer()),
+            ("partitionHash", schema_ref("Base58Hash")),
+        ],
+        &[],
+    )
+}
+
+fn block_header_properties() -> Vec<(&'static str, Value)> {
+    vec![
+        ("blockHash", schema_ref("Base58Hash")),
+        ("signature", schema_ref("Base58Signature")),
+        ("height", integer()),
+        ("diff", schema_ref("U256")),
+        ("cumulativeDiff", schema_ref("U256")),
+        ("solutionHash", schema_ref("Base58Hash")),
+        ("lastDiffTimestamp", schema_ref("StringU128")),
+        ("previousSolutionHash", schema_ref("Base58Hash")),
+        ("lastEpochHash", schema_ref("Base58Hash")),
+        ("chunkHash", schema_ref("Base58Hash")),
+        ("previousBlockHash", schema_ref("Base58Hash")),
+        ("previousCumulativeDiff", schema_ref("U256")),
+        ("poa", schema_ref("PoaData")),
+        ("rewardAddress", schema_ref("HexAddress")),
+        ("rewardAmount", schema_ref("U256")),
+        ("minerAddress", schema_ref("HexAddress")),
+        ("timestamp", schema_ref("StringU128")),
+        (
+            "systemLedgers",
+            array(schema_ref("SystemTransactionLedger")),
+        ),
+        ("dataLedgers", array(schema_ref("DataTransactionLedger"))),
+        ("evmBlockHash", schema_ref("HexHash")),
+        ("vdfLimiterInfo", schema_ref("VDFLimiterInfo")),
+        ("oracleIrysPrice", schema_ref("Amount")),
+        ("emaIrysPrice", schema_ref("Amount")),
+    ]
+}
+
+fn page(items: Value) -> Value {
+    object(
+        &[
+            ("items", array(items)),
+            ("next", nullable(schema_ref("Base58Hash"))),
+        ],
+        &[],
+    )
+}
+
+pub(crate) fn path_param(name: &str, schema: Value, description: &str) -> Value {
+    json!({
+        "name": name,
+        "in": "path",
+        "required": true,
+        "schema": schema,
+        "description": description,
+    })
+}
+
+pub(crate) fn query_param(name: &str, schema: Value, description: &str) -> Value {
+    json!({
+        "name": name,
+        "in": "query",
+        "required": false,
+        "schema": schema,
+        "description": description,
+    })
+}
+
+pub(crate) fn json_content(schema: Value) -> Value {
+    json!({ "application/json": { "schema": schema } })
+}
+
+/// JSON content that may also be sent in the binary encoding of [`irys_types::BinaryEncoding`],
+/// selected with the `Content-Type` of requests and the `Accept` header of responses
+pub(crate) fn json_or_binary_content(schema: Value) -> Value {
+    json!({
+        "application/json": { "schema": schema },
+        "application/octet-stream": { "schema": { "type": "string", "format": "binary" } },
+    })
+}
+
+pub(crate) fn ok_json(schema: Value) -> Value {
+    json!({ "200": { "description": "OK", "content": json_content(schema) } })
+}
+
+pub(crate) fn ok_json_or_binary(schema: Value) -> Value {
+    json!({ "200": { "description": "OK", "content": json_or_binary_content(schema) } })
+}
+
+/// Responses of the routes submitting transactions to the mempool
+pub(crate) fn ingress_responses() -> Value {
+    json!({
+        "200": { "description": "accepted, or already known" },
+        "400": { "description": "invalid signature, anchor or tags" },
+        "402": { "description": "the signer can't pay the fees" },
+    })
+}
+
+pub(crate) fn operation(summary: &str, parameters: Vec<Value>, responses: Value) -> Value {
+    json!({
+        "summary": summary,
+        "parameters": parameters,
+        "responses": responses,
+    })
+}
+
+pub(crate) fn with_body(mut operation: Value, content: Value) -> Value {
+    operation["requestBody"] = json!({ "required": true, "content": content });
+    operation
+}
+
+pub(crate) fn required_query_param(name: &str, schema: Value) -> Value {
+    json!({ "name": name, "in": "query", "required": true, "schema": schema })
+}
+
+pub(crate) fn tx_id_param() -> Value {
+    path_param("tx_id", schema_ref("Base58Hash"), "transaction id")
+}
+
+pub(crate) fn address_param() -> Value {
+    path_param("address", json!({ "type": "string" }), "base58 or 0x hex")
+}
+
+pub(crate) fn ledger_id_param() -> Value {
+    path_param("ledger_id", integer(), "0 for Publish, 1 for Submit")
+}
+
+pub(crate) fn paging_params() -> Vec<Value> {
+    vec![
+        query_param("limit", integer(), "maximum number of results to return"),
+        query_param(
+            "after",
+            schema_ref("Base58Hash"),
+            "`next` cursor of the previous page",
+        ),
+    ]
+}
+
+pub(crate) fn not_found_response() -> Value {
+    json!({ "description": "unknown id", "content": json_content(schema_ref("ApiError")) })
+}
+
+pub(crate) fn ok_binary() -> Value {
+    json!({ "200": { "description": "OK", "content": { "application/octet-stream": {} } } })
+}
+
+/// Schema of this document, served by `/openapi.json`
+pub(crate) fn document_schema() -> Value {
+    json!({
+        "type": "object",
+        "required": ["openapi", "info", "servers", "paths", "components"],
+        "properties": {
+            "openapi": { "const": OPENAPI_VERSION },
+            "info": { "type": "object" },
+            "servers": array(json!({ "type": "object" })),
+            "paths": { "type": "object" },
+            "components": { "type": "object" },
+        },
+    })
+}
+
+/// The operations of the routes served by [`crate::routes`], keyed by path and method
+fn paths() -> Value {
+    let mut paths = Map::new();
+    for route in api_routes() {
+        let operations = paths.entry(route.path).or_insert_with(|| json!({}));
+        operations[route.method.as_str().to_lowercase()] = route.operation;
+    }
+    Value::Object(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ApiError;
+    use crate::routes::{
+        index::NodeInfo,
+        network_config::PublicStorageConfig,
+        post_chunk::{BatchChunkResponse, RejectedChunk, UploadStatus},
+        price::PriceInfo,
+        tx::TxOffset,
+    };
+    use irys_types::{
+        ChunkFormat, CombinedBlockHeader, CommitmentTransaction, IngressProofsList,
+        IrysBlockHeader, IrysTransactionHeader, IrysTransactionResponse, PackedChunk, Tag,
+        TxChunkOffset, TxIngressProof, UnpackedChunk, H256, U256,
+    };
+    use serde::Serialize;
+
+    fn assert_valid(name: &str, value: &impl Serialize) {
+        let instance = serde_json::to_value(value).unwrap();
+        let schema = component_schema(name);
+        let validator = jsonschema::validator_for(&schema).unwrap();
+        let errors = validator
+            .iter_errors(&instance)
+            .map(|e| format!("{} at {}", e, e.instance_path))
+            .collect::<Vec<_>>();
+        assert!(
+            errors.is_empty(),
+            "{} doesn't match its schema: {:#?}",
+            name,
+            errors
+        );
+    }
+
+    #[test]
+    fn transactions_match_schema() {
+        let mut tx = IrysTransactionHeader {
+            id: H256::random(),
+            data_size: 1234,
+            ..Default::default()
+        };
+        assert_valid("IrysTransactionHeader", &tx);
+
+        tx.perm_fee = Some(u64::MAX);
+        tx.bundle_format = Some(1);
+        tx.tags = Some(vec![Tag::new("Content-Type", "text/plain")]);
+        tx.ingress_proofs = Some(TxIngressProof::default());
+        assert_valid("IrysTransactionHeader", &tx);
+        assert_valid(
+            "IrysTransactionResponse",
+            &IrysTransactionResponse::from(tx),
+        );
+
+        let commitment = CommitmentTransaction::default();
+        assert_valid("CommitmentTransaction", &commitment);
+        assert_valid(
+            "IrysTransactionResponse",
+            &IrysTransactionResponse::from(commitment),
+        );
+        assert_valid("TxOffset", &TxOffset::default());
+    }
+
+    #[test]
+    fn chunks_match_schema() {
+        assert_valid("UnpackedChunk", &UnpackedChunk::default());
+        assert_valid("PackedChunk", &PackedChunk::default());
+        assert_valid(
+            "ChunkFormat",
+            &ChunkFormat::Unpacked(UnpackedChunk::default()),
+        );
+        assert_valid("ChunkFormat", &ChunkFormat::Packed(PackedChunk::default()));
+        assert_valid(
+            "UploadStatus",
+            &UploadStatus {
+                data_root: H256::random(),
+                data_size: Some(100),
+                received_chunks: vec![TxChunkOffset(0), TxChunkOffset(2)],
+            },
+        );
+        assert_valid(
+            "BatchChunkResponse",
+            &BatchChunkResponse {
+                accepted: vec![TxChunkOffset(0)],
+                rejected: vec![RejectedChunk {
+                    tx_offset: TxChunkOffset(1),
+                    error: "Invalid proof".to_owned(),
+                }],
+            },
+        );
+    }
+
+    #[test]
+    fn blocks_match_schema() {
+        let mut block = IrysBlockHeader::new_mock_header();
+        assert_valid("IrysBlockHeader", &block);
+
+        block.data_ledgers[0].proofs = Some(IngressProofsList(vec![TxIngressProof::default()]));
+        block.vdf_limiter_info.vdf_difficulty = Some(1000);
+        assert_valid("IrysBlockHeader", &block);
+        assert_valid(
+            "CombinedBlockHeader",
+            &CombinedBlockHeader {
+                irys: block,
+                execution: Default::default(),
+            },
+        );
+    }
+
+    #[test]
+    fn node_types_match_schema() {
+        assert_valid("NodeInfo", &NodeInfo::default());
+        assert_valid(
+            "PublicStorageConfig",
+            &PublicStorageConfig {
+                chunk_size: 256 * 1024,
+                chain_id: 1270,
+                num_chunks_in_partition: 10,
+                num_chunks_in_recall_range: 2,
+                num_partitions_per_slot: 1,
+                entropy_packing_iterations: 1000,
+            },
+        );
+        assert_valid(
+            "PriceInfo",
+            &PriceInfo {
+                cost_in_irys: U256::from(u128::MAX),
+                ledger: 0,
+                bytes: 256 * 1024,
+            },
+        );
+    }
+
+    #[test]
+    fn errors_match_schema() {
+        assert_valid(
+            "ApiError",
+            &ApiError::ErrNoId {
+                id: "abc".to_owned(),
+                err: "Not found".to_owned(),
+            },
+        );
+        assert_valid(
+            "ApiError",
+            &ApiError::Internal {
+                err: "db error".to_owned(),
+            },
+        );
+    }
+
+    #[test]
+    fn schema_rejects_undocumented_fields() {
+        let mut instance = serde_json::to_value(UnpackedChunk::default()).unwrap();
+        instance["extra"] = json!(1);
+        let schema = component_schema("UnpackedChunk");
+        assert!(!jsonschema::validator_for(&schema)
+            .unwrap()
+            .is_valid(&instance));
+    }
+
+    #[test]
+    fn every_route_is_documented() {
+        let routes = api_routes();
+        assert!(routes.len() > 1);
+        for (i, route) in routes.iter().enumerate() {
+            assert!(
+                route.operation["summary"].is_string() && route.operation["responses"].is_object(),
+                "{} {} isn't documented",
+                route.method,
+                route.path
+            );
+            // a second resource at the same path would never be reached
+            assert!(
+                routes[..i].iter().all(|other| other.path != route.path),
+                "{} is routed twice",
+                route.path
+            );
+        }
+
+        let document = openapi();
+        let paths = document["paths"].as_object().unwrap();
+        assert_eq!(paths.len(), routes.len());
+        assert!(paths["/tx/{tx_id}"]["get"]["responses"]["404"].is_object());
+    }
+
+    #[test]
+    fn document_matches_its_own_description() {
+        let document = openapi();
+        let schema = &document["paths"]["/openapi.json"]["get"]["responses"]["200"]["content"]
+            ["application/json"]["schema"];
+        assert!(jsonschema::validator_for(schema)
+            .unwrap()
+            .is_valid(&document));
+    }
+
+    #[test]
+    fn document_references_resolve() {
+        let document = openapi();
+        let schemas = document["components"]["schemas"].as_object().unwrap();
+        let mut pending = vec![&document];
+        while let Some(value) = pending.pop() {
+            match value {
+                Value::Object(map) => {
+                    if let Some(Value::String(reference)) = map.get("$ref") {
+                        let name = reference.trim_start_matches("#/components/schemas/");
+                        assert!(
+                            schemas.contains_key(name),
+                            "dangling reference {}",
+                            reference
+                        );
+                    }
+                    pending.extend(map.values());
+                }
+                Value::Array(values) => pending.extend(values),
+                _ => {}
+            }
+        }
+    }
+}
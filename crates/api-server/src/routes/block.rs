@@ -1,20 +1,26 @@
+use super::query::canonical_block_hash;
 use crate::error::ApiError;
 use crate::ApiState;
 use actix_web::{
-    web::{self, Json},
-    Result,
+    http::header::{self, ContentType},
+    web, HttpRequest, HttpResponse, Result,
 };
 use base58::FromBase58 as _;
 use irys_database::{database, db::IrysDatabaseExt as _};
-use irys_types::{CombinedBlockHeader, ExecutionHeader, H256};
+use irys_types::{
+    accepts_binary, BinaryEncoding as _, CombinedBlockHeader, ExecutionHeader, BINARY_CONTENT_TYPE,
+    H256,
+};
 use reth::{providers::BlockReader, revm::primitives::alloy_primitives::TxHash};
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
+/// Responds with the block in the binary encoding if the `Accept` header lists it, as JSON otherwise
 pub async fn get_block(
     state: web::Data<ApiState>,
     path: web::Path<String>,
-) -> Result<Json<CombinedBlockHeader>, ApiError> {
+    req: HttpRequest,
+) -> Result<HttpResponse, ApiError> {
     let tag_param = BlockParam::from_str(&path).map_err(|_| ApiError::ErrNoId {
         id: path.to_string(),
         err: String::from("Invalid block tag"),
@@ -23,30 +29,11 @@ pub async fn get_block(
     // all roads lead to block hash
     let block_hash: H256 = match tag_param {
         BlockParam::Latest => state.block_tree.read().tip.clone(),
-        BlockParam::BlockHeight(height) => 'outer: {
-            let in_block_tree = state
-                .block_tree
-                .read()
-                .get_canonical_chain()
-                .0
-                .iter()
-                .find_map(|(hash, hght, _, _)| match *hght == height {
-                    true => Some(hash),
-                    false => None,
-                })
-                .cloned();
-            if let Some(hash) = in_block_tree {
-                break 'outer hash.clone();
-            }
-            state
-                .block_index
-                .read()
-                .get_item(height)
-                .ok_or(ApiError::ErrNoId {
-                    id: path.to_string(),
-                    err: String::from("Invalid block height"),
-                })
-                .map(|b| (*b).block_hash)?
+        BlockParam::BlockHeight(height) => {
+            canonical_block_hash(&state, height).ok_or(ApiError::ErrNoId {
+                id: path.to_string(),
+                err: String::from("Invalid block height"),
+            })?
         }
         BlockParam::Finalized | BlockParam::Pending => {
             return Err(ApiError::Internal {
@@ -55,13 +42,27 @@ pub async fn get_block(
         }
         BlockParam::Hash(hash) => hash,
     };
-    get_block_by_hash(&state, block_hash)
+    let block = get_block_by_hash(&state, block_hash)?;
+
+    let accept = req
+        .headers()
+        .get(header::ACCEPT)
+        .and_then(|value| value.to_str().ok());
+    if accepts_binary(accept) {
+        Ok(HttpResponse::Ok()
+            .content_type(BINARY_CONTENT_TYPE)
+            .body(block.to_binary()))
+    } else {
+        Ok(HttpResponse::Ok()
+            .content_type(ContentType::json())
+            .json(block))
+    }
 }
 
 fn get_block_by_hash(
     state: &web::Data<ApiState>,
     block_hash: H256,
-) -> Result<Json<CombinedBlockHeader>, ApiError> {
+) -> Result<CombinedBlockHeader, ApiError> {
     let irys_header = match state
         .db
         .view_eyre(|tx| database::block_header_by_hash(tx, &block_hash, true))
@@ -106,7 +107,7 @@ fn get_block_by_hash(
         },
     };
 
-    Ok(web::Json(cbh))
+    Ok(cbh)
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -0,0 +1,32 @@
+use crate::ApiState;
+use actix_web::{http::header::ContentType, web, HttpResponse};
+use irys_p2p::PooledBlockInfo;
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockPoolReport {
+    pub block_count: usize,
+    pub total_size_bytes: usize,
+    pub blocks: Vec<PooledBlockInfo>,
+}
+
+/// Lists the blocks waiting in the block pool for their parent to be processed,
+/// to debug blocks that don't connect to the chain
+pub async fn block_pool_route(state: web::Data<ApiState>) -> HttpResponse {
+    let mut blocks = state
+        .block_pool
+        .read()
+        .values()
+        .cloned()
+        .collect::<Vec<_>>();
+    blocks.sort_by_key(|block| (block.height, block.received_at));
+
+    HttpResponse::Ok()
+        .content_type(ContentType::json())
+        .json(BlockPoolReport {
+            block_count: blocks.len(),
+            total_size_bytes: blocks.iter().map(|block| block.size_bytes).sum(),
+            blocks,
+        })
+}
@@ -0,0 +1,75 @@
+use crate::error::ApiError;
+use crate::ApiState;
+use actix_web::{
+    http::header::ContentType,
+    web::{self, Json},
+    HttpResponse,
+};
+use irys_database::{database, db::IrysDatabaseExt as _};
+use irys_types::{
+    bundle::{DataItem, DataItemInfo},
+    DataLedger, H256,
+};
+use tracing::info;
+
+use super::tx::get_storage_transaction;
+
+/// Returns the metadata (signer, tags, ...) of a data item from a promoted bundle
+pub async fn get_bundle_item(
+    state: web::Data<ApiState>,
+    path: web::Path<H256>,
+) -> Result<Json<DataItemInfo>, ApiError> {
+    let item_id = path.into_inner();
+    info!("Get bundle item by id: {}", item_id);
+    let (item, bundle_tx_id) = get_data_item(&state, item_id)?;
+    Ok(Json(DataItemInfo::new(&item, bundle_tx_id)))
+}
+
+/// Returns the raw data of a data item from a promoted bundle
+pub async fn get_bundle_item_data(
+    state: web::Data<ApiState>,
+    path: web::Path<H256>,
+) -> Result<HttpResponse, ApiError> {
+    let item_id = path.into_inner();
+    let (item, _) = get_data_item(&state, item_id)?;
+    Ok(HttpResponse::Ok()
+        .content_type(ContentType::octet_stream())
+        .body(item.data.0))
+}
+
+/// Looks up the location of a data item in its bundle and reads it from the storage modules,
+/// returning the decoded item and the id of the bundle transaction
+fn get_data_item(state: &web::Data<ApiState>, item_id: H256) -> Result<(DataItem, H256), ApiError> {
+    let location = state
+        .db
+        .view_eyre(|tx| database::bundle_item_by_id(tx, &item_id))
+        .map_err(|_| ApiError::Internal {
+            err: String::from("db error while looking up bundle item"),
+        })?
+        .ok_or(ApiError::ErrNoId {
+            id: item_id.to_string(),
+            err: String::from("bundle item not found"),
+        })?;
+
+    let bundle_tx = get_storage_transaction(state, location.bundle_tx_id)?;
+    let bytes = state
+        .chunk_provider
+        .get_tx_data_range(
+            DataLedger::Publish,
+            bundle_tx.data_root,
+            bundle_tx.data_size,
+            location.offset..location.offset + location.size,
+        )
+        .map_err(|e| ApiError::Internal {
+            err: format!("error reading bundle data: {}", e),
+        })?
+        .ok_or(ApiError::ErrNoId {
+            id: item_id.to_string(),
+            err: String::from("bundle item data is not stored on this node"),
+        })?;
+
+    let item = DataItem::decode(&bytes).map_err(|e| ApiError::Internal {
+        err: format!("error decoding bundle item: {}", e),
+    })?;
+    Ok((item, location.bundle_tx_id))
+}
@@ -44,7 +44,7 @@ pub async fn post_commitment_tx(
                 Ok(HttpResponse::build(StatusCode::INTERNAL_SERVER_ERROR)
                     .body(format!("Failed to deliver transaction: {:?}", err)))
             }
-            TxIngressError::InvalidAnchor => {
+            TxIngressError::InvalidAnchor | TxIngressError::InvalidTags => {
                 Ok(HttpResponse::build(StatusCode::BAD_REQUEST).body(format!("{:?}", err)))
             }
             TxIngressError::DatabaseError => {
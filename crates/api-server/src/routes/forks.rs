@@ -0,0 +1,12 @@
+use crate::ApiState;
+use actix_web::{http::header::ContentType, web, HttpResponse};
+
+/// Lists the recent forks, reorgs and forks rejected for exceeding the reorg limits,
+/// oldest first
+pub async fn get_fork_history(state: web::Data<ApiState>) -> HttpResponse {
+    let fork_history = state.block_tree.read().get_fork_history();
+
+    HttpResponse::Ok()
+        .content_type(ContentType::json())
+        .json(fork_history)
+}
@@ -1,11 +1,14 @@
 use crate::ApiState;
 use actix_web::{
-    http::header::ContentType,
+    http::header::{self, ContentType},
     web::{self},
-    HttpResponse,
+    HttpRequest, HttpResponse,
 };
 
-use irys_types::{ChunkFormat, DataLedger, H256};
+use irys_types::{
+    accepts_binary, BinaryEncoding as _, ChunkFormat, DataLedger, TxChunkOffset,
+    BINARY_CONTENT_TYPE, H256,
+};
 use serde::Deserialize;
 
 #[derive(Deserialize)]
@@ -14,9 +17,27 @@ pub struct LedgerChunkApiPath {
     ledger_offset: u64,
 }
 
+/// Responds with the chunk in the binary encoding if the `Accept` header lists it, as JSON otherwise
+fn chunk_response(req: &HttpRequest, chunk: &ChunkFormat) -> HttpResponse {
+    let accept = req
+        .headers()
+        .get(header::ACCEPT)
+        .and_then(|value| value.to_str().ok());
+    if accepts_binary(accept) {
+        HttpResponse::Ok()
+            .content_type(BINARY_CONTENT_TYPE)
+            .body(chunk.to_binary())
+    } else {
+        HttpResponse::Ok()
+            .content_type(ContentType::json())
+            .json(chunk)
+    }
+}
+
 pub async fn get_chunk_by_ledger_offset(
     state: web::Data<ApiState>,
     path: web::Path<LedgerChunkApiPath>,
+    req: HttpRequest,
 ) -> actix_web::Result<HttpResponse> {
     let ledger = match DataLedger::try_from(path.ledger_id) {
         Ok(l) => l,
@@ -27,9 +48,7 @@ pub async fn get_chunk_by_ledger_offset(
         .chunk_provider
         .get_chunk_by_ledger_offset(ledger, path.ledger_offset.into())
     {
-        Ok(Some(chunk)) => Ok(HttpResponse::Ok()
-            .content_type(ContentType::json())
-            .json(ChunkFormat::Packed(chunk))),
+        Ok(Some(chunk)) => Ok(chunk_response(&req, &ChunkFormat::Packed(chunk))),
         Ok(None) => Ok(HttpResponse::NotFound().body("Chunk not found")),
         Err(e) => {
             Ok(HttpResponse::InternalServerError().body(format!("Error retrieving chunk: {}", e)))
@@ -44,22 +63,40 @@ pub struct DataRootChunkApiPath {
     offset: u32,
 }
 
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct DataRootChunkQuery {
+    /// Skips asking peers for the chunk, set by peers fetching from each other
+    local: bool,
+}
+
+/// Looks a chunk up in the storage modules and the chunk cache, then asks peers for it if
+/// the data root belongs to a known transaction
 pub async fn get_chunk_by_data_root_offset(
     state: web::Data<ApiState>,
     path: web::Path<DataRootChunkApiPath>,
+    query: web::Query<DataRootChunkQuery>,
+    req: HttpRequest,
 ) -> actix_web::Result<HttpResponse> {
     let ledger = match DataLedger::try_from(path.ledger_id) {
         Ok(l) => l,
         Err(e) => return Ok(HttpResponse::BadRequest().body(format!("Invalid ledger id: {}", e))),
     };
+    let offset = TxChunkOffset::from(path.offset);
 
-    match state
-        .chunk_provider
-        .get_chunk_by_data_root(ledger, path.data_root, path.offset.into())
-    {
-        Ok(Some(chunk)) => Ok(HttpResponse::Ok()
-            .content_type(ContentType::json())
-            .json(chunk)),
+    let chunk = if query.local {
+        state
+            .chunk_provider
+            .get_chunk_by_data_root(ledger, path.data_root, offset)
+    } else {
+        state
+            .chunk_provider
+            .get_chunk_or_fetch(ledger, path.data_root, None, offset)
+            .await
+    };
+
+    match chunk {
+        Ok(Some(chunk)) => Ok(chunk_response(&req, &chunk)),
         Ok(None) => Ok(HttpResponse::NotFound().body("Chunk not found")),
         Err(e) => {
             Ok(HttpResponse::InternalServerError().body(format!("Error retrieving chunk: {}", e)))
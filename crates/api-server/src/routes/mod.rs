@@ -1,6 +1,9 @@
 pub mod block;
 pub mod block_index;
+pub mod block_pool;
+pub mod bundle;
 pub mod commitment;
+pub mod forks;
 pub mod get_chunk;
 pub mod index;
 pub mod network_config;
@@ -9,4 +12,6 @@ pub mod post_chunk;
 pub mod post_version;
 pub mod price;
 pub mod proxy;
+pub mod query;
+pub mod storage_modules;
 pub mod tx;
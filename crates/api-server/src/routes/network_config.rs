@@ -10,7 +10,7 @@ pub struct PublicStorageConfig {
     /// Size of each chunk in bytes
     #[serde(with = "string_u64")]
     pub chunk_size: u64,
-    /// Size of each chunk in bytes
+    /// EVM chain ID, signed into transactions to prevent cross-chain replays
     #[serde(with = "string_u64")]
     pub chain_id: u64,
     /// Number of chunks in a partition
@@ -33,7 +33,7 @@ pub async fn get_network_config(state: web::Data<ApiState>) -> HttpResponse {
         .content_type(ContentType::json())
         .json(PublicStorageConfig {
             chunk_size: state.config.consensus.chunk_size,
-            chain_id: state.config.consensus.chunk_size,
+            chain_id: state.config.consensus.chain_id,
             num_chunks_in_partition: state.config.consensus.num_chunks_in_partition,
             num_chunks_in_recall_range: state.config.consensus.num_chunks_in_recall_range,
             num_partitions_per_slot: state.config.consensus.num_partitions_per_slot,
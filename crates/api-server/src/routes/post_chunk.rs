@@ -1,24 +1,43 @@
 use actix_web::{
-    http::header::ContentType,
+    http::header::{self, ContentType},
     web::{self, Json},
-    HttpResponse,
+    HttpRequest, HttpResponse,
 };
 use awc::http::StatusCode;
-use irys_actors::mempool_service::{ChunkIngressError, ChunkIngressMessage};
-use irys_types::UnpackedChunk;
-use tracing::{info, warn};
+use irys_actors::mempool_service::{
+    ChunkIngressError, ChunkIngressMessage, GetReceivedChunksMessage,
+};
+use irys_types::{
+    decode_body, serialization::optional_string_u64, DataRoot, TxChunkOffset, UnpackedChunk, H256,
+};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+use crate::{error::ApiError, ApiState};
 
-use crate::ApiState;
+/// Payload limit of [`post_chunk`], enough for a base64 encoded chunk and its proof
+pub const CHUNK_PAYLOAD_LIMIT: usize = 1024 * 1024;
 
 /// Handles the HTTP POST request for adding a chunk to the mempool.
-/// This function takes in a JSON payload of a `Chunk` type, encapsulates it
-/// into a `ChunkIngressMessage` for further processing by the mempool actor,
+/// This function takes in a JSON or binary (`application/octet-stream`) payload of a `Chunk`
+/// type, encapsulates it into a `ChunkIngressMessage` for further processing by the mempool actor,
 /// and manages error handling based on the results of message delivery and validation.
 pub async fn post_chunk(
     state: web::Data<ApiState>,
-    body: Json<UnpackedChunk>,
+    req: HttpRequest,
+    body: web::Bytes,
 ) -> actix_web::Result<HttpResponse> {
-    let chunk = body.into_inner();
+    let content_type = req
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok());
+    let chunk: UnpackedChunk = match decode_body(content_type, &body) {
+        Ok(chunk) => chunk,
+        Err(err) => {
+            debug!("Chunk decode error: {:?}", err);
+            return Ok(HttpResponse::BadRequest().body(format!("Invalid chunk: {}", err)));
+        }
+    };
     let data_root = chunk.data_root;
     let number = chunk.tx_offset;
     info!(?data_root, ?number, "Received chunk");
@@ -37,32 +56,7 @@ pub async fn post_chunk(
     let inner_result = msg_result.unwrap();
     if let Err(err) = inner_result {
         warn!(?data_root, ?number, "Error processing chunk: {:?}", &err);
-        return match err {
-            ChunkIngressError::InvalidProof => Ok(HttpResponse::build(StatusCode::BAD_REQUEST)
-                .body(format!("Invalid proof: {:?}", err))),
-            ChunkIngressError::InvalidDataHash => Ok(HttpResponse::build(StatusCode::BAD_REQUEST)
-                .body(format!("Invalid data_hash: {:?}", err))),
-            ChunkIngressError::InvalidChunkSize => Ok(HttpResponse::build(StatusCode::BAD_REQUEST)
-                .body(format!("Invalid chunk size: {:?}", err))),
-            ChunkIngressError::InvalidDataSize => Ok(HttpResponse::build(StatusCode::BAD_REQUEST)
-                .body(format!("Invalid data_size field : {:?}", err))),
-            ChunkIngressError::UnknownTransaction => {
-                Ok(HttpResponse::build(StatusCode::BAD_REQUEST)
-                    .body(format!("Unknown transaction: {:?}", err)))
-            }
-            ChunkIngressError::DatabaseError => {
-                Ok(HttpResponse::build(StatusCode::INTERNAL_SERVER_ERROR)
-                    .body(format!("Failed to store chunk: {:?}", err)))
-            }
-            ChunkIngressError::Other(err) => {
-                Ok(HttpResponse::build(StatusCode::INTERNAL_SERVER_ERROR)
-                    .body(format!("Internal error: {:?}", err)))
-            }
-            ChunkIngressError::ServiceUninitialized => {
-                Ok(HttpResponse::build(StatusCode::INTERNAL_SERVER_ERROR)
-                    .body(format!("Internal service error: {:?}", err)))
-            }
-        };
+        return Ok(chunk_ingress_error_response(err));
     }
 
     // If everything succeeded, return an HTTP 200 OK response
@@ -70,3 +64,124 @@ pub async fn post_chunk(
         .content_type(ContentType::json())
         .finish())
 }
+
+/// Maps a [`ChunkIngressError`] to the response returned to the uploader
+fn chunk_ingress_error_response(err: ChunkIngressError) -> HttpResponse {
+    let (status, message) = chunk_ingress_error_status(&err);
+    HttpResponse::build(status).body(format!("{}: {:?}", message, err))
+}
+
+fn chunk_ingress_error_status(err: &ChunkIngressError) -> (StatusCode, &'static str) {
+    match err {
+        ChunkIngressError::InvalidProof => (StatusCode::BAD_REQUEST, "Invalid proof"),
+        ChunkIngressError::InvalidDataHash => (StatusCode::BAD_REQUEST, "Invalid data_hash"),
+        ChunkIngressError::InvalidChunkSize => (StatusCode::BAD_REQUEST, "Invalid chunk size"),
+        ChunkIngressError::InvalidDataSize => (StatusCode::BAD_REQUEST, "Invalid data_size field "),
+        ChunkIngressError::UnknownTransaction => (StatusCode::BAD_REQUEST, "Unknown transaction"),
+        ChunkIngressError::DatabaseError => {
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to store chunk")
+        }
+        ChunkIngressError::Other(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Internal error"),
+        ChunkIngressError::ServiceUninitialized => {
+            (StatusCode::INTERNAL_SERVER_ERROR, "Internal service error")
+        }
+    }
+}
+
+/// Maximum number of chunks accepted by a single [`post_chunks`] request
+pub const MAX_CHUNKS_PER_BATCH: usize = 32;
+
+/// JSON payload limit of [`post_chunks`], enough for [`MAX_CHUNKS_PER_BATCH`] base64 encoded chunks
+pub const BATCH_PAYLOAD_LIMIT: usize = 16 * 1024 * 1024;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RejectedChunk {
+    pub tx_offset: TxChunkOffset,
+    pub error: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchChunkResponse {
+    /// Offsets of the chunks accepted by the mempool
+    pub accepted: Vec<TxChunkOffset>,
+    /// Chunks the mempool rejected, with the reason
+    pub rejected: Vec<RejectedChunk>,
+}
+
+/// Handles the HTTP POST request for adding many chunks to the mempool at once.
+/// Every chunk is ingested on its own, so the response lists which chunks were
+/// accepted and why the others were rejected rather than failing the whole batch.
+pub async fn post_chunks(
+    state: web::Data<ApiState>,
+    body: Json<Vec<UnpackedChunk>>,
+) -> actix_web::Result<HttpResponse> {
+    let chunks = body.into_inner();
+    if chunks.len() > MAX_CHUNKS_PER_BATCH {
+        return Ok(HttpResponse::build(StatusCode::BAD_REQUEST).body(format!(
+            "Too many chunks: {} exceeds the limit of {}",
+            chunks.len(),
+            MAX_CHUNKS_PER_BATCH
+        )));
+    }
+    info!(count = chunks.len(), "Received chunk batch");
+
+    let mut response = BatchChunkResponse::default();
+    for chunk in chunks {
+        let data_root = chunk.data_root;
+        let tx_offset = chunk.tx_offset;
+        match state.mempool.send(ChunkIngressMessage(chunk)).await {
+            Ok(Ok(())) => response.accepted.push(tx_offset),
+            Ok(Err(err)) => {
+                warn!(?data_root, ?tx_offset, "Error processing chunk: {:?}", &err);
+                response.rejected.push(RejectedChunk {
+                    tx_offset,
+                    error: format!("{}: {:?}", chunk_ingress_error_status(&err).1, err),
+                });
+            }
+            Err(err) => {
+                return Ok(HttpResponse::build(StatusCode::INTERNAL_SERVER_ERROR)
+                    .body(format!("Failed to deliver chunk: {:?}", err)));
+            }
+        }
+    }
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadStatus {
+    pub data_root: DataRoot,
+    /// Size of the data, once the node has received a tx with this data root
+    #[serde(default, with = "optional_string_u64")]
+    pub data_size: Option<u64>,
+    /// Offsets of the chunks the node has received, in ascending order
+    pub received_chunks: Vec<TxChunkOffset>,
+}
+
+/// Returns the offsets of the chunks of a data root the node already holds, letting
+/// uploaders resume an interrupted upload by sending only the missing chunks
+pub async fn get_upload_status(
+    state: web::Data<ApiState>,
+    path: web::Path<H256>,
+) -> Result<Json<UploadStatus>, ApiError> {
+    let data_root = path.into_inner();
+    let received = state
+        .mempool
+        .send(GetReceivedChunksMessage(data_root))
+        .await
+        .map_err(|err| ApiError::Internal {
+            err: format!("Failed to deliver message: {:?}", err),
+        })?
+        .map_err(|err| ApiError::Internal {
+            err: format!("Failed to read received chunks: {:?}", err),
+        })?;
+
+    Ok(Json(UploadStatus {
+        data_root,
+        data_size: received.data_size,
+        received_chunks: received.offsets,
+    }))
+}
@@ -1,103 +1,641 @@
 use actix_web::{
-    web::{self, Data, Payload},
+    http::{Method, StatusCode},
+    web::{self, BytesMut, Data, Payload},
     HttpRequest, HttpResponse,
 };
 use awc::Client;
+use futures::StreamExt as _;
+use irys_types::ExecutionRpcProxyConfig;
+use lru::LruCache;
+use serde_json::{json, Map, Value};
+use std::{
+    collections::HashMap,
+    net::{IpAddr, Ipv6Addr},
+    num::NonZeroUsize,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Maximum size of the responses read from reth, in bytes
+const RETH_RESPONSE_LIMIT: usize = 64 * 1024 * 1024;
+/// Number of rate limited clients, or of cached results, that are tracked at most. The least
+/// recently seen clients are dropped first, cached results stop being added until some expire
+const MAX_TRACKED_ENTRIES: usize = 10_000;
 
-use crate::ApiState;
+// JSON-RPC error codes
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INTERNAL_ERROR: i64 = -32603;
+/// EIP-1474 "limit exceeded"
+const LIMIT_EXCEEDED: i64 = -32005;
 
 #[derive(Debug)]
 pub enum ProxyError {
     RequestError(awc::error::SendRequestError),
-    #[allow(unused)]
+    ResponseError(String),
     ParseError(String),
+    InvalidRequest(String),
     MethodNotAllowed,
+    PayloadTooLarge(usize),
+    BatchTooLarge(usize),
+    RateLimited,
 }
 
 impl std::fmt::Display for ProxyError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             ProxyError::RequestError(e) => write!(f, "Request error: {}", e),
+            ProxyError::ResponseError(e) => write!(f, "Response error: {}", e),
             ProxyError::ParseError(e) => write!(f, "Parse error: {}", e),
+            ProxyError::InvalidRequest(e) => write!(f, "Invalid request: {}", e),
             ProxyError::MethodNotAllowed => write!(f, "Method not allowed"),
+            ProxyError::PayloadTooLarge(limit) => {
+                write!(f, "Request body is larger than {} bytes", limit)
+            }
+            ProxyError::BatchTooLarge(limit) => {
+                write!(f, "Batch holds more than {} calls", limit)
+            }
+            ProxyError::RateLimited => write!(f, "Rate limit exceeded"),
         }
     }
 }
 
 impl actix_web::ResponseError for ProxyError {
     fn error_response(&self) -> HttpResponse {
+        // errors of the whole request are answered with a JSON-RPC error without an id
+        let error = |code| error_response(Value::Null, code, &self.to_string());
         match self {
-            ProxyError::RequestError(_) => HttpResponse::BadGateway().finish(),
-            ProxyError::ParseError(_) => HttpResponse::BadRequest().finish(),
+            ProxyError::RequestError(_) | ProxyError::ResponseError(_) => {
+                HttpResponse::BadGateway().finish()
+            }
+            ProxyError::ParseError(_) => HttpResponse::BadRequest().json(error(PARSE_ERROR)),
+            ProxyError::InvalidRequest(_) | ProxyError::BatchTooLarge(_) => {
+                HttpResponse::BadRequest().json(error(INVALID_REQUEST))
+            }
             ProxyError::MethodNotAllowed => HttpResponse::MethodNotAllowed().finish(),
+            ProxyError::PayloadTooLarge(_) => {
+                HttpResponse::PayloadTooLarge().json(error(INVALID_REQUEST))
+            }
+            ProxyError::RateLimited => {
+                HttpResponse::build(StatusCode::TOO_MANY_REQUESTS).json(error(LIMIT_EXCEEDED))
+            }
+        }
+    }
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+#[derive(Debug)]
+struct CachedResult {
+    result: Value,
+    expires: Instant,
+}
+
+/// Limits and cached results of the execution RPC proxy, shared by the workers of the API server
+#[derive(Debug)]
+pub struct ExecutionRpcProxy {
+    config: ExecutionRpcProxyConfig,
+    /// Url of the reth JSON-RPC server calls are forwarded to
+    reth_url: String,
+    /// Token buckets by client, see [`rate_limit_key`]
+    rate_limits: Mutex<LruCache<IpAddr, TokenBucket>>,
+    cache: Mutex<HashMap<String, CachedResult>>,
+}
+
+/// What to do with a single call of a request
+#[derive(Debug, PartialEq)]
+enum Resolution {
+    /// Answer the call without reaching reth
+    Respond(Value),
+    /// Send the call to reth, caching its result under the key if there is one
+    Forward {
+        method: String,
+        params: Option<Value>,
+        cache_key: Option<String>,
+    },
+}
+
+impl ExecutionRpcProxy {
+    pub fn new(config: ExecutionRpcProxyConfig, reth_url: String) -> Self {
+        let capacity = NonZeroUsize::new(MAX_TRACKED_ENTRIES).expect("capacity is not zero");
+        Self {
+            config,
+            reth_url,
+            rate_limits: Mutex::new(LruCache::new(capacity)),
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn is_allowed(&self, method: &str) -> bool {
+        matches_any(&self.config.allowed_methods, method)
+            && !matches_any(&self.config.denied_methods, method)
+    }
+
+    /// Takes a token per call from the bucket of the client at `ip`, returns false if it holds
+    /// too few
+    fn take_tokens(&self, ip: IpAddr, calls: usize, now: Instant) -> bool {
+        if self.config.rate_limit_per_second == 0 {
+            return true;
+        }
+        let rate = f64::from(self.config.rate_limit_per_second);
+        let burst = f64::from(
+            self.config
+                .rate_limit_burst
+                .max(self.config.rate_limit_per_second),
+        );
+        let refill = |bucket: &TokenBucket| {
+            (bucket.tokens + now.saturating_duration_since(bucket.updated).as_secs_f64() * rate)
+                .min(burst)
+        };
+
+        let mut buckets = self.rate_limits.lock().unwrap();
+        let bucket = buckets.get_or_insert_mut(rate_limit_key(ip), || TokenBucket {
+            tokens: burst,
+            updated: now,
+        });
+        bucket.tokens = refill(bucket);
+        bucket.updated = now;
+        if bucket.tokens < calls as f64 {
+            return false;
+        }
+        bucket.tokens -= calls as f64;
+        true
+    }
+
+    fn cached_result(&self, key: &str, now: Instant) -> Option<Value> {
+        let cache = self.cache.lock().unwrap();
+        cache
+            .get(key)
+            .filter(|cached| cached.expires > now)
+            .map(|cached| cached.result.clone())
+    }
+
+    fn cache_result(&self, key: String, result: Value, now: Instant) {
+        let mut cache = self.cache.lock().unwrap();
+        if cache.len() >= MAX_TRACKED_ENTRIES {
+            cache.retain(|_, cached| cached.expires > now);
+            if cache.len() >= MAX_TRACKED_ENTRIES {
+                return;
+            }
         }
+        let expires = now + Duration::from_millis(self.config.cache_ttl_millis);
+        cache.insert(key, CachedResult { result, expires });
+    }
+
+    fn resolve(&self, call: &Value, now: Instant) -> Resolution {
+        let id = call.get("id").cloned().unwrap_or(Value::Null);
+        let Some(method) = call.get("method").and_then(Value::as_str) else {
+            return Resolution::Respond(error_response(
+                id,
+                INVALID_REQUEST,
+                "Invalid request: expected a JSON-RPC call with a method",
+            ));
+        };
+        if !self.is_allowed(method) {
+            return Resolution::Respond(error_response(
+                id,
+                METHOD_NOT_FOUND,
+                &format!("Method {} is not available", method),
+            ));
+        }
+
+        let params = call.get("params").cloned();
+        let cache_key = (self.config.cache_ttl_millis > 0
+            && self.config.cached_methods.iter().any(|m| m == method))
+        .then(|| format!("{}:{}", method, params.as_ref().unwrap_or(&Value::Null)));
+        if let Some(result) = cache_key
+            .as_deref()
+            .and_then(|key| self.cached_result(key, now))
+        {
+            return Resolution::Respond(json!({ "jsonrpc": "2.0", "id": id, "result": result }));
+        }
+        Resolution::Forward {
+            method: method.to_owned(),
+            params,
+            cache_key,
+        }
+    }
+}
+
+/// Clients are rate limited by IPv4 address or by IPv6 /64 prefix, the smallest block
+/// usually assigned to a single host
+fn rate_limit_key(ip: IpAddr) -> IpAddr {
+    match ip.to_canonical() {
+        IpAddr::V6(ip) => IpAddr::V6(Ipv6Addr::from(u128::from(ip) & !u128::from(u64::MAX))),
+        ip => ip,
     }
 }
 
+/// Whether `method` is one of `patterns`, which are full method names or namespaces written as
+/// `eth_*`
+fn matches_any(patterns: &[String], method: &str) -> bool {
+    patterns
+        .iter()
+        .any(|pattern| match pattern.strip_suffix('*') {
+            Some(namespace) => method.starts_with(namespace),
+            None => method == pattern,
+        })
+}
+
+/// A call without an id, which gets no response
+fn is_notification(call: &Value) -> bool {
+    call.get("method").is_some() && call.get("id").is_none()
+}
+
+async fn read_body(mut payload: Payload, limit: usize) -> Result<BytesMut, ProxyError> {
+    let mut body = BytesMut::new();
+    while let Some(chunk) = payload.next().await {
+        let chunk = chunk.map_err(|e| ProxyError::ParseError(e.to_string()))?;
+        if body.len() + chunk.len() > limit {
+            return Err(ProxyError::PayloadTooLarge(limit));
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(body)
+}
+
+/// Forwards JSON-RPC calls to reth. Calls to methods outside the allowlist are answered with an
+/// error, and the results of cacheable calls are reused while fresh
 pub async fn proxy(
     req: HttpRequest,
     payload: Payload,
     client: Data<Client>,
-    state: web::Data<ApiState>,
+    proxy: web::Data<ExecutionRpcProxy>,
 ) -> Result<HttpResponse, ProxyError> {
-    let target_uri = &state.reth_http_url;
-
-    // Create a new client request
-    let mut client_req = match *req.method() {
-        actix_web::http::Method::GET => client.get(target_uri),
-        actix_web::http::Method::POST => client.post(target_uri),
-        actix_web::http::Method::PUT => client.put(target_uri),
-        actix_web::http::Method::DELETE => client.delete(target_uri),
-        actix_web::http::Method::HEAD => client.head(target_uri),
-        actix_web::http::Method::OPTIONS => client.options(target_uri),
-        actix_web::http::Method::PATCH => client.patch(target_uri),
-        _ => return Err(ProxyError::MethodNotAllowed),
+    if req.method() != Method::POST {
+        return Err(ProxyError::MethodNotAllowed);
+    }
+    let body = read_body(payload, proxy.config.max_request_size).await?;
+    let request: Value =
+        serde_json::from_slice(&body).map_err(|e| ProxyError::ParseError(e.to_string()))?;
+    let (calls, is_batch) = match request {
+        Value::Array(calls) => (calls, true),
+        call => (vec![call], false),
     };
+    if is_batch && calls.is_empty() {
+        return Err(ProxyError::InvalidRequest("empty batch".to_owned()));
+    }
+    if calls.len() > proxy.config.max_batch_size {
+        return Err(ProxyError::BatchTooLarge(proxy.config.max_batch_size));
+    }
+    if let Some(peer) = req.peer_addr() {
+        if !proxy.take_tokens(peer.ip(), calls.len(), Instant::now()) {
+            return Err(ProxyError::RateLimited);
+        }
+    }
 
-    // Forward relevant headers
-    for (header_name, header_value) in req.headers() {
-        // Skip hop-by-hop headers
-        if !is_hop_by_hop_header(header_name.as_str()) {
-            client_req = client_req.insert_header((header_name.clone(), header_value.clone()));
+    let now = Instant::now();
+    let mut responses: Vec<Option<Value>> = vec![None; calls.len()];
+    let mut forwarded = Vec::new();
+    let mut cache_keys = HashMap::new();
+    for (index, call) in calls.iter().enumerate() {
+        match proxy.resolve(call, now) {
+            Resolution::Respond(response) => responses[index] = Some(response),
+            Resolution::Forward {
+                method,
+                params,
+                cache_key,
+            } => {
+                // calls are numbered so the responses of reth can be matched with them
+                let mut forwarded_call = Map::new();
+                forwarded_call.insert("jsonrpc".to_owned(), "2.0".into());
+                forwarded_call.insert("id".to_owned(), index.into());
+                forwarded_call.insert("method".to_owned(), method.into());
+                if let Some(params) = params {
+                    forwarded_call.insert("params".to_owned(), params);
+                }
+                forwarded.push(Value::Object(forwarded_call));
+                if let Some(cache_key) = cache_key {
+                    cache_keys.insert(index, cache_key);
+                }
+            }
         }
     }
 
-    // Send the request with the payload for methods that support it
-    let response = match req.method() {
-        &actix_web::http::Method::POST
-        | &actix_web::http::Method::PUT
-        | &actix_web::http::Method::PATCH => client_req
-            .send_stream(payload)
+    if !forwarded.is_empty() {
+        let reth_responses: Vec<Value> = client
+            .post(&proxy.reth_url)
+            .send_json(&forwarded)
+            .await
+            .map_err(ProxyError::RequestError)?
+            .json()
+            .limit(RETH_RESPONSE_LIMIT)
             .await
-            .map_err(ProxyError::RequestError)?,
-        _ => client_req.send().await.map_err(ProxyError::RequestError)?,
+            .map_err(|e| ProxyError::ResponseError(e.to_string()))?;
+        for mut response in reth_responses {
+            let Some(index) = response
+                .get("id")
+                .and_then(Value::as_u64)
+                .and_then(|id| usize::try_from(id).ok())
+                .filter(|index| *index < calls.len())
+            else {
+                continue;
+            };
+            if let (Some(cache_key), Some(result)) =
+                (cache_keys.remove(&index), response.get("result"))
+            {
+                proxy.cache_result(cache_key, result.clone(), Instant::now());
+            }
+            response["id"] = calls[index].get("id").cloned().unwrap_or(Value::Null);
+            responses[index] = Some(response);
+        }
+    }
+
+    let responses: Vec<Value> = calls
+        .iter()
+        .zip(responses)
+        .filter(|(call, _)| !is_notification(call))
+        .map(|(call, response)| {
+            response.unwrap_or_else(|| {
+                let id = call.get("id").cloned().unwrap_or(Value::Null);
+                error_response(id, INTERNAL_ERROR, "No response from the execution layer")
+            })
+        })
+        .collect();
+    if responses.is_empty() {
+        return Ok(HttpResponse::NoContent().finish());
+    }
+    Ok(if is_batch {
+        HttpResponse::Ok().json(responses)
+    } else {
+        HttpResponse::Ok().json(&responses[0])
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{
+        http::header::ContentType,
+        test::{self, TestRequest},
+        App, HttpServer,
     };
 
-    // Build response
-    let mut client_response = HttpResponse::build(response.status());
+    fn call(method: &str) -> Value {
+        json!({ "jsonrpc": "2.0", "id": 7, "method": method, "params": [] })
+    }
+
+    fn new_proxy(config: ExecutionRpcProxyConfig) -> ExecutionRpcProxy {
+        ExecutionRpcProxy::new(config, "http://127.0.0.1:8545".to_owned())
+    }
 
-    // Forward response headers
-    for (header_name, header_value) in response.headers() {
-        if !is_hop_by_hop_header(header_name.as_str()) {
-            client_response.insert_header((header_name.clone(), header_value.clone()));
+    #[test]
+    fn only_allowed_methods_are_forwarded() {
+        let proxy = new_proxy(ExecutionRpcProxyConfig::default());
+        let now = Instant::now();
+        for method in [
+            "eth_getBalance",
+            "eth_chainId",
+            "eth_call",
+            "eth_sendRawTransaction",
+            "net_version",
+            "web3_clientVersion",
+        ] {
+            assert!(
+                matches!(
+                    proxy.resolve(&call(method), now),
+                    Resolution::Forward { .. }
+                ),
+                "{}",
+                method
+            );
+        }
+        for method in [
+            "admin_addPeer",
+            "debug_traceTransaction",
+            "txpool_content",
+            "personal_sign",
+            "trace_block",
+        ] {
+            let Resolution::Respond(response) = proxy.resolve(&call(method), now) else {
+                panic!("{} was forwarded", method);
+            };
+            assert_eq!(response["id"], 7);
+            assert_eq!(response["error"]["code"], METHOD_NOT_FOUND);
+        }
+
+        let Resolution::Respond(response) = proxy.resolve(&json!({ "id": 1 }), now) else {
+            panic!("a call without a method was forwarded");
+        };
+        assert_eq!(response["error"]["code"], INVALID_REQUEST);
+
+        // denied methods win over allowed namespaces
+        let proxy = new_proxy(ExecutionRpcProxyConfig {
+            allowed_methods: vec!["eth_*".to_owned()],
+            denied_methods: vec!["eth_sendRawTransaction".to_owned()],
+            ..Default::default()
+        });
+        assert!(matches!(
+            proxy.resolve(&call("eth_call"), now),
+            Resolution::Forward { .. }
+        ));
+        for method in ["eth_sendRawTransaction", "net_version"] {
+            assert!(matches!(
+                proxy.resolve(&call(method), now),
+                Resolution::Respond(_)
+            ));
         }
     }
 
-    // Stream the response body
-    Ok(client_response.streaming(response))
-}
+    #[test]
+    fn calls_are_rate_limited_per_ip() {
+        let proxy = new_proxy(ExecutionRpcProxyConfig {
+            rate_limit_per_second: 10,
+            rate_limit_burst: 20,
+            ..Default::default()
+        });
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+        let other_ip: IpAddr = "10.0.0.2".parse().unwrap();
+        let start = Instant::now();
+
+        assert!(proxy.take_tokens(ip, 15, start));
+        assert!(proxy.take_tokens(ip, 5, start));
+        assert!(!proxy.take_tokens(ip, 1, start));
+        assert!(proxy.take_tokens(other_ip, 20, start));
 
-fn is_hop_by_hop_header(header: &str) -> bool {
-    let hop_by_hop_headers = [
-        "connection",
-        "keep-alive",
-        "proxy-authenticate",
-        "proxy-authorization",
-        "te",
-        "trailers",
-        "transfer-encoding",
-        "upgrade",
-    ];
-
-    hop_by_hop_headers.contains(&header.to_lowercase().as_str())
+        // the bucket refills at the average rate, up to the burst
+        assert!(proxy.take_tokens(ip, 5, start + Duration::from_millis(500)));
+        assert!(!proxy.take_tokens(ip, 1, start + Duration::from_millis(500)));
+        assert!(!proxy.take_tokens(ip, 21, start + Duration::from_secs(60)));
+        assert!(proxy.take_tokens(ip, 20, start + Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn ipv6_clients_are_rate_limited_per_prefix() {
+        let proxy = new_proxy(ExecutionRpcProxyConfig {
+            rate_limit_per_second: 10,
+            rate_limit_burst: 20,
+            ..Default::default()
+        });
+        let start = Instant::now();
+
+        // addresses of the same /64 share a bucket
+        assert!(proxy.take_tokens("2001:db8::1".parse().unwrap(), 20, start));
+        assert!(!proxy.take_tokens("2001:db8::ffff:1".parse().unwrap(), 1, start));
+        assert!(proxy.take_tokens("2001:db8:0:1::1".parse().unwrap(), 20, start));
+
+        // IPv4-mapped addresses share the bucket of the IPv4 address
+        assert!(proxy.take_tokens("10.0.0.1".parse().unwrap(), 20, start));
+        assert!(!proxy.take_tokens("::ffff:10.0.0.1".parse().unwrap(), 1, start));
+    }
+
+    #[test]
+    fn rate_limited_clients_are_capped() {
+        let proxy = new_proxy(ExecutionRpcProxyConfig {
+            rate_limit_per_second: 10,
+            rate_limit_burst: 20,
+            ..Default::default()
+        });
+        let start = Instant::now();
+        let first: IpAddr = "10.0.0.0".parse().unwrap();
+        assert!(proxy.take_tokens(first, 20, start));
+
+        for client in 1..=MAX_TRACKED_ENTRIES as u32 {
+            let ip = IpAddr::from(std::net::Ipv4Addr::from(0x0a00_0000 + client));
+            assert!(proxy.take_tokens(ip, 1, start));
+        }
+        assert_eq!(proxy.rate_limits.lock().unwrap().len(), MAX_TRACKED_ENTRIES);
+        // the least recently seen client was dropped, and starts over with a full bucket
+        assert!(proxy.take_tokens(first, 20, start));
+    }
+
+    #[test]
+    fn cacheable_results_are_reused_while_fresh() {
+        let proxy = new_proxy(ExecutionRpcProxyConfig {
+            cache_ttl_millis: 1000,
+            ..Default::default()
+        });
+        let start = Instant::now();
+
+        let Resolution::Forward {
+            cache_key: Some(cache_key),
+            ..
+        } = proxy.resolve(&call("eth_chainId"), start)
+        else {
+            panic!("eth_chainId is cacheable");
+        };
+        proxy.cache_result(cache_key, json!("0x4f6"), start);
+
+        let Resolution::Respond(response) =
+            proxy.resolve(&call("eth_chainId"), start + Duration::from_millis(999))
+        else {
+            panic!("the cached result wasn't used");
+        };
+        assert_eq!(
+            response,
+            json!({ "jsonrpc": "2.0", "id": 7, "result": "0x4f6" })
+        );
+
+        assert!(matches!(
+            proxy.resolve(&call("eth_chainId"), start + Duration::from_millis(1000)),
+            Resolution::Forward { .. }
+        ));
+        assert!(matches!(
+            proxy.resolve(&call("eth_getBalance"), start),
+            Resolution::Forward {
+                cache_key: None,
+                ..
+            }
+        ));
+    }
+
+    /// Serves the reth side of the proxy, answering every call with its method name
+    fn start_fake_reth() -> String {
+        let server = HttpServer::new(|| {
+            App::new().route(
+                "/",
+                web::post().to(|calls: web::Json<Vec<Value>>| async move {
+                    let responses: Vec<Value> = calls
+                        .iter()
+                        .map(|call| {
+                            json!({ "jsonrpc": "2.0", "id": call["id"], "result": call["method"] })
+                        })
+                        .collect();
+                    HttpResponse::Ok().json(responses)
+                }),
+            )
+        })
+        .workers(1)
+        .bind("127.0.0.1:0")
+        .unwrap();
+        let address = server.addrs()[0];
+        actix_web::rt::spawn(server.run());
+        format!("http://{}", address)
+    }
+
+    #[actix_web::test]
+    async fn route_enforces_request_limits() {
+        let proxy = web::Data::new(ExecutionRpcProxy::new(
+            ExecutionRpcProxyConfig {
+                max_batch_size: 2,
+                max_request_size: 256,
+                ..Default::default()
+            },
+            start_fake_reth(),
+        ));
+        let app = test::init_service(
+            App::new()
+                .app_data(Data::new(Client::new()))
+                .app_data(proxy)
+                .route("/execution-rpc", web::to(super::proxy)),
+        )
+        .await;
+        let post = |body: Value| {
+            TestRequest::post()
+                .uri("/execution-rpc")
+                .set_json(body)
+                .to_request()
+        };
+
+        let response: Value = test::call_and_read_body_json(&app, post(call("eth_chainId"))).await;
+        assert_eq!(
+            response,
+            json!({ "jsonrpc": "2.0", "id": 7, "result": "eth_chainId" })
+        );
+
+        let batch = json!([
+            call("eth_chainId"),
+            call("eth_blockNumber"),
+            call("eth_gasPrice")
+        ]);
+        let response = test::call_service(&app, post(batch)).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let response: Value = test::read_body_json(response).await;
+        assert_eq!(response["error"]["code"], INVALID_REQUEST);
+
+        let oversized = TestRequest::post()
+            .uri("/execution-rpc")
+            .insert_header(ContentType::json())
+            .set_payload(vec![b' '; 257])
+            .to_request();
+        let response = test::call_service(&app, oversized).await;
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        // notifications are forwarded but get no response
+        let notification = json!({ "jsonrpc": "2.0", "method": "eth_blockNumber" });
+        let response = test::call_service(&app, post(json!([notification, notification]))).await;
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        let response: Value = test::call_and_read_body_json(
+            &app,
+            post(json!([notification, call("eth_blockNumber")])),
+        )
+        .await;
+        assert_eq!(
+            response,
+            json!([{ "jsonrpc": "2.0", "id": 7, "result": "eth_blockNumber" }])
+        );
+    }
 }
@@ -0,0 +1,226 @@
+use crate::error::ApiError;
+use crate::ApiState;
+use actix_web::web::{self, Json};
+use base58::FromBase58 as _;
+use irys_database::{database, db::IrysDatabaseExt as _};
+use irys_types::{
+    page_limit, Address, BlockRangeQuery, BlockWithTxs, CommitmentWithStatus,
+    IrysTransactionHeader, IrysTransactionId, Page, PageQuery, TxWithPromotionStatus, H256,
+};
+use std::{collections::HashSet, str::FromStr as _};
+
+/// Lists the headers of data transactions signed by an address, ordered by tx id
+pub async fn get_txs_by_signer(
+    state: web::Data<ApiState>,
+    path: web::Path<String>,
+    query: web::Query<PageQuery>,
+) -> Result<Json<Page<IrysTransactionHeader>>, ApiError> {
+    let signer = parse_address(&path)?;
+    let limit = page_limit(query.limit);
+
+    let tx_headers = state
+        .db
+        .view_eyre(|tx| {
+            // fetch one extra id to know whether there is a next page
+            database::tx_ids_by_signer(tx, signer, query.after, limit + 1)?
+                .iter()
+                .filter_map(|tx_id| database::tx_header_by_txid(tx, tx_id).transpose())
+                .collect::<eyre::Result<Vec<_>>>()
+        })
+        .map_err(|_| ApiError::Internal {
+            err: String::from("db error while looking up transactions by signer"),
+        })?;
+
+    Ok(Json(Page::from_results(tx_headers, limit, |tx_header| {
+        tx_header.id
+    })))
+}
+
+/// Lists the data transactions destined for a ledger along with their promotion status, ordered by tx id
+pub async fn get_txs_by_ledger(
+    state: web::Data<ApiState>,
+    path: web::Path<u32>,
+    query: web::Query<PageQuery>,
+) -> Result<Json<Page<TxWithPromotionStatus>>, ApiError> {
+    let ledger_id = path.into_inner();
+    let limit = page_limit(query.limit);
+
+    let txs = state
+        .db
+        .view_eyre(|tx| {
+            database::tx_ids_by_ledger(tx, ledger_id, query.after, limit + 1)?
+                .iter()
+                .filter_map(|tx_id| database::tx_header_by_txid(tx, tx_id).transpose())
+                .map(|tx_header| {
+                    tx_header.map(|tx_header| TxWithPromotionStatus {
+                        is_promoted: tx_header.ingress_proofs.is_some(),
+                        tx: tx_header,
+                    })
+                })
+                .collect::<eyre::Result<Vec<_>>>()
+        })
+        .map_err(|_| ApiError::Internal {
+            err: String::from("db error while looking up transactions by ledger"),
+        })?;
+
+    Ok(Json(Page::from_results(txs, limit, |tx| tx.tx.id)))
+}
+
+/// Lists the commitment transactions signed by an address along with their
+/// status in the current commitment state, ordered by tx id
+pub async fn get_commitments_by_signer(
+    state: web::Data<ApiState>,
+    path: web::Path<String>,
+    query: web::Query<PageQuery>,
+) -> Result<Json<Page<CommitmentWithStatus>>, ApiError> {
+    let signer = parse_address(&path)?;
+    let limit = page_limit(query.limit);
+
+    let commitments = state
+        .db
+        .view_eyre(|tx| {
+            database::commitment_tx_ids_by_signer(tx, signer, query.after, limit + 1)?
+                .iter()
+                .filter_map(|tx_id| database::commitment_tx_by_txid(tx, tx_id).transpose())
+                .collect::<eyre::Result<Vec<_>>>()
+        })
+        .map_err(|_| ApiError::Internal {
+            err: String::from("db error while looking up commitments by signer"),
+        })?;
+
+    let commitment_state = state.commitment_state.read();
+    let status_of = |id: IrysTransactionId| {
+        commitment_state
+            .stake_commitments
+            .get(&signer)
+            .into_iter()
+            .chain(
+                commitment_state
+                    .pledge_commitments
+                    .get(&signer)
+                    .into_iter()
+                    .flatten(),
+            )
+            .find(|entry| entry.id == id)
+            .map(|entry| entry.commitment_status)
+    };
+    let commitments = commitments
+        .into_iter()
+        .map(|commitment| CommitmentWithStatus {
+            status: status_of(commitment.id),
+            commitment,
+        })
+        .collect();
+
+    Ok(Json(Page::from_results(commitments, limit, |c| {
+        c.commitment.id
+    })))
+}
+
+/// Lists the canonical blocks starting at a height, optionally with their data transaction headers
+pub async fn get_blocks(
+    state: web::Data<ApiState>,
+    query: web::Query<BlockRangeQuery>,
+) -> Result<Json<Vec<BlockWithTxs>>, ApiError> {
+    let limit = page_limit(query.limit) as u64;
+    let block_hashes = canonical_block_hashes(&state, query.from, limit);
+
+    let blocks = state
+        .db
+        .view_eyre(|tx| {
+            block_hashes
+                .iter()
+                .filter_map(|hash| database::block_header_by_hash(tx, hash, false).transpose())
+                .map(|block| {
+                    let block = block?;
+                    let txs = if query.include_txs {
+                        let mut seen = HashSet::new();
+                        let txs = block
+                            .data_ledgers
+                            .iter()
+                            .flat_map(|ledger| ledger.tx_ids.0.iter())
+                            // promoted txs are listed in both the Submit and Publish ledgers
+                            .filter(|tx_id| seen.insert(**tx_id))
+                            .filter_map(|tx_id| database::tx_header_by_txid(tx, tx_id).transpose())
+                            .collect::<eyre::Result<Vec<_>>>()?;
+                        Some(txs)
+                    } else {
+                        None
+                    };
+                    Ok(BlockWithTxs { block, txs })
+                })
+                .collect::<eyre::Result<Vec<_>>>()
+        })
+        .map_err(|_| ApiError::Internal {
+            err: String::from("db error while looking up blocks"),
+        })?;
+
+    Ok(Json(blocks))
+}
+
+/// Resolves the hashes of up to `limit` consecutive canonical blocks starting at `from`,
+/// stopping at the tip. Reads the canonical chain once and takes the blocks that are
+/// still in the block tree from it, and the older ones from the block index
+fn canonical_block_hashes(state: &web::Data<ApiState>, from: u64, limit: u64) -> Vec<H256> {
+    let (chain, _) = state.block_tree.read().get_canonical_chain();
+    let tree_start = chain.first().map_or(u64::MAX, |(_, height, _, _)| *height);
+    let to = from.saturating_add(limit);
+
+    let index_end = to.min(tree_start).max(from);
+    let mut hashes = {
+        let block_index = state.block_index.read();
+        (from..index_end)
+            .map_while(|height| block_index.get_item(height).map(|item| item.block_hash))
+            .collect::<Vec<_>>()
+    };
+    // only continue into the block tree if the index had every block before it
+    if hashes.len() as u64 == index_end - from {
+        let skip = usize::try_from(from.saturating_sub(tree_start)).unwrap_or(usize::MAX);
+        let take = to.saturating_sub(from.max(tree_start)) as usize;
+        hashes.extend(
+            chain
+                .iter()
+                .skip(skip)
+                .take(take)
+                .map(|(hash, _, _, _)| *hash),
+        );
+    }
+    hashes
+}
+
+/// Resolves the hash of the canonical block at `height`, checking the block
+/// tree first as the block index only holds migrated blocks
+pub fn canonical_block_hash(state: &web::Data<ApiState>, height: u64) -> Option<H256> {
+    let in_block_tree = state
+        .block_tree
+        .read()
+        .get_canonical_chain()
+        .0
+        .iter()
+        .find_map(|(hash, hght, _, _)| (*hght == height).then_some(*hash));
+    in_block_tree.or_else(|| {
+        state
+            .block_index
+            .read()
+            .get_item(height)
+            .map(|item| item.block_hash)
+    })
+}
+
+/// Parses an address path parameter, accepting both the base58 encoding used
+/// by the API's JSON and 0x prefixed hex
+fn parse_address(address: &str) -> Result<Address, ApiError> {
+    let parsed = if address.starts_with("0x") {
+        Address::from_str(address).ok()
+    } else {
+        address
+            .from_base58()
+            .ok()
+            .filter(|bytes| bytes.len() == 20)
+            .map(|bytes| Address::from_slice(&bytes))
+    };
+    parsed.ok_or(ApiError::ErrNoId {
+        id: address.to_string(),
+        err: String::from("Invalid address"),
+    })
+}
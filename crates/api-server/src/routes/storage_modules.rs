@@ -0,0 +1,80 @@
+use crate::ApiState;
+use actix_web::{http::header::ContentType, web, HttpResponse};
+use irys_actors::packing::GetPackingStatus;
+use irys_storage::disk_space_for_path;
+use irys_types::{PackingProgress, StorageModuleStatus, SubmoduleStatus};
+use nodit::InclusiveInterval as _;
+
+/// Reports partition assignment, disk usage, chunk state and packing progress
+/// for every storage module of this node
+pub async fn storage_modules_route(state: web::Data<ApiState>) -> HttpResponse {
+    let packing_status = match state.packing.send(GetPackingStatus).await {
+        Ok(status) => status,
+        Err(e) => {
+            return HttpResponse::InternalServerError()
+                .body(format!("Failed to fetch packing status: {}", e));
+        }
+    };
+
+    let storage_modules = state.chunk_provider.storage_modules_guard.read().clone();
+    let report = storage_modules
+        .iter()
+        .map(|sm| {
+            let assignment = sm.partition_assignment();
+            let chunk_counts = sm.chunk_type_counts();
+
+            let submodules = sm
+                .submodule_paths()
+                .into_iter()
+                .map(|(interval, path)| {
+                    // the path itself isn't reported, it would expose the node's filesystem
+                    let space = disk_space_for_path(&path);
+                    SubmoduleStatus {
+                        start_offset: *interval.start(),
+                        end_offset: *interval.end(),
+                        available_space: space.map(|(available, _)| available),
+                        total_space: space.map(|(_, total)| total),
+                    }
+                })
+                .collect();
+
+            let (queued_requests, queued_chunks) = packing_status
+                .queued
+                .get(&sm.id)
+                .copied()
+                .unwrap_or_default();
+            let chunks_per_second = packing_status
+                .chunks_per_second
+                .get(&sm.id)
+                .copied()
+                .unwrap_or_default();
+            let estimated_secs_remaining = if chunk_counts.uninitialized == 0 {
+                Some(0)
+            } else if chunks_per_second > 0.0 {
+                Some((chunk_counts.uninitialized as f64 / chunks_per_second).ceil() as u64)
+            } else {
+                None
+            };
+
+            StorageModuleStatus {
+                id: sm.id,
+                partition_hash: assignment.map(|pa| pa.partition_hash),
+                ledger_id: assignment.and_then(|pa| pa.ledger_id),
+                slot_index: assignment.and_then(|pa| pa.slot_index),
+                submodules,
+                chunk_counts,
+                packing: PackingProgress {
+                    queued_requests,
+                    queued_chunks,
+                    chunks_per_second,
+                    estimated_secs_remaining,
+                },
+                is_minable: assignment.is_some() && chunk_counts.uninitialized == 0,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    HttpResponse::Ok()
+        .content_type(ContentType::json())
+        .json(report)
+}
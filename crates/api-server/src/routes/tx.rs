@@ -1,17 +1,24 @@
 use crate::error::ApiError;
 use crate::ApiState;
 use actix_web::{
-    web::{self, Json},
+    http::header::{
+        AcceptRanges, ContentRange, ContentRangeSpec, ContentType, Range as RangeHeader, RangeUnit,
+    },
+    web::{self, Bytes, Json},
     HttpResponse, Result,
 };
 use awc::http::StatusCode;
+use futures::StreamExt as _;
 use irys_actors::mempool_service::{TxIngressError, TxIngressMessage};
 use irys_database::{database, db::IrysDatabaseExt as _};
+use irys_storage::ChunkProvider;
 use irys_types::{
-    u64_stringify, CommitmentTransaction, DataLedger, IrysTransactionHeader,
-    IrysTransactionResponse, H256,
+    page_limit, u64_stringify, ChunkFormat, CommitmentTransaction, DataLedger,
+    IrysTransactionHeader, IrysTransactionResponse, LedgerChunkOffset, Page, TxChunkOffset,
+    TxTagQuery, H256,
 };
 use serde::{Deserialize, Serialize};
+use std::{ops::Range, sync::Arc};
 use tracing::info;
 
 /// Handles the HTTP POST request for adding a transaction to the mempool.
@@ -51,6 +58,8 @@ pub async fn post_tx(
             }
             TxIngressError::InvalidAnchor => Ok(HttpResponse::build(StatusCode::BAD_REQUEST)
                 .body(format!("Invalid Anchor: {:?}", err))),
+            TxIngressError::InvalidTags => Ok(HttpResponse::build(StatusCode::BAD_REQUEST)
+                .body(format!("Invalid Tags: {:?}", err))),
             TxIngressError::DatabaseError => {
                 Ok(HttpResponse::build(StatusCode::INTERNAL_SERVER_ERROR)
                     .body(format!("Internal database error: {:?}", err)))
@@ -168,6 +177,147 @@ pub async fn get_tx_local_start_offset(
     }
 }
 
+/// Streams the original data of a storage transaction, unpacking its chunks from the local
+/// storage modules or fetching them from peers if this node doesn't store the data. A `Range`
+/// header with a single byte range returns just those bytes
+pub async fn get_tx_data(
+    state: web::Data<ApiState>,
+    path: web::Path<H256>,
+    range: Option<web::Header<RangeHeader>>,
+) -> Result<HttpResponse, ApiError> {
+    let tx_id: H256 = path.into_inner();
+    info!("Get tx data by tx_id: {}", tx_id);
+
+    let tx_header = get_storage_transaction(&state, tx_id)?;
+    let ledger = DataLedger::try_from(tx_header.ledger_id).map_err(|_| ApiError::Internal {
+        err: format!("unknown ledger id {}", tx_header.ledger_id),
+    })?;
+    let data_size = tx_header.data_size;
+    let data_root = tx_header.data_root;
+    let data_start_offset = state
+        .chunk_provider
+        .get_ledger_offsets_for_data_root(ledger, data_root)
+        .map_err(|_| ApiError::Internal {
+            err: String::from("db error"),
+        })?
+        .and_then(|offsets| offsets.first().copied());
+    if data_start_offset.is_none() && !state.chunk_provider.has_peer_source() {
+        return Err(ApiError::ErrNoId {
+            id: tx_id.to_string(),
+            err: String::from("Transaction data isn't stored by this node"),
+        });
+    }
+
+    // Multiple ranges aren't supported, those requests get the whole data
+    let requested_range = match range.as_deref() {
+        Some(RangeHeader::Bytes(specs)) if specs.len() == 1 => {
+            match specs[0].to_satisfiable_range(data_size) {
+                Some((start, end)) => Some(start..end + 1),
+                None => {
+                    return Ok(HttpResponse::RangeNotSatisfiable()
+                        .insert_header(ContentRange(ContentRangeSpec::Bytes {
+                            range: None,
+                            instance_length: Some(data_size),
+                        }))
+                        .finish())
+                }
+            }
+        }
+        _ => None,
+    };
+    let byte_range = requested_range.clone().unwrap_or(0..data_size);
+
+    let chunk_size = state.chunk_provider.config.consensus.chunk_size;
+    let chunk_indexes = byte_range.start / chunk_size..byte_range.end.div_ceil(chunk_size);
+    let chunk_provider = state.chunk_provider.clone();
+    let body_range = byte_range.clone();
+    let body = futures::stream::iter(chunk_indexes).then(move |chunk_index| {
+        let chunk_provider = chunk_provider.clone();
+        let body_range = body_range.clone();
+        async move {
+            read_tx_data_chunk(
+                chunk_provider,
+                ledger,
+                data_root,
+                data_size,
+                data_start_offset,
+                chunk_index,
+                &body_range,
+            )
+            .await
+            .map_err(|e| std::io::Error::other(e.to_string()))
+        }
+    });
+
+    let mut response = match requested_range {
+        Some(range) => {
+            let mut response = HttpResponse::PartialContent();
+            response.insert_header(ContentRange(ContentRangeSpec::Bytes {
+                range: Some((range.start, range.end - 1)),
+                instance_length: Some(data_size),
+            }));
+            response
+        }
+        None => HttpResponse::Ok(),
+    };
+    Ok(response
+        .insert_header(AcceptRanges(vec![RangeUnit::Bytes]))
+        .content_type(ContentType::octet_stream())
+        .no_chunking(byte_range.end - byte_range.start)
+        .streaming(body))
+}
+
+/// Reads and unpacks the chunk at `chunk_index` of a transaction's data, trimmed to the
+/// part of it that overlaps `byte_range`. Data that isn't stored locally, i.e. has no
+/// `data_start_offset`, is fetched from peers
+async fn read_tx_data_chunk(
+    chunk_provider: Arc<ChunkProvider>,
+    ledger: DataLedger,
+    data_root: H256,
+    data_size: u64,
+    data_start_offset: Option<u64>,
+    chunk_index: u64,
+    byte_range: &Range<u64>,
+) -> eyre::Result<Bytes> {
+    let chunk = match data_start_offset {
+        Some(data_start_offset) => {
+            let chunk_provider = chunk_provider.clone();
+            tokio::task::spawn_blocking(move || {
+                chunk_provider.get_chunk_by_ledger_offset(
+                    ledger,
+                    LedgerChunkOffset::from(data_start_offset + chunk_index),
+                )
+            })
+            .await??
+            .map(ChunkFormat::Packed)
+        }
+        None => {
+            chunk_provider
+                .get_chunk_or_fetch(
+                    ledger,
+                    data_root,
+                    Some(data_size),
+                    TxChunkOffset::from(chunk_index),
+                )
+                .await?
+        }
+    };
+    let chunk = chunk.ok_or_else(|| eyre::eyre!("chunk {} isn't available", chunk_index))?;
+    let bytes = chunk_provider.unpack_chunk(chunk).bytes.0;
+
+    let chunk_size = chunk_provider.config.consensus.chunk_size;
+    let chunk_start = chunk_index * chunk_size;
+    let start = byte_range.start.saturating_sub(chunk_start) as usize;
+    let end = ((byte_range.end - chunk_start).min(chunk_size) as usize).min(bytes.len());
+    eyre::ensure!(
+        start <= end,
+        "chunk {} of data_root {} is shorter than expected",
+        chunk_index,
+        data_root
+    );
+    Ok(Bytes::copy_from_slice(&bytes[start..end]))
+}
+
 // TODO: REMOVE ME ONCE WE HAVE A GATEWAY
 /// Returns whether or not a transaction has been promoted
 /// by checking if the ingress_proofs field of the tx's header is `Some`,
@@ -182,3 +332,29 @@ pub async fn get_tx_is_promoted(
 
     Ok(web::Json(tx_header.ingress_proofs.is_some()))
 }
+
+/// Lists the headers of transactions tagged with the queried tag name and value, ordered by tx id
+pub async fn get_txs_by_tag(
+    state: web::Data<ApiState>,
+    query: web::Query<TxTagQuery>,
+) -> Result<Json<Page<IrysTransactionHeader>>, ApiError> {
+    let query = query.into_inner();
+    let limit = page_limit(query.limit);
+
+    let tx_headers = state
+        .db
+        .view_eyre(|tx| {
+            // fetch one extra id to know whether there is a next page
+            database::tx_ids_by_tag(tx, &query.name, &query.value, query.after, limit + 1)?
+                .iter()
+                .filter_map(|tx_id| database::tx_header_by_txid(tx, tx_id).transpose())
+                .collect::<eyre::Result<Vec<_>>>()
+        })
+        .map_err(|_| ApiError::Internal {
+            err: String::from("db error while looking up tagged transactions"),
+        })?;
+
+    Ok(Json(Page::from_results(tx_headers, limit, |tx_header| {
+        tx_header.id
+    })))
+}
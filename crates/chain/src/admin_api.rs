@@ -0,0 +1,234 @@
+//! Operational controls of the node served over HTTP, for orchestrators that can't call
+//! [`IrysNodeCtx`] directly.
+//!
+//! The admin API listens on its own address, configured by [`AdminApiConfig`], so it can be kept
+//! off the public network. Every request must carry one of the configured tokens in an
+//! `Authorization: Bearer <token>` header.
+use crate::IrysNodeCtx;
+use actix_web::{
+    body::MessageBody,
+    dev::{Server, ServiceRequest, ServiceResponse},
+    error::InternalError,
+    http::header,
+    middleware::{from_fn, Next},
+    web, App, HttpResponse, HttpServer,
+};
+use irys_actors::cache_service::CacheServiceAction;
+use irys_types::AdminApiConfig;
+use serde::Deserialize;
+use sha2::{Digest as _, Sha256};
+use std::{net::SocketAddr, net::TcpListener, sync::Arc};
+use tokio::sync::oneshot;
+use tracing::{info, warn};
+
+/// Replaces the filter of the node's log output with the given directives,
+/// e.g. `info,irys_p2p=debug`
+pub type LogFilterReload = Arc<dyn Fn(&str) -> eyre::Result<()> + Send + Sync>;
+
+#[derive(Clone)]
+struct AdminState {
+    node: IrysNodeCtx,
+    /// SHA-256 digests of the accepted tokens, compared instead of the tokens so the time taken
+    /// by a comparison reveals nothing about them
+    token_digests: Arc<Vec<[u8; 32]>>,
+    log_filter_reload: Option<LogFilterReload>,
+}
+
+fn token_digest(token: &str) -> [u8; 32] {
+    Sha256::digest(token.as_bytes()).into()
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TrustedPeerRequest {
+    pub api: SocketAddr,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LogLevelRequest {
+    pub filter: String,
+}
+
+pub fn run_admin_server(
+    node: IrysNodeCtx,
+    config: &AdminApiConfig,
+    listener: TcpListener,
+    log_filter_reload: Option<LogFilterReload>,
+) -> eyre::Result<Server> {
+    eyre::ensure!(
+        !config.tokens.is_empty(),
+        "the admin API requires at least one token"
+    );
+    let port = listener.local_addr()?.port();
+    info!(?port, "Starting admin API server");
+
+    let state = AdminState {
+        node,
+        token_digests: Arc::new(config.tokens.iter().map(|t| token_digest(t)).collect()),
+        log_filter_reload,
+    };
+    let server = HttpServer::new(move || {
+        App::new()
+            .app_data(web::Data::new(state.clone()))
+            .service(
+                web::scope("v1")
+                    .route("/mining/start", web::post().to(start_mining))
+                    .route("/mining/stop", web::post().to(stop_mining))
+                    .route("/peers/flush", web::post().to(flush_peer_list))
+                    .route("/peers/trusted", web::get().to(trusted_peers))
+                    .route("/peers/trusted", web::post().to(add_trusted_peer))
+                    .route(
+                        "/peers/trusted/{api_address}",
+                        web::delete().to(remove_trusted_peer),
+                    )
+                    .route("/cache/prune", web::post().to(prune_cache))
+                    .route("/log_level", web::put().to(set_log_level))
+                    .route("/shutdown", web::post().to(shutdown)),
+            )
+            .wrap(from_fn(authenticate))
+    })
+    .workers(1)
+    .listen(listener)?
+    .run();
+    Ok(server)
+}
+
+async fn authenticate(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, actix_web::Error> {
+    let state = req
+        .app_data::<web::Data<AdminState>>()
+        .expect("admin state to be registered");
+    let authorized = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|token| state.token_digests.contains(&token_digest(token)));
+
+    if !authorized {
+        warn!(
+            peer = ?req.peer_addr(),
+            path = req.path(),
+            "Rejected unauthenticated admin request"
+        );
+        let response = HttpResponse::Unauthorized()
+            .insert_header((header::WWW_AUTHENTICATE, "Bearer"))
+            .finish();
+        return Err(InternalError::from_response("unauthorized", response).into());
+    }
+    next.call(req).await
+}
+
+async fn start_mining(state: web::Data<AdminState>) -> HttpResponse {
+    info!("Starting mining on admin request");
+    match state.node.start_mining().await {
+        Ok(()) => HttpResponse::Ok().finish(),
+        Err(e) => {
+            HttpResponse::InternalServerError().body(format!("Failed to start mining: {}", e))
+        }
+    }
+}
+
+async fn stop_mining(state: web::Data<AdminState>) -> HttpResponse {
+    info!("Stopping mining on admin request");
+    match state.node.stop_mining().await {
+        Ok(()) => HttpResponse::Ok().finish(),
+        Err(e) => HttpResponse::InternalServerError().body(format!("Failed to stop mining: {}", e)),
+    }
+}
+
+async fn flush_peer_list(state: web::Data<AdminState>) -> HttpResponse {
+    match state.node.peer_list.flush().await {
+        Ok(()) => HttpResponse::Ok().finish(),
+        Err(e) => {
+            HttpResponse::InternalServerError().body(format!("Failed to flush peer list: {}", e))
+        }
+    }
+}
+
+async fn trusted_peers(state: web::Data<AdminState>) -> HttpResponse {
+    match state.node.peer_list.trusted_peer_addresses().await {
+        Ok(addresses) => HttpResponse::Ok().json(addresses),
+        Err(e) => HttpResponse::InternalServerError().body(format!("Failed to fetch peers: {}", e)),
+    }
+}
+
+/// Trusts a peer until the node restarts, the trusted peers of the config are left untouched
+async fn add_trusted_peer(
+    state: web::Data<AdminState>,
+    body: web::Json<TrustedPeerRequest>,
+) -> HttpResponse {
+    match state.node.peer_list.add_trusted_peer(body.api).await {
+        Ok(()) => HttpResponse::Ok().finish(),
+        Err(e) => {
+            HttpResponse::InternalServerError().body(format!("Failed to add trusted peer: {}", e))
+        }
+    }
+}
+
+async fn remove_trusted_peer(
+    state: web::Data<AdminState>,
+    path: web::Path<SocketAddr>,
+) -> HttpResponse {
+    match state.node.peer_list.remove_trusted_peer(*path).await {
+        Ok(true) => HttpResponse::Ok().finish(),
+        Ok(false) => HttpResponse::NotFound().body(format!("{} is not a trusted peer", path)),
+        Err(e) => HttpResponse::InternalServerError()
+            .body(format!("Failed to remove trusted peer: {}", e)),
+    }
+}
+
+/// Prunes the chunk cache as if the latest block of the block index was just finalized
+async fn prune_cache(state: web::Data<AdminState>) -> HttpResponse {
+    let finalized_height = state.node.block_index_guard.read().latest_height();
+    let (tx, rx) = oneshot::channel();
+    let cache_service = &state.node.service_senders.chunk_cache;
+    if let Err(e) = cache_service.send(CacheServiceAction::OnFinalizedBlock(
+        finalized_height,
+        Some(tx),
+    )) {
+        return HttpResponse::InternalServerError()
+            .body(format!("Failed to reach the cache service: {}", e));
+    }
+    match rx.await {
+        Ok(Ok(())) => HttpResponse::Ok().finish(),
+        Ok(Err(e)) => {
+            HttpResponse::InternalServerError().body(format!("Failed to prune the cache: {}", e))
+        }
+        Err(e) => HttpResponse::InternalServerError()
+            .body(format!("Failed to reach the cache service: {}", e)),
+    }
+}
+
+async fn set_log_level(
+    state: web::Data<AdminState>,
+    body: web::Json<LogLevelRequest>,
+) -> HttpResponse {
+    let Some(reload) = &state.log_filter_reload else {
+        return HttpResponse::NotImplemented()
+            .body("The log filter of this node can't be changed at runtime");
+    };
+    match reload(&body.filter) {
+        Ok(()) => {
+            info!(filter = %body.filter, "Changed the log filter on admin request");
+            HttpResponse::Ok().finish()
+        }
+        Err(e) => HttpResponse::BadRequest().body(format!("Invalid log filter: {}", e)),
+    }
+}
+
+/// Stops mining and signals the node to shut down, which also stops this server. Returns before
+/// the shutdown completes
+async fn shutdown(state: web::Data<AdminState>) -> HttpResponse {
+    info!("Shutting down on admin request");
+    let node = state.node.clone();
+    actix_rt::spawn(async move {
+        if let Err(e) = node.stop_mining().await {
+            warn!("Failed to stop mining before shutting down: {:?}", e);
+        }
+        // stopping reth propagates to the rest of the node, as it does on ctrl-c
+        let _ = node.reth_shutdown_sender.send(()).await;
+    });
+    HttpResponse::Accepted().finish()
+}
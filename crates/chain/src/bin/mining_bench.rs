@@ -0,0 +1,317 @@
+//! Benchmarks the mining hardware of the given storage modules without producing blocks.
+//!
+//! Runs a `PartitionMiningActor` per storage module against synthetic VDF seeds,
+//! as fast as the storage and CPU allow, then reports per partition the chunks
+//! read per second, hashes per second, recall range read latency and the
+//! expected time to find a solution at the network difficulty. VDF steps arrive
+//! at the rate this machine computes them with the VDF parameters of the loaded
+//! consensus config, which is measured before mining. The difficulty
+//! defaults to the initial difficulty of a network made of the benchmarked
+//! partitions, pass `--difficulty` to compare against the current network
+//! difficulty instead.
+use actix::{Actor, Addr, Arbiter, Context, Handler};
+use clap::Parser;
+use futures::future::try_join_all;
+use irys_actors::{
+    block_producer::SolutionFoundMessage,
+    broadcast_mining_service::BroadcastMiningSeed,
+    mining::{GetPartitionMiningStatsMessage, PartitionMiningActor, PartitionMiningStats},
+    packing::{PackingActor, PackingConfig},
+    vdf_service::{calc_capacity, VdfState, VdfStepsReadGuard},
+};
+use irys_storage::{StorageModule, StorageModuleInfo};
+use irys_types::{
+    block_production::Seed, calculate_initial_difficulty, Config, H256List, IrysBlockHeader,
+    NodeConfig, H256, U256,
+};
+use irys_vdf::vdf_sha;
+use reth::{payload::EthBuiltPayload, tasks::TaskManager};
+use sha2::{Digest as _, Sha256};
+use std::{
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, RwLock,
+    },
+    time::{Duration, Instant},
+};
+use tracing::{info, Span};
+use tracing_subscriber::{layer::SubscriberExt as _, util::SubscriberInitExt as _, EnvFilter};
+
+#[derive(Debug, Parser)]
+#[command(name = "irys-mining-bench")]
+struct Args {
+    /// Node config file, defaults to $CONFIG or ./config.toml
+    #[arg(long)]
+    config: Option<PathBuf>,
+    /// Number of synthetic VDF steps to mine
+    #[arg(long, default_value_t = 60)]
+    steps: u64,
+    /// Network difficulty (decimal) to estimate the time to a solution with
+    #[arg(long)]
+    difficulty: Option<String>,
+    /// Storage module info JSON files of the partitions to benchmark
+    #[arg(required = true)]
+    storage_modules: Vec<PathBuf>,
+}
+
+/// Discards the solutions of the benchmarked partitions, none are expected as
+/// they mine against the maximum difficulty
+struct SolutionSink;
+
+impl Actor for SolutionSink {
+    type Context = Context<Self>;
+}
+
+impl Handler<SolutionFoundMessage> for SolutionSink {
+    type Result = eyre::Result<Option<(Arc<IrysBlockHeader>, EthBuiltPayload)>>;
+
+    fn handle(&mut self, _msg: SolutionFoundMessage, _: &mut Context<Self>) -> Self::Result {
+        Ok(None)
+    }
+}
+
+#[actix_web::main]
+async fn main() -> eyre::Result<()> {
+    tracing_subscriber::registry()
+        .with(
+            EnvFilter::new("warn")
+                .add_directive(EnvFilter::from_default_env().to_string().parse()?),
+        )
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_line_number(true)
+                .with_file(true),
+        )
+        .init();
+    color_eyre::install()?;
+
+    let args = Args::parse();
+    let config_path = args
+        .config
+        .or_else(|| std::env::var("CONFIG").ok().map(PathBuf::from))
+        .unwrap_or_else(|| {
+            std::env::current_dir()
+                .expect("working dir")
+                .join("config.toml")
+        });
+    let node_config: NodeConfig = toml::from_str(&std::fs::read_to_string(&config_path)?)?;
+    let config = Config::new(node_config);
+
+    let storage_modules = args
+        .storage_modules
+        .iter()
+        .map(|path| {
+            let info = StorageModuleInfo::from_json(path)?;
+            Ok(Arc::new(StorageModule::new(&info, &config)?))
+        })
+        .collect::<eyre::Result<Vec<_>>>()?;
+
+    let network_difficulty = match &args.difficulty {
+        Some(difficulty) => U256::from_dec_str(difficulty)
+            .map_err(|e| eyre::eyre!("invalid difficulty {}: {:?}", difficulty, e))?,
+        None => calculate_initial_difficulty(&config.consensus, storage_modules.len() as u64)?,
+    };
+
+    // the packing actor is only used to repack expired partitions, which never happens here
+    let task_manager = TaskManager::current();
+    let packing = PackingActor::new(
+        task_manager.executor(),
+        storage_modules.iter().map(|sm| sm.id).collect(),
+        PackingConfig::new(&config),
+    )
+    .start();
+    let solution_sink = SolutionSink.start();
+
+    let vdf_state = Arc::new(RwLock::new(VdfState {
+        capacity: calc_capacity(&config),
+        ..VdfState::default()
+    }));
+    let global_step = Arc::new(AtomicU64::new(0));
+    let solved_step_number = Arc::new(AtomicU64::new(0));
+    let mut miners: Vec<(Arc<StorageModule>, Addr<PartitionMiningActor>)> = Vec::new();
+    let mut part_arbiters = Vec::new();
+    for sm in storage_modules {
+        // the maximum difficulty makes every partition hash its whole recall range
+        let partition_mining_actor = PartitionMiningActor::new(
+            &config,
+            solution_sink.clone().recipient(),
+            packing.clone().recipient(),
+            sm.clone(),
+            true,
+            VdfStepsReadGuard::new(vdf_state.clone()),
+            global_step.clone(),
+            solved_step_number.clone(),
+            U256::MAX,
+            Some(Span::current()),
+        );
+        let part_arbiter = Arbiter::new();
+        let addr = PartitionMiningActor::start_in_arbiter(&part_arbiter.handle(), |_| {
+            partition_mining_actor
+        });
+        miners.push((sm, addr));
+        part_arbiters.push(part_arbiter);
+    }
+
+    let vdf_step_time = measure_vdf_step_time(&config);
+    info!(
+        partitions = miners.len(),
+        vdf_step_ms = vdf_step_time.as_secs_f64() * 1000.0,
+        steps = args.steps,
+        "Running mining benchmark"
+    );
+    let started = Instant::now();
+    for step in 1..=args.steps {
+        let seed = Seed(H256::random());
+        vdf_state.write().unwrap().increment_step(seed.clone());
+        global_step.store(step, Ordering::Relaxed);
+        for (_, miner) in &miners {
+            miner.do_send(BroadcastMiningSeed {
+                seed: seed.clone(),
+                checkpoints: H256List(vec![]),
+                global_step: step,
+            });
+        }
+        // wait for every partition to finish the step so none of them lags behind and skips seeds
+        try_join_all(
+            miners
+                .iter()
+                .map(|(_, miner)| miner.send(GetPartitionMiningStatsMessage)),
+        )
+        .await?;
+    }
+    let elapsed = started.elapsed();
+
+    let stats = try_join_all(
+        miners
+            .iter()
+            .map(|(_, miner)| miner.send(GetPartitionMiningStatsMessage)),
+    )
+    .await?;
+
+    print_report(
+        &config,
+        network_difficulty,
+        vdf_step_time,
+        elapsed,
+        miners.iter().map(|(sm, _)| sm.as_ref()).zip(stats.iter()),
+    );
+
+    for part_arbiter in part_arbiters {
+        part_arbiter.stop();
+    }
+    Ok(())
+}
+
+fn print_report<'a>(
+    config: &Config,
+    network_difficulty: U256,
+    vdf_step_time: Duration,
+    elapsed: Duration,
+    partitions: impl Iterator<Item = (&'a StorageModule, &'a PartitionMiningStats)>,
+) {
+    let chunks_per_step = config.consensus.num_chunks_in_recall_range as f64;
+    let solution_probability = u256_to_f64(U256::MAX - network_difficulty) / u256_to_f64(U256::MAX);
+
+    println!(
+        "Mined synthetic VDF steps for {:.1}s against difficulty {}, with a VDF step time of {:.1}ms",
+        elapsed.as_secs_f64(),
+        network_difficulty,
+        vdf_step_time.as_secs_f64() * 1000.0
+    );
+    println!(
+        "{:>6}  {:<66}  {:>12}  {:>12}  {:>12}  {:>12}  {:>16}",
+        "module",
+        "partition",
+        "chunks/s",
+        "hashes/s",
+        "avg read ms",
+        "max read ms",
+        "time to solution"
+    );
+
+    // hashes per VDF step the whole farm can sustain
+    let mut farm_hashes_per_step = 0.0;
+    for (sm, stats) in partitions {
+        let partition = sm
+            .partition_hash()
+            .map_or_else(|| "unassigned".to_string(), |hash| format!("{:?}", hash));
+        if stats.recall_ranges_read == 0 {
+            println!("{:>6}  {:<66}  no recall range mined", sm.id, partition);
+            continue;
+        }
+
+        let avg_read_time = stats.read_time / stats.recall_ranges_read as u32;
+        let avg_step_time = stats.mining_time / stats.recall_ranges_read as u32;
+        // fraction of the recall ranges the partition mines when steps arrive at the VDF step rate
+        let keep_up = (vdf_step_time.as_secs_f64() / avg_step_time.as_secs_f64()).min(1.0);
+        let hashes_per_step = chunks_per_step * keep_up;
+        farm_hashes_per_step += hashes_per_step;
+
+        println!(
+            "{:>6}  {:<66}  {:>12.0}  {:>12.0}  {:>12.2}  {:>12.2}  {:>16}",
+            sm.id,
+            partition,
+            per_second(stats.chunks_read, stats.read_time),
+            per_second(stats.hashes, stats.hash_time()),
+            avg_read_time.as_secs_f64() * 1000.0,
+            stats.max_read_time.as_secs_f64() * 1000.0,
+            format_expected_time(vdf_step_time, solution_probability * hashes_per_step)
+        );
+    }
+
+    println!(
+        "Expected time to a solution for all partitions: {}",
+        format_expected_time(vdf_step_time, solution_probability * farm_hashes_per_step)
+    );
+}
+
+/// Times a VDF step computed with the VDF parameters of the consensus config, the
+/// interval at which the partitions get a new recall range to mine on this machine
+fn measure_vdf_step_time(config: &Config) -> Duration {
+    let vdf = &config.consensus.vdf;
+    let mut checkpoints = vec![H256::default(); vdf.num_checkpoints_in_vdf_step];
+    let started = Instant::now();
+    vdf_sha(
+        &mut Sha256::new(),
+        &mut U256::zero(),
+        &mut H256::random(),
+        vdf.num_checkpoints_in_vdf_step,
+        vdf.sha_1s_difficulty,
+        &mut checkpoints,
+    );
+    started.elapsed()
+}
+
+fn per_second(count: u64, time: Duration) -> f64 {
+    if time.is_zero() {
+        0.0
+    } else {
+        count as f64 / time.as_secs_f64()
+    }
+}
+
+/// Formats the expected time to a solution given the probability of finding one per VDF step
+fn format_expected_time(vdf_step_time: Duration, solutions_per_step: f64) -> String {
+    if solutions_per_step <= 0.0 {
+        return "never".to_string();
+    }
+    let seconds = vdf_step_time.as_secs_f64() / solutions_per_step;
+    if seconds < 120.0 {
+        format!("{:.1}s", seconds)
+    } else if seconds < 2.0 * 3600.0 {
+        format!("{:.1}m", seconds / 60.0)
+    } else if seconds < 2.0 * 86400.0 {
+        format!("{:.1}h", seconds / 3600.0)
+    } else {
+        format!("{:.1}d", seconds / 86400.0)
+    }
+}
+
+fn u256_to_f64(value: U256) -> f64 {
+    value
+        .0
+        .iter()
+        .rev()
+        .fold(0.0, |acc, limb| acc * 2f64.powi(64) + *limb as f64)
+}
@@ -0,0 +1,119 @@
+//! Mines the configured storage modules on behalf of a remote node's mining pool.
+//!
+//! The worker loads the same `config.toml` as a node and reads the
+//! `[mining_pool_worker]` section for the pool address and the storage modules
+//! to mine. The modules have to be packed for the node's mining address.
+use actix::{Actor as _, Arbiter, SystemRegistry};
+use irys_actors::{
+    broadcast_mining_service::BroadcastMiningService,
+    mining::PartitionMiningActor,
+    mining_pool::MiningPoolWorker,
+    packing::{PackingActor, PackingConfig},
+    vdf_service::{calc_capacity, VdfState, VdfStepsReadGuard},
+};
+use irys_storage::{StorageModule, StorageModuleInfo};
+use irys_types::{Config, NodeConfig};
+use reth::tasks::TaskManager;
+use std::{
+    path::PathBuf,
+    sync::{atomic::AtomicU64, Arc, RwLock},
+};
+use tracing::{info, Span};
+use tracing_subscriber::{layer::SubscriberExt as _, util::SubscriberInitExt as _, EnvFilter};
+
+#[actix_web::main]
+async fn main() -> eyre::Result<()> {
+    tracing_subscriber::registry()
+        .with(
+            EnvFilter::new("info")
+                .add_directive(EnvFilter::from_default_env().to_string().parse()?),
+        )
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_line_number(true)
+                .with_file(true),
+        )
+        .init();
+    color_eyre::install()?;
+
+    let config_path = std::env::var("CONFIG")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| {
+            std::env::current_dir()
+                .expect("working dir")
+                .join("config.toml")
+        });
+    let node_config: NodeConfig = toml::from_str(&std::fs::read_to_string(&config_path)?)?;
+    let config = Config::new(node_config);
+    let worker_config = config
+        .node_config
+        .mining_pool_worker
+        .clone()
+        .ok_or_else(|| eyre::eyre!("missing [mining_pool_worker] section in {:?}", config_path))?;
+
+    let storage_modules = worker_config
+        .storage_modules
+        .iter()
+        .map(|path| {
+            let info = StorageModuleInfo::from_json(path)?;
+            Ok(Arc::new(StorageModule::new(&info, &config)?))
+        })
+        .collect::<eyre::Result<Vec<_>>>()?;
+
+    // the local miners get their seeds and difficulty from the pool through the broadcaster
+    let broadcast_arbiter = Arbiter::new();
+    let broadcaster = BroadcastMiningService::start_in_arbiter(&broadcast_arbiter.handle(), |_| {
+        BroadcastMiningService::new(Some(Span::current()))
+    });
+    SystemRegistry::set(broadcaster);
+
+    let vdf_state = Arc::new(RwLock::new(VdfState {
+        capacity: calc_capacity(&config),
+        ..VdfState::default()
+    }));
+    let global_step = Arc::new(AtomicU64::new(0));
+    let (worker, difficulty, relay) = MiningPoolWorker::connect(
+        &config,
+        worker_config.pool_address,
+        vdf_state.clone(),
+        global_step.clone(),
+    )
+    .await?;
+
+    // repacks the partitions the pool reports as expired
+    let task_manager = TaskManager::current();
+    let packing = PackingActor::new(
+        task_manager.executor(),
+        storage_modules.iter().map(|sm| sm.id).collect(),
+        PackingConfig::new(&config),
+    )
+    .start();
+
+    let solved_step_number = Arc::new(AtomicU64::new(0));
+    let mut part_arbiters = Vec::new();
+    for sm in storage_modules {
+        let partition_mining_actor = PartitionMiningActor::new(
+            &config,
+            worker.clone().recipient(),
+            packing.clone().recipient(),
+            sm,
+            true,
+            VdfStepsReadGuard::new(vdf_state.clone()),
+            global_step.clone(),
+            solved_step_number.clone(),
+            difficulty,
+            Some(Span::current()),
+        );
+        let part_arbiter = Arbiter::new();
+        PartitionMiningActor::start_in_arbiter(&part_arbiter.handle(), |_| partition_mining_actor);
+        part_arbiters.push(part_arbiter);
+    }
+    info!(
+        pool = %worker_config.pool_address,
+        partitions = part_arbiters.len(),
+        "Mining for the pool"
+    );
+
+    // mine until the pool connection drops
+    relay.await?
+}
@@ -1,3 +1,4 @@
+use crate::admin_api::{run_admin_server, LogFilterReload};
 use crate::peer_utilities::{fetch_genesis_block, fetch_genesis_commitments};
 use crate::vdf::run_vdf;
 use actix::{Actor, Addr, Arbiter, System, SystemRegistry};
@@ -18,6 +19,7 @@ use irys_actors::{
     mempool_service::MempoolService,
     mempool_service::MempoolServiceFacadeImpl,
     mining::{MiningControl, PartitionMiningActor},
+    mining_pool::MiningPoolService,
     packing::{PackingActor, PackingConfig, PackingRequest},
     reth_service::{
         BlockHashType, ForkChoiceUpdateMessage, GetPeeringInfoMessage, RethServiceActor,
@@ -37,7 +39,8 @@ use irys_database::{
     add_genesis_commitments, database, get_genesis_commitments, BlockIndex, SystemLedger,
 };
 use irys_p2p::{
-    P2PService, PeerListService, PeerListServiceFacade, ServiceHandleWithShutdownSignal, SyncState,
+    BlockPoolReadGuard, P2PService, PeerChunkFetcher, PeerListService, PeerListServiceFacade,
+    ServiceHandleWithShutdownSignal, SyncState,
 };
 use irys_price_oracle::{mock_oracle::MockOracle, IrysPriceOracle};
 use irys_reth_node_bridge::node::RethNode;
@@ -54,7 +57,7 @@ use irys_storage::{
 };
 use irys_types::{
     app_state::DatabaseProvider, calculate_initial_difficulty, ArbiterHandle, CloneableJoinHandle,
-    CommitmentTransaction, Config, IrysBlockHeader, NodeConfig, NodeMode, OracleConfig,
+    CommitmentTransaction, Config, GossipData, IrysBlockHeader, NodeConfig, NodeMode, OracleConfig,
     PartitionChunkRange, H256, U256,
 };
 use reth::{
@@ -88,6 +91,7 @@ pub struct IrysNodeCtx {
     pub block_index_guard: BlockIndexReadGuard,
     pub block_tree_guard: BlockTreeReadGuard,
     pub vdf_steps_guard: VdfStepsReadGuard,
+    pub commitment_state_guard: CommitmentStateReadGuard,
     pub service_senders: ServiceSenders,
     // Shutdown channels
     pub reth_shutdown_sender: tokio::sync::mpsc::Sender<()>,
@@ -96,6 +100,7 @@ pub struct IrysNodeCtx {
     stop_guard: StopGuard,
     pub peer_list: PeerListServiceFacade,
     pub sync_state: SyncState,
+    pub block_pool: BlockPoolReadGuard,
 }
 
 impl IrysNodeCtx {
@@ -112,6 +117,9 @@ impl IrysNodeCtx {
             block_tree: self.block_tree_guard.clone(),
             block_index: self.block_index_guard.clone(),
             sync_state: self.sync_state.clone(),
+            block_pool: self.block_pool.clone(),
+            packing: self.actor_addresses.packing.clone(),
+            commitment_state: self.commitment_state_guard.clone(),
         }
     }
 
@@ -244,6 +252,8 @@ pub struct IrysNode {
     pub config: Config,
     pub http_listener: TcpListener,
     pub gossip_listener: TcpListener,
+    pub admin_listener: Option<TcpListener>,
+    pub log_filter_reload: Option<LogFilterReload>,
 }
 
 impl IrysNode {
@@ -292,14 +302,42 @@ impl IrysNode {
             node_config.gossip.public_port = node_config.gossip.bind_port;
         }
 
+        let admin_listener = match node_config.admin_api.as_mut() {
+            Some(admin_config) => {
+                let admin_listener = create_listener(
+                    format!("{}:{}", &admin_config.bind_ip, &admin_config.bind_port)
+                        .parse()
+                        .expect("A valid admin API IP & port"),
+                )?;
+                if admin_config.bind_port == 0 {
+                    admin_config.bind_port = admin_listener.local_addr()?.port();
+                }
+                Some(admin_listener)
+            }
+            None => None,
+        };
+
         let config = Config::new(node_config);
+        // promoted txs carry this many proofs, block validation splits them by it
+        eyre::ensure!(
+            config.consensus.number_of_ingress_proofs >= 1,
+            "number_of_ingress_proofs must be at least 1"
+        );
         Ok(IrysNode {
             config,
             http_listener,
             gossip_listener,
+            admin_listener,
+            log_filter_reload: None,
         })
     }
 
+    /// Lets the admin API replace the filter of the node's log output
+    pub fn with_log_filter_reload(mut self, log_filter_reload: LogFilterReload) -> Self {
+        self.log_filter_reload = Some(log_filter_reload);
+        self
+    }
+
     async fn get_or_create_genesis_info(
         &self,
         node_mode: &NodeMode,
@@ -451,6 +489,7 @@ impl IrysNode {
         for commitment_tx in genesis_commitments {
             debug!("Persisting genesis commitment: {}", commitment_tx.id);
             database::insert_commitment_tx(&write_tx, commitment_tx)?;
+            database::index_commitment_tx(&write_tx, commitment_tx)?;
         }
 
         // Commit the database transaction
@@ -532,6 +571,8 @@ impl IrysNode {
             irys_db,
             block_index,
             self.gossip_listener,
+            self.admin_listener,
+            self.log_filter_reload,
         )?;
 
         // await the latest height to be reported
@@ -569,6 +610,11 @@ impl IrysNode {
         );
 
         let latest_known_block_height = ctx.block_index_guard.read().latest_height();
+        // The header the blocks synced from untrusted peers have to extend
+        let latest_block = match ctx.block_index_guard.read().get_latest_item() {
+            Some(item) => database::block_header_by_hash(&ctx.db.tx()?, &item.block_hash, false)?,
+            None => None,
+        };
         // This is going to resolve instantly for a genesis node with 0 blocks,
         //  going to wait for sync otherwise.
         irys_p2p::sync_chain(
@@ -577,7 +623,10 @@ impl IrysNode {
             ctx.peer_list.clone(),
             node_mode,
             latest_known_block_height as usize,
+            latest_block,
             ctx.config.node_config.genesis_peer_discovery_timeout_millis,
+            &ctx.config,
+            ctx.service_senders.synced_blocks.clone(),
         )
         .await?;
 
@@ -599,6 +648,8 @@ impl IrysNode {
         irys_db: DatabaseProvider,
         block_index: BlockIndex,
         gossip_listener: TcpListener,
+        admin_listener: Option<TcpListener>,
+        log_filter_reload: Option<LogFilterReload>,
     ) -> Result<JoinHandle<RethNodeProvider>, eyre::Error> {
         let span = Span::current();
         let actor_main_thread_handle = std::thread::Builder::new()
@@ -618,7 +669,7 @@ impl IrysNode {
                         let block_index_service_actor = Self::init_block_index_service(&config, &block_index);
 
                         // start the rest of the services
-                        let (irys_node, actix_server, vdf_thread, reth_node, gossip_service_handle) = Self::init_services(
+                        let (irys_node, actix_server, admin_server, vdf_thread, reth_node, gossip_service_handle) = Self::init_services(
                                 &config,
                                 reth_shutdown_sender,
                                 vdf_shutdown_receiver,
@@ -630,7 +681,9 @@ impl IrysNode {
                                 &task_exec,
                                 http_listener,
                                 irys_db,
-                                gossip_listener
+                                gossip_listener,
+                                admin_listener,
+                                log_filter_reload,
                             )
                             .instrument(Span::current())
                             .await
@@ -643,6 +696,11 @@ impl IrysNode {
 
                         // await on actix web server
                         let server_handle = actix_server.handle();
+                        let admin_server_handle = admin_server.map(|admin_server| {
+                            let handle = admin_server.handle();
+                            actix_rt::spawn(admin_server);
+                            handle
+                        });
 
                         let server_stop_handle = actix_rt::spawn(async move {
                             let _ = main_actor_thread_shutdown_rx.recv().await;
@@ -651,6 +709,11 @@ impl IrysNode {
                             debug!("Stopping API server");
                             server_handle.stop(true).await;
                             info!("API server stopped");
+
+                            if let Some(admin_server_handle) = admin_server_handle {
+                                admin_server_handle.stop(true).await;
+                                info!("Admin API server stopped");
+                            }
                         });
 
                         actix_server.await.unwrap();
@@ -762,9 +825,12 @@ impl IrysNode {
         http_listener: TcpListener,
         irys_db: DatabaseProvider,
         gossip_listener: TcpListener,
+        admin_listener: Option<TcpListener>,
+        log_filter_reload: Option<LogFilterReload>,
     ) -> eyre::Result<(
         IrysNodeCtx,
         Server,
+        Option<Server>,
         JoinHandle<()>,
         RethNodeProvider,
         ServiceHandleWithShutdownSignal,
@@ -834,8 +900,10 @@ impl IrysNode {
         let p2p_service = P2PService::new(
             config.node_config.miner_address(),
             receivers.gossip_broadcast,
+            receivers.synced_blocks,
         );
         let sync_state = p2p_service.sync_state.clone();
+        let block_pool = p2p_service.block_pool.clone();
 
         // start the block tree service
         let (block_tree_service, block_tree_arbiter) = Self::init_block_tree_service(
@@ -925,7 +993,7 @@ impl IrysNode {
             &service_senders,
             &epoch_service_actor,
             &block_index_guard,
-            partition_assignments_guard,
+            partition_assignments_guard.clone(),
             &vdf_steps_guard,
             Arc::clone(&reward_curve),
         );
@@ -941,6 +1009,10 @@ impl IrysNode {
             service_senders.vdf_seed.clone(),
             gossip_listener,
             service_senders.vdf.clone(),
+            config.consensus.vdf.clone(),
+            latest_block.vdf_limiter_info.seed,
+            service_senders.gossip_broadcast.clone(),
+            config.node_config.block_pool.clone(),
         )?;
 
         // set up the price oracle
@@ -986,6 +1058,17 @@ impl IrysNode {
             latest_block.diff,
         );
 
+        // set up the mining pool for remote workers
+        let mining_pool = Self::init_mining_pool(
+            &config,
+            &block_producer_addr,
+            &block_index_guard,
+            &partition_assignments_guard,
+            &vdf_steps_guard,
+            latest_block.diff,
+        )
+        .await?;
+
         // set up the vdf thread
         let vdf_thread_handler = Self::init_vdf_thread(
             &config,
@@ -998,10 +1081,12 @@ impl IrysNode {
             broadcast_mining_actor,
             service_senders.vdf.clone(),
             atomic_global_step_number,
+            service_senders.gossip_broadcast.clone(),
         );
 
         // set up chunk provider
-        let chunk_provider = Self::init_chunk_provider(&config, storage_modules_guard);
+        let chunk_provider =
+            Self::init_chunk_provider(&config, storage_modules_guard, &irys_db, &peer_list_service);
 
         // set up IrysNodeCtx
         let irys_node_ctx = IrysNodeCtx {
@@ -1014,6 +1099,7 @@ impl IrysNode {
                 block_index: block_index_service_actor,
                 epoch_service: epoch_service_actor,
                 reth: reth_service_actor,
+                mining_pool,
             },
             arbiters: Arc::new(RwLock::new(Vec::new())),
             reward_curve,
@@ -1022,6 +1108,7 @@ impl IrysNode {
             chunk_provider: chunk_provider.clone(),
             block_index_guard: block_index_guard.clone(),
             vdf_steps_guard: vdf_steps_guard.clone(),
+            commitment_state_guard: commitment_state_guard.clone(),
             service_senders: service_senders.clone(),
             reth_shutdown_sender,
             reth_thread_handle: None,
@@ -1030,6 +1117,7 @@ impl IrysNode {
             stop_guard: StopGuard::new(),
             peer_list: peer_list_service.clone(),
             sync_state: sync_state.clone(),
+            block_pool: block_pool.clone(),
         };
 
         // Spawn the StorageModuleService to manage the lifecycle of storage modules
@@ -1101,11 +1189,24 @@ impl IrysNode {
                     .http_url()
                     .expect("Missing reth rpc url!"),
                 sync_state,
+                block_pool,
+                packing: irys_node_ctx.actor_addresses.packing.clone(),
+                commitment_state: commitment_state_guard,
             },
             http_listener,
         )
         .await;
 
+        let admin_server = match (&config.node_config.admin_api, admin_listener) {
+            (Some(admin_config), Some(admin_listener)) => Some(run_admin_server(
+                irys_node_ctx.clone(),
+                admin_config,
+                admin_listener,
+                log_filter_reload,
+            )?),
+            _ => None,
+        };
+
         // this OnceLock is due to the cyclic chain between Reth & the Irys node, where the IrysRethProvider requires both
         // this is "safe", as the OnceLock is always set before this start function returns
         let mut w = irys_provider
@@ -1118,6 +1219,7 @@ impl IrysNode {
         Ok((
             irys_node_ctx,
             server,
+            admin_server,
             vdf_thread_handler,
             reth_node,
             p2p_service_handle,
@@ -1127,8 +1229,19 @@ impl IrysNode {
     fn init_chunk_provider(
         config: &Config,
         storage_modules_guard: StorageModulesReadGuard,
+        irys_db: &DatabaseProvider,
+        peer_list_service: &PeerListServiceFacade,
     ) -> Arc<ChunkProvider> {
-        let chunk_provider = ChunkProvider::new(config.clone(), storage_modules_guard.clone());
+        let chunk_fetcher = PeerChunkFetcher::new(
+            peer_list_service.clone(),
+            config.node_config.http.peer_api_scheme.clone(),
+        );
+        let chunk_provider = ChunkProvider::new(
+            config.clone(),
+            storage_modules_guard.clone(),
+            irys_db.clone(),
+        )
+        .with_peer_source(Arc::new(chunk_fetcher));
         let chunk_provider = Arc::new(chunk_provider);
         chunk_provider
     }
@@ -1144,6 +1257,7 @@ impl IrysNode {
         broadcast_mining_actor: actix::Addr<BroadcastMiningService>,
         vdf_service: tokio::sync::mpsc::UnboundedSender<VdfServiceMessage>,
         atomic_global_step_number: Arc<AtomicU64>,
+        gossip_sender: UnboundedSender<GossipData>,
     ) -> JoinHandle<()> {
         let vdf_reset_seed = latest_block.vdf_limiter_info.seed;
         // FIXME: this should be controlled via a config parameter rather than relying on test-only artifact generation
@@ -1185,6 +1299,7 @@ impl IrysNode {
                     broadcast_mining_actor.clone(),
                     vdf_service.clone(),
                     atomic_global_step_number.clone(),
+                    gossip_sender,
                 )
             }
         });
@@ -1202,6 +1317,7 @@ impl IrysNode {
     ) -> (Vec<actix::Addr<PartitionMiningActor>>, Vec<Arbiter>) {
         let mut part_actors = Vec::new();
         let mut arbiters = Vec::new();
+        let solved_step_number = Arc::new(AtomicU64::new(0));
         for sm in storage_modules_guard.read().iter() {
             let partition_mining_actor = PartitionMiningActor::new(
                 &config,
@@ -1211,6 +1327,7 @@ impl IrysNode {
                 false, // do not start mining automatically
                 vdf_steps_guard.clone(),
                 atomic_global_step_number.clone(),
+                solved_step_number.clone(),
                 initial_difficulty,
                 Some(Span::current()),
             );
@@ -1236,6 +1353,31 @@ impl IrysNode {
         (part_actors, arbiters)
     }
 
+    async fn init_mining_pool(
+        config: &Config,
+        block_producer_addr: &actix::Addr<BlockProducerActor>,
+        block_index_guard: &BlockIndexReadGuard,
+        partition_assignments_guard: &irys_actors::epoch_service::PartitionAssignmentsReadGuard,
+        vdf_steps_guard: &VdfStepsReadGuard,
+        initial_difficulty: U256,
+    ) -> eyre::Result<Option<actix::Addr<MiningPoolService>>> {
+        let Some(pool_config) = config.node_config.mining_pool.clone() else {
+            return Ok(None);
+        };
+        let (mining_pool, local_addr) = MiningPoolService::spawn_service(
+            config,
+            pool_config,
+            block_producer_addr.clone().recipient(),
+            block_index_guard.clone(),
+            partition_assignments_guard.clone(),
+            vdf_steps_guard.clone(),
+            initial_difficulty,
+        )
+        .await?;
+        info!(?local_addr, "Mining pool service started");
+        Ok(Some(mining_pool))
+    }
+
     fn init_packing_actor(
         config: &Config,
         global_step_number: u64,
@@ -1418,6 +1560,7 @@ impl IrysNode {
             &config.node_config.miner_address(),
             block_index_guard.clone(),
             config.consensus.clone(),
+            config.node_config.fork_choice.clone(),
             service_senders.clone(),
         );
         let block_tree_arbiter = Arbiter::new();
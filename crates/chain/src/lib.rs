@@ -1,3 +1,4 @@
+pub mod admin_api;
 pub mod chain;
 pub mod genesis_utilities;
 pub mod peer_utilities;
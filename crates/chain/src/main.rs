@@ -1,16 +1,16 @@
-use irys_chain::IrysNode;
+use irys_chain::{admin_api::LogFilterReload, IrysNode};
 use irys_types::{NodeConfig, NodeMode};
-use std::path::PathBuf;
+use std::{path::PathBuf, sync::Arc};
 use tracing::{debug, info, warn};
 use tracing_error::ErrorLayer;
 use tracing_subscriber::{
-    layer::SubscriberExt, util::SubscriberInitExt as _, EnvFilter, Layer, Registry,
+    layer::SubscriberExt, reload, util::SubscriberInitExt as _, EnvFilter, Layer, Registry,
 };
 
 #[actix_web::main]
 async fn main() -> eyre::Result<()> {
     // init logging
-    init_tracing().expect("initializing tracing should work");
+    let log_filter_reload = init_tracing().expect("initializing tracing should work");
     color_eyre::install().expect("color eyre could not be installed");
 
     // load the config
@@ -43,7 +43,11 @@ async fn main() -> eyre::Result<()> {
 
     // start the node
     info!("starting the node, mode: {:?}", &config.mode);
-    let handle = IrysNode::new(config).await?.start().await?;
+    let handle = IrysNode::new(config)
+        .await?
+        .with_log_filter_reload(log_filter_reload)
+        .start()
+        .await?;
     handle.start_mining().await?;
     let reth_thread_handle = handle.reth_thread_handle.clone();
     // wait for the node to be shut down
@@ -57,10 +61,16 @@ async fn main() -> eyre::Result<()> {
     Ok(())
 }
 
-fn init_tracing() -> eyre::Result<()> {
+fn init_tracing() -> eyre::Result<LogFilterReload> {
     let subscriber = Registry::default();
     let filter =
         EnvFilter::new("info").add_directive(EnvFilter::from_default_env().to_string().parse()?);
+    // the admin API can replace the filter at runtime
+    let (filter, filter_handle) = reload::Layer::new(filter);
+    let log_filter_reload: LogFilterReload = Arc::new(move |directives: &str| {
+        filter_handle.reload(EnvFilter::try_new(directives)?)?;
+        Ok(())
+    });
 
     let output_layer = tracing_subscriber::fmt::layer()
         .with_line_number(true)
@@ -78,5 +88,5 @@ fn init_tracing() -> eyre::Result<()> {
 
     subscriber.init();
 
-    Ok(())
+    Ok(log_filter_reload)
 }
@@ -3,13 +3,22 @@ use irys_actors::{
     broadcast_mining_service::{BroadcastMiningSeed, BroadcastMiningService},
     vdf_service::VdfServiceMessage,
 };
-use irys_types::{block_production::Seed, AtomicVdfStepNumber, H256List, H256, U256};
-use irys_vdf::{apply_reset_seed, step_number_to_salt_number, vdf_sha};
+use irys_types::{
+    block_production::Seed, AtomicVdfStepNumber, GossipData, H256List, VdfStepGossip, H256, U256,
+};
+use irys_vdf::{apply_reset_seed, step_number_to_salt_number, vdf_sha_until};
 use sha2::{Digest, Sha256};
-use std::time::{Duration, Instant};
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
 use tokio::sync::mpsc::{Receiver, UnboundedSender};
 use tracing::{debug, info};
 
+/// Minimum time between two VDF steps gossiped to the peers, keeps a VDF running faster than
+/// the usual step time from flooding them
+const MIN_VDF_GOSSIP_INTERVAL: Duration = Duration::from_millis(500);
+
 pub fn run_vdf(
     config: &irys_types::VdfConfig,
     global_step_number: u64,
@@ -21,6 +30,7 @@ pub fn run_vdf(
     broadcast_mining_service: Addr<BroadcastMiningService>,
     vdf_service: UnboundedSender<VdfServiceMessage>,
     atomic_vdf_global_step: AtomicVdfStepNumber,
+    gossip_sender: UnboundedSender<GossipData>,
 ) {
     let mut hasher = Sha256::new();
     let mut hash: H256 = seed;
@@ -36,6 +46,9 @@ pub fn run_vdf(
 
     // maintain a state of whether or not this vdf loop should be mining
     let mut vdf_mining: bool = true;
+    let mut last_gossiped: Option<Instant> = None;
+    // fast forward steps received while computing a step, handled before the next one
+    let mut received_ff_steps: VecDeque<BroadcastMiningSeed> = VecDeque::new();
 
     loop {
         if shutdown_listener.try_recv().is_ok() {
@@ -44,15 +57,39 @@ pub fn run_vdf(
         };
 
         // check for VDF fast forward step
-        if let Ok(proposed_ff_to_mining_seed) = new_seed_listener.try_recv() {
+        if let Some(proposed_ff_to_mining_seed) = received_ff_steps
+            .pop_front()
+            .or_else(|| new_seed_listener.try_recv().ok())
+        {
             // if the step number is ahead of local nodes vdf steps
             if global_step_number < proposed_ff_to_mining_seed.global_step {
                 debug!(
                     "Fastforward Step {:?} with Seed {:?}",
                     proposed_ff_to_mining_seed.global_step, proposed_ff_to_mining_seed.seed
                 );
+                // Steps gossiped by peers come with their checkpoints, once verified the next
+                // one is published as if computed locally so the node mines on it right away
+                let is_next_verified_step = proposed_ff_to_mining_seed.global_step
+                    == global_step_number + 1
+                    && !proposed_ff_to_mining_seed.checkpoints.is_empty();
                 hash = proposed_ff_to_mining_seed.seed.0;
                 global_step_number = proposed_ff_to_mining_seed.global_step;
+                if is_next_verified_step {
+                    atomic_vdf_global_step
+                        .store(global_step_number, std::sync::atomic::Ordering::Relaxed);
+                    info!(
+                        "Seed verified {} step number {}",
+                        hash.clone(),
+                        global_step_number
+                    );
+                    if let Err(e) = vdf_service.send(VdfServiceMessage::VdfSeed(Seed(hash))) {
+                        panic!("Unable to send new Seed to VDF service: {:?}", e);
+                    }
+                    broadcast_mining_service.do_send(proposed_ff_to_mining_seed);
+                    if global_step_number % nonce_limiter_reset_frequency == 0 {
+                        hash = apply_reset_seed(hash, reset_seed);
+                    }
+                }
             } else {
                 debug!(
                     "Fastforward Step {} is not ahead of {}",
@@ -76,17 +113,36 @@ pub fn run_vdf(
         }
 
         let now = Instant::now();
+        let step_seed = hash;
 
         let mut salt = U256::from(step_number_to_salt_number(&config, global_step_number));
 
-        vdf_sha(
+        // A step arriving from a peer or a block while computing this one makes it moot, stop
+        // computing it so a node with a slower VDF keeps up with the network
+        let completed = vdf_sha_until(
             &mut hasher,
             &mut salt,
             &mut hash,
             config.num_checkpoints_in_vdf_step,
             config.sha_1s_difficulty,
             &mut checkpoints, // TODO: need to send also checkpoints to block producer for last_step_checkpoints?
+            || match new_seed_listener.try_recv() {
+                Ok(ff_step) => {
+                    let is_ahead = ff_step.global_step > global_step_number;
+                    received_ff_steps.push_back(ff_step);
+                    is_ahead
+                }
+                Err(_) => false,
+            },
         );
+        if !completed {
+            debug!(
+                "Step {} interrupted by a fast forward step",
+                global_step_number + 1
+            );
+            hash = step_seed;
+            continue;
+        }
 
         global_step_number += 1;
         atomic_vdf_global_step.store(global_step_number, std::sync::atomic::Ordering::Relaxed);
@@ -107,6 +163,15 @@ pub fn run_vdf(
             checkpoints: H256List(checkpoints.clone()),
             global_step: global_step_number,
         });
+        // let the peers follow this VDF, the gossip service drops it while syncing
+        if last_gossiped.is_none_or(|gossiped| gossiped.elapsed() >= MIN_VDF_GOSSIP_INTERVAL) {
+            last_gossiped = Some(Instant::now());
+            let _ = gossip_sender.send(GossipData::VdfStep(VdfStepGossip {
+                global_step: global_step_number,
+                seed: step_seed,
+                checkpoints: H256List(checkpoints.clone()),
+            }));
+        }
 
         if global_step_number % nonce_limiter_reset_frequency == 0 {
             // FIXME: is there an issue with reset_seed never changing here?
@@ -128,7 +193,7 @@ mod tests {
         vdf_service::test_helpers::mocked_vdf_service, vdf_service::vdf_steps_are_valid,
     };
     use irys_types::*;
-    use irys_vdf::vdf_sha_verification;
+    use irys_vdf::{vdf_sha, vdf_sha_verification};
     use nodit::interval::ii;
     use std::{
         sync::{atomic::AtomicU64, Arc},
@@ -216,6 +281,7 @@ mod tests {
         let (shutdown_tx, shutdown_rx) = mpsc::channel(1);
 
         let atomic_global_step_number = Arc::new(AtomicU64::new(0));
+        let (gossip_tx, _gossip_rx) = mpsc::unbounded_channel();
 
         let vdf_thread_handler = std::thread::spawn({
             let config = config.clone();
@@ -231,6 +297,7 @@ mod tests {
                     broadcast_mining_service,
                     tx,
                     atomic_global_step_number,
+                    gossip_tx,
                 )
             }
         });
@@ -295,4 +362,126 @@ mod tests {
         // Wait for vdf thread to finish
         vdf_thread_handler.join().unwrap();
     }
+
+    #[actix_rt::test]
+    async fn slower_vdf_follows_verified_steps() {
+        const STEPS: u64 = 10;
+        let mut node_config = NodeConfig::testnet();
+        node_config.consensus.get_mut().vdf.reset_frequency = 4;
+        node_config.consensus.get_mut().vdf.sha_1s_difficulty = 2_000;
+        let config = Config::new(node_config);
+        let vdf_config = config.consensus.vdf.clone();
+
+        let seed = H256::random();
+        let reset_seed = H256::random();
+
+        init_tracing();
+
+        // the steps of a faster node, computed the same way the VDF thread does
+        let started = Instant::now();
+        let mut hasher = Sha256::new();
+        let mut hash = seed;
+        let mut steps = Vec::new();
+        for global_step in 1..=STEPS {
+            let mut salt = U256::from(step_number_to_salt_number(&vdf_config, global_step - 1));
+            let mut checkpoints = vec![H256::default(); vdf_config.num_checkpoints_in_vdf_step];
+            vdf_sha(
+                &mut hasher,
+                &mut salt,
+                &mut hash,
+                vdf_config.num_checkpoints_in_vdf_step,
+                vdf_config.sha_1s_difficulty,
+                &mut checkpoints,
+            );
+            steps.push(BroadcastMiningSeed {
+                seed: Seed(hash),
+                checkpoints: H256List(checkpoints),
+                global_step,
+            });
+            if global_step % vdf_config.reset_frequency as u64 == 0 {
+                hash = apply_reset_seed(hash, reset_seed);
+            }
+        }
+        let computing_time = started.elapsed();
+
+        let broadcast_mining_service = BroadcastMiningService::from_registry();
+        let (new_seed_tx, new_seed_rx) = mpsc::channel::<BroadcastMiningSeed>(STEPS as usize);
+        let (_, mining_state_rx) = mpsc::channel::<bool>(1);
+        let (tx, _vdf_service_handle, _task_manager) = mocked_vdf_service(&config).await;
+        let (oneshot_tx, oneshot_rx) = tokio::sync::oneshot::channel();
+        tx.send(VdfServiceMessage::GetVdfStateMessage {
+            response: oneshot_tx,
+        })
+        .unwrap();
+        let vdf_steps = oneshot_rx.await.unwrap();
+        let (shutdown_tx, shutdown_rx) = mpsc::channel(1);
+        let atomic_global_step_number = Arc::new(AtomicU64::new(0));
+        let (gossip_tx, _gossip_rx) = mpsc::unbounded_channel();
+
+        let started = Instant::now();
+        let vdf_thread_handler = std::thread::spawn({
+            let vdf_config = vdf_config.clone();
+            let atomic_global_step_number = atomic_global_step_number.clone();
+            move || {
+                run_vdf(
+                    &vdf_config,
+                    0,
+                    seed,
+                    reset_seed,
+                    new_seed_rx,
+                    mining_state_rx,
+                    shutdown_rx,
+                    broadcast_mining_service,
+                    tx,
+                    atomic_global_step_number,
+                    gossip_tx,
+                )
+            }
+        });
+
+        // like the gossip server, only send the step following the local one
+        let mut sent = 0;
+        loop {
+            let local_step = atomic_global_step_number.load(std::sync::atomic::Ordering::Relaxed);
+            if local_step >= STEPS {
+                break;
+            }
+            if sent <= local_step {
+                new_seed_tx
+                    .send(steps[local_step as usize].clone())
+                    .await
+                    .unwrap();
+                sent = local_step + 1;
+            }
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+        let following_time = started.elapsed();
+        debug!(
+            "computed {} steps in {:.2?}, followed them in {:.2?}",
+            STEPS, computing_time, following_time
+        );
+        assert!(
+            following_time < computing_time / 2,
+            "following the steps took {:.2?}, computing them {:.2?}",
+            following_time,
+            computing_time
+        );
+
+        // the VDF service is updated asynchronously
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while vdf_steps.read().global_step < STEPS {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+        let expected = steps.iter().map(|step| step.seed.0).collect::<Vec<_>>();
+        assert_eq!(
+            vdf_steps.read().get_steps(ii(1, STEPS)).unwrap().0,
+            expected
+        );
+
+        shutdown_tx.send(()).await.unwrap();
+        vdf_thread_handler.join().unwrap();
+    }
 }
@@ -0,0 +1,125 @@
+//! checks the admin API authenticates its requests and reaches the node's controls
+use crate::utils::IrysNodeTest;
+use actix_http::StatusCode;
+use irys_types::{AdminApiConfig, NodeConfig};
+use serde_json::json;
+use std::net::SocketAddr;
+
+const TOKEN: &str = "admin-test-token";
+
+#[test_log::test(actix::test)]
+async fn heavy_admin_api_controls_the_node() -> eyre::Result<()> {
+    let mut config = NodeConfig::testnet();
+    config.admin_api = Some(AdminApiConfig {
+        bind_ip: "127.0.0.1".to_owned(),
+        bind_port: 0,
+        tokens: vec![TOKEN.to_owned()],
+    });
+    let node = IrysNodeTest::new_genesis(config)
+        .start_and_wait_for_packing("admin", 10)
+        .await;
+    let admin_port = node
+        .node_ctx
+        .config
+        .node_config
+        .admin_api
+        .as_ref()
+        .expect("admin API config")
+        .bind_port;
+    let address = format!("http://127.0.0.1:{}/v1", admin_port);
+    let client = awc::Client::default();
+
+    // requests without a valid token never reach the node
+    let response = client
+        .post(format!("{}/mining/start", address))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let response = client
+        .post(format!("{}/mining/start", address))
+        .bearer_auth("not-the-token")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = client
+        .post(format!("{}/mining/start", address))
+        .bearer_auth(TOKEN)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    node.wait_until_height(1, 20).await?;
+    let response = client
+        .post(format!("{}/mining/stop", address))
+        .bearer_auth(TOKEN)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let trusted_peer: SocketAddr = "127.0.0.1:1".parse()?;
+    let response = client
+        .post(format!("{}/peers/trusted", address))
+        .bearer_auth(TOKEN)
+        .send_json(&json!({ "api": trusted_peer }))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let trusted_peers: Vec<SocketAddr> = client
+        .get(format!("{}/peers/trusted", address))
+        .bearer_auth(TOKEN)
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await?;
+    assert!(trusted_peers.contains(&trusted_peer));
+    for expected in [StatusCode::OK, StatusCode::NOT_FOUND] {
+        let response = client
+            .delete(format!("{}/peers/trusted/{}", address, trusted_peer))
+            .bearer_auth(TOKEN)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), expected);
+    }
+
+    for route in ["/peers/flush", "/cache/prune"] {
+        let response = client
+            .post(format!("{}{}", address, route))
+            .bearer_auth(TOKEN)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK, "{}", route);
+    }
+
+    // test nodes don't hand a log filter reload to the admin API
+    let response = client
+        .put(format!("{}/log_level", address))
+        .bearer_auth(TOKEN)
+        .send_json(&json!({ "filter": "debug" }))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_IMPLEMENTED);
+
+    let response = client
+        .post(format!("{}/shutdown", address))
+        .bearer_auth(TOKEN)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    // returns once the shutdown the admin API started is complete
+    node.stop().await;
+    assert!(client
+        .post(format!("{}/mining/stop", address))
+        .bearer_auth(TOKEN)
+        .send()
+        .await
+        .is_err());
+    Ok(())
+}
@@ -74,6 +74,9 @@ async fn api_end_to_end_test(chunk_size: usize) {
         chunk_provider: node.node_ctx.chunk_provider.clone(),
         config: config.into(),
         sync_state: node.node_ctx.sync_state.clone(),
+        block_pool: node.node_ctx.block_pool.clone(),
+        packing: node.node_ctx.actor_addresses.packing.clone(),
+        commitment_state: node.node_ctx.commitment_state_guard.clone(),
     };
 
     // Initialize the app
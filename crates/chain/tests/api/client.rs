@@ -1,11 +1,14 @@
 //! api client tests
 
 use crate::utils::{mine_block, IrysNodeTest};
+use actix_web::http::header;
+use base58::ToBase58 as _;
 use irys_api_client::{ApiClient, IrysApiClient};
 use irys_chain::IrysNodeCtx;
 use irys_types::{
-    AcceptedResponse, BlockIndexQuery, IrysTransactionResponse, NodeConfig, PeerResponse,
-    ProtocolVersion, VersionRequest,
+    AcceptedResponse, BinaryEncoding as _, BlockIndexQuery, CombinedBlockHeader,
+    IrysTransactionResponse, NodeConfig, PeerResponse, ProtocolVersion, VersionRequest,
+    BINARY_CONTENT_TYPE,
 };
 use semver::Version;
 use std::net::{IpAddr, SocketAddr};
@@ -135,6 +138,25 @@ async fn check_get_block_endpoint(
 
     assert!(block.is_some());
     debug!("block: {:?}", block);
+
+    // the same block, in the binary encoding
+    let mut response = awc::Client::default()
+        .get(format!(
+            "http://{}/v1/block/{}",
+            api_address,
+            previous_block_hash.0.to_base58()
+        ))
+        .insert_header((header::ACCEPT, BINARY_CONTENT_TYPE))
+        .send()
+        .await
+        .expect("valid binary get block response");
+    assert_eq!(
+        response.headers().get(header::CONTENT_TYPE).unwrap(),
+        BINARY_CONTENT_TYPE
+    );
+    let body = response.body().limit(1024 * 1024).await.unwrap();
+    let binary_block = CombinedBlockHeader::decode_binary(&body).unwrap();
+    assert_eq!(binary_block.irys, block.unwrap().irys);
 }
 
 #[actix_rt::test]
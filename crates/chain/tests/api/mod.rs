@@ -3,10 +3,13 @@ use base58::ToBase58;
 use irys_types::{CommitmentTransaction, DataLedger};
 use tracing::info;
 
+mod admin_api;
 mod api;
 mod client;
 mod external_api;
+mod openapi;
 mod pricing_endpoint;
+mod sdk;
 mod tx;
 mod tx_commitments;
 
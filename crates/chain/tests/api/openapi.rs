@@ -0,0 +1,90 @@
+//! checks live responses against the schemas of the served OpenAPI document
+use crate::{api::client_request, utils::IrysNodeTest};
+use base58::ToBase58 as _;
+use serde_json::{json, Value};
+
+/// Validates the response of `uri` against the schema the document gives to `status` responses
+/// of the GET operation of `path`
+async fn assert_matches_spec(document: &Value, address: &str, path: &str, uri: &str, status: u16) {
+    let mut response = client_request(&format!("{}/v1{}", address, uri)).await;
+    assert_eq!(response.status().as_u16(), status, "status of {}", uri);
+    // error responses don't carry a JSON content type, so the body is parsed by hand
+    let body = response.body().limit(16 * 1024 * 1024).await.unwrap();
+    let body: Value = serde_json::from_slice(&body).unwrap();
+
+    let schema = &document["paths"][path]["get"]["responses"][status.to_string()]["content"]
+        ["application/json"]["schema"];
+    assert!(
+        schema.is_object(),
+        "no schema for {} responses of {}",
+        status,
+        path
+    );
+    let schema = json!({
+        "allOf": [schema],
+        "components": document["components"],
+    });
+    let validator = jsonschema::validator_for(&schema).unwrap();
+    let errors = validator
+        .iter_errors(&body)
+        .map(|e| format!("{} at {}", e, e.instance_path))
+        .collect::<Vec<_>>();
+    assert!(
+        errors.is_empty(),
+        "response of {} doesn't match the spec: {:#?}\n{}",
+        uri,
+        errors,
+        serde_json::to_string_pretty(&body).unwrap()
+    );
+}
+
+#[test_log::test(actix::test)]
+async fn heavy_responses_match_openapi_spec() -> eyre::Result<()> {
+    let node = IrysNodeTest::default_async().await.start().await;
+    node.mine_blocks(2).await?;
+    let address = format!(
+        "http://127.0.0.1:{}",
+        node.node_ctx.config.node_config.http.bind_port
+    );
+
+    let mut response = client_request(&format!("{}/v1/openapi.json", address)).await;
+    assert_eq!(response.status(), 200);
+    let document: Value = response.json().limit(1024 * 1024).await?;
+    assert_eq!(document["openapi"], "3.1.0");
+
+    let chunk_size = node.node_ctx.config.consensus.chunk_size;
+    let unknown = irys_types::H256::random().0.to_base58();
+    let cases = [
+        ("/info", "/info".to_owned(), 200),
+        ("/network/config", "/network/config".to_owned(), 200),
+        ("/block/{block_tag}", "/block/latest".to_owned(), 200),
+        ("/block/{block_tag}", "/block/1".to_owned(), 200),
+        (
+            "/block_index",
+            "/block_index?height=0&limit=5".to_owned(),
+            200,
+        ),
+        (
+            "/blocks",
+            "/blocks?from=0&limit=5&includeTxs=true".to_owned(),
+            200,
+        ),
+        (
+            "/price/{ledger}/{size}",
+            format!("/price/0/{}", chunk_size * 3 + 1),
+            200,
+        ),
+        (
+            "/chunk/upload_status/{data_root}",
+            format!("/chunk/upload_status/{}", unknown),
+            200,
+        ),
+        ("/tx/{tx_id}", format!("/tx/{}", unknown), 404),
+    ];
+    for (path, uri, status) in cases {
+        assert_matches_spec(&document, &address, path, &uri, status).await;
+    }
+
+    node.stop().await;
+    Ok(())
+}
@@ -0,0 +1,165 @@
+use crate::utils::IrysNodeTest;
+use alloy_core::primitives::U256;
+use alloy_genesis::GenesisAccount;
+use irys_actors::packing::wait_for_packing;
+use irys_api_client::sdk::{IrysClient, UploadProgress};
+use irys_testing_utils::utils::temporary_directory;
+use irys_types::{
+    irys::IrysSigner, Base64, DataLedger, NodeConfig, StreamingMerkleBuilder, TxChunkOffset,
+    UnpackedChunk,
+};
+use std::{collections::BTreeSet, time::Duration};
+
+#[test_log::test(actix_web::test)]
+async fn heavy_sdk_upload_and_download() -> eyre::Result<()> {
+    let mut config = NodeConfig::testnet();
+    config.consensus.get_mut().chunk_size = 32;
+    config.consensus.get_mut().chunk_migration_depth = 1;
+    // the node is the only signer of ingress proofs
+    config.consensus.get_mut().number_of_ingress_proofs = 1;
+    let signer = IrysSigner::random_signer(&config.consensus_config());
+    config.consensus.extend_genesis_accounts(vec![(
+        signer.address(),
+        GenesisAccount {
+            balance: U256::from(690000000000000000_u128),
+            ..Default::default()
+        },
+    )]);
+    let node = IrysNodeTest::new_genesis(config).start().await;
+    wait_for_packing(
+        node.node_ctx.actor_addresses.packing.clone(),
+        Some(Duration::from_secs(10)),
+    )
+    .await?;
+    node.node_ctx.start_mining().await?;
+
+    let http_url = format!(
+        "http://127.0.0.1:{}",
+        node.node_ctx.config.node_config.http.bind_port
+    );
+    let client = IrysClient::new(http_url, signer);
+
+    // 3.5 chunks of data
+    let data: Vec<u8> = (0..112_u8).collect();
+    let tx = client.create_transaction(data.clone()).await?;
+    client.post_transaction(&tx).await?;
+
+    // uploading again with the completed progress is a no-op
+    let mut progress = UploadProgress::default();
+    client.upload_chunks(&tx, &mut progress).await?;
+    assert_eq!(progress.uploaded_chunks.len(), 4);
+    client.upload_chunks(&tx, &mut progress).await?;
+
+    client
+        .wait_for_promotion(tx.header.id, Duration::from_secs(60))
+        .await?;
+    assert_eq!(client.download(tx.header.id).await?, data);
+
+    // files are merklized and uploaded chunk by chunk
+    let temp_dir = temporary_directory(None, false);
+    let file = temp_dir.path().join("data.bin");
+    let file_data: Vec<u8> = (0..100_u8).rev().collect();
+    std::fs::write(&file, &file_data)?;
+    let tree = StreamingMerkleBuilder::from_reader(std::fs::File::open(&file)?, 32)?;
+    let file_tx = client
+        .create_transaction_from_tree(&tree, DataLedger::Publish)
+        .await?;
+    client.post_transaction(&file_tx).await?;
+    let mut progress = UploadProgress::default();
+    client
+        .upload_file_chunks(&file_tx, &tree, &file, &mut progress)
+        .await?;
+    assert_eq!(progress.uploaded_chunks.len(), 4);
+    client
+        .wait_for_promotion(file_tx.header.id, Duration::from_secs(60))
+        .await?;
+    assert_eq!(client.download(file_tx.header.id).await?, file_data);
+
+    node.stop().await;
+    Ok(())
+}
+
+#[test_log::test(actix_web::test)]
+async fn heavy_sdk_resumes_upload_from_node_status() -> eyre::Result<()> {
+    let mut config = NodeConfig::testnet();
+    config.consensus.get_mut().chunk_size = 32;
+    config.consensus.get_mut().chunk_migration_depth = 1;
+    let signer = IrysSigner::random_signer(&config.consensus_config());
+    config.consensus.extend_genesis_accounts(vec![(
+        signer.address(),
+        GenesisAccount {
+            balance: U256::from(690000000000000000_u128),
+            ..Default::default()
+        },
+    )]);
+    let node = IrysNodeTest::new_genesis(config).start().await;
+    wait_for_packing(
+        node.node_ctx.actor_addresses.packing.clone(),
+        Some(Duration::from_secs(10)),
+    )
+    .await?;
+
+    let http_url = format!(
+        "http://127.0.0.1:{}",
+        node.node_ctx.config.node_config.http.bind_port
+    );
+    let client = IrysClient::new(http_url.clone(), signer);
+
+    // 4 chunks of data
+    let data: Vec<u8> = (0..128_u8).collect();
+    let tx = client.create_transaction(data.clone()).await?;
+    let chunks = tx
+        .chunks
+        .iter()
+        .zip(&tx.proofs)
+        .enumerate()
+        .map(|(offset, (chunk_node, proof))| UnpackedChunk {
+            data_root: tx.header.data_root,
+            data_size: tx.header.data_size,
+            data_path: Base64(proof.proof.clone()),
+            bytes: Base64(data[chunk_node.min_byte_range..chunk_node.max_byte_range].to_vec()),
+            tx_offset: TxChunkOffset::from(offset as u32),
+        })
+        .collect::<Vec<_>>();
+
+    // chunks sent before their tx are held in the pending chunks cache
+    let mut response = awc::Client::default()
+        .post(format!("{}/v1/chunks", http_url))
+        .send_json(&chunks[..2])
+        .await
+        .expect("client post failed");
+    assert!(response.status().is_success());
+    let body: serde_json::Value = response.json().await?;
+    assert_eq!(body["accepted"], serde_json::json!([0, 1]));
+    assert_eq!(
+        client.get_received_chunks(tx.header.data_root).await?,
+        BTreeSet::from([0, 1])
+    );
+
+    // once the tx arrives they're moved to the chunk cache
+    client.post_transaction(&tx).await?;
+    let mut response = awc::Client::default()
+        .post(format!("{}/v1/chunks", http_url))
+        .send_json(&chunks[3..])
+        .await
+        .expect("client post failed");
+    assert!(response.status().is_success());
+    let body: serde_json::Value = response.json().await?;
+    assert_eq!(body["accepted"], serde_json::json!([3]));
+    assert_eq!(
+        client.get_received_chunks(tx.header.data_root).await?,
+        BTreeSet::from([0, 1, 3])
+    );
+
+    // a fresh session only uploads the missing chunk
+    let mut progress = UploadProgress::default();
+    client.upload_chunks(&tx, &mut progress).await?;
+    assert_eq!(progress.uploaded_chunks, BTreeSet::from([0, 1, 2, 3]));
+    assert_eq!(
+        client.get_received_chunks(tx.header.data_root).await?,
+        BTreeSet::from([0, 1, 2, 3])
+    );
+
+    node.stop().await;
+    Ok(())
+}
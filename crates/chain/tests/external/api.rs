@@ -6,7 +6,7 @@ use base58::ToBase58;
 use irys_actors::mempool_service::GetBestMempoolTxs;
 use irys_actors::packing::wait_for_packing;
 use irys_api_server::routes::tx::TxOffset;
-use irys_database::tables::IngressProofs;
+use irys_database::tables::IngressProofsBySigner;
 use irys_types::{irys::IrysSigner, Address, NodeConfig};
 use reth_db::transaction::DbTx;
 use reth_db::Database as _;
@@ -112,8 +112,11 @@ async fn external_api() -> eyre::Result<()> {
     let ingress_proof = loop {
         // don't reuse the tx! it has read isolation (won't see anything committed after it's creation)
         let ro_tx = &node.node_ctx.db.0.tx().unwrap();
-        match ro_tx.get::<IngressProofs>(recv_tx.data_root).unwrap() {
-            Some(ip) => break ip,
+        match ro_tx
+            .get::<IngressProofsBySigner>(recv_tx.data_root)
+            .unwrap()
+        {
+            Some(ip) => break ip.proof,
             None => sleep(Duration::from_millis(100)).await,
         }
     };
@@ -9,7 +9,7 @@ use base58::ToBase58;
 use irys_actors::mempool_service::GetBestMempoolTxs;
 use irys_actors::packing::wait_for_packing;
 use irys_api_server::routes::tx::TxOffset;
-use irys_database::tables::IngressProofs;
+use irys_database::tables::IngressProofsBySigner;
 use irys_primitives::precompile::IrysPrecompileOffsets;
 use irys_types::{irys::IrysSigner, Address, NodeConfig};
 use k256::ecdsa::SigningKey;
@@ -162,8 +162,11 @@ async fn test_programmable_data_basic_external() -> eyre::Result<()> {
     let ingress_proof = loop {
         // don't reuse the tx! it has read isolation (won't see anything committed after it's creation)
         let ro_tx = &node.node_ctx.db.0.tx().unwrap();
-        match ro_tx.get::<IngressProofs>(recv_tx.data_root).unwrap() {
-            Some(ip) => break ip,
+        match ro_tx
+            .get::<IngressProofsBySigner>(recv_tx.data_root)
+            .unwrap()
+        {
+            Some(ip) => break ip.proof,
             None => sleep(Duration::from_millis(100)).await,
         }
     };
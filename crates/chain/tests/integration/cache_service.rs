@@ -222,6 +222,32 @@ async fn heavy_test_cache_pruning() -> eyre::Result<()> {
 
     assert_eq!(chunk_res.status(), StatusCode::OK);
 
+    // make sure we can download the original data, in full and by range
+    let mut data_res = client
+        .get(format!("{}/v1/tx/{}/data", http_url, &id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(data_res.status(), StatusCode::OK);
+    assert_eq!(data_res.body().await?, data_bytes.as_slice());
+
+    let mut range_res = client
+        .get(format!("{}/v1/tx/{}/data", http_url, &id))
+        .insert_header(("Range", "bytes=2-6"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(range_res.status(), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(range_res.body().await?, &data_bytes[2..7]);
+
+    let range_res = client
+        .get(format!("{}/v1/tx/{}/data", http_url, &id))
+        .insert_header(("Range", "bytes=100-"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(range_res.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+
     node.stop().await;
 
     Ok(())
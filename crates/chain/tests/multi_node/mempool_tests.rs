@@ -1,7 +1,13 @@
 use crate::utils::*;
 use assert_matches::assert_matches;
+use irys_actors::block_tree_service::ReorgMessage;
+use irys_database::{
+    db::IrysDatabaseExt as _, ingress_proofs_by_data_root, tables::IngressProofsBySigner,
+};
 use irys_testing_utils::initialize_tracing;
-use irys_types::{DataLedger, LedgerChunkOffset, NodeConfig, H256};
+use irys_types::{DataLedger, IrysBlockHeader, LedgerChunkOffset, NodeConfig, H256};
+use reth_db::transaction::DbTxMut as _;
+use std::sync::Arc;
 
 #[actix::test]
 async fn heavy_pending_chunks_test() -> eyre::Result<()> {
@@ -98,3 +104,92 @@ async fn heavy_pending_pledges_test() -> eyre::Result<()> {
 
     Ok(())
 }
+
+#[actix::test]
+async fn heavy_orphaned_promotion_test() -> eyre::Result<()> {
+    initialize_tracing();
+
+    // Configure a test network where the node's own proof is enough to promote data
+    let mut genesis_config = NodeConfig::testnet();
+    genesis_config.consensus.get_mut().chunk_size = 32;
+    genesis_config.consensus.get_mut().number_of_ingress_proofs = 1;
+
+    // Create a signer (keypair) for transactions and fund it
+    let signer = genesis_config.new_random_signer();
+    genesis_config.fund_genesis_accounts(vec![&signer]);
+
+    // Start the genesis node
+    let genesis_node = IrysNodeTest::new_genesis(genesis_config.clone())
+        .start()
+        .await;
+    let app = genesis_node.start_public_api().await;
+
+    let chunks = vec![[10; 32], [20; 32], [30; 32]];
+    let data: Vec<u8> = chunks.concat();
+    let tx = signer.create_transaction(data, None)?;
+    let tx = signer.sign_transaction(tx)?;
+    let txid = tx.header.id;
+    let data_root = tx.header.data_root;
+
+    // Include the tx in a block before uploading its data, so it's promoted in a later block
+    post_storage_tx(&app, &tx).await;
+    genesis_node.mine_block().await?;
+    for chunk_index in 0..chunks.len() {
+        post_chunk(&app, &tx, chunk_index, &chunks).await;
+    }
+
+    let is_promoted_in = |block: &IrysBlockHeader| {
+        block.data_ledgers[DataLedger::Publish]
+            .tx_ids
+            .contains(&txid)
+    };
+    let mut promotion_block = None;
+    for _ in 0..5 {
+        genesis_node.mine_block().await?;
+        let block = genesis_node
+            .get_block_by_height(genesis_node.get_height().await)
+            .await?;
+        if is_promoted_in(&block) {
+            promotion_block = Some(block);
+            break;
+        }
+    }
+    let promotion_block = promotion_block.expect("the tx to be promoted");
+    assert_matches!(genesis_node.get_tx_header(&txid)?.ingress_proofs, Some(_));
+
+    // A node may not hold the proofs of a promotion it receives from a peer
+    genesis_node.node_ctx.db.update_eyre(|db_tx| {
+        db_tx.delete::<IngressProofsBySigner>(data_root, None)?;
+        Ok(())
+    })?;
+
+    genesis_node
+        .node_ctx
+        .actor_addresses
+        .mempool
+        .send(ReorgMessage {
+            orphaned_blocks: vec![Arc::new(promotion_block)],
+            new_blocks: vec![],
+        })
+        .await?;
+
+    // The promotion is undone, keeping the proof of the orphaned block
+    assert_matches!(genesis_node.get_tx_header(&txid)?.ingress_proofs, None);
+    let proofs = genesis_node
+        .node_ctx
+        .db
+        .view_eyre(|rtx| ingress_proofs_by_data_root(rtx, data_root))?;
+    assert_eq!(proofs.len(), 1);
+
+    // So the tx is promoted again in the next block
+    genesis_node.mine_block().await?;
+    let block = genesis_node
+        .get_block_by_height(genesis_node.get_height().await)
+        .await?;
+    assert!(is_promoted_in(&block));
+    assert_matches!(genesis_node.get_tx_header(&txid)?.ingress_proofs, Some(_));
+
+    genesis_node.stop().await;
+
+    Ok(())
+}
@@ -581,6 +581,7 @@ fn init_configs() -> (
             public_port: http_port_genesis,
             bind_port: http_port_genesis,
             bind_ip: "127.0.0.1".to_string(),
+            peer_api_scheme: "http".to_string(),
         },
         gossip: GossipConfig {
             public_port: gossip_port_genesis,
@@ -604,6 +605,7 @@ fn init_configs() -> (
             public_port: http_port_peer1,
             bind_ip: "127.0.0.1".to_string(),
             public_ip: "127.0.0.1".to_string(),
+            peer_api_scheme: "http".to_string(),
         },
         gossip: GossipConfig {
             public_port: gossip_port_peer1,
@@ -628,6 +630,7 @@ fn init_configs() -> (
             public_port: http_port_peer2,
             bind_ip: "127.0.0.1".to_string(),
             public_ip: "127.0.0.1".to_string(),
+            peer_api_scheme: "http".to_string(),
         },
         gossip: GossipConfig {
             public_port: gossip_port_peer2,
@@ -30,6 +30,7 @@ async fn heavy_data_promotion_test() {
     config.storage.num_writes_before_sync = 1;
     config.consensus.get_mut().entropy_packing_iterations = 1_000;
     config.consensus.get_mut().chunk_migration_depth = 1; // Testnet / single node config
+    config.consensus.get_mut().number_of_ingress_proofs = 1; // the node is the only signer
     let signer = IrysSigner::random_signer(&config.consensus_config());
     config.consensus.extend_genesis_accounts(vec![(
         signer.address(),
@@ -67,6 +68,9 @@ async fn heavy_data_promotion_test() {
         chunk_provider: node.node_ctx.chunk_provider.clone(),
         config: config.into(),
         sync_state: node.node_ctx.sync_state.clone(),
+        block_pool: node.node_ctx.block_pool.clone(),
+        packing: node.node_ctx.actor_addresses.packing.clone(),
+        commitment_state: node.node_ctx.commitment_state_guard.clone(),
     };
 
     // Initialize the app
@@ -12,7 +12,7 @@ use awc::http::StatusCode;
 use base58::ToBase58;
 use irys_actors::packing::wait_for_packing;
 use irys_api_server::{routes, ApiState};
-use irys_database::{tables::IngressProofs, walk_all};
+use irys_database::{tables::IngressProofsBySigner, walk_all};
 use irys_types::{irys::IrysSigner, IrysTransaction, IrysTransactionHeader, LedgerChunkOffset};
 use irys_types::{DataLedger, NodeConfig};
 use reth_db::Database as _;
@@ -34,6 +34,7 @@ async fn heavy_double_root_data_promotion_test() {
     config.consensus.get_mut().entropy_packing_iterations = 1_000;
     // Testnet / single node config
     config.consensus.get_mut().chunk_migration_depth = 1;
+    config.consensus.get_mut().number_of_ingress_proofs = 1;
     let signer = IrysSigner::random_signer(&config.consensus_config());
     let signer2 = IrysSigner::random_signer(&config.consensus_config());
     config.consensus.extend_genesis_accounts(vec![
@@ -81,6 +82,9 @@ async fn heavy_double_root_data_promotion_test() {
         chunk_provider: node.node_ctx.chunk_provider.clone(),
         config: config.into(),
         sync_state: node.node_ctx.sync_state.clone(),
+        block_pool: node.node_ctx.block_pool.clone(),
+        packing: node.node_ctx.actor_addresses.packing.clone(),
+        commitment_state: node.node_ctx.commitment_state_guard.clone(),
     };
 
     // Initialize the app
@@ -358,7 +362,7 @@ async fn heavy_double_root_data_promotion_test() {
 
     // ensure the ingress proof still exists
     let ingress_proofs = db
-        .view(|rtx| walk_all::<IngressProofs, _>(rtx))
+        .view(|rtx| walk_all::<IngressProofsBySigner, _>(rtx))
         .unwrap()
         .unwrap();
     assert_eq!(ingress_proofs.len(), 1);
@@ -577,7 +581,7 @@ async fn heavy_double_root_data_promotion_test() {
     mine_blocks(&node.node_ctx, 5).await.unwrap();
     // ensure the ingress proof is gone
     let ingress_proofs = db
-        .view(|rtx| walk_all::<IngressProofs, _>(rtx))
+        .view(|rtx| walk_all::<IngressProofsBySigner, _>(rtx))
         .unwrap()
         .unwrap();
     assert_eq!(ingress_proofs.len(), 0);
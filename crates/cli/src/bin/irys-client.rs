@@ -0,0 +1,246 @@
+//! Uploads data to, and downloads it from, an Irys node, and posts stake and pledge commitments.
+//!
+//! The signing key is read from `--private-key` or the `IRYS_PRIVATE_KEY` environment variable,
+//! as hex. It's required by the commands posting transactions (upload, stake and pledge), the read
+//! only commands don't sign anything and work without it. The chain ID and chunk size the transactions are built with are fetched from the node.
+use base58::{FromBase58 as _, ToBase58 as _};
+use clap::{Parser, Subcommand, ValueEnum};
+use eyre::{eyre, WrapErr as _};
+use irys_api_client::sdk::{IrysClient, UploadProgress};
+use irys_primitives::CommitmentType;
+use irys_types::{
+    irys::IrysSigner, serialization::string_u64, DataLedger, IrysTransactionResponse,
+    StreamingMerkleBuilder, H256,
+};
+use serde::Deserialize;
+use std::{path::PathBuf, time::Duration};
+use tracing::info;
+use tracing::level_filters::LevelFilter;
+use tracing_subscriber::EnvFilter;
+
+#[derive(Debug, Parser, Clone)]
+pub struct IrysClientCli {
+    /// Base URL of the node's HTTP API
+    #[arg(long, env = "IRYS_NODE_URL", default_value = "http://127.0.0.1:8080")]
+    pub node_url: String,
+    /// Hex encoded private key used to sign transactions, required by upload, stake and pledge
+    #[arg(long, env = "IRYS_PRIVATE_KEY", hide_env_values = true)]
+    pub private_key: Option<String>,
+    #[command(subcommand)]
+    pub command: Commands,
+}
+
+#[derive(Debug, Subcommand, Clone)]
+pub enum Commands {
+    /// Price, sign and post a transaction for a file, upload its chunks and wait for promotion
+    Upload {
+        file: PathBuf,
+        #[arg(long, value_enum, default_value_t = Ledger::Publish)]
+        ledger: Ledger,
+        /// Return once the chunks are uploaded rather than waiting for promotion
+        #[arg(long, default_value_t = false)]
+        no_wait: bool,
+        /// How long to wait for promotion, in seconds
+        #[arg(long, default_value_t = 600)]
+        timeout: u64,
+    },
+    /// Download the data of a transaction, verified against its data root
+    Download { tx_id: String, out: PathBuf },
+    /// Report whether a transaction is known, promoted, and how many of its chunks the node holds
+    Status { tx_id: String },
+    /// Price permanently storing a number of bytes
+    Price { bytes: u64 },
+    /// Sign and post a stake commitment
+    Stake {
+        /// Fee paid for the commitment
+        #[arg(long)]
+        fee: u64,
+    },
+    /// Sign and post a pledge commitment
+    Pledge {
+        /// Fee paid for the commitment
+        #[arg(long)]
+        fee: u64,
+    },
+}
+
+impl Commands {
+    /// Whether the command signs and posts transactions, and so needs the private key
+    fn signs(&self) -> bool {
+        match self {
+            Self::Upload { .. } | Self::Stake { .. } | Self::Pledge { .. } => true,
+            Self::Download { .. } | Self::Status { .. } | Self::Price { .. } => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Ledger {
+    Publish,
+    Submit,
+}
+
+impl From<Ledger> for DataLedger {
+    fn from(ledger: Ledger) -> Self {
+        match ledger {
+            Ledger::Publish => Self::Publish,
+            Ledger::Submit => Self::Submit,
+        }
+    }
+}
+
+/// The parts of the node's `/network/config` response transactions are built with
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NetworkConfig {
+    #[serde(with = "string_u64")]
+    chunk_size: u64,
+    #[serde(with = "string_u64")]
+    chain_id: u64,
+}
+
+#[tokio::main]
+async fn main() -> eyre::Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            EnvFilter::builder()
+                .with_default_directive(LevelFilter::INFO.into())
+                .from_env_lossy(),
+        )
+        .init();
+    color_eyre::install().expect("color eyre could not be installed");
+
+    let args = IrysClientCli::parse();
+    if args.command.signs() && args.private_key.is_none() {
+        eyre::bail!("--private-key or IRYS_PRIVATE_KEY is required to sign transactions");
+    }
+    let client = connect(&args.node_url, args.private_key.as_deref()).await?;
+
+    match args.command {
+        Commands::Upload {
+            file,
+            ledger,
+            no_wait,
+            timeout,
+        } => upload(&client, file, ledger.into(), no_wait, timeout).await?,
+        Commands::Download { tx_id, out } => {
+            let data = client.download(parse_tx_id(&tx_id)?).await?;
+            tokio::fs::write(&out, &data).await?;
+            info!("Saved {} bytes to {}", data.len(), out.display());
+        }
+        Commands::Status { tx_id } => status(&client, parse_tx_id(&tx_id)?).await?,
+        Commands::Price { bytes } => {
+            println!("{}", client.get_price(bytes).await?);
+        }
+        Commands::Stake { fee } => {
+            let commitment = client.post_commitment(CommitmentType::Stake, fee).await?;
+            println!("{}", commitment.id.0.to_base58());
+        }
+        Commands::Pledge { fee } => {
+            let commitment = client.post_commitment(CommitmentType::Pledge, fee).await?;
+            println!("{}", commitment.id.0.to_base58());
+        }
+    }
+    Ok(())
+}
+
+/// Builds a client signing with `private_key`. Read only commands don't need a key, they get a
+/// random one as the client always carries a signer
+async fn connect(node_url: &str, private_key: Option<&str>) -> eyre::Result<IrysClient> {
+    let network: NetworkConfig = reqwest::get(format!("{}/v1/network/config", node_url))
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    let signer = match private_key {
+        Some(key) => {
+            let key = hex::decode(key.trim_start_matches("0x")).wrap_err("invalid private key")?;
+            k256::ecdsa::SigningKey::from_slice(&key).wrap_err("invalid private key")?
+        }
+        None => k256::ecdsa::SigningKey::random(&mut rand::rngs::OsRng),
+    };
+    let signer = IrysSigner {
+        signer,
+        chain_id: network.chain_id,
+        chunk_size: network.chunk_size,
+    };
+    Ok(IrysClient::new(node_url, signer))
+}
+
+fn parse_tx_id(tx_id: &str) -> eyre::Result<H256> {
+    let bytes = tx_id
+        .from_base58()
+        .map_err(|e| eyre!("invalid tx id {}: {:?}", tx_id, e))?;
+    eyre::ensure!(
+        bytes.len() == 32,
+        "invalid tx id {}: expected 32 bytes",
+        tx_id
+    );
+    Ok(H256::from_slice(&bytes))
+}
+
+async fn upload(
+    client: &IrysClient,
+    file: PathBuf,
+    ledger: DataLedger,
+    no_wait: bool,
+    timeout: u64,
+) -> eyre::Result<()> {
+    // the file is hashed and uploaded chunk by chunk, never loaded into memory whole
+    let reader = tokio::fs::File::open(&file).await?;
+    let tree =
+        StreamingMerkleBuilder::from_async_reader(reader, client.chunk_size() as usize).await?;
+    info!(
+        "Merklized {} bytes in {} chunks, data root {}",
+        tree.data_size(),
+        tree.chunk_count(),
+        tree.data_root().0.to_base58()
+    );
+
+    let tx = client.create_transaction_from_tree(&tree, ledger).await?;
+    let tx_id = tx.header.id;
+    client.post_transaction(&tx).await?;
+    info!("Posted tx {}", tx_id.0.to_base58());
+
+    // chunks the node already holds for this data root, e.g. from an interrupted upload, are skipped
+    let mut progress = UploadProgress::default();
+    client
+        .upload_file_chunks(&tx, &tree, &file, &mut progress)
+        .await?;
+    info!("Uploaded {} chunks", progress.uploaded_chunks.len());
+
+    if ledger == DataLedger::Publish && !no_wait {
+        client
+            .wait_for_promotion(tx_id, Duration::from_secs(timeout))
+            .await?;
+        info!("Tx {} is promoted", tx_id.0.to_base58());
+    }
+    println!("{}", tx_id.0.to_base58());
+    Ok(())
+}
+
+async fn status(client: &IrysClient, tx_id: H256) -> eyre::Result<()> {
+    let header = match client.get_transaction(tx_id).await? {
+        IrysTransactionResponse::Storage(header) => header,
+        IrysTransactionResponse::Commitment(commitment) => {
+            println!(
+                "commitment {:?} signed by {}",
+                commitment.commitment_type, commitment.signer
+            );
+            return Ok(());
+        }
+    };
+
+    let ledger = DataLedger::try_from(header.ledger_id)
+        .map_or_else(|_| header.ledger_id.to_string(), |l| format!("{:?}", l));
+    println!("ledger: {}", ledger);
+    println!("data root: {}", header.data_root.0.to_base58());
+    println!("data size: {} bytes", header.data_size);
+    println!("promoted: {}", client.is_promoted(tx_id).await?);
+
+    let chunk_count = header.data_size.div_ceil(client.chunk_size());
+    let received = client.get_received_chunks(header.data_root).await?;
+    println!("cached chunks: {}/{}", received.len(), chunk_count);
+    Ok(())
+}
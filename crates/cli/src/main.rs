@@ -3,7 +3,7 @@ use irys_database::reth_db::{
     cursor::*, transaction::*, Database as _, DatabaseEnv, DatabaseEnvKind, PlainAccountState,
     StageCheckpoints,
 };
-use irys_types::NodeConfig;
+use irys_types::{NodeConfig, StorageModuleStatus};
 use reth_node_core::version::default_client_version;
 use std::fs::File;
 use std::io::{BufWriter, Write as _};
@@ -24,6 +24,16 @@ pub struct IrysCli {
 pub enum Commands {
     #[command(name = "backup-accounts")]
     BackupAccounts {},
+    /// Report partition assignment, chunk states and packing progress of a running node's storage modules
+    #[command(name = "storage-status")]
+    StorageStatus {
+        /// Base URL of the node's HTTP API, defaults to the configured HTTP bind address
+        #[arg(long)]
+        node_url: Option<String>,
+        /// Print the raw JSON report instead of a summary
+        #[arg(long, default_value_t = false)]
+        json: bool,
+    },
 }
 
 fn main() -> eyre::Result<()> {
@@ -51,17 +61,17 @@ fn main() -> eyre::Result<()> {
 
     match args.command {
         Commands::BackupAccounts { .. } => backup_accounts()?,
+        Commands::StorageStatus { node_url, json } => storage_status(node_url, json)?,
     }
     Ok(())
 }
 
-fn backup_accounts() -> eyre::Result<()> {
-    // load the config
+fn load_config() -> NodeConfig {
     let config = std::env::var("CONFIG")
         .unwrap_or_else(|_| "config.toml".to_owned())
         .parse::<PathBuf>()
         .expect("file path to be valid");
-    let config = std::fs::read_to_string(config)
+    std::fs::read_to_string(config)
         .map(|config_file| toml::from_str::<NodeConfig>(&config_file).expect("invalid config file"))
         .unwrap_or_else(|err| {
             tracing::warn!(
@@ -69,7 +79,11 @@ fn backup_accounts() -> eyre::Result<()> {
                 "config file not provided, defaulting to testnet config"
             );
             NodeConfig::testnet()
-        });
+        })
+}
+
+fn backup_accounts() -> eyre::Result<()> {
+    let config = load_config();
 
     // open the database, read the current account state
     let db_path = config.reth_data_dir().join("db");
@@ -132,3 +146,60 @@ fn backup_accounts() -> eyre::Result<()> {
 
     Ok(())
 }
+
+fn storage_status(node_url: Option<String>, json: bool) -> eyre::Result<()> {
+    let node_url = node_url.unwrap_or_else(|| {
+        let config = load_config();
+        format!("http://{}:{}", config.http.bind_ip, config.http.bind_port)
+    });
+
+    let report = reqwest::blocking::get(format!("{}/v1/storage_modules", node_url))?
+        .error_for_status()?
+        .json::<Vec<StorageModuleStatus>>()?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    for sm in &report {
+        let assignment = match (sm.partition_hash, sm.ledger_id, sm.slot_index) {
+            (Some(hash), Some(ledger), Some(slot)) => {
+                format!("partition {:?} ledger {} slot {}", hash, ledger, slot)
+            }
+            (Some(hash), _, _) => format!("partition {:?} (capacity)", hash),
+            _ => "unassigned".to_owned(),
+        };
+        println!("storage module {}: {}", sm.id, assignment);
+
+        for sub in &sm.submodules {
+            let free = sub
+                .available_space
+                .map_or_else(|| "unknown".to_owned(), |bytes| format!("{} bytes", bytes));
+            println!(
+                "  submodule {} chunks {}..={} free space {}",
+                sub.path.display(),
+                sub.start_offset,
+                sub.end_offset,
+                free
+            );
+        }
+
+        println!(
+            "  chunks: entropy {} data {} uninitialized {}",
+            sm.chunk_counts.entropy, sm.chunk_counts.data, sm.chunk_counts.uninitialized
+        );
+
+        let eta = sm
+            .packing
+            .estimated_secs_remaining
+            .map_or_else(|| "unknown".to_owned(), |secs| format!("{}s", secs));
+        println!(
+            "  packing: {} requests ({} chunks) queued, {:.2} chunks/s, fully packed in {}",
+            sm.packing.queued_requests, sm.packing.queued_chunks, sm.packing.chunks_per_second, eta
+        );
+        println!("  minable: {}", sm.is_minable);
+    }
+
+    Ok(())
+}
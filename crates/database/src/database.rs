@@ -2,22 +2,26 @@ use std::path::Path;
 
 use crate::db_cache::{
     CachedChunk, CachedChunkIndexEntry, CachedChunkIndexMetadata, CachedDataRoot,
+    CachedIngressProof,
 };
+use crate::db_index::BundleItemLocation;
 use crate::tables::{
-    CachedChunks, CachedChunksIndex, CachedDataRoots, IrysBlockHeaders, IrysCommitments,
-    IrysPoAChunks, IrysTxHeaders, Metadata, PeerListItems,
+    BundleItems, CachedChunks, CachedChunksIndex, CachedDataRoots, CommitmentsBySigner,
+    IngressProofsBySigner, IrysBlockHeaders, IrysCommitments, IrysPoAChunks, IrysTxHeaders,
+    Metadata, PeerListItems, PendingBundles, TxTags, TxsByLedger, TxsBySigner, VdfSteps,
 };
 
 use crate::metadata::MetadataKey;
 use crate::reth_ext::IrysRethDatabaseEnvMetricsExt as _;
+use alloy_primitives::keccak256;
 use irys_types::{
-    Address, BlockHash, ChunkPathHash, CommitmentTransaction, DataRoot, IrysBlockHeader,
-    IrysTransactionHeader, IrysTransactionId, PeerListItem, TxChunkOffset, UnpackedChunk, MEGABYTE,
-    U256,
+    ingress::IngressProof, Address, BlockHash, ChunkPathHash, CommitmentTransaction, DataRoot,
+    IrysBlockHeader, IrysTransactionHeader, IrysTransactionId, PeerListItem, TxChunkOffset,
+    UnpackedChunk, H256, MEGABYTE, U256,
 };
 use reth_db::cursor::DbDupCursorRO;
 use reth_db::mdbx::init_db_for;
-use reth_db::table::{Table, TableInfo};
+use reth_db::table::{DupSort, Table, TableInfo};
 use reth_db::transaction::DbTx;
 use reth_db::transaction::DbTxMut;
 use reth_db::{
@@ -101,6 +105,96 @@ pub fn insert_tx_header<T: DbTxMut>(tx: &T, tx_header: &IrysTransactionHeader) -
     Ok(tx.put::<IrysTxHeaders>(tx_header.id, tx_header.clone().into())?)
 }
 
+/// Indexes a confirmed [`IrysTransactionHeader`] by signer ([`TxsBySigner`]), ledger ([`TxsByLedger`])
+/// and tags ([`TxTags`]). Called when the block including the tx migrates, so unconfirmed
+/// mempool txs never show up in the indexes
+pub fn index_tx_header<T: DbTxMut>(tx: &T, tx_header: &IrysTransactionHeader) -> eyre::Result<()> {
+    tx.put::<TxsBySigner>(tx_header.signer, tx_header.id)?;
+    tx.put::<TxsByLedger>(tx_header.ledger_id, tx_header.id)?;
+    for tag in tx_header.tags.iter().flatten() {
+        tx.put::<TxTags>(tx_tag_key(&tag.name, &tag.value), tx_header.id)?;
+    }
+    Ok(())
+}
+
+/// Computes the [`TxTags`] key for a tag name and value
+pub fn tx_tag_key(name: &str, value: &str) -> H256 {
+    let mut bytes = Vec::with_capacity(8 + name.len() + value.len());
+    bytes.extend_from_slice(&(name.len() as u64).to_le_bytes());
+    bytes.extend_from_slice(name.as_bytes());
+    bytes.extend_from_slice(value.as_bytes());
+    H256(keccak256(&bytes).0)
+}
+
+/// Gets up to `limit` ids stored under `key` in a transaction id index table, ordered by id.
+/// To paginate, pass the last id of the previous page as `after`.
+fn tx_ids_page<Tbl, T>(
+    tx: &T,
+    key: Tbl::Key,
+    after: Option<IrysTransactionId>,
+    limit: usize,
+) -> eyre::Result<Vec<IrysTransactionId>>
+where
+    Tbl: DupSort<Value = IrysTransactionId, SubKey = IrysTransactionId>,
+    T: DbTx,
+{
+    let mut cursor = tx.cursor_dup_read::<Tbl>()?;
+    let walker = cursor.walk_dup(Some(key), after)?;
+    let mut tx_ids = Vec::new();
+    for entry in walker {
+        let (_, tx_id) = entry?;
+        if Some(tx_id) == after {
+            continue;
+        }
+        if tx_ids.len() == limit {
+            break;
+        }
+        tx_ids.push(tx_id);
+    }
+    Ok(tx_ids)
+}
+
+/// Gets up to `limit` ids of transactions tagged with `name` = `value`, see [`tx_ids_page`]
+pub fn tx_ids_by_tag<T: DbTx>(
+    tx: &T,
+    name: &str,
+    value: &str,
+    after: Option<IrysTransactionId>,
+    limit: usize,
+) -> eyre::Result<Vec<IrysTransactionId>> {
+    tx_ids_page::<TxTags, _>(tx, tx_tag_key(name, value), after, limit)
+}
+
+/// Gets up to `limit` ids of data transactions signed by `signer`, see [`tx_ids_page`]
+pub fn tx_ids_by_signer<T: DbTx>(
+    tx: &T,
+    signer: Address,
+    after: Option<IrysTransactionId>,
+    limit: usize,
+) -> eyre::Result<Vec<IrysTransactionId>> {
+    tx_ids_page::<TxsBySigner, _>(tx, signer, after, limit)
+}
+
+/// Gets up to `limit` ids of data transactions destined for `ledger_id`, see [`tx_ids_page`]
+pub fn tx_ids_by_ledger<T: DbTx>(
+    tx: &T,
+    ledger_id: u32,
+    after: Option<IrysTransactionId>,
+    limit: usize,
+) -> eyre::Result<Vec<IrysTransactionId>> {
+    tx_ids_page::<TxsByLedger, _>(tx, ledger_id, after, limit)
+}
+
+/// Gets up to `limit` ids of commitment transactions signed by `signer`, see [`tx_ids_page`]
+pub fn commitment_tx_ids_by_signer<T: DbTx>(
+    tx: &T,
+    signer: Address,
+    after: Option<IrysTransactionId>,
+    limit: usize,
+) -> eyre::Result<Vec<IrysTransactionId>> {
+    tx_ids_page::<CommitmentsBySigner, _>(tx, signer, after, limit)
+}
+
 /// Gets a [`IrysTransactionHeader`] by it's [`IrysTransactionId`]
 pub fn tx_header_by_txid<T: DbTx>(
     tx: &T,
@@ -119,6 +213,15 @@ pub fn insert_commitment_tx<T: DbTxMut>(
     Ok(tx.put::<IrysCommitments>(commitment_tx.id, commitment_tx.clone().into())?)
 }
 
+/// Indexes a confirmed [`CommitmentTransaction`] by signer in [`CommitmentsBySigner`].
+/// Like [`index_tx_header`], called when the block including the commitment migrates
+pub fn index_commitment_tx<T: DbTxMut>(
+    tx: &T,
+    commitment_tx: &CommitmentTransaction,
+) -> eyre::Result<()> {
+    Ok(tx.put::<CommitmentsBySigner>(commitment_tx.signer, commitment_tx.id)?)
+}
+
 /// Gets a [`CommitmentTransaction`] by it's [`IrysTransactionId`]
 pub fn commitment_tx_by_txid<T: DbTx>(
     tx: &T,
@@ -129,6 +232,40 @@ pub fn commitment_tx_by_txid<T: DbTx>(
         .map(CommitmentTransaction::from))
 }
 
+/// Inserts the location of a bundle data item into [`BundleItems`]
+pub fn insert_bundle_item<T: DbTxMut>(
+    tx: &T,
+    item_id: H256,
+    location: BundleItemLocation,
+) -> eyre::Result<()> {
+    Ok(tx.put::<BundleItems>(item_id, location)?)
+}
+
+/// Gets the [`BundleItemLocation`] of a bundle data item by its id
+pub fn bundle_item_by_id<T: DbTx>(
+    tx: &T,
+    item_id: &H256,
+) -> eyre::Result<Option<BundleItemLocation>> {
+    Ok(tx.get::<BundleItems>(*item_id)?)
+}
+
+/// Records that indexing the items of a bundle transaction failed `attempts` times,
+/// see [`PendingBundles`]
+pub fn insert_pending_bundle<T: DbTxMut>(tx: &T, tx_id: H256, attempts: u64) -> eyre::Result<()> {
+    Ok(tx.put::<PendingBundles>(tx_id, attempts)?)
+}
+
+/// Removes a bundle transaction from [`PendingBundles`], if it's there
+pub fn delete_pending_bundle<T: DbTxMut>(tx: &T, tx_id: &H256) -> eyre::Result<()> {
+    tx.delete::<PendingBundles>(*tx_id, None)?;
+    Ok(())
+}
+
+/// Gets the bundle transactions waiting to be indexed with their failed attempts
+pub fn pending_bundles<T: DbTx>(tx: &T) -> eyre::Result<Vec<(H256, u64)>> {
+    walk_all::<PendingBundles, _>(tx)
+}
+
 /// Takes an [`IrysTransactionHeader`] and caches its `data_root` and tx.id in a
 /// cache database table ([`CachedDataRoots`]). Tracks all the tx.ids' that share the same `data_root`.
 pub fn cache_data_root<T: DbTx + DbTxMut>(
@@ -239,6 +376,18 @@ pub fn cached_chunk_by_chunk_offset<T: DbTx>(
     }
 }
 
+/// Lists the offsets of the chunks of a [`DataRoot`] in the [`CachedChunksIndex`], in ascending order
+pub fn cached_chunk_offsets_by_data_root<T: DbTx>(
+    tx: &T,
+    data_root: DataRoot,
+) -> eyre::Result<Vec<TxChunkOffset>> {
+    let mut cursor = tx.cursor_dup_read::<CachedChunksIndex>()?;
+    let walker = cursor.walk_dup(Some(data_root), None)?;
+    Ok(walker
+        .map(|entry| entry.map(|(_, index_entry)| index_entry.index))
+        .collect::<Result<Vec<_>, _>>()?)
+}
+
 /// Retrieves a [`CachedChunk`] from [`CachedChunks`] using its [`ChunkPathHash`]
 pub fn cached_chunk_by_chunk_path_hash<T: DbTx>(
     tx: &T,
@@ -267,6 +416,52 @@ pub fn delete_cached_chunks_by_data_root<T: DbTxMut>(
     Ok(chunks_pruned)
 }
 
+/// Stores an [`IngressProof`] signed by `address` in [`IngressProofsBySigner`] - returns `true` if
+/// a proof from this address was already present for the proof's data root, in which case nothing is written
+pub fn store_ingress_proof<T: DbTx + DbTxMut>(
+    tx: &T,
+    address: Address,
+    proof: &IngressProof,
+) -> eyre::Result<IsDuplicate> {
+    if ingress_proof_by_data_root_and_address(tx, proof.data_root, address)?.is_some() {
+        return Ok(true);
+    }
+    tx.put::<IngressProofsBySigner>(
+        proof.data_root,
+        CachedIngressProof {
+            address,
+            proof: proof.clone(),
+        },
+    )?;
+    Ok(false)
+}
+
+/// Retrieves the [`IngressProof`] signed by `address` for a [`DataRoot`] from [`IngressProofsBySigner`]
+pub fn ingress_proof_by_data_root_and_address<T: DbTx>(
+    tx: &T,
+    data_root: DataRoot,
+    address: Address,
+) -> eyre::Result<Option<IngressProof>> {
+    let mut cursor = tx.cursor_dup_read::<IngressProofsBySigner>()?;
+    Ok(cursor
+        .seek_by_key_subkey(data_root, address)?
+        // dupsort seek can return the next greater subkey, make sure it's an exact match
+        .filter(|entry| entry.address == address)
+        .map(|entry| entry.proof))
+}
+
+/// Retrieves all the [`IngressProof`]s for a [`DataRoot`] from [`IngressProofsBySigner`], one per signer
+pub fn ingress_proofs_by_data_root<T: DbTx>(
+    tx: &T,
+    data_root: DataRoot,
+) -> eyre::Result<Vec<CachedIngressProof>> {
+    let mut cursor = tx.cursor_dup_read::<IngressProofsBySigner>()?;
+    let walker = cursor.walk_dup(Some(data_root), None)?;
+    Ok(walker
+        .map(|entry| entry.map(|(_, proof)| proof))
+        .collect::<Result<Vec<_>, _>>()?)
+}
+
 pub fn get_cache_size<T: Table, TX: DbTx>(tx: &TX, chunk_size: u64) -> eyre::Result<(u64, u64)> {
     let chunk_count: usize = tx.entries::<T>()?;
     Ok((chunk_count as u64, chunk_count as u64 * chunk_size))
@@ -289,6 +484,28 @@ pub fn insert_peer_list_item<T: DbTxMut>(
     Ok(tx.put::<PeerListItems>(mining_address.clone(), peer_list_entry.clone().into())?)
 }
 
+/// Stores the output of a VDF step in [`VdfSteps`]
+pub fn insert_vdf_step<T: DbTxMut>(tx: &T, global_step: u64, output: H256) -> eyre::Result<()> {
+    Ok(tx.put::<VdfSteps>(global_step, output)?)
+}
+
+/// Deletes the VDF step with the given number from [`VdfSteps`], if stored
+pub fn delete_vdf_step<T: DbTxMut>(tx: &T, global_step: u64) -> eyre::Result<()> {
+    tx.delete::<VdfSteps>(global_step, None)?;
+    Ok(())
+}
+
+/// Gets up to `limit` of the most recent VDF steps stored in [`VdfSteps`], oldest first
+pub fn latest_vdf_steps<T: DbTx>(tx: &T, limit: usize) -> eyre::Result<Vec<(u64, H256)>> {
+    let mut cursor = tx.cursor_read::<VdfSteps>()?;
+    let mut steps = cursor
+        .walk_back(None)?
+        .take(limit)
+        .collect::<Result<Vec<_>, _>>()?;
+    steps.reverse();
+    Ok(steps)
+}
+
 pub fn walk_all<T: Table, TX: DbTx>(
     read_tx: &TX,
 ) -> eyre::Result<Vec<(<T as Table>::Key, <T as Table>::Value)>> {
@@ -317,12 +534,18 @@ pub fn database_schema_version<T: DbTx>(tx: &T) -> Result<Option<u32>, DatabaseE
 
 #[cfg(test)]
 mod tests {
-    use irys_types::{CommitmentTransaction, IrysBlockHeader, IrysTransactionHeader, H256};
+    use irys_types::{
+        ingress::IngressProof, Address, CommitmentTransaction, IrysBlockHeader,
+        IrysTransactionHeader, Tag, H256,
+    };
     use reth_db::Database;
 
     use crate::{
-        block_header_by_hash, commitment_tx_by_txid, config::get_data_dir,
-        db::IrysDatabaseExt as _, insert_commitment_tx, tables::IrysTables,
+        block_header_by_hash, commitment_tx_by_txid, commitment_tx_ids_by_signer,
+        config::get_data_dir, db::IrysDatabaseExt as _, index_commitment_tx,
+        ingress_proof_by_data_root_and_address, ingress_proofs_by_data_root, insert_commitment_tx,
+        insert_vdf_step, latest_vdf_steps, store_ingress_proof, tables::IrysTables,
+        tx_ids_by_ledger, tx_ids_by_signer, tx_ids_by_tag,
     };
 
     use super::{insert_block_header, insert_tx_header, open_or_create_db, tx_header_by_txid};
@@ -375,6 +598,183 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn ingress_proofs_per_signer_test() -> eyre::Result<()> {
+        let path = get_data_dir();
+        let db = open_or_create_db(path, IrysTables::ALL, None).unwrap();
+
+        let data_root = H256::from([3u8; 32]);
+        let proof_a = IngressProof {
+            data_root,
+            proof: H256::from([1u8; 32]),
+            ..Default::default()
+        };
+        let proof_b = IngressProof {
+            data_root,
+            proof: H256::from([2u8; 32]),
+            ..Default::default()
+        };
+        let address_a = Address::from([10u8; 20]);
+        let address_b = Address::from([5u8; 20]);
+
+        let duplicates = db.update_eyre(|tx| {
+            Ok((
+                store_ingress_proof(tx, address_a, &proof_a)?,
+                store_ingress_proof(tx, address_b, &proof_b)?,
+                store_ingress_proof(tx, address_a, &proof_a)?,
+            ))
+        })?;
+        assert_eq!(duplicates, (false, false, true));
+
+        let proofs = db.view_eyre(|tx| ingress_proofs_by_data_root(tx, data_root))?;
+        // entries are ordered by signer address
+        assert_eq!(proofs.len(), 2);
+        assert_eq!(proofs[0].address, address_b);
+        assert_eq!(proofs[0].proof, proof_b);
+        assert_eq!(proofs[1].address, address_a);
+        assert_eq!(proofs[1].proof, proof_a);
+
+        let proof =
+            db.view_eyre(|tx| ingress_proof_by_data_root_and_address(tx, data_root, address_a))?;
+        assert_eq!(proof, Some(proof_a));
+        let missing = db.view_eyre(|tx| {
+            ingress_proof_by_data_root_and_address(tx, data_root, Address::from([7u8; 20]))
+        })?;
+        assert_eq!(missing, None);
+        Ok(())
+    }
+
+    #[test]
+    fn tx_tags_index_test() -> eyre::Result<()> {
+        let path = get_data_dir();
+        let db = open_or_create_db(path, IrysTables::ALL, None).unwrap();
+
+        let tx_headers = (1..=5_u8)
+            .map(|i| IrysTransactionHeader {
+                id: H256::from([i; 32]),
+                tags: Some(vec![
+                    Tag::new("App-Name", "irys-test"),
+                    Tag::new("index", i.to_string()),
+                ]),
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+        db.update_eyre(|tx| {
+            for tx_header in &tx_headers {
+                insert_tx_header(tx, tx_header)?;
+            }
+            Ok(())
+        })?;
+        // unconfirmed txs aren't indexed
+        let unconfirmed =
+            db.view_eyre(|tx| tx_ids_by_tag(tx, "App-Name", "irys-test", None, 10))?;
+        assert!(unconfirmed.is_empty());
+
+        db.update_eyre(|tx| {
+            for tx_header in &tx_headers {
+                index_tx_header(tx, tx_header)?;
+            }
+            // re-indexing a header doesn't duplicate its index entries
+            index_tx_header(tx, &tx_headers[0])
+        })?;
+
+        // the tags are stored with the header
+        let stored = db.view_eyre(|tx| tx_header_by_txid(tx, &tx_headers[2].id))?;
+        assert_eq!(stored, Some(tx_headers[2].clone()));
+
+        // page through all the txs sharing a tag
+        let first_page = db.view_eyre(|tx| tx_ids_by_tag(tx, "App-Name", "irys-test", None, 3))?;
+        assert_eq!(
+            first_page,
+            tx_headers[..3].iter().map(|h| h.id).collect::<Vec<_>>()
+        );
+        let second_page = db.view_eyre(|tx| {
+            tx_ids_by_tag(tx, "App-Name", "irys-test", first_page.last().copied(), 3)
+        })?;
+        assert_eq!(
+            second_page,
+            tx_headers[3..].iter().map(|h| h.id).collect::<Vec<_>>()
+        );
+
+        let by_index = db.view_eyre(|tx| tx_ids_by_tag(tx, "index", "4", None, 10))?;
+        assert_eq!(by_index, vec![tx_headers[3].id]);
+        let missing = db.view_eyre(|tx| tx_ids_by_tag(tx, "App-Name", "other", None, 10))?;
+        assert!(missing.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn tx_secondary_indexes_test() -> eyre::Result<()> {
+        let path = get_data_dir();
+        let db = open_or_create_db(path, IrysTables::ALL, None).unwrap();
+
+        let signer_a = Address::from([1u8; 20]);
+        let signer_b = Address::from([2u8; 20]);
+        let tx_headers = [(1_u8, signer_a, 0), (2, signer_b, 0), (3, signer_a, 1)].map(
+            |(i, signer, ledger_id)| IrysTransactionHeader {
+                id: H256::from([i; 32]),
+                signer,
+                ledger_id,
+                ..Default::default()
+            },
+        );
+        let commitment_tx = CommitmentTransaction {
+            id: H256::from([4u8; 32]),
+            signer: signer_b,
+            ..Default::default()
+        };
+        db.update_eyre(|tx| {
+            for tx_header in &tx_headers {
+                insert_tx_header(tx, tx_header)?;
+                index_tx_header(tx, tx_header)?;
+            }
+            insert_commitment_tx(tx, &commitment_tx)
+        })?;
+        // unconfirmed commitments aren't indexed
+        let unconfirmed = db.view_eyre(|tx| commitment_tx_ids_by_signer(tx, signer_b, None, 10))?;
+        assert!(unconfirmed.is_empty());
+        db.update_eyre(|tx| index_commitment_tx(tx, &commitment_tx))?;
+
+        let by_signer = db.view_eyre(|tx| tx_ids_by_signer(tx, signer_a, None, 10))?;
+        assert_eq!(by_signer, vec![tx_headers[0].id, tx_headers[2].id]);
+        let next_page =
+            db.view_eyre(|tx| tx_ids_by_signer(tx, signer_a, Some(tx_headers[0].id), 10))?;
+        assert_eq!(next_page, vec![tx_headers[2].id]);
+
+        let by_ledger = db.view_eyre(|tx| tx_ids_by_ledger(tx, 0, None, 10))?;
+        assert_eq!(by_ledger, vec![tx_headers[0].id, tx_headers[1].id]);
+
+        let commitments = db.view_eyre(|tx| commitment_tx_ids_by_signer(tx, signer_b, None, 10))?;
+        assert_eq!(commitments, vec![commitment_tx.id]);
+        let commitments = db.view_eyre(|tx| commitment_tx_ids_by_signer(tx, signer_a, None, 10))?;
+        assert!(commitments.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn latest_vdf_steps_test() -> eyre::Result<()> {
+        let path = get_data_dir();
+        let db = open_or_create_db(path, IrysTables::ALL, None).unwrap();
+
+        assert!(db.view_eyre(|tx| latest_vdf_steps(tx, 3))?.is_empty());
+
+        db.update_eyre(|tx| {
+            for step in 1..=5_u8 {
+                insert_vdf_step(tx, step.into(), H256::from([step; 32]))?;
+            }
+            Ok(())
+        })?;
+
+        let steps = db.view_eyre(|tx| latest_vdf_steps(tx, 3))?;
+        assert_eq!(
+            steps,
+            (3..=5_u8)
+                .map(|step| (u64::from(step), H256::from([step; 32])))
+                .collect::<Vec<_>>()
+        );
+        Ok(())
+    }
+
     // #[test]
     // fn insert_and_get_a_block() {
     //     //let path = tempdir().unwrap();
@@ -2,7 +2,8 @@ use alloy_primitives::aliases::U232;
 use arbitrary::Arbitrary;
 use bytes::Buf as _;
 use irys_types::{
-    partition::PartitionHash, Base64, ChunkPathHash, Compact, TxChunkOffset, UnpackedChunk, H256,
+    ingress::IngressProof, partition::PartitionHash, Address, Base64, ChunkPathHash, Compact,
+    TxChunkOffset, UnpackedChunk, H256,
 };
 use reth_db::table::{Decode, Encode};
 use reth_db::DatabaseError;
@@ -165,6 +166,33 @@ impl Compact for CachedChunkIndexEntry {
     }
 }
 
+#[derive(Clone, Debug, Eq, Default, PartialEq, Serialize, Deserialize)]
+/// An ingress proof for a data root, keyed by the address that signed it
+pub struct CachedIngressProof {
+    pub address: Address, // subkey
+    pub proof: IngressProof,
+}
+
+const ADDRESS_BYTES: usize = std::mem::size_of::<Address>();
+
+// Same as `CachedChunkIndexEntry`, the subkey is encoded uncompressed at the start of the value
+impl Compact for CachedIngressProof {
+    fn to_compact<B>(&self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        buf.put_slice(self.address.as_slice());
+        let proof_bytes = self.proof.to_compact(buf);
+        proof_bytes + ADDRESS_BYTES
+    }
+
+    fn from_compact(buf: &[u8], len: usize) -> (Self, &[u8]) {
+        let address = Address::from_slice(&buf[..ADDRESS_BYTES]);
+        let (proof, out) = IngressProof::from_compact(&buf[ADDRESS_BYTES..], len - ADDRESS_BYTES);
+        (Self { address, proof }, out)
+    }
+}
+
 /// converts a size (in bytes) to the number of chunks, rounding up (size 0 -> illegal state, size 1 -> 1, size 262144 -> 1, 262145 -> 2 )
 pub fn data_size_to_chunk_count(data_size: u64, chunk_size: u64) -> eyre::Result<u32> {
     assert_ne!(data_size, 0, "tx data_size 0 is illegal");
@@ -1,5 +1,17 @@
-/// Once data has been well confirmed by being part of a transaction that is
-/// in a block with several confirmations, it can move out of the `db_cache`
-/// and into a `db_index` that is able to store more properties (to support
-/// mining) now that the data is confirmed.
-const _X: u64 = 1;
+//! Once data has been well confirmed by being part of a transaction that is
+//! in a block with several confirmations, it can move out of the `db_cache`
+//! and into a `db_index` that is able to store more properties (to support
+//! mining) now that the data is confirmed.
+use irys_types::{Compact, H256};
+use serde::{Deserialize, Serialize};
+
+/// Location of a bundle data item within the data of its promoted bundle transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, Compact)]
+pub struct BundleItemLocation {
+    /// Id of the transaction whose data contains the bundle
+    pub bundle_tx_id: H256,
+    /// Byte offset of the encoded item relative to the start of the transaction data
+    pub offset: u64,
+    /// Size in bytes of the encoded item
+    pub size: u64,
+}
@@ -8,17 +8,11 @@ use std::fmt::Debug;
 use tracing::debug;
 
 /// Bump this every time you need to migrate data
-const CURRENT_DB_VERSION: u32 = 1;
-
-/// Example migration step to version 2
-fn migration_to_v2(_db: &DatabaseEnv) -> Result<(), DatabaseError> {
-    // template for future migrations
-    // update the database schema version here
-    Ok(())
-}
+const CURRENT_DB_VERSION: u32 = 3;
 
 mod v0_to_v1 {
     use super::*;
+    use crate::reth_db::RawTable;
     use crate::tables::{
         CachedChunks, CachedChunksIndex, CachedDataRoots, DataRootLRU, IngressProofs,
         IrysBlockHeaders, IrysTxHeaders,
@@ -33,7 +27,8 @@ mod v0_to_v1 {
     {
         debug!("Migrating from v0 to v1");
         move_all_records::<IrysBlockHeaders, TXOld, TXNew>(tx_old, tx_new)?;
-        move_all_records::<IrysTxHeaders, TXOld, TXNew>(tx_old, tx_new)?;
+        // copied as raw bytes, as the v2 to v3 migration expects the v0 layout of the tx headers
+        move_all_records::<RawTable<IrysTxHeaders>, TXOld, TXNew>(tx_old, tx_new)?;
         move_all_records::<CachedDataRoots, TXOld, TXNew>(tx_old, tx_new)?;
         move_all_records::<CachedChunksIndex, TXOld, TXNew>(tx_old, tx_new)?;
         move_all_records::<CachedChunks, TXOld, TXNew>(tx_old, tx_new)?;
@@ -66,6 +61,115 @@ mod v0_to_v1 {
     }
 }
 
+/// Moves the ingress proofs to a table holding one proof per signer for each data root
+mod v1_to_v2 {
+    use super::*;
+    use crate::tables::IngressProofs;
+    use reth_db_api::cursor::DbCursorRO;
+    use tracing::warn;
+
+    pub(crate) fn migrate<TX>(tx: &TX) -> eyre::Result<()>
+    where
+        TX: DbTxMut + DbTx + Debug,
+    {
+        debug!("Migrating from v1 to v2");
+        let mut cursor = tx.cursor_read::<IngressProofs>()?;
+        for entry in cursor.walk(None)? {
+            let (data_root, proof) = entry?;
+            match proof.recover_signer() {
+                Ok(signer) => {
+                    crate::store_ingress_proof(tx, signer, &proof)?;
+                }
+                // the proof would be rejected by promotion anyway, it's regenerated once the data
+                // root's chunks are ingested again
+                Err(error) => warn!(
+                    ?data_root,
+                    "Dropping ingress proof with an invalid signature: {}", error
+                ),
+            }
+        }
+        tx.clear::<IngressProofs>()?;
+
+        crate::set_database_schema_version(tx, 2)?;
+        Ok(())
+    }
+}
+
+/// Rewrites the tx headers in the layout that includes the `tags` field
+mod v2_to_v3 {
+    use super::*;
+    use crate::reth_db::RawTable;
+    use crate::tables::{CompactTxHeader, IrysTxHeaders};
+    use irys_types::{Address, IrysSignature, IrysTransactionHeader, TxIngressProof, H256};
+    use reth_codecs::Compact;
+    use reth_db_api::cursor::DbCursorRO;
+
+    /// [`IrysTransactionHeader`] as stored up to schema version 2
+    #[derive(Debug, Default, Compact)]
+    pub(crate) struct LegacyTxHeader {
+        pub id: H256,
+        pub version: u8,
+        pub anchor: H256,
+        pub signer: Address,
+        pub data_root: H256,
+        pub data_size: u64,
+        pub term_fee: u64,
+        pub ledger_id: u32,
+        pub chain_id: u64,
+        pub signature: IrysSignature,
+        pub bundle_format: Option<u64>,
+        pub perm_fee: Option<u64>,
+        pub ingress_proofs: Option<TxIngressProof>,
+    }
+
+    /// Mirrors the [`CompactTxHeader`] wrapper so the stored bytes decode the same way
+    #[derive(Debug, Default, Compact)]
+    pub(crate) struct LegacyCompactTxHeader(pub LegacyTxHeader);
+
+    impl From<LegacyTxHeader> for IrysTransactionHeader {
+        fn from(legacy: LegacyTxHeader) -> Self {
+            Self {
+                id: legacy.id,
+                version: legacy.version,
+                anchor: legacy.anchor,
+                signer: legacy.signer,
+                data_root: legacy.data_root,
+                data_size: legacy.data_size,
+                term_fee: legacy.term_fee,
+                ledger_id: legacy.ledger_id,
+                chain_id: legacy.chain_id,
+                signature: legacy.signature,
+                bundle_format: legacy.bundle_format,
+                perm_fee: legacy.perm_fee,
+                tags: None,
+                ingress_proofs: legacy.ingress_proofs,
+            }
+        }
+    }
+
+    pub(crate) fn migrate<TX>(tx: &TX) -> eyre::Result<()>
+    where
+        TX: DbTxMut + DbTx + Debug,
+    {
+        debug!("Migrating from v2 to v3");
+        // the values can't be decoded as `CompactTxHeader` anymore, so read the raw bytes
+        let mut cursor = tx.cursor_read::<RawTable<IrysTxHeaders>>()?;
+        let mut headers = Vec::new();
+        for entry in cursor.walk(None)? {
+            let (key, value) = entry?;
+            let bytes = value.raw_value();
+            let (legacy, _) = LegacyCompactTxHeader::from_compact(bytes, bytes.len());
+            headers.push((key.key()?, IrysTransactionHeader::from(legacy.0)));
+        }
+        for (tx_id, header) in headers {
+            tx.put::<IrysTxHeaders>(tx_id, CompactTxHeader(header))?;
+        }
+
+        crate::set_database_schema_version(tx, 3)?;
+        Ok(())
+    }
+}
+
 /// This function migrates data from an old DB instance to a new DB instance.
 pub fn check_db_version_and_run_migrations_if_needed(
     old_db: &RethDbWrapper,
@@ -75,25 +179,27 @@ pub fn check_db_version_and_run_migrations_if_needed(
     let version = new_db.view(crate::database_schema_version)??;
     debug!("Database version: {:?}", version);
     debug!("Current database version: {:?}", CURRENT_DB_VERSION);
-    if let Some(v) = version {
-        // A version exists. If it’s less than CURRENT_DB_VERSION, apply sequential migrations.
-        if v < CURRENT_DB_VERSION {
-            for next_version in (v + 1)..=CURRENT_DB_VERSION {
-                match next_version {
-                    // This is a template for future migrations.
-                    2 => migration_to_v2(new_db)?,
-                    _ => (),
-                }
-            }
+    let version = match version {
+        Some(v) => v,
+        None => {
+            debug!("No DB schema version information found in the new database. Applying initial migration from v0 to v1.");
+            old_db.update_eyre(|tx_old| {
+                new_db.update_eyre(|tx_new| {
+                    v0_to_v1::migrate(tx_old, tx_new)?;
+                    Ok(())
+                })
+            })?;
+            1
+        }
+    };
+
+    // If the version is less than CURRENT_DB_VERSION, apply sequential migrations.
+    for next_version in (version + 1)..=CURRENT_DB_VERSION {
+        match next_version {
+            2 => new_db.update_eyre(|tx| v1_to_v2::migrate(tx))?,
+            3 => new_db.update_eyre(|tx| v2_to_v3::migrate(tx))?,
+            _ => (),
         }
-    } else {
-        debug!("No DB schema version information found in the new database. Applying initial migration from v0 to v1.");
-        old_db.update_eyre(|tx_old| {
-            new_db.update_eyre(|tx_new| {
-                v0_to_v1::migrate(tx_old, tx_new)?;
-                Ok(())
-            })
-        })?;
     }
 
     Ok(())
@@ -102,14 +208,24 @@ pub fn check_db_version_and_run_migrations_if_needed(
 #[cfg(test)]
 mod tests {
     use crate::db::RethDbWrapper;
-    use crate::migration::check_db_version_and_run_migrations_if_needed;
+    use crate::migration::{
+        check_db_version_and_run_migrations_if_needed,
+        v2_to_v3::{LegacyCompactTxHeader, LegacyTxHeader},
+        CURRENT_DB_VERSION,
+    };
     use crate::open_or_create_db;
+    use crate::reth_db::{RawKey, RawTable, RawValue};
     use crate::{
         db_cache::DataRootLRUEntry,
-        tables::{DataRootLRU, IrysTables},
+        tables::{DataRootLRU, IngressProofs, IngressProofsBySigner, IrysTables, IrysTxHeaders},
     };
     use irys_testing_utils::utils::temporary_directory;
-    use irys_types::H256;
+    use irys_types::{
+        ingress::{generate_ingress_proof, IngressProof},
+        irys::IrysSigner,
+        ConsensusConfig, IrysTransactionHeader, H256,
+    };
+    use reth_codecs::Compact as _;
     use reth_db_api::transaction::{DbTx, DbTxMut};
     use reth_db_api::Database;
 
@@ -156,7 +272,100 @@ mod tests {
         assert_eq!(new_db_value.unwrap(), value);
         new_tx.commit()?;
 
-        assert_eq!(new_version.unwrap(), 1);
+        assert_eq!(new_version.unwrap(), CURRENT_DB_VERSION);
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_migrate_ingress_proofs_from_v1_to_v2() -> Result<(), Box<dyn std::error::Error>> {
+        let old_db_path = temporary_directory(None, false);
+        let old_db = RethDbWrapper::new(open_or_create_db(old_db_path, IrysTables::ALL, None)?);
+
+        let new_db_path = temporary_directory(None, false);
+        let new_db = open_or_create_db(new_db_path, IrysTables::ALL, None)?;
+
+        let signer = IrysSigner::random_signer(&ConsensusConfig::testnet());
+        let data_root = H256::random();
+        let chunks = vec![Ok(vec![7_u8; 32])];
+        let proof = generate_ingress_proof(signer.clone(), data_root, chunks.into_iter())?;
+        // a proof whose signer can't be recovered is dropped
+        let bad_proof = IngressProof {
+            data_root: H256::random(),
+            ..Default::default()
+        };
+
+        let write_tx = new_db.tx_mut()?;
+        write_tx.put::<IngressProofs>(data_root, proof.clone())?;
+        write_tx.put::<IngressProofs>(bad_proof.data_root, bad_proof)?;
+        crate::set_database_schema_version(&write_tx, 1)?;
+        write_tx.commit()?;
+
+        check_db_version_and_run_migrations_if_needed(&old_db, &new_db)?;
+
+        let new_version = new_db.view(|tx| crate::database_schema_version(tx).unwrap())?;
+        assert_eq!(new_version.unwrap(), CURRENT_DB_VERSION);
+
+        let read_tx = new_db.tx()?;
+        assert_eq!(
+            crate::ingress_proof_by_data_root_and_address(&read_tx, data_root, signer.address())?,
+            Some(proof)
+        );
+        assert_eq!(read_tx.entries::<IngressProofsBySigner>()?, 1);
+        assert_eq!(read_tx.entries::<IngressProofs>()?, 0);
+        read_tx.commit()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_migrate_tx_headers_from_v2_to_v3() -> Result<(), Box<dyn std::error::Error>> {
+        let old_db_path = temporary_directory(None, false);
+        let old_db = RethDbWrapper::new(open_or_create_db(old_db_path, IrysTables::ALL, None)?);
+
+        let new_db_path = temporary_directory(None, false);
+        let new_db = open_or_create_db(new_db_path, IrysTables::ALL, None)?;
+
+        let legacy = LegacyTxHeader {
+            id: H256::random(),
+            anchor: H256::random(),
+            data_root: H256::random(),
+            data_size: 1024,
+            term_fee: 100,
+            chain_id: 1270,
+            perm_fee: Some(200),
+            ..Default::default()
+        };
+        let expected = IrysTransactionHeader {
+            id: legacy.id,
+            anchor: legacy.anchor,
+            data_root: legacy.data_root,
+            data_size: legacy.data_size,
+            term_fee: legacy.term_fee,
+            chain_id: legacy.chain_id,
+            perm_fee: legacy.perm_fee,
+            ..Default::default()
+        };
+        let mut bytes = Vec::new();
+        LegacyCompactTxHeader(legacy).to_compact(&mut bytes);
+
+        let write_tx = new_db.tx_mut()?;
+        write_tx
+            .put::<RawTable<IrysTxHeaders>>(RawKey::new(expected.id), RawValue::from_vec(bytes))?;
+        crate::set_database_schema_version(&write_tx, 2)?;
+        write_tx.commit()?;
+
+        check_db_version_and_run_migrations_if_needed(&old_db, &new_db)?;
+
+        let new_version = new_db.view(|tx| crate::database_schema_version(tx).unwrap())?;
+        assert_eq!(new_version.unwrap(), 3);
+
+        let read_tx = new_db.tx()?;
+        assert_eq!(
+            crate::tx_header_by_txid(&read_tx, &expected.id)?,
+            Some(expected)
+        );
+        read_tx.commit()?;
 
         Ok(())
     }
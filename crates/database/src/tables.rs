@@ -1,8 +1,9 @@
 use crate::db_cache::{DataRootLRUEntry, GlobalChunkOffset, PartitionHashes};
+use crate::db_index::BundleItemLocation;
 use crate::metadata::MetadataKey;
 use crate::submodule::tables::RelativeStartOffsets;
 use crate::{
-    db_cache::{CachedChunk, CachedChunkIndexEntry, CachedDataRoot},
+    db_cache::{CachedChunk, CachedChunkIndexEntry, CachedDataRoot, CachedIngressProof},
     submodule::tables::{ChunkOffsets, ChunkPathHashes},
 };
 use irys_types::{
@@ -86,6 +87,7 @@ impl_compression_for_compact!(
     CompactPeerListItem,
     CachedDataRoot,
     CachedChunkIndexEntry,
+    CachedIngressProof,
     CachedChunk,
     ChunkOffsets,
     ChunkPathHashes,
@@ -93,7 +95,8 @@ impl_compression_for_compact!(
     RelativeStartOffsets,
     DataRootLRUEntry,
     GlobalChunkOffset,
-    CompactBase64
+    CompactBase64,
+    BundleItemLocation
 );
 
 use paste::paste;
@@ -144,12 +147,20 @@ table CachedChunks {
     type Value = CachedChunk;
 }
 
-/// Indexes Ingress proofs by their data_root
+/// Indexes Ingress proofs by their data_root, as stored up to schema version 1.
+/// The v1 to v2 migration moves them to [`IngressProofsBySigner`]
 table IngressProofs {
     type Key = DataRoot;
     type Value = IngressProof;
 }
 
+/// Indexes Ingress proofs by their data_root, with one entry per signer address
+table IngressProofsBySigner {
+    type Key = DataRoot;
+    type Value = CachedIngressProof;
+    type SubKey = Address;
+}
+
 /// Maps an ingress proof (by data_root) to the latest possible height it could be used at, given known transactions.
 /// this value is updated every time we receive a valid to-be-promoted transaction to (<height of anchor block> + ANCHOR_EXPIRY_DEPTH)
 /// and is pruned if value > <current_height>
@@ -180,6 +191,55 @@ table PeerListItems {
     type Value = CompactPeerListItem;
 }
 
+/// Indexes transaction ids by their tags, keyed by the hash of the tag name and value
+/// (see [`crate::tx_tag_key`]), with one entry per transaction
+table TxTags {
+    type Key = H256;
+    type Value = H256;
+    type SubKey = H256;
+}
+
+/// Indexes data transaction ids by their signer address, with one entry per transaction
+table TxsBySigner {
+    type Key = Address;
+    type Value = H256;
+    type SubKey = H256;
+}
+
+/// Indexes data transaction ids by their destination ledger id, with one entry per transaction
+table TxsByLedger {
+    type Key = u32;
+    type Value = H256;
+    type SubKey = H256;
+}
+
+/// Indexes commitment transaction ids by their signer address, with one entry per transaction
+table CommitmentsBySigner {
+    type Key = Address;
+    type Value = H256;
+    type SubKey = H256;
+}
+
+/// Maps a bundle data item id to its location in the data of a promoted bundle transaction
+table BundleItems {
+    type Key = H256;
+    type Value = BundleItemLocation;
+}
+
+/// Promoted bundle transactions whose items failed to be indexed, with the number of
+/// failed attempts, so indexing is retried on later migrations
+table PendingBundles {
+    type Key = H256;
+    type Value = u64;
+}
+
+/// Stores the outputs of the most recent VDF steps by global step number, so
+/// the VDF state can be restored after a restart
+table VdfSteps {
+    type Key = u64;
+    type Value = H256;
+}
+
 /// Table to store various metadata, such as the current db schema version
 table Metadata {
     type Key = MetadataKey;
@@ -1,7 +1,7 @@
 use irys_database::{
     open_or_create_db,
     reth_db::{Database as _, DatabaseEnv},
-    tables::{IngressProofs, IrysTables, IrysTxHeaders},
+    tables::{IngressProofsBySigner, IrysTables, IrysTxHeaders},
     walk_all,
 };
 
@@ -13,7 +13,7 @@ pub fn load_db() -> DatabaseEnv {
 fn _promotion_debug() -> eyre::Result<()> {
     let db = load_db();
     let read_tx = db.tx()?;
-    let ingress_proofs = walk_all::<IngressProofs, _>(&read_tx)?;
+    let ingress_proofs = walk_all::<IngressProofsBySigner, _>(&read_tx)?;
     dbg!(ingress_proofs);
     let storage_transactions = walk_all::<IrysTxHeaders, _>(&read_tx)?;
     dbg!(storage_transactions);
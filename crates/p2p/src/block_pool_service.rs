@@ -1,4 +1,4 @@
-use crate::peer_list::{PeerListFacade, PeerListFacadeError};
+use crate::peer_list::{PeerListFacade, PeerListFacadeError, ScoreDecreaseReason};
 use crate::{fast_forward_vdf_steps_from_block, wait_for_vdf_step, SyncState};
 use actix::{
     Actor, AsyncContext, Context, Handler, Message, ResponseActFuture, Supervised, SystemService,
@@ -11,16 +11,56 @@ use irys_actors::vdf_service::VdfServiceMessage;
 use irys_api_client::ApiClient;
 use irys_database::block_header_by_hash;
 use irys_database::db::IrysDatabaseExt as _;
-use irys_types::{BlockHash, DatabaseProvider, IrysBlockHeader, RethPeerInfo};
+use irys_types::{
+    Address, Base64, BlockHash, BlockPoolConfig, DatabaseProvider, IrysBlockHeader, RethPeerInfo,
+    H256,
+};
+use serde::Serialize;
 use std::collections::HashMap;
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tokio::sync::mpsc::{Sender, UnboundedSender};
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
+
+/// How often the pool looks for orphaned blocks whose parent never arrived
+const ORPHAN_EXPIRY_CHECK_INTERVAL: Duration = Duration::from_secs(30);
 
 #[derive(Debug, Clone)]
 pub enum BlockPoolError {
     DatabaseError(String),
     OtherInternal(String),
     BlockError(String),
+    /// The block was rejected because the peer that sent it has too many blocks in the pool
+    PeerQuotaExceeded(Address),
+}
+
+/// A block held by the [`BlockPoolService`]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PooledBlockInfo {
+    pub block_hash: BlockHash,
+    pub previous_block_hash: BlockHash,
+    pub height: u64,
+    /// The peer the block was received from
+    pub source_miner_address: Address,
+    /// Unix timestamp in milliseconds of when the block entered the pool
+    pub received_at: u64,
+    /// Estimated memory used by the block header, in bytes
+    pub size_bytes: usize,
+}
+
+/// Read access to the blocks held by the [`BlockPoolService`], keyed by block hash
+#[derive(Debug, Clone, Default)]
+pub struct BlockPoolReadGuard(Arc<RwLock<HashMap<BlockHash, PooledBlockInfo>>>);
+
+impl BlockPoolReadGuard {
+    pub fn read(&self) -> RwLockReadGuard<'_, HashMap<BlockHash, PooledBlockInfo>> {
+        self.0.read().expect("block pool lock poisoned")
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, HashMap<BlockHash, PooledBlockInfo>> {
+        self.0.write().expect("block pool lock poisoned")
+    }
 }
 
 impl From<PeerListFacadeError> for BlockPoolError {
@@ -41,6 +81,9 @@ where
 
     pub(crate) orphaned_blocks_by_parent: HashMap<BlockHash, IrysBlockHeader>,
     pub(crate) block_hash_to_parent_hash: HashMap<BlockHash, BlockHash>,
+    /// Source, age and size of the pooled blocks, used to enforce the pool limits
+    pooled_blocks: BlockPoolReadGuard,
+    config: BlockPoolConfig,
 
     pub(crate) block_producer: Option<B>,
     pub(crate) peer_list: Option<PeerListFacade<A, R>>,
@@ -61,6 +104,8 @@ where
             db: None,
             orphaned_blocks_by_parent: HashMap::new(),
             block_hash_to_parent_hash: HashMap::new(),
+            pooled_blocks: BlockPoolReadGuard::default(),
+            config: BlockPoolConfig::default(),
             block_producer: None,
             peer_list: None,
             vdf_sender: None,
@@ -79,7 +124,9 @@ where
     type Context = actix::Context<Self>;
 
     fn started(&mut self, ctx: &mut Self::Context) {
-        let _block_service_addr = ctx.address();
+        ctx.run_interval(ORPHAN_EXPIRY_CHECK_INTERVAL, |act, ctx| {
+            act.expire_orphans(ctx);
+        });
     }
 }
 
@@ -112,11 +159,15 @@ where
         vdf_sender: Option<Sender<BroadcastMiningSeed>>,
         sync_state: SyncState,
         vdf_service_sender: UnboundedSender<VdfServiceMessage>,
+        config: BlockPoolConfig,
+        pooled_blocks: BlockPoolReadGuard,
     ) -> Self {
         Self {
             db: Some(db),
             orphaned_blocks_by_parent: HashMap::new(),
             block_hash_to_parent_hash: HashMap::new(),
+            pooled_blocks,
+            config,
             peer_list: Some(peer_list),
             block_producer: Some(block_producer_addr),
             vdf_sender,
@@ -125,9 +176,159 @@ where
         }
     }
 
+    /// Adds the block to the pool unless it's already there. Blocks of a peer that already
+    /// holds its whole share of the pool are rejected, otherwise the oldest blocks are evicted
+    /// to keep the pool within its limits
+    fn add_to_pool(
+        &mut self,
+        block_header: &IrysBlockHeader,
+        source_miner_address: Address,
+        ctx: &mut <BlockPoolService<A, R, B> as Actor>::Context,
+    ) -> Result<(), BlockPoolError> {
+        let block_hash = block_header.block_hash;
+        if self.block_hash_to_parent_hash.contains_key(&block_hash) {
+            return Ok(());
+        }
+
+        let blocks_from_peer = self
+            .pooled_blocks
+            .read()
+            .values()
+            .filter(|block| block.source_miner_address == source_miner_address)
+            .count();
+        if blocks_from_peer >= self.config.max_blocks_per_peer {
+            warn!(
+                "Block pool: Peer {} exceeded its quota of {} pooled blocks, rejecting block {}",
+                source_miner_address,
+                self.config.max_blocks_per_peer,
+                block_hash.0.to_base58()
+            );
+            self.penalize_peer(source_miner_address, ctx);
+            return Err(BlockPoolError::PeerQuotaExceeded(source_miner_address));
+        }
+
+        let previous_block_hash = block_header.previous_block_hash;
+        // Only one child per parent is kept, a competing sibling replaces the previous one
+        if let Some(sibling) = self
+            .orphaned_blocks_by_parent
+            .insert(previous_block_hash, block_header.clone())
+        {
+            self.block_hash_to_parent_hash.remove(&sibling.block_hash);
+            self.pooled_blocks.write().remove(&sibling.block_hash);
+        }
+        self.block_hash_to_parent_hash
+            .insert(block_hash, previous_block_hash);
+        self.pooled_blocks.write().insert(
+            block_hash,
+            PooledBlockInfo {
+                block_hash,
+                previous_block_hash,
+                height: block_header.height,
+                source_miner_address,
+                received_at: unix_timestamp_millis(),
+                size_bytes: estimated_size(block_header),
+            },
+        );
+
+        self.evict_oldest_blocks(block_hash);
+        Ok(())
+    }
+
+    /// Evicts the oldest blocks, except `keep`, until the pool is within its size limits
+    fn evict_oldest_blocks(&mut self, keep: BlockHash) {
+        loop {
+            let oldest_block = {
+                let pooled_blocks = self.pooled_blocks.read();
+                let total_size: usize = pooled_blocks.values().map(|block| block.size_bytes).sum();
+                if pooled_blocks.len() <= self.config.max_blocks
+                    && total_size <= self.config.max_size_bytes
+                {
+                    return;
+                }
+                pooled_blocks
+                    .values()
+                    .filter(|block| block.block_hash != keep)
+                    .min_by_key(|block| block.received_at)
+                    .map(|block| block.block_hash)
+            };
+            let Some(oldest_block) = oldest_block else {
+                return;
+            };
+            warn!(
+                "Block pool: Pool is full, evicting block {}",
+                oldest_block.0.to_base58()
+            );
+            self.remove_from_pool(&oldest_block);
+        }
+    }
+
+    fn remove_from_pool(&mut self, block_hash: &BlockHash) -> Option<PooledBlockInfo> {
+        if let Some(parent_hash) = self.block_hash_to_parent_hash.remove(block_hash) {
+            self.orphaned_blocks_by_parent.remove(&parent_hash);
+        }
+        self.pooled_blocks.write().remove(block_hash)
+    }
+
+    /// Removes the orphans waiting on `parent_hash`, along with the pooled blocks built on top
+    /// of them
+    fn remove_orphans_of(&mut self, parent_hash: &BlockHash) {
+        let mut parent_hash = *parent_hash;
+        while let Some(orphan) = self.orphaned_blocks_by_parent.get(&parent_hash) {
+            let block_hash = orphan.block_hash;
+            self.remove_from_pool(&block_hash);
+            parent_hash = block_hash;
+        }
+    }
+
+    /// Drops the blocks whose parent didn't arrive in time, lowering the score of the peers
+    /// that sent them
+    fn expire_orphans(&mut self, ctx: &mut <BlockPoolService<A, R, B> as Actor>::Context) {
+        let expiry_millis = self.config.orphan_expiry_secs.saturating_mul(1000);
+        let now = unix_timestamp_millis();
+        let expired_blocks: Vec<BlockHash> = self
+            .pooled_blocks
+            .read()
+            .values()
+            .filter(|block| now.saturating_sub(block.received_at) > expiry_millis)
+            .map(|block| block.block_hash)
+            .collect();
+
+        for block_hash in expired_blocks {
+            if let Some(expired_block) = self.remove_from_pool(&block_hash) {
+                warn!(
+                    "Block pool: Parent of block {} didn't arrive in time, dropping the block",
+                    block_hash.0.to_base58()
+                );
+                self.penalize_peer(expired_block.source_miner_address, ctx);
+            }
+        }
+    }
+
+    fn penalize_peer(
+        &self,
+        miner_address: Address,
+        ctx: &mut <BlockPoolService<A, R, B> as Actor>::Context,
+    ) {
+        let Some(peer_list) = self.peer_list.clone() else {
+            return;
+        };
+        ctx.spawn(
+            async move {
+                if let Err(err) = peer_list
+                    .decrease_peer_score(&miner_address, ScoreDecreaseReason::UnconnectableBlock)
+                    .await
+                {
+                    error!("Failed to decrease peer score: {:?}", err);
+                }
+            }
+            .into_actor(self),
+        );
+    }
+
     fn process_block(
         self: &mut Self,
         block_header: IrysBlockHeader,
+        source_miner_address: Address,
         ctx: &mut <BlockPoolService<A, R, B> as Actor>::Context,
     ) -> ResponseActFuture<Self, Result<(), BlockPoolError>> {
         debug!(
@@ -150,10 +351,9 @@ where
 
         // Adding the block to the pool, so if a block depending on that block arrives,
         // this block won't be requested from the network
-        self.orphaned_blocks_by_parent
-            .insert(prev_block_hash, block_header.clone());
-        self.block_hash_to_parent_hash
-            .insert(current_block_hash, prev_block_hash);
+        if let Err(err) = self.add_to_pool(&block_header, source_miner_address, ctx) {
+            return Box::pin(actix::fut::ready(Err(err)));
+        }
 
         let sync_state = self.sync_state.clone();
 
@@ -255,6 +455,7 @@ where
                 self_addr
                     .send(TryToFetchParent {
                         header: block_header,
+                        source_miner_address,
                     })
                     .await
                     .map_err(|mailbox_error| {
@@ -274,6 +475,8 @@ where
 #[rtype(result = "Result<(), BlockPoolError>")]
 pub(crate) struct ProcessBlock {
     pub header: IrysBlockHeader,
+    /// The peer the block was received from
+    pub source_miner_address: Address,
 }
 
 impl<A, R, B> Handler<ProcessBlock> for BlockPoolService<A, R, B>
@@ -285,7 +488,7 @@ where
     type Result = ResponseActFuture<Self, Result<(), BlockPoolError>>;
 
     fn handle(&mut self, msg: ProcessBlock, ctx: &mut Self::Context) -> Self::Result {
-        self.process_block(msg.header, ctx)
+        self.process_block(msg.header, msg.source_miner_address, ctx)
     }
 }
 
@@ -293,6 +496,7 @@ where
 #[rtype(result = "Result<(), BlockPoolError>")]
 struct TryToFetchParent {
     pub header: IrysBlockHeader,
+    pub source_miner_address: Address,
 }
 
 impl<A, R, B> Handler<TryToFetchParent> for BlockPoolService<A, R, B>
@@ -305,6 +509,7 @@ where
 
     fn handle(&mut self, msg: TryToFetchParent, ctx: &mut Self::Context) -> Self::Result {
         let block_header = msg.header;
+        let source_miner_address = msg.source_miner_address;
         let self_addr = ctx.address();
         let previous_block_hash = block_header.previous_block_hash;
         let parent_is_already_in_the_pool = self
@@ -316,8 +521,10 @@ where
                 // If the parent is also in the cache it's likely that processing has already started
                 if !parent_is_already_in_the_pool {
                     debug!(
-                        "Block pool: Parent block {} not found in the cache, requesting it from the network",
-                        previous_block_hash.0.to_base58()
+                        "Block pool: Parent block {} of block {} from peer {} not found in the cache, requesting it from the network",
+                        previous_block_hash.0.to_base58(),
+                        block_header.block_hash.0.to_base58(),
+                        source_miner_address
                     );
                     self_addr
                         .send(RequestBlockFromTheNetwork {
@@ -359,9 +566,27 @@ where
     type Result = ();
 
     fn handle(&mut self, msg: RemoveBlockFromPool, _ctx: &mut Self::Context) -> () {
-        if let Some(parent_hash) = self.block_hash_to_parent_hash.remove(&msg.block_hash) {
-            self.orphaned_blocks_by_parent.remove(&parent_hash);
-        }
+        self.remove_from_pool(&msg.block_hash);
+    }
+}
+
+/// Removes the blocks waiting on a parent that couldn't be fetched from the network
+#[derive(Message, Debug, Clone)]
+#[rtype(result = "()")]
+struct RemoveOrphansOfBlock {
+    pub parent_hash: BlockHash,
+}
+
+impl<A, R, B> Handler<RemoveOrphansOfBlock> for BlockPoolService<A, R, B>
+where
+    A: ApiClient,
+    R: Handler<RethPeerInfo, Result = eyre::Result<()>> + Actor<Context = Context<R>>,
+    B: BlockDiscoveryFacade,
+{
+    type Result = ();
+
+    fn handle(&mut self, msg: RemoveOrphansOfBlock, _ctx: &mut Self::Context) -> () {
+        self.remove_orphans_of(&msg.parent_hash);
     }
 }
 
@@ -403,8 +628,15 @@ where
                     Ok(())
                 }
                 Err(error) => {
-                    error!("Error while trying to fetch parent block {}: {:?}. Removing the block from the pool", block_hash.0.to_base58(), error);
-                    if let Err(err) = self_addr.send(RemoveBlockFromPool { block_hash }).await {
+                    error!("Error while trying to fetch parent block {}: {:?}. Removing the blocks waiting on it from the pool", block_hash.0.to_base58(), error);
+                    // The orphans can't be connected anymore, so they shouldn't take up the
+                    // quota of the peer that sent them
+                    if let Err(err) = self_addr
+                        .send(RemoveOrphansOfBlock {
+                            parent_hash: block_hash,
+                        })
+                        .await
+                    {
                         error!(
                             "Error while trying to request the block from the network: {:?}",
                             err
@@ -504,10 +736,18 @@ where
     fn handle(&mut self, msg: ProcessOrphanedAncestor, ctx: &mut Self::Context) -> Self::Result {
         let address = ctx.address();
         let maybe_orphaned_block = self.orphaned_blocks_by_parent.get(&msg.block_hash).cloned();
+        let source_miner_address = maybe_orphaned_block.as_ref().and_then(|orphaned_block| {
+            self.pooled_blocks
+                .read()
+                .get(&orphaned_block.block_hash)
+                .map(|block| block.source_miner_address)
+        });
 
         Box::pin(
             async move {
-                if let Some(orphaned_block) = maybe_orphaned_block {
+                if let (Some(orphaned_block), Some(source_miner_address)) =
+                    (maybe_orphaned_block, source_miner_address)
+                {
                     let block_hash_string = orphaned_block.block_hash.0.to_base58();
                     info!(
                         "Start processing orphaned ancestor block: {:?}",
@@ -517,6 +757,7 @@ where
                     address
                         .send(ProcessBlock {
                             header: orphaned_block,
+                            source_miner_address,
                         })
                         .await
                         .map_err(|mailbox_error| {
@@ -546,3 +787,34 @@ where
         )
     }
 }
+
+fn unix_timestamp_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+/// Estimates the memory used by the block header, including the data it holds on the heap
+fn estimated_size(block_header: &IrysBlockHeader) -> usize {
+    let base64_size = |data: &Option<Base64>| data.as_ref().map_or(0, |data| data.0.len());
+    let tx_ids = block_header
+        .data_ledgers
+        .iter()
+        .map(|ledger| ledger.tx_ids.0.len())
+        .chain(
+            block_header
+                .system_ledgers
+                .iter()
+                .map(|ledger| ledger.tx_ids.0.len()),
+        )
+        .sum::<usize>();
+    let vdf_hashes = block_header.vdf_limiter_info.steps.0.len()
+        + block_header.vdf_limiter_info.last_step_checkpoints.0.len();
+
+    std::mem::size_of::<IrysBlockHeader>()
+        + base64_size(&block_header.poa.chunk)
+        + base64_size(&block_header.poa.tx_path)
+        + base64_size(&block_header.poa.data_path)
+        + (tx_ids + vdf_hashes) * std::mem::size_of::<H256>()
+}
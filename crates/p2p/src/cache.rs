@@ -20,6 +20,10 @@ pub(crate) struct GossipCache {
     chunks: Arc<RwLock<HashMap<ChunkPathHash, HashMap<Address, Instant>>>>,
     transactions: Arc<RwLock<HashMap<IrysTransactionId, HashMap<Address, Instant>>>>,
     blocks: Arc<RwLock<HashMap<BlockHash, HashMap<Address, Instant>>>>,
+    /// Ingress proofs are identified by their proof hash, which is unique per signer and data root
+    ingress_proofs: Arc<RwLock<HashMap<H256, HashMap<Address, Instant>>>>,
+    /// VDF steps are identified by their output
+    vdf_steps: Arc<RwLock<HashMap<H256, HashMap<Address, Instant>>>>,
 }
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
@@ -27,6 +31,8 @@ pub(crate) enum GossipCacheKey {
     Chunk(ChunkPathHash),
     Transaction(IrysTransactionId),
     Block(BlockHash),
+    IngressProof(H256),
+    VdfStep(H256),
 }
 
 impl From<&GossipData> for GossipCacheKey {
@@ -36,6 +42,8 @@ impl From<&GossipData> for GossipCacheKey {
             GossipData::Transaction(transaction) => GossipCacheKey::Transaction(transaction.id),
             GossipData::CommitmentTransaction(comm_tx) => GossipCacheKey::Transaction(comm_tx.id),
             GossipData::Block(block) => GossipCacheKey::Block(block.block_hash),
+            GossipData::IngressProof(proof) => GossipCacheKey::IngressProof(proof.proof),
+            GossipData::VdfStep(step) => GossipCacheKey::VdfStep(step.output().unwrap_or_default()),
         }
     }
 }
@@ -55,6 +63,24 @@ impl GossipCache {
         Ok(blocks.contains_key(block_hash))
     }
 
+    pub(crate) fn seen_ingress_proof_from_any_peer(&self, proof_hash: &H256) -> GossipResult<bool> {
+        let ingress_proofs = self
+            .ingress_proofs
+            .read()
+            .map_err(|error| GossipError::Cache(error.to_string()))?;
+
+        Ok(ingress_proofs.contains_key(proof_hash))
+    }
+
+    pub(crate) fn seen_vdf_step_from_any_peer(&self, output: &H256) -> GossipResult<bool> {
+        let vdf_steps = self
+            .vdf_steps
+            .read()
+            .map_err(|error| GossipError::Cache(error.to_string()))?;
+
+        Ok(vdf_steps.contains_key(output))
+    }
+
     pub(crate) fn seen_transaction_from_any_peer(
         &self,
         transaction_id: &IrysTransactionId,
@@ -103,6 +129,22 @@ impl GossipCache {
                 let peer_map = blocks.entry(irys_block_hash).or_default();
                 peer_map.insert(miner_address, now);
             }
+            GossipCacheKey::IngressProof(proof_hash) => {
+                let mut ingress_proofs = self
+                    .ingress_proofs
+                    .write()
+                    .map_err(|error| GossipError::Cache(error.to_string()))?;
+                let peer_map = ingress_proofs.entry(proof_hash).or_default();
+                peer_map.insert(miner_address, now);
+            }
+            GossipCacheKey::VdfStep(output) => {
+                let mut vdf_steps = self
+                    .vdf_steps
+                    .write()
+                    .map_err(|error| GossipError::Cache(error.to_string()))?;
+                let peer_map = vdf_steps.entry(output).or_default();
+                peer_map.insert(miner_address, now);
+            }
         }
         Ok(())
     }
@@ -138,6 +180,26 @@ impl GossipCache {
                     .map_err(|error| GossipError::Cache(error.to_string()))?;
                 blocks.get(&block.block_hash).cloned().unwrap_or_default()
             }
+            GossipData::IngressProof(proof) => {
+                let ingress_proofs = self
+                    .ingress_proofs
+                    .read()
+                    .map_err(|error| GossipError::Cache(error.to_string()))?;
+                ingress_proofs
+                    .get(&proof.proof)
+                    .cloned()
+                    .unwrap_or_default()
+            }
+            GossipData::VdfStep(step) => {
+                let vdf_steps = self
+                    .vdf_steps
+                    .read()
+                    .map_err(|error| GossipError::Cache(error.to_string()))?;
+                vdf_steps
+                    .get(&step.output().unwrap_or_default())
+                    .cloned()
+                    .unwrap_or_default()
+            }
         };
 
         Ok(result.keys().copied().collect())
@@ -185,6 +247,24 @@ impl GossipCache {
             cleanup_map(blocks);
         };
 
+        {
+            let mut ingress_proofs_guard = self
+                .ingress_proofs
+                .write()
+                .map_err(|error| GossipError::Cache(error.to_string()))?;
+            let ingress_proofs = &mut *ingress_proofs_guard;
+            cleanup_map(ingress_proofs);
+        };
+
+        {
+            let mut vdf_steps_guard = self
+                .vdf_steps
+                .write()
+                .map_err(|error| GossipError::Cache(error.to_string()))?;
+            let vdf_steps = &mut *vdf_steps_guard;
+            cleanup_map(vdf_steps);
+        };
+
         Ok(())
     }
 }
@@ -0,0 +1,124 @@
+use crate::peer_list::{PeerListServiceFacade, ScoreDecreaseReason};
+use base58::ToBase58 as _;
+use core::time::Duration;
+use irys_storage::{ChunkPeerSource, VerifyChunk};
+use irys_types::{
+    decode_body, ChunkFormat, DataLedger, DataRoot, TxChunkOffset, UnpackedChunk,
+    BINARY_CONTENT_TYPE,
+};
+use reqwest::header::{ACCEPT, CONTENT_TYPE};
+use tracing::{debug, warn};
+
+/// Number of peers asked for a chunk this node doesn't have
+const CHUNK_FETCH_PEERS: usize = 3;
+const CHUNK_FETCH_TIMEOUT: Duration = Duration::from_secs(5);
+/// Body limit of chunk responses from peers, enough for a JSON encoded chunk and its proof
+const CHUNK_RESPONSE_LIMIT: usize = 1024 * 1024;
+
+/// Fetches chunks from the API of the most active peers
+#[derive(Debug, Clone)]
+pub struct PeerChunkFetcher {
+    peer_list: PeerListServiceFacade,
+    client: reqwest::Client,
+    /// Scheme of the peer API urls, see `HttpConfig::peer_api_scheme`
+    scheme: String,
+}
+
+impl PeerChunkFetcher {
+    pub fn new(peer_list: PeerListServiceFacade, scheme: String) -> Self {
+        Self {
+            peer_list,
+            client: reqwest::Client::new(),
+            scheme,
+        }
+    }
+
+    /// Requests a chunk from one peer, returning `None` if the peer doesn't have it or
+    /// its response can't be read
+    async fn request_chunk(
+        &self,
+        url: &str,
+    ) -> Result<Option<ChunkFormat>, Box<dyn std::error::Error + Send + Sync>> {
+        // peers that predate the binary encoding ignore the Accept header and respond with JSON
+        let mut response = self
+            .client
+            .get(url)
+            .timeout(CHUNK_FETCH_TIMEOUT)
+            .header(ACCEPT, BINARY_CONTENT_TYPE)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            debug!(
+                "Peer doesn't have the chunk at {}: {}",
+                url,
+                response.status()
+            );
+            return Ok(None);
+        }
+
+        let content_type = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(ToOwned::to_owned);
+        let mut body = Vec::new();
+        while let Some(part) = response.chunk().await? {
+            if body.len() + part.len() > CHUNK_RESPONSE_LIMIT {
+                return Err("chunk response is too large".into());
+            }
+            body.extend_from_slice(&part);
+        }
+        Ok(Some(
+            decode_body::<ChunkFormat>(content_type.as_deref(), &body)
+                .map_err(|e| e.to_string())?,
+        ))
+    }
+}
+
+#[async_trait::async_trait]
+impl ChunkPeerSource for PeerChunkFetcher {
+    async fn fetch_chunk(
+        &self,
+        ledger: DataLedger,
+        data_root: DataRoot,
+        data_tx_offset: TxChunkOffset,
+        verify: &VerifyChunk<'_>,
+    ) -> eyre::Result<Option<UnpackedChunk>> {
+        let peers = self
+            .peer_list
+            .top_active_peers(Some(CHUNK_FETCH_PEERS), None)
+            .await?;
+
+        for (miner_address, peer) in peers {
+            let url = format!(
+                "{}://{}/v1/chunk/data_root/{}/{}/{}?local=true",
+                self.scheme,
+                peer.address.api,
+                u32::from(ledger),
+                data_root.0.to_base58(),
+                *data_tx_offset
+            );
+            let chunk = match self.request_chunk(&url).await {
+                Ok(Some(chunk)) => chunk,
+                Ok(None) => continue,
+                Err(e) => {
+                    debug!("Failed to fetch the chunk from {}: {}", peer.address.api, e);
+                    continue;
+                }
+            };
+
+            match verify(chunk) {
+                Ok(verified) => return Ok(Some(verified)),
+                Err(e) => {
+                    warn!("Peer {} sent an invalid chunk: {}", peer.address.api, e);
+                    let _ = self
+                        .peer_list
+                        .decrease_peer_score(&miner_address, ScoreDecreaseReason::BogusData)
+                        .await;
+                }
+            }
+        }
+
+        Ok(None)
+    }
+}
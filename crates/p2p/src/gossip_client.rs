@@ -7,10 +7,13 @@ use crate::types::{GossipDataRequest, GossipError, GossipResult};
 use actix::{Actor, Context, Handler};
 use core::time::Duration;
 use irys_api_client::ApiClient;
-use irys_types::{Address, GossipData, GossipRequest, PeerListItem, RethPeerInfo};
-use reqwest::Response;
+use irys_types::{
+    Address, BinaryEncoding, GossipData, GossipRequest, PeerListItem, RethPeerInfo,
+    BINARY_CONTENT_TYPE,
+};
+use reqwest::{header::CONTENT_TYPE, Response, StatusCode};
 use serde::Serialize;
-use tracing::error;
+use tracing::{debug, error};
 
 #[derive(Debug, Clone, Default)]
 pub struct GossipClient {
@@ -40,37 +43,60 @@ impl GossipClient {
     /// If the peer is offline or the request fails, an error is returned.
     pub async fn send_data(&self, peer: &PeerListItem, data: &GossipData) -> GossipResult<()> {
         Self::check_if_peer_is_online(peer)?;
-        match data {
+        let response = match data {
             GossipData::Chunk(unpacked_chunk) => {
-                self.send_data_internal(
+                self.send_binary_data_internal(
                     format!("http://{}/gossip/chunk", peer.address.gossip),
                     unpacked_chunk,
                 )
-                .await?;
+                .await?
             }
             GossipData::Transaction(irys_transaction_header) => {
                 self.send_data_internal(
                     format!("http://{}/gossip/transaction", peer.address.gossip),
                     irys_transaction_header,
                 )
-                .await?;
+                .await?
             }
             GossipData::CommitmentTransaction(commitment_tx) => {
                 self.send_data_internal(
                     format!("http://{}/gossip/commitment_tx", peer.address.gossip),
                     commitment_tx,
                 )
-                .await?;
+                .await?
             }
             GossipData::Block(irys_block_header) => {
-                self.send_data_internal(
+                self.send_binary_data_internal(
                     format!("http://{}/gossip/block", peer.address.gossip),
-                    &irys_block_header,
+                    irys_block_header,
+                )
+                .await?
+            }
+            GossipData::IngressProof(ingress_proof) => {
+                self.send_data_internal(
+                    format!("http://{}/gossip/ingress_proof", peer.address.gossip),
+                    ingress_proof,
                 )
-                .await?;
+                .await?
+            }
+            GossipData::VdfStep(vdf_step) => {
+                self.send_data_internal(
+                    format!("http://{}/gossip/vdf_step", peer.address.gossip),
+                    vdf_step,
+                )
+                .await?
             }
         };
 
+        // a rejected request means the peer didn't take the data. Server errors are returned for
+        // data the peer failed to handle (e.g. already known), which it did receive
+        let status = response.status();
+        if status.is_client_error() {
+            return Err(GossipError::Network(format!(
+                "Peer rejected the gossip request with status {}",
+                status
+            )));
+        }
         Ok(())
     }
 
@@ -96,6 +122,32 @@ impl GossipClient {
             .map_err(|error| GossipError::Network(error.to_string()))
     }
 
+    /// Sends chunks and block headers in the binary encoding, which avoids base64 encoding the
+    /// chunk bytes. Peers that don't support it answer 415 Unsupported Media Type, the data is
+    /// resent as JSON then. Other client errors are about the data itself and aren't retried
+    async fn send_binary_data_internal<T: Serialize + BinaryEncoding + Clone>(
+        &self,
+        url: String,
+        data: &T,
+    ) -> Result<Response, GossipError> {
+        let body = self.create_request(data.clone()).to_binary();
+        let response = self
+            .client
+            .post(&url)
+            .timeout(self.timeout)
+            .header(CONTENT_TYPE, BINARY_CONTENT_TYPE)
+            .body(body)
+            .send()
+            .await
+            .map_err(|error| GossipError::Network(error.to_string()))?;
+
+        if response.status() == StatusCode::UNSUPPORTED_MEDIA_TYPE {
+            debug!("{} doesn't accept binary gossip, falling back to JSON", url);
+            return self.send_data_internal(url, data).await;
+        }
+        Ok(response)
+    }
+
     /// Send data to a peer and update their score based on the result
     ///
     /// # Errors
@@ -6,7 +6,7 @@
     clippy::module_name_repetitions,
     reason = "I don't know how to name it"
 )]
-use crate::block_pool_service::BlockPoolService;
+use crate::block_pool_service::{BlockPoolReadGuard, BlockPoolService};
 use crate::cache::GossipCacheKey;
 use crate::peer_list::PeerListFacade;
 use crate::server_data_handler::GossipServerDataHandler;
@@ -26,14 +26,20 @@ use irys_actors::broadcast_mining_service::BroadcastMiningSeed;
 use irys_actors::mempool_service::MempoolFacade;
 use irys_actors::vdf_service::VdfServiceMessage;
 use irys_api_client::ApiClient;
-use irys_types::{Address, DatabaseProvider, GossipData, PeerListItem, RethPeerInfo};
+use irys_types::{
+    Address, BlockPoolConfig, DatabaseProvider, GossipData, PeerListItem, RethPeerInfo,
+    SyncedBlock, VdfConfig, H256,
+};
 use rand::prelude::SliceRandom as _;
 use reth_tasks::{TaskExecutor, TaskManager};
 use std::net::TcpListener;
 use std::sync::Arc;
 use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
 use tokio::{
-    sync::mpsc::{channel, error::SendError, Receiver, Sender},
+    sync::{
+        mpsc::{channel, error::SendError, Receiver, Sender},
+        Semaphore,
+    },
     time,
 };
 use tracing::{debug, error, info, warn};
@@ -110,8 +116,10 @@ impl ServiceHandleWithShutdownSignal {
 pub struct P2PService {
     cache: Arc<GossipCache>,
     mempool_data_receiver: Option<UnboundedReceiver<GossipData>>,
+    synced_blocks_receiver: Option<UnboundedReceiver<SyncedBlock>>,
     client: GossipClient,
     pub sync_state: SyncState,
+    pub block_pool: BlockPoolReadGuard,
 }
 
 impl P2PService {
@@ -128,9 +136,11 @@ impl P2PService {
     /// Create a new gossip service. To run the service, use the [`P2PService::run`] method.
     /// Also returns a channel to send trusted gossip data to the service. Trusted data should
     /// be sent by the internal components of the system only after complete validation.
+    /// Blocks downloaded by [`crate::sync_chain`] are received through `synced_blocks_receiver`.
     pub fn new(
         mining_address: Address,
         broadcast_data_receiver: UnboundedReceiver<GossipData>,
+        synced_blocks_receiver: UnboundedReceiver<SyncedBlock>,
     ) -> Self {
         let cache = Arc::new(GossipCache::new());
 
@@ -141,7 +151,9 @@ impl P2PService {
             client,
             cache,
             mempool_data_receiver: Some(broadcast_data_receiver),
+            synced_blocks_receiver: Some(synced_blocks_receiver),
             sync_state: SyncState::new(true),
+            block_pool: BlockPoolReadGuard::default(),
         }
     }
 
@@ -163,6 +175,10 @@ impl P2PService {
         vdf_sender: Sender<BroadcastMiningSeed>,
         listener: TcpListener,
         vdf_service_sender: UnboundedSender<VdfServiceMessage>,
+        vdf_config: VdfConfig,
+        vdf_reset_seed: H256,
+        gossip_broadcast: UnboundedSender<GossipData>,
+        block_pool_config: BlockPoolConfig,
     ) -> GossipResult<ServiceHandleWithShutdownSignal>
     where
         A: ApiClient,
@@ -174,14 +190,20 @@ impl P2PService {
             db,
             peer_list.clone(),
             block_discovery.clone(),
-            Some(vdf_sender),
+            Some(vdf_sender.clone()),
             self.sync_state.clone(),
-            vdf_service_sender,
+            vdf_service_sender.clone(),
+            block_pool_config,
+            self.block_pool.clone(),
         );
         let arbiter = actix::Arbiter::new();
         let block_pool_addr =
             BlockPoolService::start_in_arbiter(&arbiter.handle(), |_| block_pool_service);
 
+        let vdf_verification_pool =
+            irys_vdf::verification_thread_pool(&vdf_config).map_err(|error| {
+                GossipError::Internal(InternalGossipError::Unknown(error.to_string()))
+            })?;
         let server_data_handler = GossipServerDataHandler {
             mempool,
             block_pool: block_pool_addr,
@@ -190,7 +212,26 @@ impl P2PService {
             gossip_client: self.client.clone(),
             peer_list_service: peer_list.clone(),
             sync_state: self.sync_state.clone(),
+            vdf_config,
+            vdf_reset_seed,
+            vdf_verification_pool: Arc::new(vdf_verification_pool),
+            vdf_verification_permits: Arc::new(Semaphore::new(1)),
+            vdf_sender,
+            vdf_service_sender,
+            gossip_broadcast,
         };
+        let synced_blocks_receiver =
+            self.synced_blocks_receiver
+                .take()
+                .ok_or(GossipError::Internal(
+                    InternalGossipError::BroadcastReceiverShutdown,
+                ))?;
+        let synced_blocks_task_handle = spawn_synced_blocks_task(
+            synced_blocks_receiver,
+            server_data_handler.clone(),
+            task_executor,
+        );
+
         let server = GossipServer::new(server_data_handler, peer_list.clone());
 
         let server = server.run(listener)?;
@@ -219,6 +260,7 @@ impl P2PService {
             server_handle,
             cache_pruning_task_handle,
             broadcast_task_handle,
+            synced_blocks_task_handle,
             task_executor,
             arbiter,
         );
@@ -314,6 +356,12 @@ impl P2PService {
                 break;
             }
 
+            // VDF steps are superseded every second, the peers relay them
+            // further once verified so a single round is enough
+            if matches!(data, GossipData::VdfStep(_)) {
+                break;
+            }
+
             tokio::time::sleep(BROADCAST_INTERVAL).await;
         }
 
@@ -390,11 +438,57 @@ where
     )
 }
 
+/// Hands the blocks downloaded by the sync task over to the block pool, one at a time and
+/// in the order they were downloaded
+fn spawn_synced_blocks_task<M, B, A, R>(
+    mut synced_blocks_receiver: UnboundedReceiver<SyncedBlock>,
+    server_data_handler: GossipServerDataHandler<M, B, A, R>,
+    task_executor: &TaskExecutor,
+) -> ServiceHandleWithShutdownSignal
+where
+    M: MempoolFacade,
+    B: BlockDiscoveryFacade,
+    A: ApiClient,
+    R: Handler<RethPeerInfo, Result = eyre::Result<()>> + Actor<Context = Context<R>>,
+{
+    ServiceHandleWithShutdownSignal::spawn(
+        "gossip synced blocks",
+        move |mut shutdown_rx| async move {
+            loop {
+                tokio::select! {
+                    maybe_block = synced_blocks_receiver.recv() => {
+                        match maybe_block {
+                            Some(synced_block) => {
+                                if let Err(error) = server_data_handler.handle_synced_block(synced_block).await {
+                                    warn!("Failed to process synced block: {}", error);
+                                };
+                            },
+                            None => {
+                                // No more blocks are going to be synced, which is not a reason
+                                //  to stop the rest of the gossip service
+                                let _ = shutdown_rx.recv().await;
+                                break;
+                            }
+                        }
+                    },
+                    _ = shutdown_rx.recv() => {
+                        break;
+                    }
+                }
+            }
+
+            debug!("Synced blocks task complete");
+        },
+        task_executor,
+    )
+}
+
 fn spawn_watcher_task(
     server: Server,
     server_handle: ServerHandle,
     mut cache_pruning_task_handle: ServiceHandleWithShutdownSignal,
     mut broadcast_task_handle: ServiceHandleWithShutdownSignal,
+    mut synced_blocks_task_handle: ServiceHandleWithShutdownSignal,
     task_executor: &TaskExecutor,
     block_pool_arbiter: actix::Arbiter,
 ) -> ServiceHandleWithShutdownSignal {
@@ -416,6 +510,9 @@ fn spawn_watcher_task(
                         broadcast_res = broadcast_task_handle.wait_for_exit() => {
                             warn!("Gossip broadcast exited because: {:?}", broadcast_res);
                         }
+                        synced_blocks_res = synced_blocks_task_handle.wait_for_exit() => {
+                            warn!("Gossip synced blocks processing exited because: {:?}", synced_blocks_res);
+                        }
                     }
 
                     debug!("Sending stop signal to server handle...");
@@ -438,6 +535,8 @@ fn spawn_watcher_task(
                     handle_result(cache_pruning_task_handle.stop().await);
                     info!("Stopping gossip broadcast");
                     handle_result(broadcast_task_handle.stop().await);
+                    info!("Stopping gossip synced blocks processing");
+                    handle_result(synced_blocks_task_handle.stop().await);
 
                     if errors.is_empty() {
                         info!("Gossip main task finished without errors");
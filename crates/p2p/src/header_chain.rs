@@ -0,0 +1,192 @@
+//! Verification of the header chains received from untrusted peers, before any block
+//! body is downloaded
+use base58::ToBase58;
+use eyre::{ensure, WrapErr as _};
+use irys_actors::block_validation::{
+    cumulative_difficulty_is_valid, difficulty_is_valid, prev_output_is_valid,
+    solution_hash_is_valid,
+};
+use irys_types::{ConsensusConfig, IrysBlockHeader};
+use irys_vdf::last_step_checkpoints_is_valid;
+
+/// Verifies the headers form a chain extending `parent`: the hash, signature, linkage,
+/// difficulty and cumulative difficulty of every header, and the VDF checkpoints of every
+/// `vdf_spot_check_interval`-th header and of the last one
+pub(crate) async fn verify_header_chain(
+    parent: &IrysBlockHeader,
+    headers: &[IrysBlockHeader],
+    consensus: &ConsensusConfig,
+    vdf_spot_check_interval: usize,
+) -> eyre::Result<()> {
+    let mut previous = parent;
+    for (index, header) in headers.iter().enumerate() {
+        header_is_valid(header, previous, consensus).wrap_err_with(|| {
+            format!(
+                "Invalid header {} at height {}",
+                header.block_hash.0.to_base58(),
+                header.height
+            )
+        })?;
+
+        let is_spot_checked = vdf_spot_check_interval > 0
+            && ((index + 1) % vdf_spot_check_interval == 0 || index + 1 == headers.len());
+        if is_spot_checked {
+            last_step_checkpoints_is_valid(&header.vdf_limiter_info, &consensus.vdf)
+                .await
+                .wrap_err_with(|| {
+                    format!(
+                        "Invalid VDF checkpoints in header {} at height {}",
+                        header.block_hash.0.to_base58(),
+                        header.height
+                    )
+                })?;
+        }
+
+        previous = header;
+    }
+    Ok(())
+}
+
+/// Checks the header is authentic, i.e. its hash and signature are valid. Used for the
+/// first header of a chain when there's no local block to verify it against
+pub(crate) fn header_is_authentic(header: &IrysBlockHeader) -> eyre::Result<()> {
+    ensure!(
+        header.is_block_hash_valid(),
+        "block hash doesn't match the block signature"
+    );
+    ensure!(header.is_signature_valid(), "block signature is not valid");
+    Ok(())
+}
+
+/// The checks of the block pre-validation that only need the previous header
+fn header_is_valid(
+    header: &IrysBlockHeader,
+    previous: &IrysBlockHeader,
+    consensus: &ConsensusConfig,
+) -> eyre::Result<()> {
+    ensure!(
+        header.previous_block_hash == previous.block_hash,
+        "previous block hash {} doesn't match the previous header {}",
+        header.previous_block_hash.0.to_base58(),
+        previous.block_hash.0.to_base58()
+    );
+    ensure!(
+        header.height == previous.height + 1,
+        "height {} doesn't follow the previous header height {}",
+        header.height,
+        previous.height
+    );
+    header_is_authentic(header)?;
+    prev_output_is_valid(header, previous)?;
+    difficulty_is_valid(header, previous, &consensus.difficulty_adjustment)?;
+    cumulative_difficulty_is_valid(header, previous)?;
+    solution_hash_is_valid(header, previous)?;
+    Ok(())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use irys_types::{irys::IrysSigner, next_cumulative_diff, H256, U256};
+
+    /// Builds a chain of signed headers on top of `parent`
+    pub(crate) fn build_chain(
+        signer: &IrysSigner,
+        parent: &IrysBlockHeader,
+        length: usize,
+    ) -> Vec<IrysBlockHeader> {
+        let mut previous = parent.clone();
+        let mut headers = Vec::with_capacity(length);
+        for _ in 0..length {
+            let mut header = IrysBlockHeader {
+                height: previous.height + 1,
+                previous_block_hash: previous.block_hash,
+                diff: previous.diff,
+                cumulative_diff: next_cumulative_diff(previous.cumulative_diff, previous.diff),
+                last_diff_timestamp: previous.last_diff_timestamp,
+                timestamp: previous.timestamp + 1000,
+                solution_hash: H256([0xff; 32]),
+                ..IrysBlockHeader::new_mock_header()
+            };
+            header.vdf_limiter_info.prev_output = previous.vdf_limiter_info.output;
+            header.vdf_limiter_info.output = H256::random();
+            signer
+                .sign_block_header(&mut header)
+                .expect("to sign the header");
+            previous = header.clone();
+            headers.push(header);
+        }
+        headers
+    }
+
+    pub(crate) fn genesis(signer: &IrysSigner) -> IrysBlockHeader {
+        let mut genesis = IrysBlockHeader {
+            height: 0,
+            diff: U256::from(1000),
+            cumulative_diff: U256::from(1000),
+            ..IrysBlockHeader::new_mock_header()
+        };
+        signer
+            .sign_block_header(&mut genesis)
+            .expect("to sign the header");
+        genesis
+    }
+
+    #[actix_rt::test]
+    async fn should_verify_a_valid_header_chain() -> eyre::Result<()> {
+        let consensus = ConsensusConfig::testnet();
+        let signer = IrysSigner::random_signer(&consensus);
+        let genesis = genesis(&signer);
+        let headers = build_chain(&signer, &genesis, 5);
+
+        header_is_authentic(&genesis)?;
+        verify_header_chain(&genesis, &headers, &consensus, 0).await
+    }
+
+    #[actix_rt::test]
+    async fn should_reject_broken_header_chains() {
+        let consensus = ConsensusConfig::testnet();
+        let signer = IrysSigner::random_signer(&consensus);
+        let genesis = genesis(&signer);
+        let headers = build_chain(&signer, &genesis, 5);
+
+        // Not linked to the parent
+        let other_genesis = genesis_with_output(&signer, H256::random());
+        assert!(verify_header_chain(&other_genesis, &headers, &consensus, 0)
+            .await
+            .is_err());
+
+        // Tampered with after signing
+        let mut tampered = headers.clone();
+        tampered[2].cumulative_diff = tampered[2].cumulative_diff + U256::from(1);
+        assert!(verify_header_chain(&genesis, &tampered, &consensus, 0)
+            .await
+            .is_err());
+
+        // Re-signed with an inflated cumulative difficulty
+        let mut inflated = headers.clone();
+        inflated[2].cumulative_diff = inflated[2].cumulative_diff * U256::from(2);
+        signer
+            .sign_block_header(&mut inflated[2])
+            .expect("to sign the header");
+        assert!(verify_header_chain(&genesis, &inflated[..3], &consensus, 0)
+            .await
+            .is_err());
+
+        // Missing header
+        let mut gapped = headers.clone();
+        gapped.remove(1);
+        assert!(verify_header_chain(&genesis, &gapped, &consensus, 0)
+            .await
+            .is_err());
+    }
+
+    fn genesis_with_output(signer: &IrysSigner, output: H256) -> IrysBlockHeader {
+        let mut genesis = genesis(signer);
+        genesis.vdf_limiter_info.output = output;
+        signer
+            .sign_block_header(&mut genesis)
+            .expect("to sign the header");
+        genesis
+    }
+}
@@ -1,7 +1,9 @@
 mod block_pool_service;
 mod cache;
+mod chunk_fetcher;
 mod gossip_client;
 mod gossip_service;
+mod header_chain;
 mod peer_list;
 mod server;
 mod server_data_handler;
@@ -11,10 +13,14 @@ mod tests;
 mod types;
 mod vdf_utils;
 
+pub use block_pool_service::{BlockPoolReadGuard, PooledBlockInfo};
+pub use chunk_fetcher::PeerChunkFetcher;
 pub use gossip_client::GossipClient;
 pub use gossip_service::P2PService;
 pub use gossip_service::ServiceHandleWithShutdownSignal;
-pub use peer_list::{PeerListFacade, PeerListFacadeError, PeerListServiceFacade};
+pub use peer_list::{
+    PeerListFacade, PeerListFacadeError, PeerListServiceFacade, ScoreDecreaseReason,
+};
 pub use peer_list::{PeerListService, PeerListServiceError};
 pub use sync::{sync_chain, SyncState};
 pub use types::{GossipError, GossipResult};
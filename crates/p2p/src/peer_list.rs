@@ -38,6 +38,8 @@ const FLUSH_INTERVAL: Duration = Duration::from_secs(5);
 const INACTIVE_PEERS_HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(10);
 const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(1);
 const PEER_HANDSHAKE_RETRY_INTERVAL: Duration = Duration::from_secs(5);
+/// Deliveries faster than this increase the peer score
+const FAST_DELIVERY_THRESHOLD: Duration = Duration::from_secs(1);
 
 pub type PeerListService = PeerListServiceWithClient<IrysApiClient, RethServiceActor>;
 pub type PeerListServiceFacade = PeerListFacade<IrysApiClient, RethServiceActor>;
@@ -130,6 +132,22 @@ where
             .await?)
     }
 
+    /// Records how long the peer took to deliver the requested data. Fast deliveries
+    /// increase the peer score
+    pub async fn record_peer_delivery_time(
+        &self,
+        peer_miner_address: &Address,
+        delivery_time: Duration,
+    ) -> Result<(), PeerListFacadeError> {
+        Ok(self
+            .addr
+            .send(RecordPeerDeliveryTime {
+                peer_miner_address: *peer_miner_address,
+                delivery_time,
+            })
+            .await?)
+    }
+
     /// Returns n most active peers
     pub async fn top_active_peers(
         &self,
@@ -152,6 +170,32 @@ where
         Ok(self.addr.send(TrustedPeersRequest).await?)
     }
 
+    /// API addresses of the peers currently trusted, including the ones added at runtime
+    pub async fn trusted_peer_addresses(&self) -> Result<Vec<SocketAddr>, PeerListFacadeError> {
+        Ok(self.addr.send(TrustedPeerAddressesRequest).await?)
+    }
+
+    /// Trusts the peer at the API address until the node restarts and announces the node to it
+    pub async fn add_trusted_peer(
+        &self,
+        api_address: SocketAddr,
+    ) -> Result<(), PeerListFacadeError> {
+        Ok(self.addr.send(AddTrustedPeer { api_address }).await?)
+    }
+
+    /// Stops trusting the peer at the API address. Returns false if it wasn't trusted
+    pub async fn remove_trusted_peer(
+        &self,
+        api_address: SocketAddr,
+    ) -> Result<bool, PeerListFacadeError> {
+        Ok(self.addr.send(RemoveTrustedPeer { api_address }).await?)
+    }
+
+    /// Writes the peer list to the database without waiting for the next periodic flush
+    pub async fn flush(&self) -> Result<(), PeerListFacadeError> {
+        Ok(self.addr.send(FlushRequest).await??)
+    }
+
     /// Waits for at least one active connection to appear
     pub async fn wait_for_active_peers(&self) -> Result<(), PeerListFacadeError> {
         Ok(self.addr.send(WaitForActivePeer).await?)
@@ -465,6 +509,8 @@ impl From<DatabaseError> for PeerListServiceError {
 pub enum ScoreDecreaseReason {
     BogusData,
     Offline,
+    /// The peer sent a block whose parent never arrived or exceeded its share of the block pool
+    UnconnectableBlock,
 }
 
 #[derive(Clone, Debug, Copy)]
@@ -626,6 +672,9 @@ where
                 ScoreDecreaseReason::Offline => {
                     peer_item.reputation_score.decrease_offline();
                 }
+                ScoreDecreaseReason::UnconnectableBlock => {
+                    peer_item.reputation_score.decrease_unconnectable_block();
+                }
             }
 
             // Don't propagate inactive peers
@@ -873,6 +922,37 @@ where
     }
 }
 
+/// Record the time a peer took to deliver the requested data
+#[derive(Message, Debug)]
+#[rtype(result = "()")]
+pub struct RecordPeerDeliveryTime {
+    pub peer_miner_address: Address,
+    pub delivery_time: Duration,
+}
+
+impl<A, R> Handler<RecordPeerDeliveryTime> for PeerListServiceWithClient<A, R>
+where
+    A: ApiClient,
+    R: Handler<RethPeerInfo, Result = eyre::Result<()>> + Actor<Context = Context<R>>,
+{
+    type Result = ();
+
+    fn handle(&mut self, msg: RecordPeerDeliveryTime, _ctx: &mut Self::Context) -> Self::Result {
+        if let Some(peer_item) = self.peer_list_cache.get_mut(&msg.peer_miner_address) {
+            let delivery_millis = u16::try_from(msg.delivery_time.as_millis()).unwrap_or(u16::MAX);
+            // Moving average, so a single slow delivery doesn't outweigh the history
+            peer_item.response_time = if peer_item.response_time == 0 {
+                delivery_millis
+            } else {
+                ((u32::from(peer_item.response_time) * 3 + u32::from(delivery_millis)) / 4) as u16
+            };
+            if msg.delivery_time < FAST_DELIVERY_THRESHOLD {
+                peer_item.reputation_score.increase();
+            }
+        }
+    }
+}
+
 /// Get the list of active trusted peers
 #[derive(Message, Debug)]
 #[rtype(result = "Vec<(Address, PeerListItem)>")]
@@ -981,6 +1061,74 @@ where
     }
 }
 
+/// Get the API addresses of the trusted peers
+#[derive(Message, Debug)]
+#[rtype(result = "Vec<SocketAddr>")]
+pub struct TrustedPeerAddressesRequest;
+
+impl<A, R> Handler<TrustedPeerAddressesRequest> for PeerListServiceWithClient<A, R>
+where
+    A: ApiClient,
+    R: Handler<RethPeerInfo, Result = eyre::Result<()>> + Actor<Context = Context<R>>,
+{
+    type Result = Vec<SocketAddr>;
+
+    fn handle(
+        &mut self,
+        _msg: TrustedPeerAddressesRequest,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result {
+        self.trusted_peers_api_addresses.iter().copied().collect()
+    }
+}
+
+/// Trust a peer, in addition to the trusted peers of the config
+#[derive(Message, Debug)]
+#[rtype(result = "()")]
+pub struct AddTrustedPeer {
+    pub api_address: SocketAddr,
+}
+
+impl<A, R> Handler<AddTrustedPeer> for PeerListServiceWithClient<A, R>
+where
+    A: ApiClient,
+    R: Handler<RethPeerInfo, Result = eyre::Result<()>> + Actor<Context = Context<R>>,
+{
+    type Result = ();
+
+    fn handle(&mut self, msg: AddTrustedPeer, ctx: &mut Self::Context) -> Self::Result {
+        if self.trusted_peers_api_addresses.insert(msg.api_address) {
+            info!("Trusting peer {}", msg.api_address);
+            let handshake_task =
+                Self::trusted_peers_handshake_task(ctx.address(), HashSet::from([msg.api_address]));
+            ctx.spawn(handshake_task.into_actor(self));
+        }
+    }
+}
+
+/// Stop trusting a peer
+#[derive(Message, Debug)]
+#[rtype(result = "bool")]
+pub struct RemoveTrustedPeer {
+    pub api_address: SocketAddr,
+}
+
+impl<A, R> Handler<RemoveTrustedPeer> for PeerListServiceWithClient<A, R>
+where
+    A: ApiClient,
+    R: Handler<RethPeerInfo, Result = eyre::Result<()>> + Actor<Context = Context<R>>,
+{
+    type Result = bool;
+
+    fn handle(&mut self, msg: RemoveTrustedPeer, _ctx: &mut Self::Context) -> Self::Result {
+        let removed = self.trusted_peers_api_addresses.remove(&msg.api_address);
+        if removed {
+            info!("No longer trusting peer {}", msg.api_address);
+        }
+        removed
+    }
+}
+
 /// Add peer to the peer list
 #[derive(Message, Debug)]
 #[rtype(result = "()")]
@@ -1568,6 +1716,40 @@ mod tests {
             )
             .expect("failed to get updated peer");
         assert_eq!(updated_peer.reputation_score.get(), 48);
+
+        // Fast deliveries increase the score
+        service.handle(
+            RecordPeerDeliveryTime {
+                peer_miner_address: mining_addr,
+                delivery_time: Duration::from_millis(20),
+            },
+            ctx,
+        );
+        let updated_peer = service
+            .handle(
+                PeerListEntryRequest::GossipSocketAddress(peer.address.gossip),
+                ctx,
+            )
+            .expect("failed to get updated peer");
+        assert_eq!(updated_peer.reputation_score.get(), 49);
+        assert_eq!(updated_peer.response_time, 80);
+
+        // Slow deliveries only update the response time
+        service.handle(
+            RecordPeerDeliveryTime {
+                peer_miner_address: mining_addr,
+                delivery_time: Duration::from_secs(3),
+            },
+            ctx,
+        );
+        let updated_peer = service
+            .handle(
+                PeerListEntryRequest::GossipSocketAddress(peer.address.gossip),
+                ctx,
+            )
+            .expect("failed to get updated peer");
+        assert_eq!(updated_peer.reputation_score.get(), 49);
+        assert_eq!(updated_peer.response_time, 810);
     }
 
     #[actix_rt::test]
@@ -2549,4 +2731,61 @@ mod tests {
             "Should have called the second trusted peer's API address"
         );
     }
+
+    #[actix_rt::test]
+    async fn test_trusted_peers_can_be_changed_at_runtime() {
+        let temp_dir = setup_tracing_and_temp_dir(None, false);
+        let mut node_config = NodeConfig::testnet();
+        node_config.trusted_peers = vec![];
+        let config = Config::new(node_config);
+        let db = DatabaseProvider(Arc::new(
+            open_or_create_irys_consensus_data_db(&temp_dir.path().to_path_buf())
+                .expect("can't open temp dir"),
+        ));
+
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let mock_client = CountingMockClient {
+            post_version_calls: calls.clone(),
+        };
+        let mock_addr = MockRethServiceActor::new().start();
+        let service = PeerListServiceWithClient::new_with_custom_api_client(
+            db,
+            &config,
+            mock_client,
+            mock_addr,
+        );
+        let facade = PeerListFacade::from(service.start());
+
+        let trusted_peer: SocketAddr = "127.0.0.1:9002".parse().expect("valid SocketAddr");
+        facade
+            .add_trusted_peer(trusted_peer)
+            .await
+            .expect("add trusted peer");
+        assert_eq!(
+            facade
+                .trusted_peer_addresses()
+                .await
+                .expect("trusted peers"),
+            vec![trusted_peer]
+        );
+
+        // the new trusted peer is announced to right away
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(calls.lock().await.contains(&trusted_peer));
+
+        assert!(facade
+            .remove_trusted_peer(trusted_peer)
+            .await
+            .expect("remove trusted peer"));
+        assert!(!facade
+            .remove_trusted_peer(trusted_peer)
+            .await
+            .expect("remove trusted peer"));
+        assert!(facade
+            .trusted_peer_addresses()
+            .await
+            .expect("trusted peers")
+            .is_empty());
+        facade.flush().await.expect("flush the peer list");
+    }
 }
@@ -9,21 +9,27 @@ use crate::types::{GossipError, GossipResult};
 use actix::{Actor, Context, Handler};
 use actix_web::dev::Server;
 use actix_web::{
+    http::header,
     middleware,
     web::{self, Data},
-    App, HttpResponse, HttpServer,
+    App, HttpRequest, HttpResponse, HttpServer,
 };
 use base58::ToBase58;
 use irys_actors::block_discovery::BlockDiscoveryFacade;
 use irys_actors::mempool_service::MempoolFacade;
 use irys_api_client::ApiClient;
 use irys_types::{
-    Address, CommitmentTransaction, GossipRequest, IrysBlockHeader, IrysTransactionHeader,
-    PeerListItem, RethPeerInfo, UnpackedChunk,
+    decode_body, ingress::IngressProof, is_binary_content_type, is_json_content_type, Address,
+    BinaryEncoding, CommitmentTransaction, GossipRequest, IrysBlockHeader, IrysTransactionHeader,
+    PeerListItem, RethPeerInfo, UnpackedChunk, VdfStepGossip,
 };
-use std::net::TcpListener;
+use serde::de::DeserializeOwned;
+use std::{net::TcpListener, sync::Arc};
 use tracing::{debug, error, info};
 
+/// Payload limit of the gossip routes taking binary or JSON bodies, the default JSON limit
+const GOSSIP_PAYLOAD_LIMIT: usize = 2 * 1024 * 1024;
+
 #[derive(Debug)]
 pub(crate) struct GossipServer<M, B, A, R>
 where
@@ -68,12 +74,35 @@ where
         }
     }
 
+    /// Decodes a gossip request sent in the binary encoding or as JSON. Other content types are
+    /// answered with 415 Unsupported Media Type, which tells clients to resend the data as JSON
+    fn decode_request<T: BinaryEncoding + DeserializeOwned>(
+        req: &HttpRequest,
+        body: &[u8],
+    ) -> Result<GossipRequest<T>, HttpResponse> {
+        let content_type = req
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok());
+        if !is_binary_content_type(content_type) && !is_json_content_type(content_type) {
+            debug!("Unsupported gossip request content type {:?}", content_type);
+            return Err(HttpResponse::UnsupportedMediaType().finish());
+        }
+        decode_body(content_type, body).map_err(|error| {
+            debug!("Failed to decode gossip request: {}", error);
+            HttpResponse::BadRequest().finish()
+        })
+    }
+
     async fn handle_chunk(
         server: Data<Self>,
-        unpacked_chunk_json: web::Json<GossipRequest<UnpackedChunk>>,
+        body: web::Bytes,
         req: actix_web::HttpRequest,
     ) -> HttpResponse {
-        let gossip_request = unpacked_chunk_json.0;
+        let gossip_request = match Self::decode_request::<UnpackedChunk>(&req, &body) {
+            Ok(gossip_request) => gossip_request,
+            Err(error_response) => return error_response,
+        };
         let source_miner_address = gossip_request.miner_address;
 
         match Self::check_peer(&server.peer_list, &req, source_miner_address).await {
@@ -127,10 +156,13 @@ where
 
     async fn handle_block(
         server: Data<Self>,
-        irys_block_header_json: web::Json<GossipRequest<IrysBlockHeader>>,
+        body: web::Bytes,
         req: actix_web::HttpRequest,
     ) -> HttpResponse {
-        let gossip_request = irys_block_header_json.0;
+        let gossip_request = match Self::decode_request::<IrysBlockHeader>(&req, &body) {
+            Ok(gossip_request) => gossip_request,
+            Err(error_response) => return error_response,
+        };
         let source_miner_address = gossip_request.miner_address;
         let peer =
             match Self::check_peer(&server.peer_list, &req, gossip_request.miner_address).await {
@@ -218,6 +250,85 @@ where
         HttpResponse::Ok().finish()
     }
 
+    async fn handle_ingress_proof(
+        server: Data<Self>,
+        ingress_proof_json: web::Json<GossipRequest<IngressProof>>,
+        req: actix_web::HttpRequest,
+    ) -> HttpResponse {
+        let gossip_request = ingress_proof_json.0;
+        let source_miner_address = gossip_request.miner_address;
+
+        match Self::check_peer(&server.peer_list, &req, gossip_request.miner_address).await {
+            Ok(peer_address) => peer_address,
+            Err(error_response) => return error_response,
+        };
+
+        if let Err(error) = server
+            .data_handler
+            .handle_ingress_proof(gossip_request)
+            .await
+        {
+            Self::handle_invalid_data(&source_miner_address, &error, &server.peer_list).await;
+            error!("Failed to send ingress proof: {}", error);
+            return HttpResponse::InternalServerError().finish();
+        }
+
+        debug!("Gossip data handled");
+        HttpResponse::Ok().finish()
+    }
+
+    async fn handle_vdf_step(
+        server: Data<Self>,
+        vdf_step_json: web::Json<GossipRequest<VdfStepGossip>>,
+        req: actix_web::HttpRequest,
+    ) -> HttpResponse {
+        let gossip_request = vdf_step_json.0;
+        let source_miner_address = gossip_request.miner_address;
+
+        match Self::check_peer(&server.peer_list, &req, gossip_request.miner_address).await {
+            Ok(peer_address) => peer_address,
+            Err(error_response) => return error_response,
+        };
+
+        let global_step = gossip_request.data.global_step;
+        let vdf_step = match server.data_handler.handle_vdf_step(gossip_request).await {
+            Ok(Some(vdf_step)) => vdf_step,
+            Ok(None) => return HttpResponse::Ok().finish(),
+            Err(error) => {
+                Self::handle_invalid_data(&source_miner_address, &error, &server.peer_list).await;
+                error!("Failed to process VDF step {}: {}", global_step, error);
+                return HttpResponse::InternalServerError().finish();
+            }
+        };
+
+        // One verification at a time is enough to follow a faster VDF, the steps arriving
+        // meanwhile are dropped
+        let Ok(permit) =
+            Arc::clone(&server.data_handler.vdf_verification_permits).try_acquire_owned()
+        else {
+            debug!(
+                "Already verifying a VDF step, dropping step {}",
+                global_step
+            );
+            return HttpResponse::Ok().finish();
+        };
+
+        // Verifying the checkpoints takes a while, don't keep the peer waiting for it
+        tokio::spawn(async move {
+            let _permit = permit;
+            if let Err(error) = server
+                .data_handler
+                .verify_vdf_step(source_miner_address, vdf_step)
+                .await
+            {
+                Self::handle_invalid_data(&source_miner_address, &error, &server.peer_list).await;
+                error!("Failed to verify VDF step {}: {}", global_step, error);
+            }
+        });
+
+        HttpResponse::Ok().finish()
+    }
+
     async fn handle_health_check(server: Data<Self>, req: actix_web::HttpRequest) -> HttpResponse {
         let Some(peer_addr) = req.peer_addr() else {
             return HttpResponse::BadRequest().finish();
@@ -282,6 +393,7 @@ where
         let server_handle = HttpServer::new(move || {
             App::new()
                 .app_data(Data::new(server.clone()))
+                .app_data(web::PayloadConfig::new(GOSSIP_PAYLOAD_LIMIT))
                 .wrap(middleware::Logger::default())
                 .service(
                     web::scope("/gossip")
@@ -289,6 +401,8 @@ where
                         .route("/commitment_tx", web::post().to(Self::handle_commitment_tx))
                         .route("/chunk", web::post().to(Self::handle_chunk))
                         .route("/block", web::post().to(Self::handle_block))
+                        .route("/ingress_proof", web::post().to(Self::handle_ingress_proof))
+                        .route("/vdf_step", web::post().to(Self::handle_vdf_step))
                         .route("/get_data", web::post().to(Self::handle_get_data))
                         .route("/health", web::get().to(Self::handle_health_check)),
                 )
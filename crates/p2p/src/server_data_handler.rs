@@ -8,15 +8,30 @@ use actix::{Actor, Addr, Context, Handler};
 use base58::ToBase58;
 use core::net::SocketAddr;
 use irys_actors::block_discovery::BlockDiscoveryFacade;
+use irys_actors::broadcast_mining_service::BroadcastMiningSeed;
 use irys_actors::mempool_service::{ChunkIngressError, MempoolFacade};
+use irys_actors::vdf_service::{VdfServiceMessage, VdfStepsReadGuard};
 use irys_api_client::ApiClient;
+use irys_storage::ii;
 use irys_types::{
-    CommitmentTransaction, GossipData, GossipRequest, IrysBlockHeader, IrysTransactionHeader,
-    IrysTransactionResponse, RethPeerInfo, UnpackedChunk, H256,
+    block_production::Seed, ingress::IngressProof, Address, CommitmentTransaction, GossipData,
+    GossipRequest, IrysBlockHeader, IrysTransactionHeader, IrysTransactionResponse, RethPeerInfo,
+    SyncedBlock, UnpackedChunk, VdfConfig, VdfStepGossip, H256,
+};
+use std::{sync::Arc, time::Duration};
+use tokio::sync::{
+    mpsc::{Sender, UnboundedSender},
+    Semaphore,
 };
-use std::sync::Arc;
 use tracing::{debug, error};
 
+/// How far ahead of the local VDF a gossiped step is accepted. Steps beyond the next one are
+/// applied once the local VDF reaches the step before them, so their seed can be checked
+const MAX_VDF_STEPS_AHEAD: u64 = 5;
+/// How long a verified VDF step waits for the local VDF to reach the step before it
+const VDF_STEP_WAIT_TIMEOUT: Duration = Duration::from_secs(10);
+const VDF_STEP_POLL_INTERVAL: Duration = Duration::from_millis(20);
+
 /// Handles data received by the `GossipServer`
 #[derive(Debug)]
 pub(crate) struct GossipServerDataHandler<TMempoolFacade, TBlockDiscovery, TApiClient, R>
@@ -33,6 +48,20 @@ where
     pub gossip_client: GossipClient,
     pub peer_list_service: PeerListFacade<TApiClient, R>,
     pub sync_state: SyncState,
+    pub vdf_config: VdfConfig,
+    /// Reset seed the VDF thread applies to the output of reset steps
+    pub vdf_reset_seed: H256,
+    /// Shared by the verifications of gossiped VDF steps, so they can't use more threads than
+    /// the configured limit
+    pub vdf_verification_pool: Arc<rayon::ThreadPool>,
+    /// Bounds the number of gossiped VDF steps verified at once, steps arriving while none is
+    /// available are dropped
+    pub vdf_verification_permits: Arc<Semaphore>,
+    /// Feeds verified VDF steps to the VDF thread
+    pub vdf_sender: Sender<BroadcastMiningSeed>,
+    pub vdf_service_sender: UnboundedSender<VdfServiceMessage>,
+    /// Relays verified VDF steps to the other peers
+    pub gossip_broadcast: UnboundedSender<GossipData>,
 }
 
 impl<M, B, A, R> Clone for GossipServerDataHandler<M, B, A, R>
@@ -51,6 +80,13 @@ where
             gossip_client: self.gossip_client.clone(),
             peer_list_service: self.peer_list_service.clone(),
             sync_state: self.sync_state.clone(),
+            vdf_config: self.vdf_config.clone(),
+            vdf_reset_seed: self.vdf_reset_seed,
+            vdf_verification_pool: Arc::clone(&self.vdf_verification_pool),
+            vdf_verification_permits: Arc::clone(&self.vdf_verification_permits),
+            vdf_sender: self.vdf_sender.clone(),
+            vdf_service_sender: self.vdf_service_sender.clone(),
+            gossip_broadcast: self.gossip_broadcast.clone(),
         }
     }
 }
@@ -219,6 +255,206 @@ where
         }
     }
 
+    pub(crate) async fn handle_ingress_proof(
+        &self,
+        proof_request: GossipRequest<IngressProof>,
+    ) -> GossipResult<()> {
+        let source_miner_address = proof_request.miner_address;
+        let proof = proof_request.data;
+        let proof_hash = proof.proof;
+        debug!(
+            "Node {}: Gossip ingress proof for data root {} received from peer {}",
+            self.gossip_client.mining_address, proof.data_root, source_miner_address
+        );
+
+        let already_seen = self.cache.seen_ingress_proof_from_any_peer(&proof_hash)?;
+        self.cache.record_seen(
+            source_miner_address,
+            GossipCacheKey::IngressProof(proof_hash),
+        )?;
+
+        if already_seen {
+            debug!(
+                "Node {}: Ingress proof {} is already recorded in the cache, skipping",
+                self.gossip_client.mining_address, proof_hash
+            );
+            return Ok(());
+        }
+
+        // The mempool verifies the proof against the data root's chunks and gossips it further
+        self.mempool
+            .handle_ingress_proof(proof)
+            .await
+            .map_err(GossipError::from)
+    }
+
+    /// Runs the cheap checks of a gossiped VDF step, returns the step if its checkpoints still
+    /// have to be verified before it's applied
+    pub(crate) async fn handle_vdf_step(
+        &self,
+        vdf_step_request: GossipRequest<VdfStepGossip>,
+    ) -> GossipResult<Option<VdfStepGossip>> {
+        let source_miner_address = vdf_step_request.miner_address;
+        let vdf_step = vdf_step_request.data;
+        let global_step = vdf_step.global_step;
+        let output =
+            vdf_step
+                .output()
+                .ok_or(GossipError::InvalidData(InvalidDataError::InvalidVdfStep(
+                    "missing checkpoints".to_string(),
+                )))?;
+
+        let already_seen = self.cache.seen_vdf_step_from_any_peer(&output)?;
+        self.cache
+            .record_seen(source_miner_address, GossipCacheKey::VdfStep(output))?;
+
+        if already_seen {
+            debug!(
+                "Node {}: VDF step {} is already recorded in the cache, skipping",
+                self.gossip_client.mining_address, global_step
+            );
+            return Ok(None);
+        }
+
+        if self.sync_state.is_syncing() {
+            debug!(
+                "Node {}: Syncing, skipping VDF step {}",
+                self.gossip_client.mining_address, global_step
+            );
+            return Ok(None);
+        }
+
+        let steps_guard = self.vdf_steps_guard().await?;
+        let local_step = steps_guard.read().global_step;
+        if global_step <= local_step || global_step > local_step + MAX_VDF_STEPS_AHEAD {
+            debug!(
+                "Node {}: VDF step {} is out of the accepted range from the local step {}, skipping",
+                self.gossip_client.mining_address, global_step, local_step
+            );
+            return Ok(None);
+        }
+        // The continuity of steps further ahead is checked once the local VDF catches up
+        if global_step == local_step + 1 && !self.continues_local_vdf(&steps_guard, &vdf_step) {
+            debug!(
+                "Node {}: VDF step {} doesn't continue the local VDF, skipping",
+                self.gossip_client.mining_address, global_step
+            );
+            return Ok(None);
+        }
+
+        Ok(Some(vdf_step))
+    }
+
+    /// Verifies the checkpoints of a step returned by [`Self::handle_vdf_step`], then feeds it to
+    /// the VDF thread and relays it to the other peers
+    pub(crate) async fn verify_vdf_step(
+        &self,
+        source_miner_address: Address,
+        vdf_step: VdfStepGossip,
+    ) -> GossipResult<()> {
+        let global_step = vdf_step.global_step;
+        let output =
+            vdf_step
+                .output()
+                .ok_or(GossipError::InvalidData(InvalidDataError::InvalidVdfStep(
+                    "missing checkpoints".to_string(),
+                )))?;
+
+        irys_vdf::vdf_step_is_valid(
+            global_step,
+            vdf_step.seed,
+            &vdf_step.checkpoints,
+            &self.vdf_config,
+            Arc::clone(&self.vdf_verification_pool),
+        )
+        .await
+        .map_err(|error| {
+            GossipError::InvalidData(InvalidDataError::InvalidVdfStep(error.to_string()))
+        })?;
+
+        debug!(
+            "Node {}: VDF step {} from peer {} verified",
+            self.gossip_client.mining_address, global_step, source_miner_address
+        );
+
+        // Wait for the local VDF to compute the step before this one, the VDF thread then
+        // applies this step instead of computing it
+        let steps_guard = self.vdf_steps_guard().await?;
+        let reached_previous_step = tokio::time::timeout(VDF_STEP_WAIT_TIMEOUT, async {
+            loop {
+                let local_step = steps_guard.read().global_step;
+                if local_step + 1 >= global_step {
+                    break;
+                }
+                tokio::time::sleep(VDF_STEP_POLL_INTERVAL).await;
+            }
+        })
+        .await;
+        if reached_previous_step.is_err() || steps_guard.read().global_step >= global_step {
+            debug!(
+                "Node {}: Local VDF isn't at the step before {}, skipping",
+                self.gossip_client.mining_address, global_step
+            );
+            return Ok(());
+        }
+        if !self.continues_local_vdf(&steps_guard, &vdf_step) {
+            debug!(
+                "Node {}: VDF step {} doesn't continue the local VDF, skipping",
+                self.gossip_client.mining_address, global_step
+            );
+            return Ok(());
+        }
+
+        self.vdf_sender
+            .send(BroadcastMiningSeed {
+                seed: Seed(output),
+                checkpoints: vdf_step.checkpoints.clone(),
+                global_step,
+            })
+            .await
+            .map_err(|error| GossipError::unknown(&error))?;
+
+        self.gossip_broadcast
+            .send(GossipData::VdfStep(vdf_step))
+            .map_err(|error| GossipError::unknown(&error))
+    }
+
+    /// Whether the seed of a gossiped step is the local output of the step before it, with the
+    /// reset seed applied after a reset step as the VDF thread does
+    fn continues_local_vdf(
+        &self,
+        steps_guard: &VdfStepsReadGuard,
+        vdf_step: &VdfStepGossip,
+    ) -> bool {
+        let previous_step = vdf_step.global_step.saturating_sub(1);
+        let Some(previous_output) = steps_guard
+            .read()
+            .get_steps(ii(previous_step, previous_step))
+            .ok()
+            .and_then(|steps| steps.0.first().copied())
+        else {
+            return false;
+        };
+        let expected_seed = if previous_step % self.vdf_config.reset_frequency as u64 == 0 {
+            irys_vdf::apply_reset_seed(previous_output, self.vdf_reset_seed)
+        } else {
+            previous_output
+        };
+        vdf_step.seed == expected_seed
+    }
+
+    async fn vdf_steps_guard(&self) -> GossipResult<VdfStepsReadGuard> {
+        let (oneshot_tx, oneshot_rx) = tokio::sync::oneshot::channel();
+        self.vdf_service_sender
+            .send(VdfServiceMessage::GetVdfStateMessage {
+                response: oneshot_tx,
+            })
+            .map_err(|error| GossipError::unknown(&error))?;
+        oneshot_rx
+            .await
+            .map_err(|error| GossipError::unknown(&error))
+    }
+
     pub(crate) async fn handle_block_header_request(
         &self,
         block_header_request: GossipRequest<IrysBlockHeader>,
@@ -325,8 +561,75 @@ where
                 GossipError::unknown(&error)
             })?;
 
-        // Process each transaction
-        for tx_response in missing_txs.into_iter() {
+        self.ingest_transactions(source_miner_address, missing_txs)
+            .await?;
+
+        self.block_pool
+            .send(ProcessBlock {
+                header: block_header,
+                source_miner_address,
+            })
+            .await
+            .map_err(|mailbox_error| GossipError::unknown(&mailbox_error))?
+            .map_err(|block_pool_error| GossipError::BlockPool(block_pool_error))?;
+        Ok(())
+    }
+
+    /// Processes a block downloaded by the sync task. Its transactions were downloaded
+    /// along with it, so unlike gossiped blocks nothing has to be fetched
+    pub(crate) async fn handle_synced_block(&self, synced_block: SyncedBlock) -> GossipResult<()> {
+        let SyncedBlock {
+            header: block_header,
+            transactions,
+            source_miner_address,
+        } = synced_block;
+        let block_hash = block_header.block_hash;
+        debug!(
+            "Node {}: Synced block received from peer {}: {:?}",
+            self.gossip_client.mining_address,
+            source_miner_address,
+            block_hash.0.to_base58()
+        );
+
+        self.cache
+            .record_seen(source_miner_address, GossipCacheKey::Block(block_hash))?;
+
+        let has_block_already_been_processed = self
+            .block_pool
+            .send(BlockExists { block_hash })
+            .await
+            .map_err(|mailbox_error| GossipError::unknown(&mailbox_error))?
+            .map_err(|block_pool_error| GossipError::BlockPool(block_pool_error))?;
+        if has_block_already_been_processed {
+            debug!(
+                "Node {}: Synced block {} has already been processed, skipping",
+                self.gossip_client.mining_address,
+                block_hash.0.to_base58()
+            );
+            return Ok(());
+        }
+
+        self.ingest_transactions(source_miner_address, transactions)
+            .await?;
+
+        self.block_pool
+            .send(ProcessBlock {
+                header: block_header,
+                source_miner_address,
+            })
+            .await
+            .map_err(|mailbox_error| GossipError::unknown(&mailbox_error))?
+            .map_err(|block_pool_error| GossipError::BlockPool(block_pool_error))?;
+        Ok(())
+    }
+
+    /// Sends the transactions of a block to the mempool
+    async fn ingest_transactions(
+        &self,
+        source_miner_address: Address,
+        transactions: Vec<IrysTransactionResponse>,
+    ) -> GossipResult<()> {
+        for tx_response in transactions.into_iter() {
             let tx_id;
             let mempool_response = match tx_response {
                 IrysTransactionResponse::Commitment(commitment_tx) => {
@@ -358,13 +661,6 @@ where
             }
         }
 
-        self.block_pool
-            .send(ProcessBlock {
-                header: block_header,
-            })
-            .await
-            .map_err(|mailbox_error| GossipError::unknown(&mailbox_error))?
-            .map_err(|block_pool_error| GossipError::BlockPool(block_pool_error))?;
         Ok(())
     }
 
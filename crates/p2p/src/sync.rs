@@ -1,17 +1,33 @@
+use crate::header_chain::{header_is_authentic, verify_header_chain};
+use crate::peer_list::ScoreDecreaseReason;
+use crate::types::{InternalGossipError, InvalidDataError};
 use crate::{GossipError, GossipResult, PeerListFacade};
 use actix::{Actor, Context, Handler};
 use base58::ToBase58;
+use futures::future::{join_all, try_join_all};
+use futures::stream::{FuturesOrdered, StreamExt as _};
 use irys_api_client::ApiClient;
-use irys_types::{BlockIndexItem, BlockIndexQuery, NodeMode, RethPeerInfo};
+use irys_types::{
+    BlockHash, BlockIndexItem, BlockIndexQuery, Config, IrysBlockHeader, IrysTransactionResponse,
+    NodeMode, PeerListItem, RethPeerInfo, SyncedBlock,
+};
 use rand::prelude::SliceRandom;
-use std::collections::VecDeque;
+use std::collections::{HashSet, VecDeque};
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::UnboundedSender;
 use tokio::time::timeout;
 use tracing::{debug, error, info, warn};
 
 const MAX_PROCESSING_BLOCKS_QUEUE_SIZE: usize = 100;
+/// Number of the top active peers blocks are downloaded from
+const MAX_DOWNLOAD_PEERS: usize = 10;
+/// Maximum number of headers fetched from each peer per round when syncing from untrusted
+/// peers, bounds the memory held by the competing chains
+const MAX_HEADER_CHAIN_LENGTH: usize = 100;
+/// Time a peer has to serve a batch of headers before it's left out of the comparison
+const HEADER_BATCH_TIMEOUT: Duration = Duration::from_secs(10);
 
 #[derive(Clone, Debug, Default)]
 pub struct SyncState {
@@ -149,9 +165,13 @@ pub async fn sync_chain<
     peer_list_service: PeerListFacade<A, R>,
     node_mode: &NodeMode,
     start_sync_from_height: usize,
+    latest_block: Option<IrysBlockHeader>,
     genesis_peer_discovery_timeout_millis: u64,
+    config: &Config,
+    synced_blocks_sender: UnboundedSender<SyncedBlock>,
 ) -> Result<(), GossipError> {
     sync_state.set_syncing_from(start_sync_from_height);
+    let sync_config = &config.node_config.sync;
     let is_in_genesis_mode = matches!(node_mode, NodeMode::Genesis);
 
     debug!("Sync task: Starting a chain sync task, waiting for active peers. Mode: {:?}, starting from height: {}", node_mode, sync_state.sync_target_height());
@@ -187,84 +207,441 @@ pub async fn sync_chain<
     debug!("Sync task: Syncing started");
 
     let limit = 10;
+    let download_window = sync_config.block_download_window.max(1);
 
-    let mut block_queue = VecDeque::new();
-    let block_index = get_block_index(
-        &peer_list_service,
-        &api_client,
-        sync_state.sync_target_height(),
-        limit,
-        5,
-        fetch_index_from_the_trusted_peer,
-    )
-    .await?;
+    // Blocks to download, with their header when it has already been verified
+    let mut index_queue: VecDeque<(BlockHash, Option<IrysBlockHeader>)> = VecDeque::new();
+    // Height of the next block index batch to fetch
+    let mut next_index_height = sync_state.sync_target_height();
+    let mut index_exhausted = false;
+    let mut no_new_blocks_to_process = true;
+    // Last verified header, the next untrusted header chain has to extend it
+    let mut chain_tip = latest_block;
+    // Blocks are downloaded concurrently, but handed over for processing in the index order,
+    //  so every block's parent is already in the block pool by the time the block arrives
+    let mut downloads = FuturesOrdered::new();
+
+    loop {
+        // Keep the download window full, fetching more of the index as it drains
+        while downloads.len() < download_window {
+            if index_queue.is_empty() && !index_exhausted {
+                if sync_config.sync_from_untrusted_peers {
+                    let headers = get_heaviest_verified_chain(
+                        &peer_list_service,
+                        &api_client,
+                        next_index_height,
+                        limit,
+                        MAX_HEADER_CHAIN_LENGTH,
+                        chain_tip.as_ref(),
+                        config,
+                    )
+                    .await?;
+                    next_index_height += headers.len();
+                    // The peers' chains end before the cap once the tip is reached, otherwise
+                    //  the next round extends the chain queued by this one
+                    index_exhausted = headers.len() < MAX_HEADER_CHAIN_LENGTH;
+                    if let Some(last_header) = headers.last() {
+                        chain_tip = Some(last_header.clone());
+                    }
+                    index_queue.extend(
+                        headers
+                            .into_iter()
+                            .map(|header| (header.block_hash, Some(header))),
+                    );
+                } else {
+                    let block_index = get_block_index(
+                        &peer_list_service,
+                        &api_client,
+                        next_index_height,
+                        limit,
+                        5,
+                        fetch_index_from_the_trusted_peer,
+                    )
+                    .await?;
+                    next_index_height += block_index.len();
+                    index_exhausted = block_index.is_empty();
+                    index_queue.extend(block_index.into_iter().map(|item| (item.block_hash, None)));
+                }
+            }
 
-    let mut blocks_left_to_process = block_index.len();
-    block_queue.extend(block_index);
-    let no_new_blocks_to_process = block_queue.is_empty();
+            let Some((block_hash, verified_header)) = index_queue.pop_front() else {
+                break;
+            };
+            no_new_blocks_to_process = false;
+            downloads.push_back(download_block(
+                &peer_list_service,
+                &api_client,
+                block_hash,
+                verified_header,
+                sync_config.block_download_attempts,
+            ));
+        }
+
+        let Some(download_result) = downloads.next().await else {
+            break;
+        };
+        let synced_block = match download_result {
+            Ok(synced_block) => synced_block,
+            Err(err) => {
+                // The block pool requests the missing parent once the next block arrives
+                error!("Sync task: Failed to download a block: {}", err);
+                continue;
+            }
+        };
 
-    while let Some(block) = block_queue.pop_front() {
         if sync_state.is_queue_full() {
             debug!("Sync task: Block queue is full, waiting for an empty slot");
             sync_state.wait_for_an_empty_queue_slot().await;
         }
 
-        debug!(
-            "Sync task: Requesting block {} (sync height is {}) from the network",
-            block.block_hash.0.to_base58(),
+        let block_hash = synced_block.header.block_hash;
+        synced_blocks_sender.send(synced_block).map_err(|_| {
+            GossipError::Internal(InternalGossipError::Unknown(
+                "Synced blocks receiver has been shutdown".to_string(),
+            ))
+        })?;
+        sync_state.increment_sync_target_height();
+        info!(
+            "Sync task: Downloaded block {} (sync height is {})",
+            block_hash.0.to_base58(),
             sync_state.sync_target_height()
         );
-        match peer_list_service
-            .request_block_from_the_network(block.block_hash)
+    }
+
+    // If no new blocks were added to the index, nothing is going to mark
+    //  the tip as processed
+    if !no_new_blocks_to_process {
+        sync_state.mark_processed(sync_state.sync_target_height());
+    }
+    sync_state.wait_for_processed_block_to_reach_target().await;
+    sync_state.finish_sync();
+    info!("Gossip service sync completed");
+    Ok(())
+}
+
+/// Downloads the block header, unless it has already been verified, and the headers of its
+/// transactions from a single peer, moving on to a different peer on failure. Peers that
+/// deliver faster are picked more often
+async fn download_block<
+    A: ApiClient,
+    R: Handler<RethPeerInfo, Result = eyre::Result<()>> + Actor<Context = Context<R>>,
+>(
+    peer_list_service: &PeerListFacade<A, R>,
+    api_client: &A,
+    block_hash: BlockHash,
+    verified_header: Option<IrysBlockHeader>,
+    attempts: usize,
+) -> GossipResult<SyncedBlock> {
+    let mut tried_peers = HashSet::new();
+    let mut last_error = None;
+
+    for attempt in 1..=attempts {
+        let peers = peer_list_service
+            .top_active_peers(Some(MAX_DOWNLOAD_PEERS), Some(tried_peers.clone()))
+            .await?;
+        let Ok((miner_address, peer)) = peers
+            .choose_weighted(&mut rand::thread_rng(), |(_, peer)| {
+                1.0 / (f64::from(peer.response_time) + 1.0)
+            })
+            .cloned()
+        else {
+            break;
+        };
+        tried_peers.insert(miner_address);
+
+        debug!(
+            "Sync task: Downloading block {} from peer {} (attempt {}/{})",
+            block_hash.0.to_base58(),
+            miner_address,
+            attempt,
+            attempts
+        );
+        let started = Instant::now();
+        match fetch_block_with_transactions(api_client, &peer, block_hash, verified_header.clone())
             .await
         {
-            Ok(()) => {
-                sync_state.increment_sync_target_height();
-                info!(
-                    "Successfully requested block {} (sync height is {}) from the network",
-                    block.block_hash.0.to_base58(),
-                    sync_state.sync_target_height()
-                );
+            Ok((header, transactions)) => {
+                if let Err(err) = peer_list_service
+                    .record_peer_delivery_time(&miner_address, started.elapsed())
+                    .await
+                {
+                    error!("Failed to record peer delivery time: {}", err);
+                }
+                return Ok(SyncedBlock {
+                    header,
+                    transactions,
+                    source_miner_address: miner_address,
+                });
             }
             Err(err) => {
-                error!(
-                    "Failed to request block {} (height {}) from the network: {}",
-                    block.block_hash.0.to_base58(),
-                    sync_state.sync_target_height(),
+                warn!(
+                    "Sync task: Failed to download block {} from peer {}: {}",
+                    block_hash.0.to_base58(),
+                    miner_address,
                     err
                 );
+                if let Err(err) = peer_list_service
+                    .decrease_peer_score(&miner_address, ScoreDecreaseReason::Offline)
+                    .await
+                {
+                    error!("Failed to decrease peer score: {}", err);
+                }
+                last_error = Some(err);
             }
         }
+    }
 
-        blocks_left_to_process -= 1;
-        if blocks_left_to_process == 0 {
-            block_queue.extend(
-                get_block_index(
-                    &peer_list_service,
-                    &api_client,
-                    sync_state.sync_target_height(),
-                    limit,
-                    5,
-                    fetch_index_from_the_trusted_peer,
-                )
-                .await?,
-            );
-            blocks_left_to_process = block_queue.len();
-            if blocks_left_to_process == 0 {
-                break;
+    Err(GossipError::Network(format!(
+        "Failed to download block {} after trying {} peers: {:?}",
+        block_hash.0.to_base58(),
+        tried_peers.len(),
+        last_error
+    )))
+}
+
+async fn fetch_block_with_transactions<A: ApiClient>(
+    api_client: &A,
+    peer: &PeerListItem,
+    block_hash: BlockHash,
+    verified_header: Option<IrysBlockHeader>,
+) -> GossipResult<(IrysBlockHeader, Vec<IrysTransactionResponse>)> {
+    let header = match verified_header {
+        Some(header) => header,
+        None => fetch_block_header(api_client, peer, block_hash).await?,
+    };
+
+    let tx_ids = header
+        .data_ledgers
+        .iter()
+        .flat_map(|ledger| ledger.tx_ids.0.iter())
+        .chain(
+            header
+                .system_ledgers
+                .iter()
+                .flat_map(|ledger| ledger.tx_ids.0.iter()),
+        )
+        .copied()
+        .collect::<Vec<_>>();
+    let transactions = if tx_ids.is_empty() {
+        vec![]
+    } else {
+        api_client
+            .get_transactions(peer.address.api, &tx_ids)
+            .await
+            .map_err(|network_error| GossipError::Network(network_error.to_string()))?
+    };
+
+    Ok((header, transactions))
+}
+
+async fn fetch_block_header<A: ApiClient>(
+    api_client: &A,
+    peer: &PeerListItem,
+    block_hash: BlockHash,
+) -> GossipResult<IrysBlockHeader> {
+    let header = api_client
+        .get_block_by_hash(peer.address.api, block_hash)
+        .await
+        .map_err(|network_error| GossipError::Network(network_error.to_string()))?
+        .ok_or_else(|| GossipError::Network("Peer doesn't have the block".to_string()))?
+        .irys;
+    if header.block_hash != block_hash {
+        return Err(GossipError::InvalidData(InvalidDataError::InvalidBlock(
+            "Peer returned a different block".to_string(),
+        )));
+    }
+    Ok(header)
+}
+
+/// Fetches up to `max_len` headers of the chains of several peers from `start`, verifies
+/// that each extends `parent` and returns the one with the highest cumulative difficulty.
+/// Peers serving invalid chains, or too slowly, are penalized and left out
+async fn get_heaviest_verified_chain<
+    A: ApiClient,
+    R: Handler<RethPeerInfo, Result = eyre::Result<()>> + Actor<Context = Context<R>>,
+>(
+    peer_list_service: &PeerListFacade<A, R>,
+    api_client: &A,
+    start: usize,
+    limit: usize,
+    max_len: usize,
+    parent: Option<&IrysBlockHeader>,
+    config: &Config,
+) -> GossipResult<Vec<IrysBlockHeader>> {
+    let peers = peer_list_service
+        .top_active_peers(
+            Some(config.node_config.sync.header_chain_peers.max(1)),
+            None,
+        )
+        .await?;
+    if peers.is_empty() {
+        return Err(GossipError::Network("No peers available".to_string()));
+    }
+
+    let header_chains = join_all(peers.iter().map(|(miner_address, peer)| async move {
+        (
+            miner_address,
+            fetch_verified_chain(api_client, peer, start, limit, max_len, parent, config).await,
+        )
+    }))
+    .await;
+
+    let mut heaviest_chain: Option<Vec<IrysBlockHeader>> = None;
+    for (miner_address, header_chain) in header_chains {
+        match header_chain {
+            Ok(headers) => {
+                let is_heavier = match (
+                    headers.last(),
+                    heaviest_chain.as_ref().and_then(|chain| chain.last()),
+                ) {
+                    (Some(last), Some(heaviest)) => last.cumulative_diff > heaviest.cumulative_diff,
+                    (Some(_), None) => true,
+                    (None, _) => heaviest_chain.is_none(),
+                };
+                if is_heavier {
+                    heaviest_chain = Some(headers);
+                }
+            }
+            Err(err) => {
+                warn!(
+                    "Sync task: Peer {} didn't provide a valid header chain from height {}: {}",
+                    miner_address, start, err
+                );
+                let reason = if matches!(err, GossipError::InvalidData(_)) {
+                    ScoreDecreaseReason::BogusData
+                } else {
+                    ScoreDecreaseReason::Offline
+                };
+                if let Err(err) = peer_list_service
+                    .decrease_peer_score(miner_address, reason)
+                    .await
+                {
+                    error!("Failed to decrease peer score: {}", err);
+                }
             }
         }
     }
 
-    // If no new blocks were added to the index, nothing is going to mark
-    //  the tip as processed
-    if !no_new_blocks_to_process {
-        sync_state.mark_processed(sync_state.sync_target_height());
+    heaviest_chain.ok_or_else(|| {
+        GossipError::Network(format!(
+            "No peer provided a valid header chain from height {}",
+            start
+        ))
+    })
+}
+
+/// Fetches up to `max_len` headers of the peer's chain from `start`, in batches of `limit`
+/// headers, verifying that each batch extends `parent` and the batches before it. Each batch
+/// has to arrive within [`HEADER_BATCH_TIMEOUT`]
+async fn fetch_verified_chain<A: ApiClient>(
+    api_client: &A,
+    peer: &PeerListItem,
+    start: usize,
+    limit: usize,
+    max_len: usize,
+    parent: Option<&IrysBlockHeader>,
+    config: &Config,
+) -> GossipResult<Vec<IrysBlockHeader>> {
+    let mut chain: Vec<IrysBlockHeader> = Vec::new();
+    while chain.len() < max_len {
+        let batch_start = start + chain.len();
+        let batch_limit = limit.min(max_len - chain.len());
+        let batch = timeout(
+            HEADER_BATCH_TIMEOUT,
+            fetch_verified_headers(
+                api_client,
+                peer,
+                batch_start,
+                batch_limit,
+                chain.last().or(parent),
+                config,
+            ),
+        )
+        .await
+        .map_err(|_| {
+            GossipError::Network(format!(
+                "Timed out fetching headers from height {}",
+                batch_start
+            ))
+        })??;
+        let batch_len = batch.len();
+        chain.extend(batch);
+        if batch_len < batch_limit {
+            break;
+        }
     }
-    sync_state.wait_for_processed_block_to_reach_target().await;
-    sync_state.finish_sync();
-    info!("Gossip service sync completed");
-    Ok(())
+    Ok(chain)
+}
+
+/// Fetches the block index of the peer from `start` along with the headers it points to,
+/// and verifies they form a chain extending `parent`, or starting at the configured genesis
+/// block without a parent
+async fn fetch_verified_headers<A: ApiClient>(
+    api_client: &A,
+    peer: &PeerListItem,
+    start: usize,
+    limit: usize,
+    parent: Option<&IrysBlockHeader>,
+    config: &Config,
+) -> GossipResult<Vec<IrysBlockHeader>> {
+    let block_index = api_client
+        .get_block_index(
+            peer.address.api,
+            BlockIndexQuery {
+                height: start,
+                limit,
+            },
+        )
+        .await
+        .map_err(|network_error| GossipError::Network(network_error.to_string()))?;
+    let headers = try_join_all(
+        block_index
+            .iter()
+            .map(|item| fetch_block_header(api_client, peer, item.block_hash)),
+    )
+    .await?;
+
+    let Some(first_header) = headers.first() else {
+        return Ok(headers);
+    };
+    let (parent, new_headers) = match parent {
+        // When syncing from the local tip, the index starts with the tip itself
+        Some(parent) if first_header.block_hash == parent.block_hash => (parent, &headers[1..]),
+        Some(parent) => (parent, &headers[..]),
+        // Without a local block to verify against, the chain has to start at the genesis block
+        None => {
+            let genesis_hash = config.node_config.sync.genesis_block_hash.ok_or_else(|| {
+                GossipError::Internal(InternalGossipError::Unknown(
+                    "Syncing from untrusted peers without local blocks requires the genesis block hash to be configured".to_string(),
+                ))
+            })?;
+            if first_header.height != 0 || first_header.block_hash != genesis_hash {
+                return Err(GossipError::InvalidData(InvalidDataError::InvalidBlock(
+                    format!(
+                        "Header chain starts at block {} instead of the genesis block {}",
+                        first_header.block_hash.0.to_base58(),
+                        genesis_hash.0.to_base58()
+                    ),
+                )));
+            }
+            header_is_authentic(first_header).map_err(|err| {
+                GossipError::InvalidData(InvalidDataError::InvalidBlock(err.to_string()))
+            })?;
+            (first_header, &headers[1..])
+        }
+    };
+    verify_header_chain(
+        parent,
+        new_headers,
+        &config.consensus,
+        config.node_config.sync.vdf_spot_check_interval,
+    )
+    .await
+    .map_err(|err| {
+        GossipError::InvalidData(InvalidDataError::InvalidBlock(format!("{:#}", err)))
+    })?;
+
+    Ok(headers)
 }
 
 async fn get_block_index<
@@ -338,8 +715,10 @@ mod tests {
         use irys_storage::irys_consensus_data_db::open_or_create_irys_consensus_data_db;
         use irys_testing_utils::utils::setup_tracing_and_temp_dir;
         use irys_types::{
-            Address, Config, DatabaseProvider, NodeConfig, PeerAddress, PeerListItem, PeerScore,
+            Address, CombinedBlockHeader, Config, DatabaseProvider, NodeConfig, PeerAddress,
+            PeerListItem, PeerScore,
         };
+        use std::net::SocketAddr;
         use std::sync::{Arc, Mutex};
 
         #[actix_web::test]
@@ -353,29 +732,18 @@ mod tests {
                     .expect("can't open temp dir"),
             ));
 
-            let block_requests = Arc::new(Mutex::new(vec![]));
-            let block_requests_clone = block_requests.clone();
-            let fake_gossip_server = FakeGossipServer::new();
-            let sync_state_clone = sync_state.clone();
-            fake_gossip_server.set_on_block_data_request(move |block_hash| {
-                let mut block_requests = block_requests.lock().unwrap();
-                let requests_len = block_requests.len();
-                block_requests.push(block_hash);
-
-                // Simulating one false response so the block gets requested again
-                if requests_len == 0 {
-                    false
-                } else {
-                    sync_state_clone.mark_processed(start_from + requests_len);
-                    true
-                }
-            });
-            let fake_gossip_address = fake_gossip_server.spawn();
+            let fake_gossip_address = FakeGossipServer::new().spawn();
+            let other_fake_gossip_address = FakeGossipServer::new().spawn();
             let fake_peer_address = PeerAddress {
                 gossip: fake_gossip_address,
                 api: fake_gossip_address,
                 execution: Default::default(),
             };
+            let other_fake_peer_address = PeerAddress {
+                gossip: other_fake_gossip_address,
+                api: other_fake_gossip_address,
+                execution: Default::default(),
+            };
 
             let mut node_config = NodeConfig::testnet();
             node_config.trusted_peers = vec![fake_peer_address.clone()];
@@ -384,7 +752,7 @@ mod tests {
             let api_client_stub = ApiClientStub::new();
             let calls = Arc::new(Mutex::new(vec![]));
             let block_index_requests = calls.clone();
-            api_client_stub.set_block_index_handler(move |query| {
+            api_client_stub.set_block_index_handler(move |_, query| {
                 let mut calls_ref = calls.lock().unwrap();
                 let calls_len = calls_ref.len();
                 calls_ref.push(query);
@@ -407,6 +775,30 @@ mod tests {
                 }
             });
 
+            let block_requests: Arc<Mutex<Vec<(SocketAddr, BlockHash)>>> =
+                Arc::new(Mutex::new(vec![]));
+            let block_requests_clone = block_requests.clone();
+            api_client_stub.set_block_handler(move |peer, block_hash| {
+                let mut block_requests = block_requests.lock().unwrap();
+                let first_request = !block_requests
+                    .iter()
+                    .any(|(_, requested_hash)| *requested_hash == block_hash);
+                block_requests.push((peer, block_hash));
+
+                // Simulating one failed download so the block gets requested from the other peer
+                if first_request && block_hash == BlockHash::repeat_byte(1) {
+                    Err(eyre::eyre!("connection reset"))
+                } else {
+                    Ok(Some(CombinedBlockHeader {
+                        irys: IrysBlockHeader {
+                            block_hash,
+                            ..IrysBlockHeader::new_mock_header()
+                        },
+                        execution: Default::default(),
+                    }))
+                }
+            });
+
             let reth_mock = MockRethServiceActor {};
             let reth_mock_addr = reth_mock.start();
             let peer_list_service = PeerListServiceWithClient::new_with_custom_api_client(
@@ -416,30 +808,39 @@ mod tests {
                 reth_mock_addr.clone(),
             );
             let peer_list = PeerListFacade::new(peer_list_service.start());
-            peer_list
-                .add_peer(
-                    Address::repeat_byte(2),
-                    PeerListItem {
-                        reputation_score: PeerScore::new(100),
-                        response_time: 0,
-                        address: fake_peer_address,
-                        last_seen: 0,
-                        is_online: true,
-                    },
-                )
-                .await
-                .expect("to add peer");
+            for (mining_address, address) in [
+                (Address::repeat_byte(2), fake_peer_address),
+                (Address::repeat_byte(3), other_fake_peer_address),
+            ] {
+                peer_list
+                    .add_peer(
+                        mining_address,
+                        PeerListItem {
+                            reputation_score: PeerScore::new(100),
+                            response_time: 0,
+                            address,
+                            last_seen: 0,
+                            is_online: true,
+                        },
+                    )
+                    .await
+                    .expect("to add peer");
+            }
 
             // Check that the sync status is syncing
             assert!(sync_state.is_syncing());
 
+            let (synced_blocks_tx, mut synced_blocks_rx) = tokio::sync::mpsc::unbounded_channel();
             sync_chain(
                 sync_state.clone(),
                 api_client_stub.clone(),
                 peer_list,
                 &NodeMode::PeerSync,
+                start_from,
+                None,
                 10,
-                10,
+                &config,
+                synced_blocks_tx,
             )
             .await
             .expect("to finish catching up");
@@ -456,13 +857,199 @@ mod tests {
 
             // Check that the sync status has changed to synced
             assert!(!sync_state.is_syncing());
+            assert_eq!(sync_state.sync_target_height(), 12);
 
             let block_requests = block_requests_clone.lock().unwrap();
             assert_eq!(block_requests.len(), 3);
-            assert_eq!(block_requests[0], BlockHash::repeat_byte(1));
-            // As the first call didn't return anything, the peer tries to fetch it once again
-            assert_eq!(block_requests[1], BlockHash::repeat_byte(1));
-            assert_eq!(block_requests[2], BlockHash::repeat_byte(2));
+            // As the first download failed, the block is downloaded from the other peer
+            let first_block_requests = block_requests
+                .iter()
+                .filter(|(_, block_hash)| *block_hash == BlockHash::repeat_byte(1))
+                .collect::<Vec<_>>();
+            assert_eq!(first_block_requests.len(), 2);
+            assert_ne!(first_block_requests[0].0, first_block_requests[1].0);
+
+            // Blocks are handed over in the index order
+            let first_block = synced_blocks_rx.recv().await.expect("first synced block");
+            let second_block = synced_blocks_rx.recv().await.expect("second synced block");
+            assert_eq!(first_block.header.block_hash, BlockHash::repeat_byte(1));
+            assert_eq!(second_block.header.block_hash, BlockHash::repeat_byte(2));
+
+            Ok(())
+        }
+
+        #[actix_web::test]
+        async fn should_follow_the_heaviest_chain_from_the_genesis_block() -> eyre::Result<()> {
+            use crate::header_chain::tests::{build_chain, genesis};
+            use irys_types::irys::IrysSigner;
+            use std::collections::HashMap;
+
+            let temp_dir = setup_tracing_and_temp_dir(None, false);
+            let db = DatabaseProvider(Arc::new(
+                open_or_create_irys_consensus_data_db(&temp_dir.path().to_path_buf())
+                    .expect("can't open temp dir"),
+            ));
+
+            let mut node_config = NodeConfig::testnet();
+            node_config.sync.vdf_spot_check_interval = 0;
+            let signer = IrysSigner::random_signer(&node_config.consensus_config());
+            let genesis_header = genesis(&signer);
+            node_config.sync.genesis_block_hash = Some(genesis_header.block_hash);
+            let config = Config::new(node_config);
+
+            // The heaviest chain spans several index batches, a lighter one forks off the
+            //  genesis block and an even heavier one starts at a different genesis block
+            let limit = 5;
+            let mut heaviest = vec![genesis_header.clone()];
+            heaviest.extend(build_chain(&signer, &genesis_header, 12));
+            let mut lighter = vec![genesis_header.clone()];
+            lighter.extend(build_chain(&signer, &genesis_header, 4));
+            let mut other_genesis = genesis(&signer);
+            other_genesis.timestamp += 1;
+            signer.sign_block_header(&mut other_genesis)?;
+            let mut unanchored = vec![other_genesis.clone()];
+            unanchored.extend(build_chain(&signer, &other_genesis, 15));
+
+            let peers = [
+                (
+                    Address::repeat_byte(2),
+                    FakeGossipServer::new().spawn(),
+                    heaviest.clone(),
+                ),
+                (
+                    Address::repeat_byte(3),
+                    FakeGossipServer::new().spawn(),
+                    lighter,
+                ),
+                (
+                    Address::repeat_byte(4),
+                    FakeGossipServer::new().spawn(),
+                    unanchored,
+                ),
+            ];
+            let chains: HashMap<SocketAddr, Vec<IrysBlockHeader>> = peers
+                .iter()
+                .map(|(_, address, chain)| (*address, chain.clone()))
+                .collect();
+            let headers: HashMap<BlockHash, IrysBlockHeader> = chains
+                .values()
+                .flatten()
+                .map(|header| (header.block_hash, header.clone()))
+                .collect();
+
+            let api_client_stub = ApiClientStub::new();
+            api_client_stub.set_block_index_handler(move |peer, query| {
+                Ok(chains[&peer]
+                    .iter()
+                    .skip(query.height)
+                    .take(query.limit)
+                    .map(|header| BlockIndexItem {
+                        block_hash: header.block_hash,
+                        num_ledgers: 0,
+                        ledgers: vec![],
+                    })
+                    .collect())
+            });
+            api_client_stub.set_block_handler(move |_, block_hash| {
+                Ok(headers.get(&block_hash).map(|header| CombinedBlockHeader {
+                    irys: header.clone(),
+                    execution: Default::default(),
+                }))
+            });
+
+            let reth_mock_addr = MockRethServiceActor {}.start();
+            let peer_list_service = PeerListServiceWithClient::new_with_custom_api_client(
+                db,
+                &config,
+                api_client_stub.clone(),
+                reth_mock_addr,
+            );
+            let peer_list = PeerListFacade::new(peer_list_service.start());
+            for (mining_address, address, _) in &peers {
+                peer_list
+                    .add_peer(
+                        *mining_address,
+                        PeerListItem {
+                            reputation_score: PeerScore::new(100),
+                            response_time: 0,
+                            address: PeerAddress {
+                                gossip: *address,
+                                api: *address,
+                                execution: Default::default(),
+                            },
+                            last_seen: 0,
+                            is_online: true,
+                        },
+                    )
+                    .await
+                    .expect("to add peer");
+            }
+
+            let chain = get_heaviest_verified_chain(
+                &peer_list,
+                &api_client_stub,
+                0,
+                limit,
+                MAX_HEADER_CHAIN_LENGTH,
+                None,
+                &config,
+            )
+            .await?;
+            assert_eq!(
+                chain
+                    .iter()
+                    .map(|header| header.block_hash)
+                    .collect::<Vec<_>>(),
+                heaviest
+                    .iter()
+                    .map(|header| header.block_hash)
+                    .collect::<Vec<_>>()
+            );
+
+            // The peer serving a chain from a different genesis block is penalized
+            let scores = peer_list.top_active_peers(None, None).await?;
+            let score = |address: Address| {
+                scores
+                    .iter()
+                    .find(|(mining_address, _)| *mining_address == address)
+                    .map(|(_, peer)| peer.reputation_score.get())
+            };
+            assert!(score(Address::repeat_byte(4)) < score(Address::repeat_byte(3)));
+
+            // With a cap on the chain length, the chain is extended round by round from the
+            //  last verified header
+            let first_round = get_heaviest_verified_chain(
+                &peer_list,
+                &api_client_stub,
+                0,
+                limit,
+                8,
+                None,
+                &config,
+            )
+            .await?;
+            assert_eq!(first_round.len(), 8);
+            let second_round = get_heaviest_verified_chain(
+                &peer_list,
+                &api_client_stub,
+                8,
+                limit,
+                8,
+                first_round.last(),
+                &config,
+            )
+            .await?;
+            assert_eq!(
+                first_round
+                    .iter()
+                    .chain(&second_round)
+                    .map(|header| header.block_hash)
+                    .collect::<Vec<_>>(),
+                heaviest
+                    .iter()
+                    .map(|header| header.block_hash)
+                    .collect::<Vec<_>>()
+            );
 
             Ok(())
         }
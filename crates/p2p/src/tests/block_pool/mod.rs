@@ -1,4 +1,6 @@
-use crate::block_pool_service::{BlockPoolService, ProcessBlock};
+use crate::block_pool_service::{
+    BlockPoolError, BlockPoolReadGuard, BlockPoolService, ProcessBlock,
+};
 use crate::peer_list::{AddPeer, PeerListServiceWithClient};
 use crate::tests::util::{FakeGossipServer, MockRethServiceActor};
 use crate::SyncState;
@@ -13,9 +15,10 @@ use irys_database::{block_header_by_hash, insert_block_header};
 use irys_storage::irys_consensus_data_db::open_or_create_irys_consensus_data_db;
 use irys_testing_utils::utils::setup_tracing_and_temp_dir;
 use irys_types::{
-    AcceptedResponse, Address, BlockHash, BlockIndexItem, BlockIndexQuery, CombinedBlockHeader,
-    Config, DatabaseProvider, IrysBlockHeader, IrysTransactionHeader, IrysTransactionResponse,
-    NodeConfig, PeerAddress, PeerListItem, PeerResponse, PeerScore, VersionRequest, H256,
+    AcceptedResponse, Address, BlockHash, BlockIndexItem, BlockIndexQuery, BlockPoolConfig,
+    CombinedBlockHeader, Config, DatabaseProvider, IrysBlockHeader, IrysTransactionHeader,
+    IrysTransactionResponse, NodeConfig, PeerAddress, PeerListItem, PeerResponse, PeerScore,
+    VersionRequest, H256,
 };
 use std::net::SocketAddr;
 use std::sync::{Arc, RwLock};
@@ -141,6 +144,8 @@ async fn should_process_block() {
         Some(vdf_tx),
         sync_state,
         vdf_service_tx,
+        BlockPoolConfig::default(),
+        BlockPoolReadGuard::default(),
     );
     let addr = service.start();
 
@@ -201,6 +206,7 @@ async fn should_process_block() {
 
     addr.send(ProcessBlock {
         header: test_header.clone(),
+        source_miner_address: Address::random(),
     })
     .await
     .expect("can't send block")
@@ -334,6 +340,8 @@ async fn should_process_block_with_intermediate_block_in_api() {
         Some(vdf_tx),
         sync_state,
         vdf_service_tx,
+        BlockPoolConfig::default(),
+        BlockPoolReadGuard::default(),
     );
     let addr = service.start();
 
@@ -346,10 +354,13 @@ async fn should_process_block_with_intermediate_block_in_api() {
         debug!("Receive get block: {:?}", block_hash.0.to_base58());
         tokio::spawn(async move {
             debug!("Send block to block pool");
-            addr.send(ProcessBlock { header: block })
-                .await
-                .expect("to send message")
-                .expect("to process block");
+            addr.send(ProcessBlock {
+                header: block,
+                source_miner_address: Address::random(),
+            })
+            .await
+            .expect("to send message")
+            .expect("to process block");
         });
         true
     });
@@ -382,6 +393,7 @@ async fn should_process_block_with_intermediate_block_in_api() {
     // Process block3
     addr.send(ProcessBlock {
         header: block3.clone(),
+        source_miner_address: Address::random(),
     })
     .await
     .expect("can't send block")
@@ -401,3 +413,193 @@ async fn should_process_block_with_intermediate_block_in_api() {
     assert_eq!(discovered_block2, block2);
     assert_eq!(discovered_block3, block3);
 }
+
+#[actix_rt::test]
+async fn should_enforce_pool_limits() {
+    let temp_dir = setup_tracing_and_temp_dir(None, false);
+    let mut node_config = NodeConfig::testnet();
+    node_config.trusted_peers = vec![];
+    let config = Config::new(node_config);
+
+    let db = DatabaseProvider(Arc::new(
+        open_or_create_irys_consensus_data_db(&temp_dir.path().to_path_buf())
+            .expect("can't open temp dir"),
+    ));
+
+    let block_discovery_stub = BlockDiscoveryStub {
+        received_blocks: Arc::new(RwLock::new(vec![])),
+        db: db.clone(),
+    };
+    let reth_addr = MockRethServiceActor {}.start();
+    // The peer accepts the requests for the missing parents but never sends them, so the
+    // blocks stay in the pool
+    let gossip_server = FakeGossipServer::new();
+    gossip_server.set_on_block_data_request(|_| true);
+    let fake_peer_gossip_addr = gossip_server.spawn();
+    let peer_addr = PeerListServiceWithClient::new_with_custom_api_client(
+        db.clone(),
+        &config,
+        MockApiClient::default(),
+        reth_addr,
+    )
+    .start();
+    peer_addr
+        .send(AddPeer {
+            mining_addr: Address::random(),
+            peer: PeerListItem {
+                reputation_score: PeerScore::new(100),
+                response_time: 0,
+                address: PeerAddress {
+                    gossip: fake_peer_gossip_addr,
+                    ..PeerAddress::default()
+                },
+                last_seen: 0,
+                is_online: true,
+            },
+        })
+        .await
+        .expect("can't send message to peer list");
+
+    let (vdf_tx, _vdf_rx) = tokio::sync::mpsc::channel(1);
+    let (vdf_service_tx, _vdf_service_rx) = tokio::sync::mpsc::unbounded_channel();
+    let pooled_blocks = BlockPoolReadGuard::default();
+    let service = BlockPoolService::new_with_client(
+        db.clone(),
+        peer_addr.into(),
+        block_discovery_stub.clone(),
+        Some(vdf_tx),
+        SyncState::new(false),
+        vdf_service_tx,
+        BlockPoolConfig {
+            max_blocks: 3,
+            max_blocks_per_peer: 2,
+            ..BlockPoolConfig::default()
+        },
+        pooled_blocks.clone(),
+    );
+    let addr = service.start();
+
+    let orphan = || {
+        let mut header = IrysBlockHeader::default();
+        header.block_hash = BlockHash::random();
+        header.previous_block_hash = BlockHash::random();
+        header
+    };
+    let first_peer = Address::random();
+    let second_peer = Address::random();
+
+    let mut first_peer_blocks = vec![];
+    for _ in 0..2 {
+        let block = orphan();
+        first_peer_blocks.push(block.block_hash);
+        let _ = addr
+            .send(ProcessBlock {
+                header: block,
+                source_miner_address: first_peer,
+            })
+            .await
+            .expect("to send block");
+        // Give the blocks distinct arrival times
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+    assert_eq!(pooled_blocks.read().len(), 2);
+
+    // The first peer has used up its quota
+    let rejected = addr
+        .send(ProcessBlock {
+            header: orphan(),
+            source_miner_address: first_peer,
+        })
+        .await
+        .expect("to send block");
+    assert!(matches!(
+        rejected,
+        Err(BlockPoolError::PeerQuotaExceeded(peer)) if peer == first_peer
+    ));
+    assert_eq!(pooled_blocks.read().len(), 2);
+
+    // The pool is full, so the oldest block is evicted
+    for _ in 0..2 {
+        let _ = addr
+            .send(ProcessBlock {
+                header: orphan(),
+                source_miner_address: second_peer,
+            })
+            .await
+            .expect("to send block");
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+    let pooled_blocks = pooled_blocks.read();
+    assert_eq!(pooled_blocks.len(), 3);
+    assert!(!pooled_blocks.contains_key(&first_peer_blocks[0]));
+    assert!(pooled_blocks.contains_key(&first_peer_blocks[1]));
+    assert_eq!(
+        pooled_blocks
+            .values()
+            .filter(|block| block.source_miner_address == second_peer)
+            .count(),
+        2
+    );
+}
+
+#[actix_rt::test]
+async fn should_drop_orphans_when_parent_request_fails() {
+    let temp_dir = setup_tracing_and_temp_dir(None, false);
+    let mut node_config = NodeConfig::testnet();
+    node_config.trusted_peers = vec![];
+    let config = Config::new(node_config);
+
+    let db = DatabaseProvider(Arc::new(
+        open_or_create_irys_consensus_data_db(&temp_dir.path().to_path_buf())
+            .expect("can't open temp dir"),
+    ));
+
+    let block_discovery_stub = BlockDiscoveryStub {
+        received_blocks: Arc::new(RwLock::new(vec![])),
+        db: db.clone(),
+    };
+    let reth_addr = MockRethServiceActor {}.start();
+    // No peers to request the missing parents from, so every parent request fails
+    let peer_addr = PeerListServiceWithClient::new_with_custom_api_client(
+        db.clone(),
+        &config,
+        MockApiClient::default(),
+        reth_addr,
+    )
+    .start();
+
+    let (vdf_tx, _vdf_rx) = tokio::sync::mpsc::channel(1);
+    let (vdf_service_tx, _vdf_service_rx) = tokio::sync::mpsc::unbounded_channel();
+    let pooled_blocks = BlockPoolReadGuard::default();
+    let service = BlockPoolService::new_with_client(
+        db.clone(),
+        peer_addr.into(),
+        block_discovery_stub.clone(),
+        Some(vdf_tx),
+        SyncState::new(false),
+        vdf_service_tx,
+        BlockPoolConfig {
+            max_blocks_per_peer: 1,
+            ..BlockPoolConfig::default()
+        },
+        pooled_blocks.clone(),
+    );
+    let addr = service.start();
+
+    let peer = Address::random();
+    for _ in 0..2 {
+        let mut header = IrysBlockHeader::default();
+        header.block_hash = BlockHash::random();
+        header.previous_block_hash = BlockHash::random();
+        let result = addr
+            .send(ProcessBlock {
+                header,
+                source_miner_address: peer,
+            })
+            .await
+            .expect("to send block");
+        // The orphan is dropped instead of taking up the peer's quota
+        assert!(!matches!(result, Err(BlockPoolError::PeerQuotaExceeded(_))));
+        assert!(pooled_blocks.read().is_empty());
+    }
+}
@@ -1,7 +1,13 @@
-use super::util::{create_test_chunks, generate_test_tx, GossipServiceTestFixture};
+use super::util::{
+    create_test_chunks, generate_test_tx, FakeGossipServer, GossipServiceTestFixture,
+};
+use crate::GossipClient;
 use core::time::Duration;
 use irys_actors::mempool_service::MempoolFacade;
-use irys_types::{DataTransactionLedger, GossipData, H256List, IrysBlockHeader, PeerScore};
+use irys_types::{
+    Address, DataTransactionLedger, GossipData, GossipRequest, H256List, IrysBlockHeader,
+    PeerListItem, PeerScore, BINARY_CONTENT_TYPE,
+};
 use tracing::debug;
 
 #[actix_web::test]
@@ -205,6 +211,111 @@ async fn heavy_should_broadcast_chunk_data() -> eyre::Result<()> {
     Ok(())
 }
 
+#[actix_web::test]
+async fn heavy_should_accept_json_chunks_from_peers_without_binary_support() -> eyre::Result<()> {
+    let fixture1 = GossipServiceTestFixture::new();
+    let mut fixture2 = GossipServiceTestFixture::new();
+    fixture2.add_peer(&fixture1).await;
+    let (service2_handle, _) = fixture2.run_service().await;
+
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    // an older peer posts the chunk as JSON rather than in the binary encoding
+    #[expect(clippy::indexing_slicing, reason = "just a test")]
+    let chunk = create_test_chunks(&generate_test_tx())[0].clone();
+    let url = format!("http://127.0.0.1:{}/gossip/chunk", fixture2.gossip_port);
+    let client = reqwest::Client::new();
+    let response = client
+        .post(&url)
+        .json(&GossipRequest {
+            miner_address: fixture1.mining_address,
+            data: chunk.clone(),
+        })
+        .send()
+        .await?;
+    eyre::ensure!(
+        response.status().is_success(),
+        "JSON chunk rejected: {}",
+        response.status()
+    );
+
+    // a malformed binary body is rejected without taking the server down
+    let response = client
+        .post(&url)
+        .header(reqwest::header::CONTENT_TYPE, BINARY_CONTENT_TYPE)
+        .body(vec![1_u8; 30])
+        .send()
+        .await?;
+    eyre::ensure!(
+        response.status() == reqwest::StatusCode::BAD_REQUEST,
+        "malformed binary chunk got {}",
+        response.status()
+    );
+
+    // other encodings are answered with 415, clients resend the data as JSON then
+    let response = client
+        .post(&url)
+        .header(reqwest::header::CONTENT_TYPE, "application/cbor")
+        .body(vec![1_u8; 30])
+        .send()
+        .await?;
+    eyre::ensure!(
+        response.status() == reqwest::StatusCode::UNSUPPORTED_MEDIA_TYPE,
+        "chunk in an unknown encoding got {}",
+        response.status()
+    );
+
+    {
+        let service2_chunks = fixture2
+            .mempool_chunks
+            .read()
+            .expect("Failed to read service 2 mempool chunks");
+        eyre::ensure!(
+            *service2_chunks == vec![chunk],
+            "Expected the JSON chunk in service 2 mempool, but found {:?}",
+            service2_chunks
+        );
+    };
+
+    service2_handle.stop().await?;
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn heavy_should_fall_back_to_json_for_peers_without_binary_support() -> eyre::Result<()> {
+    // the fake peer only has a JSON extractor on its chunk route
+    let fake_peer = FakeGossipServer::new();
+    let mut peer = PeerListItem {
+        is_online: true,
+        ..PeerListItem::default()
+    };
+    peer.address.gossip = fake_peer.spawn();
+    let client = GossipClient::new(Duration::from_secs(5), Address::random());
+
+    #[expect(clippy::indexing_slicing, reason = "just a test")]
+    let chunk = create_test_chunks(&generate_test_tx())[0].clone();
+    client
+        .send_data(&peer, &GossipData::Chunk(chunk.clone()))
+        .await?;
+    eyre::ensure!(
+        fake_peer.received_chunks() == vec![chunk],
+        "Expected the chunk to be resent as JSON, but the peer got {:?}",
+        fake_peer.received_chunks()
+    );
+
+    // the fake peer has no block route, data it rejects isn't reported as delivered
+    let result = client
+        .send_data(
+            &peer,
+            &GossipData::Block(IrysBlockHeader::new_mock_header()),
+        )
+        .await;
+    eyre::ensure!(result.is_err(), "Expected the rejected block to fail");
+
+    Ok(())
+}
+
 #[actix_web::test]
 async fn heavy_should_not_broadcast_to_low_reputation_peers() -> eyre::Result<()> {
     let mut fixture1 = GossipServiceTestFixture::new();
@@ -3,25 +3,30 @@ use crate::types::GossipDataRequest;
 use crate::{P2PService, ServiceHandleWithShutdownSignal};
 use actix::{Actor, Addr, Context, Handler};
 use actix_web::dev::Server;
-use actix_web::{middleware, web, App, HttpResponse, HttpServer};
+use actix_web::{
+    error::{InternalError, JsonPayloadError},
+    middleware, web, App, HttpResponse, HttpServer,
+};
 use async_trait::async_trait;
 use base58::ToBase58;
 use core::net::{IpAddr, Ipv4Addr, SocketAddr};
 use eyre::{eyre, Result};
 use irys_actors::block_discovery::BlockDiscoveryFacade;
 use irys_actors::broadcast_mining_service::BroadcastMiningSeed;
-use irys_actors::mempool_service::{ChunkIngressError, MempoolFacade, TxIngressError};
+use irys_actors::mempool_service::{
+    ChunkIngressError, IngressProofError, MempoolFacade, TxIngressError,
+};
 use irys_api_client::ApiClient;
 use irys_primitives::Address;
 use irys_storage::irys_consensus_data_db::open_or_create_irys_consensus_data_db;
 use irys_testing_utils::utils::setup_tracing_and_temp_dir;
 use irys_types::irys::IrysSigner;
 use irys_types::{
-    AcceptedResponse, Base64, BlockHash, BlockIndexItem, BlockIndexQuery, CombinedBlockHeader,
-    CommitmentTransaction, Config, DatabaseProvider, GossipData, GossipRequest, IrysBlockHeader,
-    IrysTransaction, IrysTransactionHeader, IrysTransactionResponse, NodeConfig, PeerAddress,
-    PeerListItem, PeerResponse, PeerScore, RethPeerInfo, TxChunkOffset, UnpackedChunk,
-    VersionRequest, H256,
+    ingress::IngressProof, AcceptedResponse, Base64, BlockHash, BlockIndexItem, BlockIndexQuery,
+    BlockPoolConfig, CombinedBlockHeader, CommitmentTransaction, Config, ConsensusConfig,
+    DatabaseProvider, GossipData, GossipRequest, IrysBlockHeader, IrysTransaction,
+    IrysTransactionHeader, IrysTransactionResponse, NodeConfig, PeerAddress, PeerListItem,
+    PeerResponse, PeerScore, RethPeerInfo, TxChunkOffset, UnpackedChunk, VersionRequest, H256,
 };
 use reth_tasks::{TaskExecutor, TaskManager};
 use std::collections::HashMap;
@@ -108,6 +113,13 @@ impl MempoolFacade for MempoolStub {
         Ok(())
     }
 
+    async fn handle_ingress_proof(
+        &self,
+        _proof: IngressProof,
+    ) -> std::result::Result<(), IngressProofError> {
+        Ok(())
+    }
+
     async fn is_known_tx(&self, tx_id: H256) -> std::result::Result<bool, TxIngressError> {
         let exists = self
             .txs
@@ -150,27 +162,59 @@ impl BlockDiscoveryFacade for BlockDiscoveryStub {
 pub(crate) struct ApiClientStub {
     pub txs: HashMap<H256, IrysTransactionHeader>,
     pub block_index_handler: Arc<
-        RwLock<Box<dyn Fn(BlockIndexQuery) -> Result<Vec<BlockIndexItem>> + Send + Sync + 'static>>,
+        RwLock<
+            Box<
+                dyn Fn(SocketAddr, BlockIndexQuery) -> Result<Vec<BlockIndexItem>>
+                    + Send
+                    + Sync
+                    + 'static,
+            >,
+        >,
     >,
     pub block_index_calls: Arc<RwLock<Vec<BlockIndexQuery>>>,
+    pub block_handler: Arc<
+        RwLock<
+            Box<
+                dyn Fn(SocketAddr, H256) -> Result<Option<CombinedBlockHeader>>
+                    + Send
+                    + Sync
+                    + 'static,
+            >,
+        >,
+    >,
 }
 
 impl ApiClientStub {
     pub(crate) fn new() -> Self {
         Self {
             txs: HashMap::new(),
-            block_index_handler: Arc::new(RwLock::new(Box::new(|_| Ok(Vec::new())))),
+            block_index_handler: Arc::new(RwLock::new(Box::new(|_, _| Ok(Vec::new())))),
             block_index_calls: Arc::new(Default::default()),
+            block_handler: Arc::new(RwLock::new(Box::new(|_, _| Ok(None)))),
         }
     }
 
     pub(crate) fn set_block_index_handler(
         &self,
-        handler: impl Fn(BlockIndexQuery) -> Result<Vec<BlockIndexItem>> + Send + Sync + 'static,
+        handler: impl Fn(SocketAddr, BlockIndexQuery) -> Result<Vec<BlockIndexItem>>
+            + Send
+            + Sync
+            + 'static,
     ) {
         let mut guard = self.block_index_handler.write().expect("to unlock handler");
         *guard = Box::new(handler);
     }
+
+    pub(crate) fn set_block_handler(
+        &self,
+        handler: impl Fn(SocketAddr, H256) -> Result<Option<CombinedBlockHeader>>
+            + Send
+            + Sync
+            + 'static,
+    ) {
+        let mut guard = self.block_handler.write().expect("to unlock handler");
+        *guard = Box::new(handler);
+    }
 }
 
 #[async_trait::async_trait]
@@ -222,15 +266,16 @@ impl ApiClient for ApiClientStub {
 
     async fn get_block_by_hash(
         &self,
-        _peer: SocketAddr,
-        _block_hash: H256,
+        peer: SocketAddr,
+        block_hash: H256,
     ) -> Result<Option<CombinedBlockHeader>> {
-        Ok(None)
+        let handler = self.block_handler.read().expect("to unlock handler");
+        handler(peer, block_hash)
     }
 
     async fn get_block_index(
         &self,
-        _peer: SocketAddr,
+        peer: SocketAddr,
         block_index_query: BlockIndexQuery,
     ) -> Result<Vec<BlockIndexItem>> {
         self.block_index_calls
@@ -238,7 +283,7 @@ impl ApiClient for ApiClientStub {
             .expect("To unlock calls")
             .push(block_index_query.clone());
         let handler = self.block_index_handler.read().expect("to unlock response");
-        handler(block_index_query)
+        handler(peer, block_index_query)
     }
 }
 
@@ -356,7 +401,8 @@ impl GossipServiceTestFixture {
         mpsc::UnboundedSender<GossipData>,
     ) {
         let (internal_message_bus, rx) = tokio::sync::mpsc::unbounded_channel::<GossipData>();
-        let gossip_service = P2PService::new(self.mining_address, rx);
+        let (_synced_blocks_tx, synced_blocks_rx) = tokio::sync::mpsc::unbounded_channel();
+        let gossip_service = P2PService::new(self.mining_address, rx, synced_blocks_rx);
         let gossip_listener = TcpListener::bind(
             format!("127.0.0.1:{}", self.gossip_port)
                 .parse::<SocketAddr>()
@@ -389,6 +435,10 @@ impl GossipServiceTestFixture {
                 vdf_tx,
                 gossip_listener,
                 vdf_service_tx,
+                ConsensusConfig::testnet().vdf,
+                H256::zero(),
+                internal_message_bus.clone(),
+                BlockPoolConfig::default(),
             )
             .expect("failed to run gossip service");
 
@@ -500,12 +550,14 @@ pub(crate) fn create_test_chunks(tx: &IrysTransaction) -> Vec<UnpackedChunk> {
 
 struct FakeGossipDataHandler {
     on_block_data_request: Box<dyn Fn(BlockHash) -> bool + Send + Sync>,
+    received_chunks: Vec<UnpackedChunk>,
 }
 
 impl FakeGossipDataHandler {
     fn new() -> Self {
         Self {
             on_block_data_request: Box::new(|_| false),
+            received_chunks: Vec::new(),
         }
     }
 
@@ -538,7 +590,7 @@ impl FakeGossipServer {
         }
     }
 
-    pub(crate) fn spawn(self) -> SocketAddr {
+    pub(crate) fn spawn(&self) -> SocketAddr {
         let (server_handle, fake_peer_gossip_addr) =
             self.run(SocketAddr::from(([127, 0, 0, 1], 0)));
         tokio::spawn(server_handle);
@@ -555,6 +607,15 @@ impl FakeGossipServer {
             .set_on_block_data_request(Box::new(on_block_data_request));
     }
 
+    /// Chunks gossiped to the fake server
+    pub(crate) fn received_chunks(&self) -> Vec<UnpackedChunk> {
+        self.handler
+            .read()
+            .expect("to unlock handler")
+            .received_chunks
+            .clone()
+    }
+
     /// Runs the fake server, returns the address on which the server has started, as well
     /// as the server handle
     pub(crate) fn run(&self, address: SocketAddr) -> (Server, SocketAddr) {
@@ -563,8 +624,18 @@ impl FakeGossipServer {
             let handler = handler.clone();
             App::new()
                 .app_data(web::Data::new(handler.clone()))
+                .app_data(web::JsonConfig::default().error_handler(|error, _req| {
+                    // like the gossip server, answer bodies that aren't JSON with 415
+                    let response = match error {
+                        JsonPayloadError::ContentType => HttpResponse::UnsupportedMediaType(),
+                        _ => HttpResponse::BadRequest(),
+                    }
+                    .finish();
+                    InternalError::from_response(error, response).into()
+                }))
                 .wrap(middleware::Logger::new("%r %s %D ms"))
                 .service(web::resource("/gossip/get_data").route(web::post().to(handle_get_data)))
+                .service(web::resource("/gossip/chunk").route(web::post().to(handle_chunk)))
                 .default_service(web::to(|| async {
                     warn!("Request hit default handler - check your route paths");
                     HttpResponse::NotFound()
@@ -584,6 +655,19 @@ impl FakeGossipServer {
     }
 }
 
+/// Only takes JSON, like peers without support for the binary encoding
+async fn handle_chunk(
+    handler: web::Data<Arc<RwLock<FakeGossipDataHandler>>>,
+    chunk_request: web::Json<GossipRequest<UnpackedChunk>>,
+) -> HttpResponse {
+    handler
+        .write()
+        .expect("to unlock handler")
+        .received_chunks
+        .push(chunk_request.0.data);
+    HttpResponse::Ok().finish()
+}
+
 async fn handle_get_data(
     handler: web::Data<Arc<RwLock<FakeGossipDataHandler>>>,
     data_request: web::Json<GossipRequest<GossipDataRequest>>,
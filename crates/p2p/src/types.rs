@@ -1,7 +1,7 @@
 use crate::block_pool_service::BlockPoolError;
 use crate::peer_list::PeerListFacadeError;
 use base58::ToBase58;
-use irys_actors::mempool_service::TxIngressError;
+use irys_actors::mempool_service::{IngressProofError, TxIngressError};
 use irys_types::{BlockHash, H256};
 use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
@@ -59,6 +59,10 @@ impl From<TxIngressError> for GossipError {
                 // Invalid anchor, decrease source reputation
                 GossipError::InvalidData(InvalidDataError::TransactionAnchor)
             }
+            TxIngressError::InvalidTags => {
+                // Tags exceeding the mempool limits, decrease source reputation
+                GossipError::InvalidData(InvalidDataError::TransactionTags)
+            }
             // ==== Internal errors - shouldn't be communicated to outside
             TxIngressError::DatabaseError => GossipError::Internal(InternalGossipError::Database),
             TxIngressError::ServiceUninitialized => {
@@ -71,6 +75,27 @@ impl From<TxIngressError> for GossipError {
     }
 }
 
+impl From<IngressProofError> for GossipError {
+    fn from(value: IngressProofError) -> Self {
+        match value {
+            // ==== External errors
+            IngressProofError::InvalidSignature => {
+                GossipError::InvalidData(InvalidDataError::IngressProofSignature)
+            }
+            IngressProofError::InvalidProof => {
+                GossipError::InvalidData(InvalidDataError::IngressProofInvalid)
+            }
+            // ==== Internal errors - shouldn't be communicated to outside
+            IngressProofError::DatabaseError => {
+                GossipError::Internal(InternalGossipError::Database)
+            }
+            IngressProofError::Other(error) => {
+                GossipError::Internal(InternalGossipError::Unknown(error))
+            }
+        }
+    }
+}
+
 impl GossipError {
     pub fn unknown<T: ToString + ?Sized>(error: &T) -> Self {
         Self::Internal(InternalGossipError::Unknown(error.to_string()))
@@ -85,6 +110,8 @@ pub enum InvalidDataError {
     TransactionAnchor,
     #[error("Transaction unfunded")]
     TransactionUnfunded,
+    #[error("Invalid transaction tags")]
+    TransactionTags,
     #[error("Invalid chunk proof")]
     ChunkInvalidProof,
     #[error("Invalid chunk data hash")]
@@ -95,6 +122,12 @@ pub enum InvalidDataError {
     ChunkInvalidDataSize,
     #[error("Invalid block: {0}")]
     InvalidBlock(String),
+    #[error("Invalid ingress proof signature")]
+    IngressProofSignature,
+    #[error("Invalid ingress proof")]
+    IngressProofInvalid,
+    #[error("Invalid VDF step: {0}")]
+    InvalidVdfStep(String),
 }
 
 #[derive(Debug, Error, Clone)]
@@ -1,27 +1,70 @@
 use crate::{checked_add_i32_u64, get_storage_module_at_offset, StorageModulesReadGuard};
 use base58::ToBase58;
-use eyre::OptionExt;
+use eyre::{ensure, OptionExt};
+use irys_database::{cached_chunk_by_chunk_offset, cached_data_root_by_data_root};
+use irys_packing::unpack;
 use irys_types::{
-    ChunkFormat, Config, DataLedger, DataRoot, LedgerChunkOffset, PackedChunk, TxChunkOffset,
+    hash_sha256, validate_path, ChunkFormat, Config, DataLedger, DataRoot, DatabaseProvider,
+    LedgerChunkOffset, PackedChunk, TxChunkOffset, UnpackedChunk,
 };
+use reth_db::Database as _;
+use std::{fmt::Debug, ops::Range, sync::Arc};
 use tracing::debug;
 
+/// Checks a chunk received from a peer, see [`ChunkProvider::verify_chunk`]
+pub type VerifyChunk<'a> = dyn Fn(ChunkFormat) -> eyre::Result<UnpackedChunk> + Send + Sync + 'a;
+
+/// Fetches chunks this node doesn't store from its peers
+#[async_trait::async_trait]
+pub trait ChunkPeerSource: Debug + Send + Sync {
+    /// Asks peers for the chunk at `data_tx_offset` of `data_root`, returning the first
+    /// response that passes `verify`. Implementations penalize peers whose chunks don't
+    async fn fetch_chunk(
+        &self,
+        ledger: DataLedger,
+        data_root: DataRoot,
+        data_tx_offset: TxChunkOffset,
+        verify: &VerifyChunk<'_>,
+    ) -> eyre::Result<Option<UnpackedChunk>>;
+}
+
 /// Provides chunks to `actix::web` front end (mostly)
 #[derive(Debug, Clone)]
 pub struct ChunkProvider {
     /// Collection of storage modules for distributing chunk data
     pub storage_modules_guard: StorageModulesReadGuard,
     pub config: Config,
+    /// Holds the chunk cache of data that isn't migrated to the storage modules yet
+    pub db: DatabaseProvider,
+    /// Asked for chunks of known data that isn't stored locally, if set
+    peers: Option<Arc<dyn ChunkPeerSource>>,
 }
 
 impl ChunkProvider {
-    pub fn new(config: Config, storage_modules_guard: StorageModulesReadGuard) -> Self {
+    pub fn new(
+        config: Config,
+        storage_modules_guard: StorageModulesReadGuard,
+        db: DatabaseProvider,
+    ) -> Self {
         Self {
             config,
             storage_modules_guard,
+            db,
+            peers: None,
         }
     }
 
+    /// Lets [`Self::get_chunk_or_fetch`] ask `peers` for chunks this node doesn't store
+    pub fn with_peer_source(mut self, peers: Arc<dyn ChunkPeerSource>) -> Self {
+        self.peers = Some(peers);
+        self
+    }
+
+    /// Whether chunks missing locally can be fetched from peers
+    pub fn has_peer_source(&self) -> bool {
+        self.peers.is_some()
+    }
+
     /// Retrieves a chunk from a ledger
     pub fn get_chunk_by_ledger_offset(
         &self,
@@ -35,15 +78,14 @@ impl ChunkProvider {
         module.generate_full_chunk_ledger_offset(ledger_offset)
     }
 
-    /// Retrieves a chunk by [`DataRoot`]
+    /// Retrieves a chunk by [`DataRoot`], from the storage modules assigned to the ledger
+    /// or, for data that isn't migrated yet, from the chunk cache
     pub fn get_chunk_by_data_root(
         &self,
         ledger: DataLedger,
         data_root: DataRoot,
         data_tx_offset: TxChunkOffset,
     ) -> eyre::Result<Option<ChunkFormat>> {
-        // TODO: read from the cache
-
         debug!(
             "getting ledger: {:?}, data_root: {}, offset: {}",
             &ledger,
@@ -78,8 +120,203 @@ impl ChunkProvider {
                 }
             }
         }
+        drop(binding);
 
-        Ok(None)
+        Ok(self
+            .get_cached_chunk(data_root, data_tx_offset)?
+            .map(ChunkFormat::Unpacked))
+    }
+
+    /// Retrieves a chunk from the chunk cache, where ingested chunks wait for their
+    /// data to be migrated to the storage modules
+    pub fn get_cached_chunk(
+        &self,
+        data_root: DataRoot,
+        data_tx_offset: TxChunkOffset,
+    ) -> eyre::Result<Option<UnpackedChunk>> {
+        let tx = self.db.tx()?;
+        let Some((_, cached_chunk)) = cached_chunk_by_chunk_offset(&tx, data_root, data_tx_offset)?
+        else {
+            return Ok(None);
+        };
+        // the chunk bytes may already be migrated, leaving only the data_path
+        let (Some(bytes), Some(cached_data_root)) = (
+            cached_chunk.chunk,
+            cached_data_root_by_data_root(&tx, data_root)?,
+        ) else {
+            return Ok(None);
+        };
+
+        Ok(Some(UnpackedChunk {
+            data_root,
+            data_size: cached_data_root.data_size,
+            data_path: cached_chunk.data_path,
+            bytes,
+            tx_offset: data_tx_offset,
+        }))
+    }
+
+    /// Retrieves a chunk like [`Self::get_chunk_by_data_root`], falling back to asking peers
+    /// for chunks of data this node knows of. `data_size` is the size of the data from a
+    /// local tx header, if the caller has one, otherwise the size cached for `data_root` is
+    /// used. Data roots with no known size aren't fetched
+    pub async fn get_chunk_or_fetch(
+        &self,
+        ledger: DataLedger,
+        data_root: DataRoot,
+        data_size: Option<u64>,
+        data_tx_offset: TxChunkOffset,
+    ) -> eyre::Result<Option<ChunkFormat>> {
+        if let Some(chunk) = self.get_chunk_by_data_root(ledger, data_root, data_tx_offset)? {
+            return Ok(Some(chunk));
+        }
+        let Some(peers) = &self.peers else {
+            return Ok(None);
+        };
+        let data_size = match data_size {
+            Some(data_size) => data_size,
+            None => match self.known_data_size(data_root)? {
+                Some(data_size) => data_size,
+                None => return Ok(None),
+            },
+        };
+
+        let verify = |chunk| self.verify_chunk(chunk, data_root, data_size, data_tx_offset);
+        Ok(peers
+            .fetch_chunk(ledger, data_root, data_tx_offset, &verify)
+            .await?
+            .map(ChunkFormat::Unpacked))
+    }
+
+    /// Size of the data behind `data_root`, if a transaction with it is in the data root cache
+    pub fn known_data_size(&self, data_root: DataRoot) -> eyre::Result<Option<u64>> {
+        let tx = self.db.tx()?;
+        Ok(cached_data_root_by_data_root(&tx, data_root)?.map(|cached| cached.data_size))
+    }
+
+    /// Unpacks a chunk if it's packed
+    pub fn unpack_chunk(&self, chunk: ChunkFormat) -> UnpackedChunk {
+        match chunk {
+            ChunkFormat::Unpacked(unpacked) => unpacked,
+            ChunkFormat::Packed(packed) => unpack(
+                &packed,
+                self.config.consensus.entropy_packing_iterations,
+                self.config.consensus.chunk_size as usize,
+                self.config.consensus.chain_id,
+            ),
+        }
+    }
+
+    /// Checks a chunk obtained elsewhere, e.g. from a peer, belongs to `data_root` at
+    /// `data_tx_offset`, unpacking it if needed. `data_size` must come from local state,
+    /// the size in the chunk itself is only checked against it
+    pub fn verify_chunk(
+        &self,
+        chunk: ChunkFormat,
+        data_root: DataRoot,
+        data_size: u64,
+        data_tx_offset: TxChunkOffset,
+    ) -> eyre::Result<UnpackedChunk> {
+        let chunk_size = self.config.consensus.chunk_size;
+        let chunk = self.unpack_chunk(chunk);
+        ensure!(
+            chunk.data_root == data_root && chunk.tx_offset == data_tx_offset,
+            "chunk is for data_root {} offset {}, expected {} offset {}",
+            chunk.data_root,
+            chunk.tx_offset,
+            data_root,
+            data_tx_offset
+        );
+        ensure!(
+            chunk.data_size == data_size,
+            "chunk has data_size {}, expected {}",
+            chunk.data_size,
+            data_size
+        );
+        ensure!(
+            data_size > 0 && u64::from(*data_tx_offset) < data_size.div_ceil(chunk_size),
+            "chunk offset {} is out of bounds for data of size {}",
+            data_tx_offset,
+            data_size
+        );
+
+        let target_offset = u128::from(chunk.end_byte_offset(chunk_size));
+        let path_result = validate_path(data_root.0, &chunk.data_path, target_offset)
+            .map_err(|e| eyre::eyre!("invalid data_path: {}", e))?;
+        ensure!(
+            path_result.leaf_hash == hash_sha256(&chunk.bytes.0)?,
+            "chunk bytes don't match the data_path leaf hash"
+        );
+        Ok(chunk)
+    }
+
+    /// Reads the bytes in `range` of a transaction's data, unpacking the chunks
+    /// that cover it. Returns `None` if any of those chunks isn't stored locally.
+    pub fn get_tx_data_range(
+        &self,
+        ledger: DataLedger,
+        data_root: DataRoot,
+        data_size: u64,
+        range: Range<u64>,
+    ) -> eyre::Result<Option<Vec<u8>>> {
+        ensure!(
+            range.start <= range.end && range.end <= data_size,
+            "invalid range {:?} for data of size {}",
+            range,
+            data_size
+        );
+        if range.is_empty() {
+            return Ok(Some(Vec::new()));
+        }
+
+        let chunk_size = self.config.consensus.chunk_size;
+        let first_chunk = range.start / chunk_size;
+        let last_chunk = (range.end - 1) / chunk_size;
+
+        let mut data = Vec::with_capacity((range.end - range.start) as usize);
+        for chunk_index in first_chunk..=last_chunk {
+            let bytes = match self.get_chunk_by_data_root(
+                ledger,
+                data_root,
+                TxChunkOffset::from(chunk_index),
+            )? {
+                Some(chunk) => self.unpack_chunk(chunk).bytes.0,
+                None => return Ok(None),
+            };
+
+            // trim the chunk to the part of it that overlaps the requested range
+            let chunk_start = chunk_index * chunk_size;
+            let start = range.start.saturating_sub(chunk_start) as usize;
+            let end = ((range.end - chunk_start).min(chunk_size) as usize).min(bytes.len());
+            ensure!(
+                start <= end,
+                "chunk {} of data_root {} is shorter than expected",
+                chunk_index,
+                data_root
+            );
+            data.extend_from_slice(&bytes[start..end]);
+        }
+
+        Ok(Some(data))
+    }
+
+    /// Returns a reader over a transaction's data that unpacks each chunk once, for reading
+    /// large data front to back without holding all of it in memory
+    pub fn tx_data_reader(
+        &self,
+        ledger: DataLedger,
+        data_root: DataRoot,
+        data_size: u64,
+    ) -> TxDataReader<'_> {
+        TxDataReader {
+            chunk_provider: self,
+            ledger,
+            data_root,
+            data_size,
+            position: 0,
+            chunk: Vec::new(),
+            chunk_position: 0,
+        }
     }
 
     pub fn get_ledger_offsets_for_data_root(
@@ -126,6 +363,74 @@ impl ChunkProvider {
     }
 }
 
+/// Reads a transaction's data front to back, see [`ChunkProvider::tx_data_reader`]
+#[derive(Debug)]
+pub struct TxDataReader<'a> {
+    chunk_provider: &'a ChunkProvider,
+    ledger: DataLedger,
+    data_root: DataRoot,
+    data_size: u64,
+    /// Offset in the data of the next byte read
+    position: u64,
+    /// Unpacked bytes of the chunk holding `position`, empty once they're all read
+    chunk: Vec<u8>,
+    /// Offset in `chunk` of the next byte read
+    chunk_position: usize,
+}
+
+impl TxDataReader<'_> {
+    /// Offset in the data of the next byte read
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Reads the next `len` bytes of the data. Returns `None` if any of the chunks covering
+    /// them isn't stored locally.
+    pub fn read(&mut self, len: u64) -> eyre::Result<Option<Vec<u8>>> {
+        ensure!(
+            self.position
+                .checked_add(len)
+                .is_some_and(|end| end <= self.data_size),
+            "can't read {} bytes at offset {} of data of size {}",
+            len,
+            self.position,
+            self.data_size
+        );
+
+        let chunk_size = self.chunk_provider.config.consensus.chunk_size;
+        let mut out = Vec::with_capacity(len as usize);
+        while (out.len() as u64) < len {
+            if self.chunk_position == self.chunk.len() {
+                let chunk_index = self.position / chunk_size;
+                let Some(chunk) = self.chunk_provider.get_chunk_by_data_root(
+                    self.ledger,
+                    self.data_root,
+                    TxChunkOffset::from(chunk_index),
+                )?
+                else {
+                    return Ok(None);
+                };
+                self.chunk = self.chunk_provider.unpack_chunk(chunk).bytes.0;
+                self.chunk_position = (self.position - chunk_index * chunk_size) as usize;
+                ensure!(
+                    self.chunk_position < self.chunk.len(),
+                    "chunk {} of data_root {} is shorter than expected",
+                    chunk_index,
+                    self.data_root
+                );
+            }
+
+            let remaining = (len - out.len() as u64) as usize;
+            let available = &self.chunk[self.chunk_position..];
+            let read = available.len().min(remaining);
+            out.extend_from_slice(&available[..read]);
+            self.chunk_position += read;
+            self.position += read as u64;
+        }
+        Ok(Some(out))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::{Arc, RwLock};
@@ -133,6 +438,10 @@ mod tests {
     use crate::{StorageModule, StorageModuleInfo};
 
     use super::*;
+    use irys_database::{
+        cache_chunk, cache_data_root, db::IrysDatabaseExt as _, open_or_create_db,
+        tables::IrysTables,
+    };
     use irys_packing::unpack_with_entropy;
     use irys_testing_utils::utils::setup_tracing_and_temp_dir;
     use irys_types::{
@@ -223,7 +532,9 @@ mod tests {
         let storage_modules_guard =
             StorageModulesReadGuard::new(Arc::new(RwLock::new(vec![Arc::new(storage_module)])));
 
-        let chunk_provider = ChunkProvider::new(config.clone(), storage_modules_guard);
+        let db = open_or_create_db(base_path.join("irys_db"), IrysTables::ALL, None)?;
+        let db = DatabaseProvider(Arc::new(db));
+        let chunk_provider = ChunkProvider::new(config.clone(), storage_modules_guard, db.clone());
 
         for original_chunk in unpacked_chunks {
             let chunk = chunk_provider
@@ -244,8 +555,74 @@ mod tests {
                 tx_offset: packed_chunk.tx_offset,
             };
             assert_eq!(original_chunk, unpacked_chunk);
+
+            // chunks from elsewhere are checked against the data_root and the known data size
+            let data_size = original_chunk.data_size;
+            let verified = chunk_provider.verify_chunk(
+                ChunkFormat::Packed(packed_chunk.clone()),
+                data_root,
+                data_size,
+                original_chunk.tx_offset,
+            )?;
+            assert_eq!(verified, original_chunk);
+            let mut tampered = original_chunk.clone();
+            tampered.bytes.0[0] ^= 1;
+            assert!(chunk_provider
+                .verify_chunk(
+                    ChunkFormat::Unpacked(tampered),
+                    data_root,
+                    data_size,
+                    original_chunk.tx_offset
+                )
+                .is_err());
+            let mut resized = original_chunk.clone();
+            resized.data_size += config.consensus.chunk_size;
+            assert!(chunk_provider
+                .verify_chunk(
+                    ChunkFormat::Unpacked(resized),
+                    data_root,
+                    data_size,
+                    original_chunk.tx_offset
+                )
+                .is_err());
         }
 
+        // the data is read front to back in pieces spanning chunk boundaries
+        let mut reader =
+            chunk_provider.tx_data_reader(DataLedger::Publish, data_root, data_size as u64);
+        let mut read = Vec::new();
+        for len in [5, 40, data_size as u64 - 45] {
+            read.extend(reader.read(len)?.unwrap());
+        }
+        assert_eq!(read, data_bytes);
+        assert_eq!(reader.position(), data_size as u64);
+        assert!(reader.read(1).is_err());
+
+        // data that isn't migrated yet is read from the chunk cache
+        let cached_tx = irys
+            .create_transaction(vec![7_u8; config.consensus.chunk_size as usize], None)
+            .unwrap();
+        let cached_chunk = UnpackedChunk {
+            data_root: cached_tx.header.data_root,
+            data_size: cached_tx.header.data_size,
+            data_path: Base64(cached_tx.proofs[0].proof.clone()),
+            bytes: Base64(vec![7_u8; config.consensus.chunk_size as usize]),
+            tx_offset: TxChunkOffset::from(0),
+        };
+        db.update_eyre(|tx| {
+            cache_data_root(tx, &cached_tx.header)?;
+            cache_chunk(tx, &cached_chunk)?;
+            Ok(())
+        })?;
+        assert_eq!(
+            chunk_provider.get_chunk_by_data_root(
+                DataLedger::Publish,
+                cached_chunk.data_root,
+                cached_chunk.tx_offset
+            )?,
+            Some(ChunkFormat::Unpacked(cached_chunk))
+        );
+
         Ok(())
     }
 }
@@ -55,10 +55,10 @@ use irys_types::{
     app_state::DatabaseProvider,
     get_leaf_proof, ledger_chunk_offset_ie,
     partition::{PartitionAssignment, PartitionHash},
-    partition_chunk_offset_ii, Address, Base64, ChunkBytes, ChunkDataPath, ChunkPathHash, Config,
-    DataLedger, DataRoot, LedgerChunkOffset, LedgerChunkRange, PackedChunk, PartitionChunkOffset,
-    PartitionChunkRange, ProofDeserialize, RelativeChunkOffset, TxChunkOffset, TxPath,
-    UnpackedChunk, H256,
+    partition_chunk_offset_ii, Address, Base64, ChunkBytes, ChunkDataPath, ChunkPathHash,
+    ChunkTypeCounts, Config, DataLedger, DataRoot, LedgerChunkOffset, LedgerChunkRange,
+    PackedChunk, PartitionChunkOffset, PartitionChunkRange, ProofDeserialize, RelativeChunkOffset,
+    TxChunkOffset, TxPath, UnpackedChunk, H256,
 };
 use nodit::{interval::ii, InclusiveInterval, Interval, NoditMap, NoditSet};
 use openssl::sha;
@@ -729,6 +729,34 @@ impl StorageModule {
         set.into_iter().collect::<Vec<_>>()
     }
 
+    /// Counts the chunks in each [`ChunkType`], including writes still pending a sync
+    pub fn chunk_type_counts(&self) -> ChunkTypeCounts {
+        let count = |chunk_type| -> u64 {
+            self.get_intervals(chunk_type)
+                .iter()
+                .map(|interval| u64::from(*interval.end() - *interval.start()) + 1)
+                .sum()
+        };
+        ChunkTypeCounts {
+            entropy: count(ChunkType::Entropy),
+            data: count(ChunkType::Data),
+            uninitialized: count(ChunkType::Uninitialized),
+        }
+    }
+
+    /// Returns the chunk interval and the on-disk directory of each submodule
+    pub fn submodule_paths(&self) -> Vec<(Interval<PartitionChunkOffset>, PathBuf)> {
+        self.submodules
+            .iter()
+            .map(|(interval, submodule)| {
+                (
+                    *interval,
+                    self.config.node_config.base_directory.join(&submodule.path),
+                )
+            })
+            .collect()
+    }
+
     /// Queues chunk data for later disk write. Chunks are batched for efficiency
     /// and written during periodic sync operations.
     pub fn write_chunk(
@@ -1318,6 +1346,18 @@ pub fn get_storage_module_at_offset(
         .cloned()
 }
 
+/// Returns the available and total space in bytes of the disk that holds `path`
+pub fn disk_space_for_path(path: &Path) -> Option<(u64, u64)> {
+    let path = path.canonicalize().ok()?;
+    let disks = sysinfo::Disks::new_with_refreshed_list();
+    disks
+        .list()
+        .iter()
+        .filter(|disk| path.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())
+        .map(|disk| (disk.available_space(), disk.total_space()))
+}
+
 pub const fn checked_add_i32_u64(a: i32, b: u64) -> Option<u64> {
     if a < 0 {
         // If a is negative, check if its absolute value is less than b
@@ -1866,4 +1906,77 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn chunk_type_counts_test() -> eyre::Result<()> {
+        let infos = vec![StorageModuleInfo {
+            id: 0,
+            partition_assignment: None,
+            submodules: vec![
+                (partition_chunk_offset_ii!(0, 4), "hdd0".into()),
+                (partition_chunk_offset_ii!(5, 9), "hdd1".into()),
+            ],
+        }];
+
+        let tmp_dir = setup_tracing_and_temp_dir(Some("chunk_type_counts_test"), false);
+        let base_path = tmp_dir.path().to_path_buf();
+        let node_config = NodeConfig {
+            consensus: irys_types::ConsensusOptions::Custom(ConsensusConfig {
+                chunk_size: 32,
+                num_chunks_in_partition: 10,
+                ..ConsensusConfig::testnet()
+            }),
+            base_directory: base_path.clone(),
+            ..NodeConfig::testnet()
+        };
+        let config = Config::new(node_config);
+        let storage_module = StorageModule::new(&infos[0], &config)?;
+
+        let counts = storage_module.chunk_type_counts();
+        assert_eq!(
+            counts,
+            ChunkTypeCounts {
+                entropy: 0,
+                data: 0,
+                uninitialized: 10
+            }
+        );
+
+        // entropy spanning the submodule boundary, plus one (still pending) data chunk
+        for offset in 3..=6 {
+            storage_module.write_chunk(
+                PartitionChunkOffset::from(offset),
+                vec![0xff; 32],
+                ChunkType::Entropy,
+            );
+        }
+        let _ = storage_module.sync_pending_chunks();
+        storage_module.write_chunk(
+            PartitionChunkOffset::from(9),
+            vec![0x1; 32],
+            ChunkType::Data,
+        );
+
+        let counts = storage_module.chunk_type_counts();
+        assert_eq!(
+            counts,
+            ChunkTypeCounts {
+                entropy: 4,
+                data: 1,
+                uninitialized: 5
+            }
+        );
+
+        // submodule paths are reported relative to the node's base directory
+        let paths = storage_module.submodule_paths();
+        assert_eq!(
+            paths,
+            vec![
+                (partition_chunk_offset_ii!(0, 4), base_path.join("hdd0")),
+                (partition_chunk_offset_ii!(5, 9), base_path.join("hdd1")),
+            ]
+        );
+
+        Ok(())
+    }
 }
@@ -199,6 +199,11 @@ impl IrysBlockHeader {
             .validate_signature(self.signature_hash(), self.miner_address)
     }
 
+    /// Checks the block hash is the hash of the block signature
+    pub fn is_block_hash_valid(&self) -> bool {
+        self.block_hash.0 == keccak256(self.signature.as_bytes()).0
+    }
+
     // treat any block whose height is a multiple of blocks_in_price_adjustment_interval
     pub fn is_ema_recalculation_block(&self, blocks_in_price_adjustment_interval: u64) -> bool {
         is_ema_recalculation_block(self.height, blocks_in_price_adjustment_interval)
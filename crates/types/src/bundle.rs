@@ -0,0 +1,414 @@
+//! Bundles pack many independently signed data items into the data of a single
+//! Irys transaction, so that each item can be addressed by its own id without
+//! paying for (and waiting on) one transaction per item.
+//!
+//! A transaction carries a bundle when its `bundle_format` is set to
+//! [`BUNDLE_FORMAT_V1`]. The transaction data is then laid out as follows, with
+//! all integers encoded little endian:
+//!
+//! ```text
+//! bundle := item_count: u32 | entry * item_count | item * item_count
+//! entry  := item_size: u64 | item_id: [u8; 32]
+//! item   := signature: [u8; 65] | signer: [u8; 20] | anchor: [u8; 32]
+//!           | tag_count: u16 | tag * tag_count | data
+//! tag    := name_len: u16 | name | value_len: u16 | value
+//! ```
+//!
+//! An item id is the keccak256 hash of its signature, the same way transaction
+//! ids are derived. The signature covers every field of the item after the
+//! signature itself.
+use crate::{serialization::string_u64, Address, Base64, IrysSignature, Signature, Tag, H256};
+use alloy_primitives::keccak256;
+use eyre::{ensure, eyre, OptionExt as _};
+use serde::{Deserialize, Serialize};
+
+/// `bundle_format` value for the bundle layout described in this module
+pub const BUNDLE_FORMAT_V1: u64 = 1;
+
+const SIGNATURE_BYTES: usize = 65;
+const ADDRESS_BYTES: usize = 20;
+const ID_BYTES: usize = 32;
+const ENTRY_BYTES: usize = 8 + ID_BYTES;
+
+/// A signed data item stored inside a bundle
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataItem {
+    /// keccak256 hash of the item's signature
+    pub id: H256,
+    /// The address that signed the item
+    pub signer: Address,
+    /// An application defined anchor, signed along with the rest of the item
+    pub anchor: H256,
+    pub tags: Vec<Tag>,
+    pub signature: IrysSignature,
+    pub data: Base64,
+}
+
+impl DataItem {
+    /// Creates an unsigned data item, the id and signer are set by
+    /// [`crate::irys::IrysSigner::sign_data_item`]
+    pub fn new(data: Vec<u8>, anchor: H256, tags: Vec<Tag>) -> Self {
+        Self {
+            anchor,
+            tags,
+            data: Base64(data),
+            ..Default::default()
+        }
+    }
+
+    /// Encodes every field of the item covered by its signature
+    pub fn encode_for_signing(&self, out: &mut Vec<u8>) -> eyre::Result<()> {
+        out.extend_from_slice(self.signer.as_slice());
+        out.extend_from_slice(&self.anchor.0);
+        let tag_count: u16 = self
+            .tags
+            .len()
+            .try_into()
+            .map_err(|_| eyre!("too many tags: {}", self.tags.len()))?;
+        out.extend_from_slice(&tag_count.to_le_bytes());
+        for tag in &self.tags {
+            write_tag_field(out, &tag.name)?;
+            write_tag_field(out, &tag.value)?;
+        }
+        out.extend_from_slice(&self.data.0);
+        Ok(())
+    }
+
+    pub fn signature_hash(&self) -> eyre::Result<[u8; 32]> {
+        let mut bytes = Vec::new();
+        self.encode_for_signing(&mut bytes)?;
+        Ok(keccak256(&bytes).0)
+    }
+
+    /// Validates that the item was signed by `signer` and that its id matches the signature
+    pub fn is_signature_valid(&self) -> bool {
+        let Ok(prehash) = self.signature_hash() else {
+            return false;
+        };
+        self.id.0 == keccak256(self.signature.as_bytes()).0
+            && self.signature.validate_signature(prehash, self.signer)
+    }
+
+    /// Encodes the item into its binary bundle representation
+    pub fn encode(&self) -> eyre::Result<Vec<u8>> {
+        let mut out = Vec::with_capacity(SIGNATURE_BYTES + self.data.0.len());
+        out.extend_from_slice(&self.signature.as_bytes());
+        self.encode_for_signing(&mut out)?;
+        Ok(out)
+    }
+
+    /// Decodes an item from its binary bundle representation, deriving the id from the signature
+    /// note: this does not validate the signature, see [`DataItem::is_signature_valid`]
+    pub fn decode(bytes: &[u8]) -> eyre::Result<Self> {
+        let mut reader = Reader(bytes);
+        let signature_bytes = reader.take(SIGNATURE_BYTES)?;
+        let signature = Signature::try_from(signature_bytes)
+            .map_err(|e| eyre!("invalid data item signature: {}", e))?;
+        let signer = Address::from_slice(reader.take(ADDRESS_BYTES)?);
+        let anchor = H256::from_slice(reader.take(ID_BYTES)?);
+        let tag_count = reader.read_u16()?;
+        let tags = (0..tag_count)
+            .map(|_| {
+                Ok(Tag {
+                    name: reader.read_tag_field()?,
+                    value: reader.read_tag_field()?,
+                })
+            })
+            .collect::<eyre::Result<Vec<_>>>()?;
+
+        Ok(Self {
+            id: H256(keccak256(signature_bytes).0),
+            signer,
+            anchor,
+            tags,
+            signature: IrysSignature::new(signature),
+            data: Base64(reader.0.to_vec()),
+        })
+    }
+
+    /// Decodes the item at `entry` of a bundle header from its bytes, checking that it
+    /// is validly signed and matches the id in the header
+    pub fn decode_entry(bytes: &[u8], entry: &BundleIndexEntry) -> eyre::Result<Self> {
+        let item = Self::decode(bytes)?;
+        ensure!(
+            item.id == entry.id,
+            "bundle item id {} does not match the header id {}",
+            item.id,
+            entry.id
+        );
+        ensure!(
+            item.is_signature_valid(),
+            "bundle item {} has an invalid signature",
+            item.id
+        );
+        Ok(item)
+    }
+}
+
+/// Metadata of a data item from an indexed bundle, as returned by the
+/// `/bundle/item/{item_id}` API route
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataItemInfo {
+    pub id: H256,
+    /// Id of the transaction whose data contains the bundle
+    pub bundle_tx_id: H256,
+    pub signer: Address,
+    pub anchor: H256,
+    pub tags: Vec<Tag>,
+    pub signature: IrysSignature,
+    /// Size in bytes of the item data
+    #[serde(with = "string_u64")]
+    pub data_size: u64,
+}
+
+impl DataItemInfo {
+    pub fn new(item: &DataItem, bundle_tx_id: H256) -> Self {
+        Self {
+            id: item.id,
+            bundle_tx_id,
+            signer: item.signer,
+            anchor: item.anchor,
+            tags: item.tags.clone(),
+            signature: item.signature,
+            data_size: item.data.0.len() as u64,
+        }
+    }
+}
+
+/// Location of an item in the data of its bundle transaction, as listed in the bundle header
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BundleIndexEntry {
+    pub id: H256,
+    /// Byte offset of the item relative to the start of the bundle
+    pub offset: u64,
+    /// Size in bytes of the encoded item
+    pub size: u64,
+}
+
+/// A collection of data items, see the module docs for the binary layout
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Bundle {
+    pub items: Vec<DataItem>,
+}
+
+impl Bundle {
+    pub fn new(items: Vec<DataItem>) -> Self {
+        Self { items }
+    }
+
+    /// Encodes the bundle, the result is used as the data of a transaction with
+    /// `bundle_format` set to [`BUNDLE_FORMAT_V1`]
+    pub fn encode(&self) -> eyre::Result<Vec<u8>> {
+        let item_count: u32 = self
+            .items
+            .len()
+            .try_into()
+            .map_err(|_| eyre!("too many items: {}", self.items.len()))?;
+        let encoded_items = self
+            .items
+            .iter()
+            .map(DataItem::encode)
+            .collect::<eyre::Result<Vec<_>>>()?;
+
+        let mut out = Vec::with_capacity(
+            4 + ENTRY_BYTES * encoded_items.len()
+                + encoded_items.iter().map(Vec::len).sum::<usize>(),
+        );
+        out.extend_from_slice(&item_count.to_le_bytes());
+        for (item, encoded) in self.items.iter().zip(&encoded_items) {
+            out.extend_from_slice(&(encoded.len() as u64).to_le_bytes());
+            out.extend_from_slice(&item.id.0);
+        }
+        for encoded in encoded_items {
+            out.extend_from_slice(&encoded);
+        }
+        Ok(out)
+    }
+
+    /// Returns the size in bytes of a bundle's header, given at least its first 4 bytes
+    pub fn header_size(prefix: &[u8]) -> eyre::Result<u64> {
+        let item_count = Reader(prefix).read_u32()?;
+        (item_count as u64)
+            .checked_mul(ENTRY_BYTES as u64)
+            .and_then(|size| size.checked_add(4))
+            .ok_or_eyre("bundle header size overflow")
+    }
+
+    /// Parses the header of a bundle of `bundle_size` bytes, returning the location of every
+    /// item. `header` must hold at least the entire header, see [`Bundle::header_size`], which
+    /// lets large bundles be read one item at a time.
+    pub fn decode_header(header: &[u8], bundle_size: u64) -> eyre::Result<Vec<BundleIndexEntry>> {
+        let header_size = Self::header_size(header)?;
+        ensure!(
+            header_size <= bundle_size,
+            "bundle header ({} bytes) exceeds the bundle size ({} bytes)",
+            header_size,
+            bundle_size
+        );
+
+        let mut reader = Reader(header);
+        let item_count = reader.read_u32()? as usize;
+        let mut offset = header_size;
+        let mut entries = Vec::with_capacity(item_count.min(header.len() / ENTRY_BYTES));
+        for _ in 0..item_count {
+            let size = reader.read_u64()?;
+            let id = H256::from_slice(reader.take(ID_BYTES)?);
+            entries.push(BundleIndexEntry { id, offset, size });
+            offset = offset
+                .checked_add(size)
+                .ok_or_eyre("bundle item size overflow")?;
+        }
+        ensure!(
+            offset == bundle_size,
+            "bundle items end at byte {} but the bundle is {} bytes",
+            offset,
+            bundle_size
+        );
+        Ok(entries)
+    }
+
+    /// Parses the bundle header, returning the location of every item in `bytes`.
+    /// `bytes` must hold the entire bundle, as the item sizes are checked against its length.
+    pub fn decode_index(bytes: &[u8]) -> eyre::Result<Vec<BundleIndexEntry>> {
+        Self::decode_header(bytes, bytes.len() as u64)
+    }
+
+    /// Decodes the bundle, checking that every item is validly signed and matches the id in the header
+    pub fn decode(bytes: &[u8]) -> eyre::Result<Self> {
+        let items = Self::decode_index(bytes)?
+            .iter()
+            .map(|entry| {
+                let start = entry.offset as usize;
+                DataItem::decode_entry(&bytes[start..start + entry.size as usize], entry)
+            })
+            .collect::<eyre::Result<Vec<_>>>()?;
+        Ok(Self { items })
+    }
+}
+
+fn write_tag_field(out: &mut Vec<u8>, field: &str) -> eyre::Result<()> {
+    let len: u16 = field
+        .len()
+        .try_into()
+        .map_err(|_| eyre!("tag field too long: {} bytes", field.len()))?;
+    out.extend_from_slice(&len.to_le_bytes());
+    out.extend_from_slice(field.as_bytes());
+    Ok(())
+}
+
+/// Minimal cursor over a byte slice, erroring instead of panicking on truncated input
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> eyre::Result<&'a [u8]> {
+        ensure!(
+            self.0.len() >= len,
+            "unexpected end of bundle data, needed {} bytes but only {} remain",
+            len,
+            self.0.len()
+        );
+        let (head, tail) = self.0.split_at(len);
+        self.0 = tail;
+        Ok(head)
+    }
+
+    fn read_u16(&mut self) -> eyre::Result<u16> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into()?))
+    }
+
+    fn read_u32(&mut self) -> eyre::Result<u32> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into()?))
+    }
+
+    fn read_u64(&mut self) -> eyre::Result<u64> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into()?))
+    }
+
+    fn read_tag_field(&mut self) -> eyre::Result<String> {
+        let len = self.read_u16()? as usize;
+        Ok(String::from_utf8(self.take(len)?.to_vec())?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{irys::IrysSigner, ConsensusConfig};
+
+    fn signed_items(signer: &IrysSigner) -> eyre::Result<Vec<DataItem>> {
+        (0..3_u8)
+            .map(|i| {
+                let item = DataItem::new(
+                    vec![i; 100 * (i as usize + 1)],
+                    H256::from([i; 32]),
+                    vec![
+                        Tag::new("Content-Type", "text/plain"),
+                        Tag::new("index", i.to_string()),
+                    ],
+                );
+                signer.sign_data_item(item)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn bundle_roundtrip() -> eyre::Result<()> {
+        let signer = IrysSigner::random_signer(&ConsensusConfig::testnet());
+        let bundle = Bundle::new(signed_items(&signer)?);
+        assert!(bundle.items.iter().all(DataItem::is_signature_valid));
+
+        let bytes = bundle.encode()?;
+        let index = Bundle::decode_index(&bytes)?;
+        assert_eq!(index.len(), 3);
+        for (entry, item) in index.iter().zip(&bundle.items) {
+            assert_eq!(entry.id, item.id);
+            let start = entry.offset as usize;
+            let decoded = DataItem::decode(&bytes[start..start + entry.size as usize])?;
+            assert_eq!(&decoded, item);
+        }
+
+        assert_eq!(Bundle::decode(&bytes)?, bundle);
+        Ok(())
+    }
+
+    #[test]
+    fn bundle_decodes_item_by_item() -> eyre::Result<()> {
+        let signer = IrysSigner::random_signer(&ConsensusConfig::testnet());
+        let bundle = Bundle::new(signed_items(&signer)?);
+        let bytes = bundle.encode()?;
+
+        let header_size = Bundle::header_size(&bytes[..4])? as usize;
+        assert_eq!(header_size, 4 + 3 * ENTRY_BYTES);
+        let index = Bundle::decode_header(&bytes[..header_size], bytes.len() as u64)?;
+        assert_eq!(index, Bundle::decode_index(&bytes)?);
+        for (entry, item) in index.iter().zip(&bundle.items) {
+            let start = entry.offset as usize;
+            let bytes = &bytes[start..start + entry.size as usize];
+            assert_eq!(&DataItem::decode_entry(bytes, entry)?, item);
+        }
+
+        // the header alone doesn't tell the bundle size, it has to match the item sizes
+        assert!(Bundle::decode_header(&bytes[..header_size], bytes.len() as u64 + 1).is_err());
+        assert!(Bundle::decode_header(&bytes[..header_size - 1], bytes.len() as u64).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn bundle_rejects_tampered_items() -> eyre::Result<()> {
+        let signer = IrysSigner::random_signer(&ConsensusConfig::testnet());
+        let bundle = Bundle::new(signed_items(&signer)?);
+        let bytes = bundle.encode()?;
+
+        // flip a byte of the last item's data
+        let mut tampered = bytes.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(Bundle::decode_index(&tampered).is_ok());
+        assert!(Bundle::decode(&tampered).is_err());
+
+        // truncated bundles are rejected when reading the index
+        assert!(Bundle::decode_index(&bytes[..bytes.len() - 1]).is_err());
+        Ok(())
+    }
+}
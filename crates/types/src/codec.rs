@@ -0,0 +1,439 @@
+//! Binary encoding of chunks and block headers, negotiated on the chunk, block and gossip routes.
+//!
+//! JSON encodes chunk bytes and proofs as base64url, which inflates a chunk by about a third and
+//! costs CPU on both ends. Requests with a `Content-Type: application/octet-stream` body, and
+//! requests sending `Accept: application/octet-stream`, use the encoding below instead. JSON
+//! remains the default and the fallback.
+//!
+//! Every value is encoded as a single RLP item, with byte fields as RLP strings. The input comes
+//! from untrusted peers, alloy-rlp checks every length against the remaining input before
+//! reading or allocating.
+use crate::{
+    Base64, ChunkFormat, CombinedBlockHeader, ExecutionHeader, GossipRequest, IrysBlockHeader,
+    PackedChunk, PartitionChunkOffset, TxChunkOffset, UnpackedChunk,
+};
+use alloy_rlp::{Decodable, Encodable, Header};
+use eyre::{ensure, eyre};
+use serde::de::DeserializeOwned;
+
+/// Media type of the binary encoding
+pub const BINARY_CONTENT_TYPE: &str = "application/octet-stream";
+/// Media type of the JSON encoding
+pub const JSON_CONTENT_TYPE: &str = "application/json";
+
+/// A type with a compact binary encoding, used instead of JSON when both ends support it
+pub trait BinaryEncoding: Sized {
+    /// Appends the encoding of `self`, a single RLP item
+    fn encode_binary(&self, buf: &mut Vec<u8>);
+
+    /// Decodes the RLP item at the start of `buf` and advances `buf` past it
+    fn decode_binary_item(buf: &mut &[u8]) -> alloy_rlp::Result<Self>;
+
+    /// Decodes a value from the whole of `buf`, which may come from an untrusted peer
+    fn decode_binary(buf: &[u8]) -> eyre::Result<Self> {
+        let mut rest = buf;
+        let value =
+            Self::decode_binary_item(&mut rest).map_err(|error| eyre!("invalid RLP: {}", error))?;
+        ensure!(
+            rest.is_empty(),
+            "{} trailing bytes after the binary encoding",
+            rest.len()
+        );
+        Ok(value)
+    }
+
+    fn to_binary(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        self.encode_binary(&mut buf);
+        buf
+    }
+}
+
+/// Writes an RLP list of the items written by `encode_items`
+fn encode_list(buf: &mut Vec<u8>, encode_items: impl FnOnce(&mut Vec<u8>)) {
+    let mut items = Vec::new();
+    encode_items(&mut items);
+    Header {
+        list: true,
+        payload_length: items.len(),
+    }
+    .encode(buf);
+    buf.extend_from_slice(&items);
+}
+
+/// Reads an RLP list with `decode_items`, which has to read all of its items
+fn decode_list<T>(
+    buf: &mut &[u8],
+    decode_items: impl FnOnce(&mut &[u8]) -> alloy_rlp::Result<T>,
+) -> alloy_rlp::Result<T> {
+    let mut items = Header::decode_bytes(buf, true)?;
+    let value = decode_items(&mut items)?;
+    if !items.is_empty() {
+        return Err(alloy_rlp::Error::Custom(
+            "unexpected items at the end of the list",
+        ));
+    }
+    Ok(value)
+}
+
+/// Writes bytes as an RLP string, the derived RLP encoding of [`Base64`] is a list of integers
+fn encode_bytes(buf: &mut Vec<u8>, bytes: &Base64) {
+    bytes.0.as_slice().encode(buf);
+}
+
+fn decode_bytes(buf: &mut &[u8]) -> alloy_rlp::Result<Base64> {
+    Ok(Base64(Header::decode_bytes(buf, false)?.to_vec()))
+}
+
+/// Writes optional bytes as a list holding no or one RLP string
+fn encode_optional_bytes(buf: &mut Vec<u8>, bytes: Option<&Base64>) {
+    encode_list(buf, |items| {
+        if let Some(bytes) = bytes {
+            encode_bytes(items, bytes);
+        }
+    });
+}
+
+fn decode_optional_bytes(buf: &mut &[u8]) -> alloy_rlp::Result<Option<Base64>> {
+    decode_list(buf, |items| {
+        if items.is_empty() {
+            Ok(None)
+        } else {
+            decode_bytes(items).map(Some)
+        }
+    })
+}
+
+/// A list of the data root, data size, data path, bytes and tx offset
+impl BinaryEncoding for UnpackedChunk {
+    fn encode_binary(&self, buf: &mut Vec<u8>) {
+        encode_list(buf, |items| {
+            self.data_root.encode(items);
+            self.data_size.encode(items);
+            encode_bytes(items, &self.data_path);
+            encode_bytes(items, &self.bytes);
+            self.tx_offset.0.encode(items);
+        });
+    }
+
+    fn decode_binary_item(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        decode_list(buf, |items| {
+            Ok(Self {
+                data_root: Decodable::decode(items)?,
+                data_size: Decodable::decode(items)?,
+                data_path: decode_bytes(items)?,
+                bytes: decode_bytes(items)?,
+                tx_offset: TxChunkOffset(Decodable::decode(items)?),
+            })
+        })
+    }
+}
+
+/// A list of the fields of [`UnpackedChunk`], then the packing address, partition offset and
+/// partition hash
+impl BinaryEncoding for PackedChunk {
+    fn encode_binary(&self, buf: &mut Vec<u8>) {
+        encode_list(buf, |items| {
+            self.data_root.encode(items);
+            self.data_size.encode(items);
+            encode_bytes(items, &self.data_path);
+            encode_bytes(items, &self.bytes);
+            self.tx_offset.0.encode(items);
+            self.packing_address.encode(items);
+            u32::from(self.partition_offset).encode(items);
+            self.partition_hash.encode(items);
+        });
+    }
+
+    fn decode_binary_item(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        decode_list(buf, |items| {
+            Ok(Self {
+                data_root: Decodable::decode(items)?,
+                data_size: Decodable::decode(items)?,
+                data_path: decode_bytes(items)?,
+                bytes: decode_bytes(items)?,
+                tx_offset: TxChunkOffset(Decodable::decode(items)?),
+                packing_address: Decodable::decode(items)?,
+                partition_offset: PartitionChunkOffset::from(u32::decode(items)?),
+                partition_hash: Decodable::decode(items)?,
+            })
+        })
+    }
+}
+
+/// A list of the PoA chunk, data path and tx path, the block hash, the signature and the rest of
+/// the header. The derived RLP encoding of the header writes bytes as lists of integers, so the
+/// large PoA fields are taken out of it
+impl BinaryEncoding for IrysBlockHeader {
+    fn encode_binary(&self, buf: &mut Vec<u8>) {
+        encode_list(buf, |items| {
+            encode_optional_bytes(items, self.poa.chunk.as_ref());
+            encode_optional_bytes(items, self.poa.data_path.as_ref());
+            encode_optional_bytes(items, self.poa.tx_path.as_ref());
+            self.block_hash.encode(items);
+            self.signature.encode(items);
+            let mut header = self.clone();
+            header.poa.chunk = None;
+            header.poa.data_path = None;
+            header.poa.tx_path = None;
+            header.encode(items);
+        });
+    }
+
+    fn decode_binary_item(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        decode_list(buf, |items| {
+            let chunk = decode_optional_bytes(items)?;
+            let data_path = decode_optional_bytes(items)?;
+            let tx_path = decode_optional_bytes(items)?;
+            let block_hash = Decodable::decode(items)?;
+            let signature = Decodable::decode(items)?;
+            let mut header: Self = Decodable::decode(items)?;
+            header.block_hash = block_hash;
+            header.signature = signature;
+            header.poa.chunk = chunk;
+            header.poa.data_path = data_path;
+            header.poa.tx_path = tx_path;
+            Ok(header)
+        })
+    }
+}
+
+/// A list of the binary encoding of the Irys header, the execution header and its transaction
+/// hashes
+impl BinaryEncoding for CombinedBlockHeader {
+    fn encode_binary(&self, buf: &mut Vec<u8>) {
+        encode_list(buf, |items| {
+            self.irys.encode_binary(items);
+            self.execution.header.encode(items);
+            self.execution.transactions.encode(items);
+        });
+    }
+
+    fn decode_binary_item(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        decode_list(buf, |items| {
+            Ok(Self {
+                irys: IrysBlockHeader::decode_binary_item(items)?,
+                execution: ExecutionHeader {
+                    header: Decodable::decode(items)?,
+                    transactions: Decodable::decode(items)?,
+                },
+            })
+        })
+    }
+}
+
+const UNPACKED_CHUNK_TAG: u8 = 0;
+const PACKED_CHUNK_TAG: u8 = 1;
+
+/// A list of a tag and the encoding of the chunk
+impl BinaryEncoding for ChunkFormat {
+    fn encode_binary(&self, buf: &mut Vec<u8>) {
+        encode_list(buf, |items| match self {
+            Self::Unpacked(chunk) => {
+                UNPACKED_CHUNK_TAG.encode(items);
+                chunk.encode_binary(items);
+            }
+            Self::Packed(chunk) => {
+                PACKED_CHUNK_TAG.encode(items);
+                chunk.encode_binary(items);
+            }
+        });
+    }
+
+    fn decode_binary_item(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        decode_list(buf, |items| match u8::decode(items)? {
+            UNPACKED_CHUNK_TAG => Ok(Self::Unpacked(UnpackedChunk::decode_binary_item(items)?)),
+            PACKED_CHUNK_TAG => Ok(Self::Packed(PackedChunk::decode_binary_item(items)?)),
+            _ => Err(alloy_rlp::Error::Custom("unknown chunk format tag")),
+        })
+    }
+}
+
+/// A list of the miner address and the encoding of the data
+impl<T: BinaryEncoding> BinaryEncoding for GossipRequest<T> {
+    fn encode_binary(&self, buf: &mut Vec<u8>) {
+        encode_list(buf, |items| {
+            self.miner_address.encode(items);
+            self.data.encode_binary(items);
+        });
+    }
+
+    fn decode_binary_item(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        decode_list(buf, |items| {
+            Ok(Self {
+                miner_address: Decodable::decode(items)?,
+                data: T::decode_binary_item(items)?,
+            })
+        })
+    }
+}
+
+/// Whether a `Content-Type` header value designates the binary encoding
+pub fn is_binary_content_type(content_type: Option<&str>) -> bool {
+    content_type.is_some_and(|content_type| media_type(content_type) == BINARY_CONTENT_TYPE)
+}
+
+/// Whether a `Content-Type` header value designates JSON
+pub fn is_json_content_type(content_type: Option<&str>) -> bool {
+    content_type.is_some_and(|content_type| media_type(content_type) == JSON_CONTENT_TYPE)
+}
+
+/// Whether an `Accept` header value lists the binary encoding
+pub fn accepts_binary(accept: Option<&str>) -> bool {
+    accept.is_some_and(|accept| {
+        accept
+            .split(',')
+            .any(|range| media_type(range) == BINARY_CONTENT_TYPE)
+    })
+}
+
+/// The media type of a header value, without its parameters
+fn media_type(value: &str) -> &str {
+    value.split(';').next().unwrap_or_default().trim()
+}
+
+/// Decodes a request or response body, as binary or JSON according to its `Content-Type`
+pub fn decode_body<T: BinaryEncoding + DeserializeOwned>(
+    content_type: Option<&str>,
+    body: &[u8],
+) -> eyre::Result<T> {
+    if is_binary_content_type(content_type) {
+        T::decode_binary(body)
+    } else {
+        Ok(serde_json::from_slice(body)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Address, H256};
+
+    fn unpacked_chunk() -> UnpackedChunk {
+        UnpackedChunk {
+            data_root: H256::random(),
+            data_size: 300_000,
+            data_path: Base64(vec![7; 256]),
+            bytes: Base64((0..=255).cycle().take(256 * 1024).collect()),
+            tx_offset: TxChunkOffset(1),
+        }
+    }
+
+    #[test]
+    fn chunks_round_trip() -> eyre::Result<()> {
+        let unpacked = unpacked_chunk();
+        let binary = unpacked.to_binary();
+        assert_eq!(UnpackedChunk::decode_binary(&binary)?, unpacked);
+        // the point of the encoding: no base64 overhead on the chunk bytes
+        assert!(binary.len() * 4 < serde_json::to_vec(&unpacked)?.len() * 3);
+
+        let packed = PackedChunk {
+            data_root: H256::random(),
+            data_size: 300_000,
+            data_path: Base64(vec![1; 64]),
+            bytes: Base64(vec![2; 1024]),
+            packing_address: Address::random(),
+            partition_offset: PartitionChunkOffset::from(42),
+            tx_offset: TxChunkOffset(0),
+            partition_hash: H256::random(),
+        };
+        for chunk in [ChunkFormat::Unpacked(unpacked), ChunkFormat::Packed(packed)] {
+            assert_eq!(ChunkFormat::decode_binary(&chunk.to_binary())?, chunk);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn gossip_requests_round_trip() -> eyre::Result<()> {
+        let request = GossipRequest {
+            miner_address: Address::random(),
+            data: IrysBlockHeader::new_mock_header(),
+        };
+        let decoded = GossipRequest::<IrysBlockHeader>::decode_binary(&request.to_binary())?;
+        assert_eq!(decoded.miner_address, request.miner_address);
+        assert_eq!(decoded.data, request.data);
+        Ok(())
+    }
+
+    #[test]
+    fn block_headers_round_trip() -> eyre::Result<()> {
+        let mut irys = IrysBlockHeader::new_mock_header();
+        irys.poa.chunk = Some(Base64(vec![3; 1024]));
+        irys.poa.data_path = Some(Base64(vec![4; 64]));
+        let combined = CombinedBlockHeader {
+            irys,
+            execution: ExecutionHeader {
+                transactions: vec![H256::random().0.into()],
+                ..Default::default()
+            },
+        };
+        let decoded = CombinedBlockHeader::decode_binary(&combined.to_binary())?;
+        assert_eq!(decoded.irys, combined.irys);
+        assert_eq!(decoded.execution.header, combined.execution.header);
+        assert_eq!(
+            decoded.execution.transactions,
+            combined.execution.transactions
+        );
+
+        let binary = combined.irys.to_binary();
+        for len in [0, 3, binary.len() / 2, binary.len() - 1] {
+            assert!(IrysBlockHeader::decode_binary(&binary[..len]).is_err());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn malformed_input_is_rejected() {
+        let binary = unpacked_chunk().to_binary();
+        for len in [0, 1, 32, binary.len() / 2, binary.len() - 1] {
+            assert!(UnpackedChunk::decode_binary(&binary[..len]).is_err());
+        }
+
+        let mut trailing = binary;
+        trailing.push(0);
+        assert!(UnpackedChunk::decode_binary(&trailing).is_err());
+        assert!(ChunkFormat::decode_binary(&[9, 0, 0]).is_err());
+        assert!(GossipRequest::<UnpackedChunk>::decode_binary(&[0; 19]).is_err());
+
+        let mut unknown_tag = Vec::new();
+        encode_list(&mut unknown_tag, |items| {
+            9_u8.encode(items);
+            unpacked_chunk().encode_binary(items);
+        });
+        assert!(ChunkFormat::decode_binary(&unknown_tag).is_err());
+
+        // a list holding an extra item
+        let mut extra_item = Vec::new();
+        encode_list(&mut extra_item, |items| {
+            UNPACKED_CHUNK_TAG.encode(items);
+            unpacked_chunk().encode_binary(items);
+            0_u8.encode(items);
+        });
+        assert!(ChunkFormat::decode_binary(&extra_item).is_err());
+    }
+
+    #[test]
+    fn negotiation() -> eyre::Result<()> {
+        assert!(is_binary_content_type(Some("application/octet-stream")));
+        assert!(!is_binary_content_type(Some("application/json")));
+        assert!(!is_binary_content_type(None));
+        assert!(is_json_content_type(Some(
+            "application/json; charset=utf-8"
+        )));
+        assert!(!is_json_content_type(Some("text/plain")));
+        assert!(!is_json_content_type(None));
+        assert!(accepts_binary(Some(
+            "application/json;q=0.5, application/octet-stream"
+        )));
+        assert!(!accepts_binary(Some("application/json")));
+
+        let chunk = unpacked_chunk();
+        let json = serde_json::to_vec(&chunk)?;
+        assert_eq!(decode_body::<UnpackedChunk>(None, &json)?, chunk);
+        assert_eq!(
+            decode_body::<UnpackedChunk>(Some(BINARY_CONTENT_TYPE), &chunk.to_binary())?,
+            chunk
+        );
+        Ok(())
+    }
+}
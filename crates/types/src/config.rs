@@ -4,7 +4,7 @@ use crate::{
         phantoms::{CostPerGb, DecayRate, Irys, IrysPrice, Percentage, Usd},
         Amount,
     },
-    PeerAddress, RethPeerInfo,
+    PeerAddress, RethPeerInfo, H256,
 };
 use alloy_eips::eip1559::ETHEREUM_BLOCK_GAS_LIMIT_30M;
 use alloy_genesis::{Genesis, GenesisAccount};
@@ -13,7 +13,7 @@ use reth_chainspec::Chain;
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 use serde::{Deserialize, Serialize};
-use std::{collections::BTreeMap, env, ops::Deref, path::PathBuf, sync::Arc};
+use std::{collections::BTreeMap, env, net::SocketAddr, ops::Deref, path::PathBuf, sync::Arc};
 
 /// Ergonomic and cheaply copyable Configuration that has the consensus and user-defined configs extracted out
 #[derive(Debug, Clone)]
@@ -134,6 +134,7 @@ pub struct ConsensusConfig {
 
     /// Minimum number of replicas required for data to be considered permanently stored
     /// Higher values increase data durability but require more network resources
+    /// This is also the number of distinct signers' ingress proofs required to promote a transaction
     pub number_of_ingress_proofs: u64,
 
     /// Target number of years data should be preserved on the network
@@ -217,6 +218,34 @@ pub struct NodeConfig {
     pub reth_peer_info: RethPeerInfo,
 
     pub genesis_peer_discovery_timeout_millis: u64,
+
+    /// Chain sync settings
+    #[serde(default)]
+    pub sync: SyncConfig,
+
+    /// Limits of the pool of blocks waiting for their parent
+    #[serde(default)]
+    pub block_pool: BlockPoolConfig,
+
+    /// Limits of the forks the node switches to
+    #[serde(default)]
+    pub fork_choice: ForkChoiceConfig,
+
+    /// Mining pool server settings, when set the node accepts remote mining workers
+    #[serde(default)]
+    pub mining_pool: Option<MiningPoolConfig>,
+
+    /// Settings used by `irys-pool-worker` to mine for a remote node
+    #[serde(default)]
+    pub mining_pool_worker: Option<MiningPoolWorkerConfig>,
+
+    /// Admin API settings, when set the node serves its operational controls to authenticated clients
+    #[serde(default)]
+    pub admin_api: Option<AdminApiConfig>,
+
+    /// Limits of the proxy forwarding JSON-RPC calls to the execution layer
+    #[serde(default)]
+    pub execution_rpc_proxy: ExecutionRpcProxyConfig,
 }
 
 impl Into<Config> for NodeConfig {
@@ -396,6 +425,16 @@ pub struct MempoolConfig {
     /// Maximum number of chunks that can be cached per data root
     /// Prevents memory exhaustion from excessive chunk storage for a single transaction
     pub max_chunks_per_item: usize,
+
+    /// Maximum number of data roots to keep ingress proofs for in the pending cache
+    /// For proofs gossiped by peers before all the chunks of their data root arrived
+    pub max_pending_ingress_proof_items: usize,
+
+    /// Maximum number of tags a data transaction can carry
+    pub max_tags_per_tx: usize,
+
+    /// Maximum combined size in bytes of the names and values of a data transaction's tags
+    pub max_tags_size: usize,
 }
 
 /// # Gossip Network Configuration
@@ -413,6 +452,125 @@ pub struct GossipConfig {
     pub bind_port: u16,
 }
 
+/// # Chain Sync Configuration
+///
+/// Settings for downloading the chain from peers while the node catches up.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SyncConfig {
+    /// Maximum number of blocks being downloaded at the same time
+    pub block_download_window: usize,
+    /// Number of different peers a block is requested from before giving up on it
+    pub block_download_attempts: usize,
+    /// Sync from any peer instead of trusting the block index of the trusted peers.
+    /// The header chains of several peers are verified and the heaviest one is followed
+    pub sync_from_untrusted_peers: bool,
+    /// Number of peers whose header chains are compared when syncing from untrusted peers
+    pub header_chain_peers: usize,
+    /// Verify the VDF checkpoints of every n-th header of an untrusted header chain,
+    /// 0 disables the spot checks
+    pub vdf_spot_check_interval: usize,
+    /// Hash of the genesis block untrusted header chains have to start at, required to sync
+    /// from untrusted peers without any local blocks
+    pub genesis_block_hash: Option<H256>,
+}
+
+impl Default for SyncConfig {
+    fn default() -> Self {
+        Self {
+            block_download_window: 16,
+            block_download_attempts: 5,
+            sync_from_untrusted_peers: false,
+            header_chain_peers: 3,
+            vdf_spot_check_interval: 10,
+            genesis_block_hash: None,
+        }
+    }
+}
+
+/// # Block Pool Configuration
+///
+/// Limits of the pool holding the received blocks until their parent is processed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BlockPoolConfig {
+    /// Maximum number of blocks held by the pool
+    pub max_blocks: usize,
+    /// Maximum estimated memory used by the blocks held by the pool, in bytes
+    pub max_size_bytes: usize,
+    /// Maximum number of blocks received from a single peer held by the pool
+    pub max_blocks_per_peer: usize,
+    /// Seconds after which a block whose parent never arrived is dropped from the pool
+    pub orphan_expiry_secs: u64,
+}
+
+impl Default for BlockPoolConfig {
+    fn default() -> Self {
+        Self {
+            max_blocks: 500,
+            max_size_bytes: 64 * 1024 * 1024,
+            max_blocks_per_peer: 50,
+            orphan_expiry_secs: 600,
+        }
+    }
+}
+
+/// # Fork Choice Configuration
+///
+/// Limits of the forks the node switches to. Forks that would reorganize blocks
+/// already migrated to the block index are always rejected.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ForkChoiceConfig {
+    /// Maximum number of blocks of the canonical chain a fork can replace
+    pub max_reorg_depth: u64,
+}
+
+impl Default for ForkChoiceConfig {
+    fn default() -> Self {
+        Self {
+            max_reorg_depth: 32,
+        }
+    }
+}
+
+/// # Mining Pool Configuration
+///
+/// Settings for the socket remote mining workers connect to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MiningPoolConfig {
+    /// The IP address the mining pool service binds to
+    pub bind_ip: String,
+    /// The port number the mining pool service listens on
+    pub bind_port: u16,
+    /// Mining addresses of the workers allowed to authenticate
+    pub allowed_workers: Vec<Address>,
+}
+
+/// # Admin API Configuration
+///
+/// Settings for the HTTP API used to operate the node, served apart from the public API.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AdminApiConfig {
+    /// The IP address the admin API binds to
+    pub bind_ip: String,
+    /// The port number the admin API listens on
+    pub bind_port: u16,
+    /// Bearer tokens accepted in the `Authorization` header of admin requests
+    pub tokens: Vec<String>,
+}
+
+/// # Mining Pool Worker Configuration
+///
+/// Settings for a worker mining its storage modules on behalf of a remote node.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MiningPoolWorkerConfig {
+    /// Address of the node's mining pool service
+    pub pool_address: SocketAddr,
+    /// Paths to the JSON encoded storage module infos of the modules to mine
+    pub storage_modules: Vec<PathBuf>,
+}
+
 /// # Reth Configuration
 ///
 /// Settings that are passed to the reth node
@@ -456,6 +614,53 @@ pub struct HttpConfig {
     pub bind_ip: String,
     /// The port that the Node's HTTP server should listen on. Set to 0 for randomization.
     pub bind_port: u16,
+    /// Scheme used to reach the HTTP API of peers, which only advertise a socket address
+    #[serde(default = "default_peer_api_scheme")]
+    pub peer_api_scheme: String,
+}
+
+/// # Execution RPC Proxy Configuration
+///
+/// Limits of the `/v1/execution-rpc` route, which forwards JSON-RPC calls to reth.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExecutionRpcProxyConfig {
+    /// Methods forwarded to reth, either full method names or namespaces written as `eth_*`.
+    /// Defaults to the public `eth_*`, `net_*` and `web3_*` namespaces
+    pub allowed_methods: Vec<String>,
+    /// Methods never forwarded, even if they're allowed, written like `allowed_methods`.
+    /// Defaults to the namespaces exposing node internals or keys
+    pub denied_methods: Vec<String>,
+    /// Maximum number of calls in a batch request
+    pub max_batch_size: usize,
+    /// Maximum size of a request body, in bytes
+    pub max_request_size: usize,
+    /// Calls per second allowed on average from a single IPv4 address or IPv6 /64 prefix,
+    /// 0 disables the rate limit
+    pub rate_limit_per_second: u32,
+    /// Calls a single client can make in a burst above the average rate
+    pub rate_limit_burst: u32,
+    /// Methods whose results are reused for identical calls while fresh
+    pub cached_methods: Vec<String>,
+    /// Milliseconds a cached result stays fresh
+    pub cache_ttl_millis: u64,
+}
+
+impl Default for ExecutionRpcProxyConfig {
+    fn default() -> Self {
+        Self {
+            allowed_methods: ["eth_*", "net_*", "web3_*"].map(ToOwned::to_owned).to_vec(),
+            denied_methods: ["admin_*", "debug_*", "txpool_*", "personal_*"]
+                .map(ToOwned::to_owned)
+                .to_vec(),
+            max_batch_size: 50,
+            max_request_size: 1024 * 1024,
+            rate_limit_per_second: 50,
+            rate_limit_burst: 100,
+            cached_methods: vec!["eth_chainId".to_owned(), "eth_blockNumber".to_owned()],
+            cache_ttl_millis: 1000,
+        }
+    }
 }
 
 /// # Difficulty Adjustment Configuration
@@ -476,6 +681,10 @@ pub struct DifficultyAdjustmentConfig {
     pub min_difficulty_adjustment_factor: Decimal,
 }
 
+fn default_peer_api_scheme() -> String {
+    "http".to_string()
+}
+
 fn default_irys_path() -> PathBuf {
     env::current_dir()
         .expect("Unable to determine working dir, aborting")
@@ -502,7 +711,7 @@ impl ConsensusConfig {
             annual_cost_per_gb: Amount::token(dec!(0.01)).unwrap(), // 0.01$
             decay_rate: Amount::percentage(dec!(0.01)).unwrap(),    // 1%
             safe_minimum_number_of_years: 200,
-            number_of_ingress_proofs: 10,
+            number_of_ingress_proofs: 1, // a single node testnet only has its own proofs
             genesis_price: Amount::token(dec!(1)).expect("valid token amount"),
             token_price_safe_range: Amount::percentage(dec!(1)).expect("valid percentage"),
             mempool: MempoolConfig {
@@ -513,6 +722,9 @@ impl ConsensusConfig {
                 max_pledges_per_item: 100,
                 max_pending_chunk_items: 30,
                 max_chunks_per_item: 500,
+                max_pending_ingress_proof_items: 30,
+                max_tags_per_tx: 32,
+                max_tags_size: 4096,
             },
             vdf: VdfConfig {
                 reset_frequency: 10 * 120,
@@ -675,6 +887,7 @@ impl NodeConfig {
                 public_port: 0,
                 bind_ip: "127.0.0.1".parse().expect("valid IP address"),
                 bind_port: 0,
+                peer_api_scheme: default_peer_api_scheme(),
             },
             reth: RethConfig {
                 use_random_ports: true,
@@ -693,6 +906,13 @@ impl NodeConfig {
             reth_peer_info: RethPeerInfo::default(),
 
             genesis_peer_discovery_timeout_millis: 10000,
+            sync: SyncConfig::default(),
+            block_pool: BlockPoolConfig::default(),
+            fork_choice: ForkChoiceConfig::default(),
+            mining_pool: None,
+            mining_pool_worker: None,
+            admin_api: None,
+            execution_rpc_proxy: ExecutionRpcProxyConfig::default(),
         }
     }
 
@@ -874,7 +1094,7 @@ mod tests {
         num_chunks_in_recall_range = 2
         num_partitions_per_slot = 1
         entropy_packing_iterations = 1000
-        number_of_ingress_proofs = 10
+        number_of_ingress_proofs = 1
         safe_minimum_number_of_years = 200
         genesis_peer_discovery_timeout_millis = 10000
 
@@ -908,6 +1128,9 @@ mod tests {
         max_pledges_per_item = 100
         max_pending_chunk_items = 30
         max_chunks_per_item = 500
+        max_pending_ingress_proof_items = 30
+        max_tags_per_tx = 32
+        max_tags_size = 4096
 
         [difficulty_adjustment]
         block_time = 1
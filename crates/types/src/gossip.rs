@@ -1,4 +1,7 @@
-use crate::{CommitmentTransaction, IrysBlockHeader, IrysTransactionHeader, UnpackedChunk};
+use crate::{
+    ingress::IngressProof, serialization::string_u64, CommitmentTransaction, H256List,
+    IrysBlockHeader, IrysTransactionHeader, IrysTransactionResponse, UnpackedChunk, H256,
+};
 use alloy_primitives::Address;
 use base58::ToBase58;
 use serde::{Deserialize, Serialize};
@@ -9,6 +12,8 @@ pub enum GossipData {
     Transaction(IrysTransactionHeader),
     CommitmentTransaction(CommitmentTransaction),
     Block(IrysBlockHeader),
+    IngressProof(IngressProof),
+    VdfStep(VdfStepGossip),
 }
 
 impl GossipData {
@@ -26,10 +31,47 @@ impl GossipData {
             GossipData::Block(block) => {
                 format!("block {}", block.block_hash.0.to_base58())
             }
+            GossipData::IngressProof(proof) => {
+                format!("ingress proof for data root {}", proof.data_root)
+            }
+            GossipData::VdfStep(step) => {
+                format!("vdf step {}", step.global_step)
+            }
         }
     }
 }
 
+/// A VDF step computed by a peer. Each checkpoint is computed from the previous
+/// one, so all of them can be verified in parallel
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VdfStepGossip {
+    #[serde(with = "string_u64")]
+    pub global_step: u64,
+    /// The seed the step was computed from: the output of the previous step,
+    /// with the reset seed applied if the previous step was a reset step
+    pub seed: H256,
+    /// The step checkpoints, the last one is the step output
+    pub checkpoints: H256List,
+}
+
+impl VdfStepGossip {
+    /// The output of the step, `None` for a step without checkpoints
+    pub fn output(&self) -> Option<H256> {
+        self.checkpoints.0.last().copied()
+    }
+}
+
+/// A block downloaded from a peer while syncing the chain, along with the headers
+/// of its transactions
+#[derive(Debug, Clone)]
+pub struct SyncedBlock {
+    pub header: IrysBlockHeader,
+    pub transactions: Vec<IrysTransactionResponse>,
+    /// Mining address of the peer the block was downloaded from
+    pub source_miner_address: Address,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GossipRequest<T> {
     pub miner_address: Address,
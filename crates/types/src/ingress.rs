@@ -33,6 +33,23 @@ impl Decompress for IngressProof {
     }
 }
 
+impl IngressProof {
+    /// The digest signed by the proof's signer, combining the proof and the data_root
+    pub fn prehash(&self) -> [u8; 32] {
+        let mut hasher = sha::Sha256::new();
+        hasher.update(&self.proof.0);
+        hasher.update(&self.data_root.0);
+        hasher.finish()
+    }
+
+    /// Recovers the address of the signer of this proof
+    /// note: this only checks the signature, see [`verify_ingress_proof`] to validate the proof itself
+    pub fn recover_signer(&self) -> eyre::Result<Address> {
+        let sig = self.signature.as_bytes();
+        Ok(recover_signer(&sig[..].try_into()?, self.prehash().into())?)
+    }
+}
+
 pub fn generate_ingress_proof_tree(
     chunks: impl Iterator<Item = eyre::Result<ChunkBytes>>,
     address: Address,
@@ -74,14 +91,8 @@ pub fn verify_ingress_proof(
     proof: IngressProof,
     chunks: impl Iterator<Item = eyre::Result<ChunkBytes>>,
 ) -> eyre::Result<bool> {
-    let mut hasher = sha::Sha256::new();
-    hasher.update(&proof.proof.0);
-    hasher.update(&proof.data_root.0);
-    let prehash = hasher.finish();
-
-    let sig = proof.signature.as_bytes();
-
-    let recovered_address = recover_signer(&sig[..].try_into()?, prehash.into())?;
+    let prehash = proof.prehash();
+    let recovered_address = proof.recover_signer()?;
 
     // re-compute the ingress proof & regular trees & roots
     let (proof_root, regular_root) = generate_ingress_proof_tree(chunks, recovered_address, true)?;
@@ -1,6 +1,7 @@
 use crate::{
-    generate_data_root, generate_leaves, resolve_proofs, Address, Base64, CommitmentTransaction,
-    IrysBlockHeader, IrysSignature, IrysTransaction, IrysTransactionHeader, Signature, H256,
+    bundle::DataItem, generate_data_root, generate_leaves, resolve_proofs, Address, Base64,
+    CommitmentTransaction, IrysBlockHeader, IrysSignature, IrysTransaction, IrysTransactionHeader,
+    Signature, StreamingMerkleTree, H256,
 };
 use alloy_core::primitives::keccak256;
 
@@ -65,6 +66,36 @@ impl IrysSigner {
         Ok(transaction)
     }
 
+    /// Creates a transaction for data merklized with a [`StreamingMerkleBuilder`], for data
+    /// too large to hold in memory. The transaction carries no data, chunks or proofs; read
+    /// the chunks back from the source and take their proofs from the `tree`
+    pub fn create_transaction_from_tree(
+        &self,
+        tree: &StreamingMerkleTree,
+        anchor: Option<H256>,
+    ) -> Result<IrysTransaction> {
+        eyre::ensure!(
+            tree.chunk_size() == self.chunk_size as usize,
+            "tree was built with chunk size {}, expected {}",
+            tree.chunk_size(),
+            self.chunk_size
+        );
+
+        Ok(IrysTransaction {
+            header: IrysTransactionHeader {
+                data_size: tree.data_size(),
+                data_root: tree.data_root(),
+                // TODO: These should be calculated from some pricing params passed in
+                // as a parameter
+                perm_fee: Some(1),
+                term_fee: 1,
+                anchor: anchor.unwrap_or_default(),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+    }
+
     /// signs and sets signature and id.
     pub fn sign_transaction(&self, mut transaction: IrysTransaction) -> Result<IrysTransaction> {
         // Store the signer address
@@ -102,6 +133,22 @@ impl IrysSigner {
         Ok(commitment)
     }
 
+    /// signs a bundle data item, setting its signer, signature and id.
+    pub fn sign_data_item(&self, mut item: DataItem) -> Result<DataItem> {
+        // Store the signer address
+        item.signer = Address::from_public_key(self.signer.verifying_key());
+
+        // Create the signature hash and sign it
+        let prehash = item.signature_hash()?;
+        let signature: Signature = self.signer.sign_prehash_recoverable(&prehash)?.into();
+        item.signature = IrysSignature::new(signature);
+
+        // Derive the item id by hashing the signature
+        let id: [u8; 32] = keccak256(signature.as_bytes()).into();
+        item.id = H256::from(id);
+        Ok(item)
+    }
+
     pub fn sign_block_header(&self, block_header: &mut IrysBlockHeader) -> Result<()> {
         // Store the signer address
         block_header.miner_address = Address::from_public_key(self.signer.verifying_key());
@@ -154,7 +201,7 @@ impl From<IrysSigner> for LocalSigner<SigningKey> {
 
 #[cfg(test)]
 mod tests {
-    use crate::{hash_sha256, validate_chunk};
+    use crate::{hash_sha256, validate_chunk, StreamingMerkleBuilder};
     use rand::Rng;
     use reth_primitives::transaction::recover_signer;
 
@@ -228,4 +275,25 @@ mod tests {
 
         assert_eq!(signer, tx.header.signer);
     }
+
+    #[test]
+    fn create_transaction_from_tree_matches_in_memory() {
+        let config = crate::ConsensusConfig::testnet();
+        let data_size = (config.chunk_size as f64 * 2.5).round() as usize;
+        let mut data_bytes = vec![0u8; data_size];
+        rand::thread_rng().fill(&mut data_bytes[..]);
+        let irys = IrysSigner::random_signer(&config);
+
+        let tx = irys.create_transaction(data_bytes.clone(), None).unwrap();
+        let tree =
+            StreamingMerkleBuilder::from_reader(data_bytes.as_slice(), irys.chunk_size as usize)
+                .unwrap();
+        let streamed_tx = irys.create_transaction_from_tree(&tree, None).unwrap();
+
+        assert_eq!(streamed_tx.header, tx.header);
+        assert_eq!(
+            tree.proofs().collect::<eyre::Result<Vec<_>>>().unwrap(),
+            tx.proofs
+        );
+    }
 }
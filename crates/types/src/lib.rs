@@ -6,21 +6,27 @@ pub mod app_state;
 pub mod arbiter_handle;
 pub mod block;
 pub mod block_production;
+pub mod bundle;
 pub mod chunk;
 pub mod chunked;
+pub mod codec;
 pub mod config;
 pub mod difficulty_adjustment_config;
 pub mod gossip;
 pub mod ingress;
 pub mod irys;
 mod merkle;
+mod merkle_stream;
+pub mod mining_pool;
 pub mod partition;
 pub mod peer_list;
+pub mod query;
 pub mod serialization;
 pub mod signature;
 pub mod simple_rng;
 pub mod storage;
 pub mod storage_pricing;
+pub mod storage_status;
 pub mod transaction;
 pub mod version;
 
@@ -35,6 +41,7 @@ pub use gossip::*;
 pub use serialization::*;
 pub use signature::*;
 pub use storage::*;
+pub use storage_status::*;
 pub use transaction::*;
 
 pub use alloy_primitives::{Address, Signature};
@@ -42,9 +49,12 @@ pub use app_state::*;
 pub use arbiter_handle::*;
 pub use arbitrary::Arbitrary;
 pub use chunk::*;
+pub use codec::*;
 pub use merkle::*;
+pub use merkle_stream::*;
 pub use nodit::Interval;
 pub use peer_list::*;
+pub use query::*;
 pub use reth_codecs::Compact;
 pub use simple_rng::*;
 pub use version::*;
@@ -0,0 +1,306 @@
+//! Builds the merkle tree of transaction data incrementally, for data too large to hold in memory.
+//!
+//! [`StreamingMerkleBuilder`] hashes the data chunk by chunk as it is read, keeping at most one
+//! chunk of data buffered. The resulting [`StreamingMerkleTree`] only stores the node hashes
+//! (about 112 bytes per chunk), from which the data root and the [`Proof`] of any chunk can be
+//! produced on demand. The tree, root and proofs are identical to the ones produced by
+//! [`generate_leaves`](crate::generate_leaves), [`generate_data_root`](crate::generate_data_root)
+//! and [`resolve_proofs`](crate::resolve_proofs).
+//!
+//! Sign the tree's data with [`IrysSigner::create_transaction_from_tree`](crate::irys::IrysSigner::create_transaction_from_tree).
+
+use crate::{hash_all_sha256, hash_sha256, Helpers as _, Proof, H256, HASH_SIZE};
+use eyre::{ensure, eyre, Error};
+use std::{io::Read, ops::Range};
+use tokio::io::{AsyncRead, AsyncReadExt as _};
+
+/// The hash and right bound of a node, all that's needed to compute its parent and proofs
+#[derive(Debug, PartialEq, Clone, Copy)]
+struct LayerNode {
+    id: [u8; HASH_SIZE],
+    max_byte_range: usize,
+}
+
+/// Consumes data chunk by chunk and builds a [`StreamingMerkleTree`]
+#[derive(Debug)]
+pub struct StreamingMerkleBuilder {
+    chunk_size: usize,
+    /// Bytes of the chunk being filled, never more than `chunk_size`
+    buffer: Vec<u8>,
+    data_hashes: Vec<[u8; HASH_SIZE]>,
+    leaves: Vec<LayerNode>,
+    data_size: usize,
+}
+
+impl StreamingMerkleBuilder {
+    pub fn new(chunk_size: usize) -> Self {
+        assert!(chunk_size > 0, "chunk_size must be greater than zero");
+        Self {
+            chunk_size,
+            buffer: Vec::with_capacity(chunk_size),
+            data_hashes: Vec::new(),
+            leaves: Vec::new(),
+            data_size: 0,
+        }
+    }
+
+    /// Builds the tree of all the data read from `reader`
+    pub fn from_reader(
+        mut reader: impl Read,
+        chunk_size: usize,
+    ) -> Result<StreamingMerkleTree, Error> {
+        let mut builder = Self::new(chunk_size);
+        let mut buf = vec![0; chunk_size];
+        loop {
+            match reader.read(&mut buf) {
+                Ok(0) => break,
+                Ok(read) => builder.update(&buf[..read])?,
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e.into()),
+            }
+        }
+        builder.finish()
+    }
+
+    /// Builds the tree of all the data read from the async `reader`
+    pub async fn from_async_reader(
+        mut reader: impl AsyncRead + Unpin,
+        chunk_size: usize,
+    ) -> Result<StreamingMerkleTree, Error> {
+        let mut builder = Self::new(chunk_size);
+        let mut buf = vec![0; chunk_size];
+        loop {
+            let read = reader.read(&mut buf).await?;
+            if read == 0 {
+                break;
+            }
+            builder.update(&buf[..read])?;
+        }
+        builder.finish()
+    }
+
+    /// Appends `bytes` to the data, hashing every chunk it completes
+    pub fn update(&mut self, mut bytes: &[u8]) -> Result<(), Error> {
+        while !bytes.is_empty() {
+            // hash whole chunks in place rather than copying them to the buffer
+            if self.buffer.is_empty() && bytes.len() >= self.chunk_size {
+                let (chunk, rest) = bytes.split_at(self.chunk_size);
+                self.push_leaf(chunk)?;
+                bytes = rest;
+                continue;
+            }
+
+            let take = (self.chunk_size - self.buffer.len()).min(bytes.len());
+            let (head, rest) = bytes.split_at(take);
+            self.buffer.extend_from_slice(head);
+            bytes = rest;
+            if self.buffer.len() == self.chunk_size {
+                let chunk = std::mem::take(&mut self.buffer);
+                self.push_leaf(&chunk)?;
+                self.buffer = chunk;
+                self.buffer.clear();
+            }
+        }
+        Ok(())
+    }
+
+    fn push_leaf(&mut self, chunk: &[u8]) -> Result<(), Error> {
+        let data_hash = hash_sha256(chunk)?;
+        let max_byte_range = self.data_size + chunk.len();
+        let id = hash_all_sha256(vec![&data_hash, &max_byte_range.to_note_vec()])?;
+
+        self.data_hashes.push(data_hash);
+        self.leaves.push(LayerNode { id, max_byte_range });
+        self.data_size = max_byte_range;
+        Ok(())
+    }
+
+    /// Hashes the trailing partial chunk, if any, and builds the branch layers up to the root
+    pub fn finish(mut self) -> Result<StreamingMerkleTree, Error> {
+        if !self.buffer.is_empty() {
+            let chunk = std::mem::take(&mut self.buffer);
+            self.push_leaf(&chunk)?;
+        }
+        ensure!(
+            !self.leaves.is_empty(),
+            "At least one data node is required"
+        );
+
+        let mut layers = vec![self.leaves];
+        while let Some(layer) = layers.last().filter(|layer| layer.len() > 1) {
+            // same pairing as `build_layer`: an odd node out is carried up unchanged
+            let next = layer
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => Ok(LayerNode {
+                        id: hash_all_sha256(vec![
+                            &left.id,
+                            &right.id,
+                            &left.max_byte_range.to_note_vec(),
+                        ])?,
+                        max_byte_range: right.max_byte_range,
+                    }),
+                    [single] => Ok(*single),
+                    _ => unreachable!(),
+                })
+                .collect::<Result<Vec<_>, Error>>()?;
+            layers.push(next);
+        }
+
+        Ok(StreamingMerkleTree {
+            chunk_size: self.chunk_size,
+            data_size: self.data_size,
+            data_hashes: self.data_hashes,
+            layers,
+        })
+    }
+}
+
+/// Node hashes of the merkle tree of some data, built by [`StreamingMerkleBuilder`]
+#[derive(Debug, Clone)]
+pub struct StreamingMerkleTree {
+    chunk_size: usize,
+    data_size: usize,
+    data_hashes: Vec<[u8; HASH_SIZE]>,
+    /// Node layers, from the leaves up to the root
+    layers: Vec<Vec<LayerNode>>,
+}
+
+impl StreamingMerkleTree {
+    pub fn data_root(&self) -> H256 {
+        H256(self.layers[self.layers.len() - 1][0].id)
+    }
+
+    pub fn chunk_size(&self) -> usize {
+        self.chunk_size
+    }
+
+    pub fn data_size(&self) -> u64 {
+        self.data_size as u64
+    }
+
+    pub fn chunk_count(&self) -> usize {
+        self.data_hashes.len()
+    }
+
+    /// Byte range of the chunk at `index` within the data
+    pub fn chunk_range(&self, index: usize) -> Option<Range<usize>> {
+        let leaf = self.layers[0].get(index)?;
+        Some(index * self.chunk_size..leaf.max_byte_range)
+    }
+
+    /// Computes the [`Proof`] of the chunk at `index`
+    pub fn proof(&self, index: usize) -> Result<Proof, Error> {
+        let data_hash = self.data_hashes.get(index).ok_or_else(|| {
+            eyre!(
+                "chunk {} is out of range, the data has {} chunks",
+                index,
+                self.chunk_count()
+            )
+        })?;
+
+        let mut proof = Vec::new();
+        // walk down from the root, the position of the chunk's ancestor in a layer is index >> level
+        for level in (1..self.layers.len()).rev() {
+            let children = &self.layers[level - 1];
+            let left = (index >> level) << 1;
+            // a carried up node has no branch of its own
+            if let (Some(left), Some(right)) = (children.get(left), children.get(left + 1)) {
+                proof.extend(left.id);
+                proof.extend(right.id);
+                proof.extend(left.max_byte_range.to_note_vec());
+            }
+        }
+
+        let max_byte_range = self.layers[0][index].max_byte_range;
+        proof.extend(data_hash);
+        proof.extend(max_byte_range.to_note_vec());
+        Ok(Proof {
+            offset: max_byte_range - 1,
+            proof,
+        })
+    }
+
+    /// Computes the proofs of all the chunks, in order
+    pub fn proofs(&self) -> impl Iterator<Item = Result<Proof, Error>> + '_ {
+        (0..self.chunk_count()).map(|index| self.proof(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{generate_data_root, generate_leaves, resolve_proofs, validate_path, Base64};
+
+    fn data(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i * 31 % 251) as u8).collect()
+    }
+
+    #[test]
+    fn matches_in_memory_tree() -> eyre::Result<()> {
+        let chunk_size = 32;
+        for len in (1..=40 * chunk_size).step_by(13) {
+            let data = data(len);
+            let leaves = generate_leaves(std::iter::once(Ok(data.clone())), chunk_size)?;
+            let root = generate_data_root(leaves)?;
+            let expected_root = H256(root.id);
+            let expected_proofs = resolve_proofs(root, None)?;
+
+            // feed the data in pieces that don't line up with chunk boundaries
+            let mut builder = StreamingMerkleBuilder::new(chunk_size);
+            for piece in data.chunks(7) {
+                builder.update(piece)?;
+            }
+            let tree = builder.finish()?;
+
+            assert_eq!(
+                tree.data_root(),
+                expected_root,
+                "data root of {} bytes",
+                len
+            );
+            assert_eq!(tree.data_size(), len as u64);
+            assert_eq!(tree.chunk_count(), expected_proofs.len());
+            let proofs = tree.proofs().collect::<eyre::Result<Vec<_>>>()?;
+            assert_eq!(proofs, expected_proofs, "proofs of {} bytes", len);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn proofs_validate_against_data_root() -> eyre::Result<()> {
+        let chunk_size = 32;
+        let data = data(chunk_size * 5 + 3);
+        let tree = StreamingMerkleBuilder::from_reader(data.as_slice(), chunk_size)?;
+
+        for index in 0..tree.chunk_count() {
+            let range = tree.chunk_range(index).unwrap();
+            let proof = tree.proof(index)?;
+            let result = validate_path(
+                tree.data_root().0,
+                &Base64(proof.proof),
+                proof.offset as u128,
+            )?;
+            assert_eq!(result.leaf_hash, hash_sha256(&data[range.clone()])?);
+            assert_eq!(result.left_bound, range.start as u128);
+            assert_eq!(result.right_bound, range.end as u128);
+        }
+        assert!(tree.proof(tree.chunk_count()).is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn async_reader_matches_reader() -> eyre::Result<()> {
+        let data = data(1000);
+        let tree = StreamingMerkleBuilder::from_reader(data.as_slice(), 64)?;
+        let async_tree = StreamingMerkleBuilder::from_async_reader(data.as_slice(), 64).await?;
+        assert_eq!(async_tree.data_root(), tree.data_root());
+        assert_eq!(async_tree.data_size(), tree.data_size());
+        Ok(())
+    }
+
+    #[test]
+    fn empty_data_is_rejected() {
+        assert!(StreamingMerkleBuilder::new(32).finish().is_err());
+    }
+}
@@ -0,0 +1,221 @@
+//! Wire protocol spoken between a node and its remote mining workers.
+//!
+//! Messages are JSON objects, one per line, exchanged over a plain TCP socket.
+//! On connect the node sends a [`PoolServerMessage::Challenge`] which the worker
+//! answers with a [`PoolWorkerMessage::Auth`] signed by its mining key. Once
+//! authenticated the node streams VDF seeds and difficulty updates to the worker
+//! and the worker submits the [`PoolSolution`]s found in its storage modules.
+use crate::{
+    block_production::{Seed, SolutionContext},
+    partition::PartitionHash,
+    serialization::{address_base58_stringify, string_u64},
+    Address, Base64, H256List, IrysSignature, H256, U256,
+};
+use alloy_primitives::keccak256;
+use serde::{Deserialize, Serialize};
+
+/// Domain separator mixed into the authentication prehash so a challenge
+/// signature can't be replayed as any other kind of signed payload
+const POOL_AUTH_DOMAIN: &[u8] = b"irys-mining-pool-auth";
+
+/// Messages sent by the node to a connected worker
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(
+    tag = "type",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum PoolServerMessage {
+    /// Random nonce the worker has to sign to authenticate
+    Challenge { nonce: H256 },
+    /// Sent once the worker is authenticated, with the VDF steps since the last
+    /// recall range reset so the worker can reconstruct its recall ranges
+    Authenticated {
+        #[serde(with = "string_u64")]
+        global_step: u64,
+        seeds: H256List,
+        difficulty: U256,
+    },
+    /// The most recent VDF step
+    Seed {
+        seed: Seed,
+        checkpoints: H256List,
+        #[serde(with = "string_u64")]
+        global_step: u64,
+    },
+    /// The difficulty of the new canonical tip
+    Difficulty {
+        #[serde(with = "string_u64")]
+        block_height: u64,
+        difficulty: U256,
+    },
+    /// A solution was found for the step, workers stop mining it
+    StepSolved {
+        #[serde(with = "string_u64")]
+        vdf_step: u64,
+    },
+    /// Partitions that expired and have to be repacked
+    PartitionsExpired { partition_hashes: H256List },
+    /// Outcome of a submitted solution
+    SolutionResult {
+        #[serde(with = "string_u64")]
+        vdf_step: u64,
+        accepted: bool,
+        reason: Option<String>,
+    },
+}
+
+/// Messages sent by a worker to the node
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(
+    tag = "type",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum PoolWorkerMessage {
+    /// Signature over [`pool_auth_prehash`] of the challenge nonce
+    Auth {
+        #[serde(with = "address_base58_stringify")]
+        signer: Address,
+        signature: IrysSignature,
+    },
+    /// A candidate solution found by one of the worker's partitions
+    Solution(PoolSolution),
+}
+
+/// A [`SolutionContext`] as submitted by a worker. The node fills in its own
+/// mining address and the VDF checkpoints of the step before producing a block
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolSolution {
+    pub partition_hash: PartitionHash,
+    pub chunk_offset: u32,
+    pub recall_chunk_index: u32,
+    pub tx_path: Option<Base64>,
+    pub data_path: Option<Base64>,
+    pub chunk: Base64,
+    #[serde(with = "string_u64")]
+    pub vdf_step: u64,
+    pub seed: Seed,
+    pub solution_hash: H256,
+}
+
+impl From<SolutionContext> for PoolSolution {
+    fn from(solution: SolutionContext) -> Self {
+        Self {
+            partition_hash: solution.partition_hash,
+            chunk_offset: solution.chunk_offset,
+            recall_chunk_index: solution.recall_chunk_index,
+            tx_path: solution.tx_path.map(Base64),
+            data_path: solution.data_path.map(Base64),
+            chunk: Base64(solution.chunk),
+            vdf_step: solution.vdf_step,
+            seed: solution.seed,
+            solution_hash: solution.solution_hash,
+        }
+    }
+}
+
+impl PoolSolution {
+    /// Converts the submitted solution into a [`SolutionContext`] mined by `mining_address`
+    pub fn into_solution_context(
+        self,
+        mining_address: Address,
+        checkpoints: H256List,
+    ) -> SolutionContext {
+        SolutionContext {
+            partition_hash: self.partition_hash,
+            chunk_offset: self.chunk_offset,
+            recall_chunk_index: self.recall_chunk_index,
+            mining_address,
+            tx_path: self.tx_path.map(|p| p.0),
+            data_path: self.data_path.map(|p| p.0),
+            chunk: self.chunk.0,
+            vdf_step: self.vdf_step,
+            checkpoints,
+            seed: self.seed,
+            solution_hash: self.solution_hash,
+        }
+    }
+}
+
+/// The prehash a worker signs to answer the node's authentication challenge
+pub fn pool_auth_prehash(chain_id: u64, nonce: &H256) -> [u8; 32] {
+    let mut bytes = Vec::with_capacity(POOL_AUTH_DOMAIN.len() + 8 + 32);
+    bytes.extend_from_slice(POOL_AUTH_DOMAIN);
+    bytes.extend_from_slice(&chain_id.to_be_bytes());
+    bytes.extend_from_slice(nonce.as_bytes());
+    keccak256(bytes).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{irys::IrysSigner, ConsensusConfig, Signature};
+
+    #[test]
+    fn pool_messages_roundtrip() {
+        let solution = SolutionContext {
+            partition_hash: H256::random(),
+            chunk_offset: 7,
+            recall_chunk_index: 1,
+            mining_address: Address::random(),
+            tx_path: Some(vec![1, 2, 3]),
+            data_path: None,
+            chunk: vec![4; 32],
+            vdf_step: 42,
+            checkpoints: H256List(vec![H256::random()]),
+            seed: Seed(H256::random()),
+            solution_hash: H256::random(),
+        };
+        let msg = PoolWorkerMessage::Solution(solution.clone().into());
+        let json = serde_json::to_string(&msg).unwrap();
+        let decoded: PoolWorkerMessage = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, msg);
+
+        let PoolWorkerMessage::Solution(decoded) = decoded else {
+            panic!("expected a solution");
+        };
+        let context =
+            decoded.into_solution_context(solution.mining_address, solution.checkpoints.clone());
+        assert_eq!(context, solution);
+
+        let msg = PoolServerMessage::Seed {
+            seed: Seed(H256::random()),
+            checkpoints: H256List(vec![H256::random()]),
+            global_step: 12,
+        };
+        let json = serde_json::to_string(&msg).unwrap();
+        assert!(json.contains(r#""type":"seed""#));
+        assert!(json.contains(r#""globalStep":"12""#));
+        assert_eq!(
+            serde_json::from_str::<PoolServerMessage>(&json).unwrap(),
+            msg
+        );
+    }
+
+    #[test]
+    fn pool_auth_signature_is_bound_to_nonce() {
+        let config = ConsensusConfig::testnet();
+        let worker = IrysSigner::random_signer(&config);
+        let nonce = H256::random();
+
+        let prehash = pool_auth_prehash(config.chain_id, &nonce);
+        let signature: Signature = worker
+            .signer
+            .sign_prehash_recoverable(&prehash)
+            .unwrap()
+            .into();
+        let signature = IrysSignature::new(signature);
+
+        assert!(signature.validate_signature(prehash, worker.address()));
+        assert!(!signature.validate_signature(
+            pool_auth_prehash(config.chain_id, &H256::random()),
+            worker.address()
+        ));
+        assert!(!signature.validate_signature(
+            pool_auth_prehash(config.chain_id + 1, &nonce),
+            worker.address()
+        ));
+    }
+}
@@ -31,6 +31,10 @@ impl PeerScore {
         self.0 = self.0.saturating_sub(5);
     }
 
+    pub fn decrease_unconnectable_block(&mut self) {
+        self.0 = self.0.saturating_sub(1);
+    }
+
     pub fn is_active(&self) -> bool {
         self.0 >= Self::ACTIVE_THRESHOLD
     }
@@ -0,0 +1,100 @@
+use crate::{CommitmentTransaction, IrysBlockHeader, IrysTransactionHeader, H256};
+use irys_primitives::CommitmentStatus;
+use serde::{Deserialize, Serialize};
+
+/// Page size used by list API routes when the query doesn't set a limit
+pub const DEFAULT_PAGE_LIMIT: usize = 100;
+
+/// Largest page size list API routes will return, larger limits are clamped
+pub const MAX_PAGE_LIMIT: usize = 1000;
+
+/// Query parameters for the `/txs/tag` API route
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxTagQuery {
+    /// The tag name to match
+    pub name: String,
+    /// The tag value to match
+    pub value: String,
+    /// Maximum number of results to return
+    pub limit: Option<usize>,
+    /// The `next` cursor of the previous page
+    pub after: Option<H256>,
+}
+
+/// Pagination query parameters for list API routes keyed by an address or ledger
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageQuery {
+    /// Maximum number of results to return
+    pub limit: Option<usize>,
+    /// The `next` cursor of the previous page
+    pub after: Option<H256>,
+}
+
+/// Query parameters for the `/blocks` API route
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BlockRangeQuery {
+    /// Height of the first block to return
+    pub from: u64,
+    /// Maximum number of blocks to return
+    pub limit: Option<usize>,
+    /// Whether to include the headers of the data transactions in each block
+    pub include_txs: bool,
+}
+
+/// A block header, optionally with the headers of its data transactions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockWithTxs {
+    pub block: IrysBlockHeader,
+    /// Data transactions of every data ledger, in ledger order, when requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub txs: Option<Vec<IrysTransactionHeader>>,
+}
+
+/// A data transaction header along with whether it has been promoted to the Publish ledger
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxWithPromotionStatus {
+    pub tx: IrysTransactionHeader,
+    pub is_promoted: bool,
+}
+
+/// A commitment transaction along with its status in the current commitment state.
+/// The status is `None` until the commitment has been processed by an epoch block.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitmentWithStatus {
+    pub commitment: CommitmentTransaction,
+    pub status: Option<CommitmentStatus>,
+}
+
+/// A page of results from a list API route
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Cursor to pass as `after` to fetch the next page, `None` on the last page
+    pub next: Option<H256>,
+}
+
+impl<T> Page<T> {
+    /// Builds a page from up to `limit + 1` results, the extra result only
+    /// being used to know whether there is a next page
+    pub fn from_results(mut items: Vec<T>, limit: usize, cursor: impl Fn(&T) -> H256) -> Self {
+        let next = if items.len() > limit {
+            items.truncate(limit);
+            items.last().map(cursor)
+        } else {
+            None
+        };
+        Self { items, next }
+    }
+}
+
+/// Clamps a requested page size to [`MAX_PAGE_LIMIT`], defaulting to [`DEFAULT_PAGE_LIMIT`]
+pub fn page_limit(limit: Option<usize>) -> usize {
+    limit.unwrap_or(DEFAULT_PAGE_LIMIT).clamp(1, MAX_PAGE_LIMIT)
+}
@@ -216,6 +216,7 @@ mod tests {
             version: 0,
             ingress_proofs: None,
             signature: Default::default(),
+            tags: None,
         };
         let transaction = IrysTransaction {
             header: original_header,
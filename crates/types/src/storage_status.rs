@@ -0,0 +1,72 @@
+use crate::{serialization::optional_string_u64, serialization::string_u64, H256};
+use serde::{Deserialize, Serialize};
+
+/// Capacity and packing report for a single storage module, as returned by the
+/// `/storage_modules` API route
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageModuleStatus {
+    /// The storage module id
+    pub id: usize,
+    /// Hash of the partition assigned to this storage module, if any
+    pub partition_hash: Option<H256>,
+    /// If assigned to a data ledger, the ledger id
+    pub ledger_id: Option<u32>,
+    /// If assigned to a data ledger, the slot index in the ledger
+    pub slot_index: Option<usize>,
+    /// The physical submodules backing this storage module
+    pub submodules: Vec<SubmoduleStatus>,
+    /// Number of chunks in each storage state
+    pub chunk_counts: ChunkTypeCounts,
+    /// Progress of any packing work queued for this storage module
+    pub packing: PackingProgress,
+    /// True once the module has a partition assigned and no uninitialized chunks left
+    pub is_minable: bool,
+}
+
+/// Chunk range and disk usage of a single storage submodule
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmoduleStatus {
+    /// First partition relative chunk offset stored by this submodule (inclusive)
+    pub start_offset: u32,
+    /// Last partition relative chunk offset stored by this submodule (inclusive)
+    pub end_offset: u32,
+    /// Free space in bytes on the disk holding the submodule, if it could be determined
+    #[serde(default, with = "optional_string_u64")]
+    pub available_space: Option<u64>,
+    /// Total size in bytes of the disk holding the submodule, if it could be determined
+    #[serde(default, with = "optional_string_u64")]
+    pub total_space: Option<u64>,
+}
+
+/// Number of chunks in a storage module per storage state
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChunkTypeCounts {
+    /// Chunks packed with entropy only
+    #[serde(with = "string_u64")]
+    pub entropy: u64,
+    /// Chunks containing packed transaction data
+    #[serde(with = "string_u64")]
+    pub data: u64,
+    /// Chunks that have not been packed yet
+    #[serde(with = "string_u64")]
+    pub uninitialized: u64,
+}
+
+/// Packing queue state for a storage module
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackingProgress {
+    /// Number of packing requests waiting in the packing queue
+    pub queued_requests: usize,
+    /// Number of chunks covered by the queued packing requests
+    #[serde(with = "string_u64")]
+    pub queued_chunks: u64,
+    /// Recent packing throughput of this storage module as a moving average
+    pub chunks_per_second: f64,
+    /// Projected time to pack the remaining uninitialized chunks at this module's rate
+    #[serde(default, with = "optional_string_u64")]
+    pub estimated_secs_remaining: Option<u64>,
+}
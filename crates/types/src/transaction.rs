@@ -2,8 +2,9 @@ use crate::{
     address_base58_stringify, optional_string_u64, string_u64, Address, Arbitrary, Base64, Compact,
     ConsensusConfig, IrysSignature, Node, Proof, Signature, TxIngressProof, H256,
 };
-use alloy_primitives::keccak256;
+use alloy_primitives::{bytes, keccak256};
 use alloy_rlp::{Encodable, RlpDecodable, RlpEncodable};
+use bytes::Buf as _;
 use irys_primitives::CommitmentType;
 use serde::{Deserialize, Serialize};
 
@@ -76,6 +77,9 @@ pub struct IrysTransactionHeader {
     #[serde(default, with = "optional_string_u64")]
     pub perm_fee: Option<u64>,
 
+    /// Application defined name/value metadata (content type, app name, ...)
+    pub tags: Option<Vec<Tag>>,
+
     /// INTERNAL: Signed ingress proofs used to promote this transaction to the Publish ledger
     /// TODO: put these somewhere else?
     #[rlp(skip)]
@@ -106,6 +110,15 @@ impl IrysTransactionHeader {
         self.encode(out)
     }
 
+    /// Total size in bytes of the names and values of the transaction's tags
+    pub fn tags_size(&self) -> usize {
+        self.tags
+            .iter()
+            .flatten()
+            .map(|tag| tag.name.len() + tag.value.len())
+            .sum()
+    }
+
     pub fn signature_hash(&self) -> [u8; 32] {
         let mut bytes = Vec::new();
         self.encode_for_signing(&mut bytes);
@@ -158,10 +171,78 @@ impl IrysTransactionHeader {
             chain_id: config.chain_id,
             signature: Signature::test_signature().into(),
             ingress_proofs: None,
+            tags: None,
+        }
+    }
+}
+
+/// A name/value pair of application metadata, attached to transactions and bundle data items
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    Eq,
+    PartialEq,
+    Hash,
+    Serialize,
+    Deserialize,
+    Arbitrary,
+    RlpEncodable,
+    RlpDecodable,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct Tag {
+    pub name: String,
+    pub value: String,
+}
+
+impl Tag {
+    /// Maximum size in bytes of a tag name or value
+    pub const MAX_FIELD_SIZE: usize = u16::MAX as usize;
+
+    pub fn new(name: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            value: value.into(),
         }
     }
 }
 
+// Strings aren't supported by the Compact derive, so the name and value are
+// written as u16 (big endian) length prefixed byte strings.
+// Names and values longer than `Tag::MAX_FIELD_SIZE` are rejected by mempool validation.
+impl Compact for Tag {
+    fn to_compact<B>(&self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        let mut size = 0;
+        for field in [&self.name, &self.value] {
+            let len = u16::try_from(field.len()).expect("tag fields are at most u16::MAX bytes");
+            buf.put_u16(len);
+            buf.put_slice(field.as_bytes());
+            size += 2 + field.len();
+        }
+        size
+    }
+
+    fn from_compact(mut buf: &[u8], _len: usize) -> (Self, &[u8]) {
+        let mut fields = [String::new(), String::new()];
+        for field in fields.iter_mut() {
+            // a truncated buffer decodes to empty/shortened fields rather than panicking
+            if buf.remaining() < 2 {
+                buf.advance(buf.remaining());
+                break;
+            }
+            let len = (buf.get_u16() as usize).min(buf.remaining());
+            *field = String::from_utf8_lossy(&buf[..len]).into_owned();
+            buf.advance(len);
+        }
+        let [name, value] = fields;
+        (Self { name, value }, buf)
+    }
+}
+
 pub type TxPath = Vec<u8>;
 
 /// sha256(tx_path)
@@ -392,6 +473,32 @@ mod tests {
         assert!(signed_tx.header.is_signature_valid());
     }
 
+    #[test]
+    fn test_tx_tags_are_signed() {
+        let config = ConsensusConfig::testnet();
+        let signer = IrysSigner {
+            signer: SigningKey::random(&mut rand::thread_rng()),
+            chain_id: config.chain_id,
+            chunk_size: config.chunk_size,
+        };
+        let tx = IrysTransaction {
+            header: mock_header(&config),
+            ..Default::default()
+        };
+        let signed_tx = signer.sign_transaction(tx).unwrap();
+        assert!(signed_tx.header.is_signature_valid());
+
+        // changing a tag invalidates the signature
+        let mut tampered = signed_tx.header.clone();
+        tampered.tags = Some(vec![Tag::new("Content-Type", "text/html")]);
+        assert!(!tampered.is_signature_valid());
+
+        // as does dropping the tags
+        let mut tampered = signed_tx.header;
+        tampered.tags = None;
+        assert!(!tampered.is_signature_valid());
+    }
+
     #[test]
     fn test_commitment_tx_encode_and_signing() {
         // setup
@@ -433,6 +540,7 @@ mod tests {
             version: 0,
             ingress_proofs: None,
             signature: Signature::test_signature().into(),
+            tags: Some(vec![Tag::new("Content-Type", "text/plain")]),
         };
         original_header
     }
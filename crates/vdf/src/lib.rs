@@ -6,6 +6,7 @@ use irys_types::{H256List, VDFLimiterInfo, VdfConfig, H256, U256};
 use openssl::sha;
 use rayon::prelude::*;
 use sha2::{Digest, Sha256};
+use std::sync::Arc;
 
 #[inline]
 pub fn vdf_sha(
@@ -16,9 +17,35 @@ pub fn vdf_sha(
     num_iterations: u64,
     checkpoints: &mut Vec<H256>,
 ) {
+    vdf_sha_until(
+        hasher,
+        salt,
+        seed,
+        num_checkpoints,
+        num_iterations,
+        checkpoints,
+        || false,
+    );
+}
+
+/// Same as [`vdf_sha`], but calls `abort` before each checkpoint and stops as soon as it
+/// returns true, leaving `salt`, `seed` and `checkpoints` partially computed.
+/// Returns whether every checkpoint was computed
+pub fn vdf_sha_until(
+    hasher: &mut Sha256,
+    salt: &mut U256,
+    seed: &mut H256,
+    num_checkpoints: usize,
+    num_iterations: u64,
+    checkpoints: &mut Vec<H256>,
+    mut abort: impl FnMut() -> bool,
+) -> bool {
     let mut local_salt: [u8; 32] = [0; 32];
 
     for checkpoint_idx in 0..num_checkpoints {
+        if abort() {
+            return false;
+        }
         salt.to_little_endian(&mut local_salt);
 
         for _ in 0..num_iterations {
@@ -33,6 +60,7 @@ pub fn vdf_sha(
         // Increment the salt for the next checkpoint calculation
         *salt = *salt + 1;
     }
+    true
 }
 
 /// Vdf verification code
@@ -194,12 +222,9 @@ pub async fn last_step_checkpoints_is_valid(
     ));
     let config = config.clone();
 
-    let test = tokio::task::spawn_blocking(move || {
+    let test = tokio::task::spawn_blocking(move || -> eyre::Result<Vec<H256>> {
         // Limit threads number to avoid overloading the system using configuration limit
-        let pool = rayon::ThreadPoolBuilder::new()
-            .num_threads(config.parallel_verification_thread_limit)
-            .build()
-            .unwrap();
+        let pool = verification_thread_pool(&config)?;
 
         let num_iterations = config.sha_1s_difficulty;
         let test: Vec<H256> = pool.install(|| {
@@ -220,9 +245,9 @@ pub async fn last_step_checkpoints_is_valid(
                 })
                 .collect::<Vec<H256>>()
         });
-        test
+        Ok(test)
     })
-    .await?;
+    .await??;
 
     // println!("test{}: {}", 0, Base64::from(test[0].to_vec()));
     // println!("test{}: {}", 24, Base64::from(test[24].to_vec()));
@@ -238,6 +263,67 @@ pub async fn last_step_checkpoints_is_valid(
     }
 }
 
+/// Builds a thread pool for checkpoint verification, limited to the configured
+/// number of threads to avoid overloading the system
+pub fn verification_thread_pool(config: &VdfConfig) -> eyre::Result<rayon::ThreadPool> {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(config.parallel_verification_thread_limit)
+        .build()
+        .wrap_err("Failed to build the VDF verification thread pool")
+}
+
+/// Validates the checkpoints of a single VDF step computed from `seed`, as
+/// gossiped by peers.
+///
+/// Each checkpoint is recomputed from the previous one (or from `seed` for the
+/// first one) with `vdf_sha_verification`, in parallel on the given `pool`,
+/// which callers share between verifications to bound the threads they use.
+///
+/// Returns Ok(()) if the checkpoints are valid, Err otherwise.
+pub async fn vdf_step_is_valid(
+    global_step_number: u64,
+    seed: H256,
+    checkpoints: &H256List,
+    config: &VdfConfig,
+    pool: Arc<rayon::ThreadPool>,
+) -> eyre::Result<()> {
+    eyre::ensure!(global_step_number > 0, "VDF step 0 can't be verified");
+    eyre::ensure!(
+        checkpoints.len() == config.num_checkpoints_in_vdf_step,
+        "Expected {} checkpoints, got {}",
+        config.num_checkpoints_in_vdf_step,
+        checkpoints.len()
+    );
+
+    // Insert the seed at the head of the checkpoint list
+    let mut cp = checkpoints.clone();
+    cp.0.insert(0, seed);
+
+    let start_salt = U256::from(step_number_to_salt_number(config, global_step_number - 1));
+    let num_iterations: usize = config
+        .sha_1s_difficulty
+        .try_into()
+        .wrap_err("Should run in a 64 bits architecture!")?;
+    let num_checkpoints = config.num_checkpoints_in_vdf_step;
+
+    let test = tokio::task::spawn_blocking(move || {
+        pool.install(|| {
+            (0..num_checkpoints)
+                .into_par_iter()
+                .map(|i| vdf_sha_verification(start_salt + i, cp[i], 1, num_iterations)[0])
+                .collect::<Vec<H256>>()
+        })
+    })
+    .await?;
+
+    let test = H256List(test);
+    if test != *checkpoints {
+        warn_mismatches(checkpoints, &test);
+        return Err(eyre::eyre!("VDF step checkpoints are invalid"));
+    }
+    Ok(())
+}
+
 /// Derives a salt value from the `step_number` for checkpoint hashing
 ///
 /// # Arguments
@@ -754,6 +840,71 @@ mod tests {
         assert!(x.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_vdf_step_is_valid() {
+        let mut config = ConsensusConfig::testnet().vdf;
+        config.sha_1s_difficulty = 1_000;
+        let global_step_number = 5;
+
+        let seed = H256::random();
+        let mut hash = seed;
+        let mut salt = U256::from(step_number_to_salt_number(&config, global_step_number - 1));
+        let mut checkpoints = vec![H256::default(); config.num_checkpoints_in_vdf_step];
+        vdf_sha(
+            &mut Sha256::new(),
+            &mut salt,
+            &mut hash,
+            config.num_checkpoints_in_vdf_step,
+            config.sha_1s_difficulty,
+            &mut checkpoints,
+        );
+        let checkpoints = H256List(checkpoints);
+        let pool = Arc::new(verification_thread_pool(&config).unwrap());
+
+        assert!(vdf_step_is_valid(
+            global_step_number,
+            seed,
+            &checkpoints,
+            &config,
+            pool.clone()
+        )
+        .await
+        .is_ok());
+        // the checkpoints are bound to the step number and the seed
+        assert!(vdf_step_is_valid(
+            global_step_number + 1,
+            seed,
+            &checkpoints,
+            &config,
+            pool.clone()
+        )
+        .await
+        .is_err());
+        assert!(vdf_step_is_valid(
+            global_step_number,
+            H256::random(),
+            &checkpoints,
+            &config,
+            pool.clone()
+        )
+        .await
+        .is_err());
+
+        let mut tampered = checkpoints.clone();
+        tampered.0[3] = H256::random();
+        assert!(
+            vdf_step_is_valid(global_step_number, seed, &tampered, &config, pool.clone())
+                .await
+                .is_err()
+        );
+        tampered.0.truncate(3);
+        assert!(
+            vdf_step_is_valid(global_step_number, seed, &tampered, &config, pool.clone())
+                .await
+                .is_err()
+        );
+    }
+
     // one special case that do not apply reset seed
     #[tokio::test]
     async fn test_checkpoints_for_single_step_one() {